@@ -6,19 +6,139 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use datafusion::datasource::TableProvider;
+use datafusion::datasource::listing::ListingTableUrl;
+use datafusion::datasource::physical_plan::{FileGroup, FileSinkConfig};
 use datafusion::execution::context::SessionState;
+use datafusion::execution::object_store::ObjectStoreUrl;
 use datafusion::physical_plan::ExecutionPlan;
+use datafusion_shared::{assigned_crs_metadata, parse_geometry_type};
 use geoetl_core_common::{
-    DataReader, DataWriter, Driver, FormatFactory, FormatOptions, SupportStatus,
+    DataReader, DataWriter, Driver, FormatFactory, FormatOption, FormatOptions, OptionType,
+    OptionValues, SupportStatus, WriteMode,
 };
 use std::sync::Arc;
 
-use crate::{CsvFormatOptions, file_source};
+use crate::{CsvFormatOptions, CsvSink, CsvWriterExec, CsvWriterOptions, file_source};
+
+/// Open options understood by the CSV reader.
+const OPEN_OPTIONS: &[FormatOption] = &[
+    FormatOption::new(
+        "GEOMETRY_COLUMN",
+        OptionType::String,
+        "Column holding WKT geometries; without it the file is read as a plain table",
+    ),
+    FormatOption::new(
+        "GEOMETRY_TYPE",
+        OptionType::String,
+        "Geometry type of the WKT column (Geometry, Point, LineString, Polygon, MultiPoint, \
+         MultiLineString, MultiPolygon, GeometryCollection; Z, M or ZM suffix for XYZ, XYM or \
+         XYZM)",
+    )
+    .with_default("Geometry"),
+    FormatOption::new(
+        "CRS",
+        OptionType::String,
+        "CRS assigned to the geometry column (EPSG:<code>, AUTHORITY:CODE or a PROJ string)",
+    ),
+    FormatOption::new(
+        "HEADER",
+        OptionType::Boolean,
+        "The first row holds column names",
+    )
+    .with_default("YES"),
+    FormatOption::new("SEPARATOR", OptionType::Char, "Field separator").with_default(","),
+    FormatOption::new(
+        "BATCH_SIZE",
+        OptionType::Integer,
+        "Number of rows per record batch",
+    )
+    .with_default("8192"),
+    FormatOption::new(
+        "SCHEMA_INFER_MAX_RECORDS",
+        OptionType::Integer,
+        "Maximum number of rows sampled for schema inference",
+    )
+    .with_default("1000"),
+];
+
+/// Creation options understood by the CSV writer.
+const CREATION_OPTIONS: &[FormatOption] = &[
+    FormatOption::new("HEADER", OptionType::Boolean, "Write a header row").with_default("YES"),
+    FormatOption::new("SEPARATOR", OptionType::Char, "Field separator").with_default(","),
+    FormatOption::new(
+        "NULL_VALUE",
+        OptionType::String,
+        "Text written for null values",
+    ),
+    FormatOption::new("DATE_FORMAT", OptionType::String, "chrono format for dates"),
+    FormatOption::new(
+        "DATETIME_FORMAT",
+        OptionType::String,
+        "chrono format for datetimes",
+    ),
+    FormatOption::new(
+        "TIMESTAMP_FORMAT",
+        OptionType::String,
+        "chrono format for timestamps",
+    ),
+    FormatOption::new("TIME_FORMAT", OptionType::String, "chrono format for times"),
+];
 
-/// CSV format options wrapper for the factory system.
 impl FormatOptions for CsvFormatOptions {
-    fn as_any(&self) -> Box<dyn std::any::Any + Send> {
-        Box::new(self.clone())
+    fn from_option_values(values: &OptionValues) -> Result<Self> {
+        let mut options = CsvFormatOptions::default();
+        if let Some(has_header) = values.get_bool("HEADER")? {
+            options = options.with_has_header(has_header);
+        }
+        if let Some(delimiter) = values.get_char("SEPARATOR")? {
+            options = options.with_delimiter(delimiter);
+        }
+        if let Some(batch_size) = values.get_usize("BATCH_SIZE")? {
+            options = options.with_batch_size(batch_size);
+        }
+        if let Some(max_rec) = values.get_usize("SCHEMA_INFER_MAX_RECORDS")? {
+            options = options.with_schema_infer_max_rec(Some(max_rec));
+        }
+        if let Some(column) = values.get("GEOMETRY_COLUMN") {
+            let metadata = Arc::new(
+                values
+                    .get("CRS")
+                    .map(assigned_crs_metadata)
+                    .unwrap_or_default(),
+            );
+            let geoarrow_type =
+                parse_geometry_type(values.get("GEOMETRY_TYPE").unwrap_or("Geometry"), metadata)?;
+            options = options.with_geometry_from_wkt(column, geoarrow_type);
+        }
+        Ok(options)
+    }
+}
+
+impl FormatOptions for CsvWriterOptions {
+    fn from_option_values(values: &OptionValues) -> Result<Self> {
+        let mut options = CsvWriterOptions::default();
+        if let Some(has_header) = values.get_bool("HEADER")? {
+            options = options.with_header(has_header);
+        }
+        if let Some(delimiter) = values.get_char("SEPARATOR")? {
+            options = options.with_delimiter(delimiter);
+        }
+        if let Some(null_value) = values.get("NULL_VALUE") {
+            options = options.with_null_value(null_value);
+        }
+        if let Some(format) = values.get("DATE_FORMAT") {
+            options = options.with_date_format(format);
+        }
+        if let Some(format) = values.get("DATETIME_FORMAT") {
+            options = options.with_datetime_format(format);
+        }
+        if let Some(format) = values.get("TIMESTAMP_FORMAT") {
+            options = options.with_timestamp_format(format);
+        }
+        if let Some(format) = values.get("TIME_FORMAT") {
+            options = options.with_time_format(format);
+        }
+        Ok(options)
     }
 }
 
@@ -31,13 +151,11 @@ impl DataReader for CsvReader {
         &self,
         state: &SessionState,
         path: &str,
-        options: Box<dyn std::any::Any + Send>,
+        options: &OptionValues,
     ) -> Result<Arc<dyn TableProvider>> {
-        let csv_options = options
-            .downcast::<CsvFormatOptions>()
-            .map_err(|_| anyhow::anyhow!("Invalid options type for CSV reader"))?;
+        let csv_options = CsvFormatOptions::from_option_values(options)?;
 
-        let table = file_source::create_csv_table_provider(state, path, *csv_options).await?;
+        let table = file_source::create_csv_table_provider(state, path, csv_options).await?;
         Ok(table)
     }
 }
@@ -49,13 +167,28 @@ struct CsvWriter;
 impl DataWriter for CsvWriter {
     async fn create_writer_plan(
         &self,
-        _input: Arc<dyn ExecutionPlan>,
-        _path: &str,
-        _options: Box<dyn std::any::Any + Send>,
+        input: Arc<dyn ExecutionPlan>,
+        path: &str,
+        mode: WriteMode,
+        options: &OptionValues,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        // TODO: Implement writer plan creation
-        // This requires creating a CsvSink with FileSinkConfig
-        Err(anyhow::anyhow!("CSV writer not yet implemented in factory"))
+        let writer_options = CsvWriterOptions::from_option_values(options)?;
+
+        let config = FileSinkConfig {
+            original_url: path.to_string(),
+            object_store_url: ObjectStoreUrl::local_filesystem(),
+            file_group: FileGroup::default(),
+            table_paths: vec![ListingTableUrl::parse(path)?],
+            output_schema: input.schema(),
+            table_partition_cols: vec![],
+            insert_op: mode.insert_op(),
+            keep_partition_by_columns: false,
+            file_extension: "csv".to_string(),
+        };
+        let sink =
+            Arc::new(CsvSink::new(config, writer_options).with_create_new(mode == WriteMode::Fail));
+
+        Ok(Arc::new(CsvWriterExec::new(input, sink, None)))
     }
 }
 
@@ -80,6 +213,18 @@ impl FormatFactory for CsvFormatFactory {
     fn create_writer(&self) -> Option<Arc<dyn DataWriter>> {
         Some(Arc::new(CsvWriter))
     }
+
+    fn open_options(&self) -> Vec<FormatOption> {
+        OPEN_OPTIONS.to_vec()
+    }
+
+    fn creation_options(&self) -> Vec<FormatOption> {
+        CREATION_OPTIONS.to_vec()
+    }
+
+    fn extensions(&self) -> Vec<&'static str> {
+        vec!["csv"]
+    }
 }
 
 /// Registers the CSV format with the global driver registry.
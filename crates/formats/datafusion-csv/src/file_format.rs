@@ -236,9 +236,10 @@ impl FileFormat for CsvFormat {
     ) -> Result<Arc<dyn ExecutionPlan>> {
         use datafusion::logical_expr::dml::InsertOp;
 
-        if conf.insert_op != InsertOp::Append {
+        // Appends and overwrites are handled by the sink
+        if conf.insert_op == InsertOp::Replace {
             return Err(datafusion_common::DataFusionError::NotImplemented(
-                "Overwrites are not implemented yet for CSV".to_string(),
+                "Replacing rows is not supported for CSV".to_string(),
             ));
         }
 
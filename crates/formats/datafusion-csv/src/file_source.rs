@@ -13,18 +13,24 @@ use datafusion::datasource::TableProvider;
 use datafusion::datasource::listing::{
     ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
 };
-use datafusion::datasource::physical_plan::{FileGroup, FileOpener, FileScanConfig, FileSource};
+use datafusion::datasource::physical_plan::{
+    FileGroup, FileOpener, FileScanConfig, FileScanConfigBuilder, FileSource,
+};
 use datafusion::error::Result;
 use datafusion::execution::TaskContext;
 use datafusion::execution::context::SessionState;
 use datafusion::physical_plan::execution_plan::{Boundedness, EmissionType};
+use datafusion::physical_plan::filter_pushdown::{
+    ChildPushdownResult, FilterPushdownPhase, FilterPushdownPropagation, PushedDown,
+};
 use datafusion::physical_plan::metrics::ExecutionPlanMetricsSet;
 use datafusion::physical_plan::{
     DisplayAs, DisplayFormatType, ExecutionPlan, PlanProperties, SendableRecordBatchStream,
 };
+use datafusion_common::config::ConfigOptions;
 use datafusion_common::{DataFusionError, Statistics};
-use datafusion_physical_expr::EquivalenceProperties;
-use datafusion_shared::SpatialFormatReadError;
+use datafusion_physical_expr::{EquivalenceProperties, PhysicalExpr};
+use datafusion_shared::{SpatialFilter, SpatialFormatReadError};
 use object_store::ObjectStore;
 use object_store::aws::AmazonS3Builder;
 use object_store::azure::MicrosoftAzureBuilder;
@@ -91,6 +97,7 @@ pub struct CsvFileSource {
     projection: Option<Vec<usize>>,
     statistics: Option<Statistics>,
     metrics: ExecutionPlanMetricsSet,
+    spatial_filters: Vec<SpatialFilter>,
 }
 
 impl CsvFileSource {
@@ -103,6 +110,7 @@ impl CsvFileSource {
             projection: None,
             statistics: None,
             metrics: ExecutionPlanMetricsSet::new(),
+            spatial_filters: Vec::new(),
         }
     }
 
@@ -137,7 +145,8 @@ impl FileSource for CsvFileSource {
         let batch_size = self.resolve_batch_size(base_config);
 
         let opener = CsvOpener::new(self.options.clone(), schema, projection, object_store)
-            .with_batch_size(batch_size);
+            .with_batch_size(batch_size)
+            .with_spatial_filters(self.spatial_filters.clone());
 
         Arc::new(opener)
     }
@@ -184,10 +193,38 @@ impl FileSource for CsvFileSource {
         "csv"
     }
 
+    /// Records whose WKT geometry lies outside an `st_intersects` filter are skipped while
+    /// reading. The filters are inexact, so they are still reported as not pushed down.
+    fn try_pushdown_filters(
+        &self,
+        filters: Vec<Arc<dyn PhysicalExpr>>,
+        _config: &ConfigOptions,
+    ) -> Result<FilterPushdownPropagation<Arc<dyn FileSource>>> {
+        let result = FilterPushdownPropagation::with_parent_pushdown_result(vec![
+            PushedDown::No;
+            filters.len()
+        ]);
+        let spatial_filters: Vec<SpatialFilter> = filters
+            .iter()
+            .filter_map(SpatialFilter::from_predicate)
+            .collect();
+        if spatial_filters.is_empty() {
+            return Ok(result);
+        }
+
+        let mut source = self.clone();
+        source.spatial_filters.extend(spatial_filters);
+        Ok(result.with_updated_node(Arc::new(source)))
+    }
+
     fn fmt_extra(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
         match t {
             DisplayFormatType::Default | DisplayFormatType::Verbose => {
-                write!(f, ", has_header={}", self.options.has_header)
+                write!(f, ", has_header={}", self.options.has_header)?;
+                for filter in &self.spatial_filters {
+                    write!(f, ", spatial_filter={filter}")?;
+                }
+                Ok(())
             },
             DisplayFormatType::TreeRender => Ok(()),
         }
@@ -483,7 +520,8 @@ impl DisplayAs for CsvExec {
         match t {
             DisplayFormatType::Default | DisplayFormatType::Verbose => {
                 let file_count: usize = self.config.file_groups.iter().map(FileGroup::len).sum();
-                write!(f, "CsvExec: file_groups={{count={file_count}}}")
+                write!(f, "CsvExec: file_groups={{count={file_count}}}")?;
+                self.config.file_source.fmt_extra(t, f)
             },
             DisplayFormatType::TreeRender => Ok(()),
         }
@@ -518,6 +556,34 @@ impl ExecutionPlan for CsvExec {
         Ok(self)
     }
 
+    /// Offers the filters above the scan to the file source, as `DataSourceExec` does
+    fn handle_child_pushdown_result(
+        &self,
+        _phase: FilterPushdownPhase,
+        child_pushdown_result: ChildPushdownResult,
+        config: &ConfigOptions,
+    ) -> Result<FilterPushdownPropagation<Arc<dyn ExecutionPlan>>> {
+        let filters = child_pushdown_result
+            .parent_filters
+            .into_iter()
+            .map(|f| f.filter)
+            .collect();
+        let result = self
+            .config
+            .file_source
+            .try_pushdown_filters(filters, config)?;
+        let updated_node = result.updated_node.map(|file_source| {
+            let config = FileScanConfigBuilder::from(self.config.clone())
+                .with_source(file_source)
+                .build();
+            Arc::new(Self::new(config)) as Arc<dyn ExecutionPlan>
+        });
+        Ok(FilterPushdownPropagation {
+            filters: result.filters,
+            updated_node,
+        })
+    }
+
     fn execute(
         &self,
         partition: usize,
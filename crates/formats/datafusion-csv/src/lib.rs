@@ -47,7 +47,10 @@ pub use file_format::CsvFormatOptions;
 pub use file_source::CsvSourceBuilder;
 pub use object_store_reader::CsvFileMetadata;
 pub use sink::{CsvSink, CsvWriterExec};
-pub use writer::{CsvWriterOptions, write_csv, write_csv_to_bytes};
+pub use writer::{
+    CsvWriterOptions, csv_stream_writer, geometry_columns_to_wkt, write_csv, write_csv_stream,
+    write_csv_to_bytes,
+};
 
 use datafusion::prelude::*;
 use datafusion_common::Result;
@@ -16,7 +16,7 @@ use csv_async::{AsyncReaderBuilder, StringRecord as AsyncStringRecord};
 use datafusion::datasource::listing::PartitionedFile;
 use datafusion::datasource::physical_plan::{FileMeta, FileOpenFuture, FileOpener};
 use datafusion::error::{DataFusionError, Result};
-use datafusion_shared::{SourcePosition, SpatialFormatReadError};
+use datafusion_shared::{SourcePosition, SpatialFilter, SpatialFormatReadError};
 use futures::{Stream, StreamExt, TryStreamExt};
 use object_store::ObjectStore;
 use tokio_util::io::StreamReader;
@@ -37,6 +37,8 @@ pub struct CsvOpener {
     batch_size: usize,
     /// Object store for reading files
     object_store: Arc<dyn ObjectStore>,
+    /// Spatial filters pushed into the scan, with the index of the column each applies to
+    spatial_filters: Vec<(usize, SpatialFilter)>,
 }
 
 impl CsvOpener {
@@ -52,6 +54,7 @@ impl CsvOpener {
             projection,
             batch_size: 8192,
             object_store,
+            spatial_filters: Vec::new(),
         }
     }
 
@@ -59,6 +62,23 @@ impl CsvOpener {
         self.batch_size = batch_size;
         self
     }
+
+    /// Skip records whose WKT geometry cannot satisfy the given filters
+    ///
+    /// Filters naming a column that is not in the file are ignored.
+    pub fn with_spatial_filters(mut self, filters: Vec<SpatialFilter>) -> Self {
+        self.spatial_filters = filters
+            .into_iter()
+            .filter_map(|filter| Some((self.schema.index_of(&filter.column).ok()?, filter)))
+            .collect();
+        self
+    }
+
+    fn keeps(&self, record: &AsyncStringRecord) -> bool {
+        self.spatial_filters
+            .iter()
+            .all(|(idx, filter)| filter.matches_wkt(record.get(*idx).unwrap_or_default()))
+    }
 }
 
 impl FileOpener for CsvOpener {
@@ -113,7 +133,11 @@ impl FileOpener for CsvOpener {
 
                 while state.record_buffer.len() < state.opener.batch_size {
                     match state.records.as_mut().next().await {
-                        Some(Ok(record)) => state.record_buffer.push(record),
+                        Some(Ok(record)) => {
+                            if state.opener.keeps(&record) {
+                                state.record_buffer.push(record);
+                            }
+                        },
                         Some(Err(err)) => {
                             return Err(DataFusionError::from(csv_error_to_spatial(
                                 &err,
@@ -325,13 +349,14 @@ pub fn infer_schema(bytes: &[u8], options: &CsvFormatOptions) -> Result<Schema>
     }
 
     let schema = sanitize_schema_types(&inferred_schema);
-    let schema = apply_geometry_overrides(schema, options)?;
-
-    if options.has_header {
-        Ok(schema)
+    // Name headerless columns first so geometry columns can be referenced as `column_N`
+    let schema = if options.has_header {
+        schema
     } else {
-        Ok(rename_fields_without_header(&schema))
-    }
+        rename_fields_without_header(&schema)
+    };
+
+    apply_geometry_overrides(schema, options)
 }
 
 fn sanitize_schema_types(schema: &Schema) -> Schema {
@@ -424,4 +449,26 @@ mod tests {
         assert_eq!(schema.field(1).name(), "age");
         assert_eq!(schema.field(2).name(), "city");
     }
+
+    #[test]
+    fn test_infer_schema_headerless_geometry_column() {
+        use geoarrow_schema::{Dimension, GeoArrowType, PointType};
+
+        let csv_data = b"Alice,POINT(1 2)\nBob,POINT(3 4)";
+        let options = CsvFormatOptions::default()
+            .with_has_header(false)
+            .with_geometry_from_wkt(
+                "column_1",
+                GeoArrowType::Point(PointType::new(Dimension::XY, Arc::default())),
+            );
+
+        let schema = infer_schema(csv_data, &options).unwrap();
+
+        assert_eq!(schema.field(0).name(), "column_0");
+        assert_eq!(schema.field(1).name(), "column_1");
+        assert_eq!(
+            schema.field(1).extension_type_name(),
+            Some("geoarrow.point")
+        );
+    }
 }
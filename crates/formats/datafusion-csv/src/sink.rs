@@ -1,25 +1,41 @@
 //! CSV Data Sink implementation for writing data to CSV files
 
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use arrow_schema::SchemaRef;
+use arrow_array::{RecordBatch, UInt64Array};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
 use async_trait::async_trait;
 use datafusion::datasource::physical_plan::FileSinkConfig;
 use datafusion::datasource::sink::DataSink;
+use datafusion::logical_expr::dml::InsertOp;
+use datafusion::physical_expr::EquivalenceProperties;
+use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::execution_plan::{Boundedness, EmissionType};
 use datafusion::physical_plan::metrics::MetricsSet;
-use datafusion::physical_plan::{DisplayAs, DisplayFormatType, ExecutionPlan};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, Distribution, ExecutionPlan, ExecutionPlanProperties,
+    Partitioning, PlanProperties,
+};
 use datafusion_common::{DataFusionError, Result};
 use datafusion_execution::{SendableRecordBatchStream, TaskContext};
 use datafusion_physical_expr::LexRequirement;
-use futures::StreamExt;
+use datafusion_shared::{create_output, holds_written_values, open_for_append};
 
-use crate::writer::{CsvWriterOptions, write_csv};
+use crate::writer::{CsvWriterOptions, is_binary_geometry_field, write_csv_stream};
+
+/// Number of rows of an existing file read to infer the types of its columns before appending
+const APPEND_INFER_RECORDS: usize = 1000;
 
 /// CSV data sink that implements the `DataSink` trait
 #[derive(Debug)]
 pub struct CsvSink {
     config: FileSinkConfig,
     writer_options: CsvWriterOptions,
+    create_new: bool,
 }
 
 impl CsvSink {
@@ -29,9 +45,19 @@ impl CsvSink {
         Self {
             config,
             writer_options,
+            create_new: false,
         }
     }
 
+    /// Fail instead of replacing the output file when it already exists (default: false)
+    ///
+    /// Only applies when overwriting; the file is checked and created in one operation.
+    #[must_use]
+    pub fn with_create_new(mut self, create_new: bool) -> Self {
+        self.create_new = create_new;
+        self
+    }
+
     /// Get the sink configuration
     #[must_use]
     pub fn config(&self) -> &FileSinkConfig {
@@ -43,6 +69,127 @@ impl CsvSink {
     pub fn writer_options(&self) -> &CsvWriterOptions {
         &self.writer_options
     }
+
+    /// Resolve the local file the sink writes to
+    ///
+    /// A table path ending in `/` is treated as a directory and receives a `data.csv`
+    /// file; any other path is written as-is.
+    fn output_file(&self) -> Result<PathBuf> {
+        let output_path = self
+            .config
+            .table_paths
+            .first()
+            .ok_or_else(|| DataFusionError::Internal("No output path specified".to_string()))?;
+
+        // For now, write to local filesystem
+        let path = output_path.get_url().to_file_path().map_err(|()| {
+            DataFusionError::NotImplemented(format!(
+                "CSV sink only supports local files, got '{}'",
+                output_path.as_str()
+            ))
+        })?;
+
+        if output_path.is_collection() {
+            Ok(path.join("data.csv"))
+        } else {
+            Ok(path)
+        }
+    }
+
+    /// Open the output file for writing, along with the writer options to use
+    ///
+    /// Overwrites truncate the file, or fail if it exists when the sink creates new files
+    /// only. Appends to an existing, non-empty file check that its
+    /// columns match the written ones and skip the header; otherwise the file is created.
+    fn open_output(&self, path: &Path) -> Result<(File, CsvWriterOptions)> {
+        let mut writer_options = self.writer_options.clone();
+        let file = match self.config.insert_op {
+            InsertOp::Append => {
+                // The file is only opened, which may end its last line, once the appended
+                // columns are known to fit
+                if path.metadata().is_ok_and(|metadata| metadata.len() > 0) {
+                    self.check_appended_columns(path)?;
+                    writer_options.has_header = false;
+                }
+                match open_for_append(path)? {
+                    Some(file) => file,
+                    None => File::create(path)?,
+                }
+            },
+            InsertOp::Overwrite => create_output(path, self.create_new)?,
+            InsertOp::Replace => {
+                return Err(DataFusionError::NotImplemented(
+                    "CSV sink cannot replace rows; use append or overwrite".to_string(),
+                ));
+            },
+        };
+        Ok((file, writer_options))
+    }
+
+    /// Check that the existing file at `path` has the columns being appended
+    ///
+    /// With a header, the column names must match in order; without one, the number of
+    /// columns is compared. The types inferred from the first rows of the file must then be
+    /// able to hold the written values, so that the file still reads back with those types.
+    fn check_appended_columns(&self, path: &Path) -> Result<()> {
+        let format = arrow_csv::reader::Format::default()
+            .with_header(self.writer_options.has_header)
+            .with_delimiter(self.writer_options.delimiter);
+        let (existing, _) = format.infer_schema(File::open(path)?, Some(APPEND_INFER_RECORDS))?;
+        let written = self.config.output_schema();
+
+        let compatible = if self.writer_options.has_header {
+            existing
+                .fields()
+                .iter()
+                .map(|field| field.name())
+                .eq(written.fields().iter().map(|field| field.name()))
+        } else {
+            existing.fields().len() == written.fields().len()
+        };
+        if compatible {
+            let mismatch =
+                existing
+                    .fields()
+                    .iter()
+                    .zip(written.fields())
+                    .find(|(existing, written)| {
+                        // Geometries are written as WKT text
+                        let written = if is_binary_geometry_field(written) {
+                            &DataType::Utf8
+                        } else {
+                            written.data_type()
+                        };
+                        !holds_written_values(existing.data_type(), written)
+                    });
+            return match mismatch {
+                None => Ok(()),
+                Some((existing, written)) => Err(DataFusionError::Execution(format!(
+                    "Cannot append to '{}': its column '{}' holds {} values, which cannot hold \
+                     the written {} values",
+                    path.display(),
+                    existing.name(),
+                    existing.data_type(),
+                    written.data_type()
+                ))),
+            };
+        }
+
+        let names = |schema: &Schema| {
+            schema
+                .fields()
+                .iter()
+                .map(|field| field.name().as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        Err(DataFusionError::Execution(format!(
+            "Cannot append to '{}': its columns ({}) do not match the written columns ({})",
+            path.display(),
+            names(&existing),
+            names(written)
+        )))
+    }
 }
 
 #[async_trait]
@@ -61,41 +208,14 @@ impl DataSink for CsvSink {
 
     async fn write_all(
         &self,
-        mut data: SendableRecordBatchStream,
+        data: SendableRecordBatchStream,
         _context: &Arc<TaskContext>,
     ) -> Result<u64> {
-        let mut batches = Vec::new();
-        let mut row_count = 0u64;
-
-        // Collect all batches from the stream
-        while let Some(batch_result) = data.next().await {
-            let batch = batch_result?;
-            row_count += batch.num_rows() as u64;
-            batches.push(batch);
-        }
+        let file_path = self.output_file()?;
+        let (file, writer_options) = self.open_output(&file_path)?;
 
-        // Write to output - for now write to a single file
-        // In a full implementation, this would handle partitioning
-        // and write to object store
-        let output_path = self
-            .config
-            .table_paths
-            .first()
-            .ok_or_else(|| DataFusionError::Internal("No output path specified".to_string()))?;
-
-        let file_path = format!(
-            "{}/data.csv",
-            <datafusion::datasource::listing::ListingTableUrl as AsRef<str>>::as_ref(output_path)
-        );
-
-        // For now, write to local filesystem
-        // A full implementation would use object store
-        let mut file = std::fs::File::create(&file_path)
-            .map_err(|e| DataFusionError::External(Box::new(e)))?;
-
-        write_csv(&mut file, &batches, &self.writer_options)?;
-
-        Ok(row_count)
+        // Rows are written batch by batch as they arrive
+        write_csv_stream(data, BufWriter::new(file), &writer_options).await
     }
 }
 
@@ -106,11 +226,15 @@ impl DisplayAs for CsvSink {
 }
 
 /// CSV writer physical execution plan
+///
+/// Drains its input into a [`CsvSink`] and produces a single batch with a `count` column
+/// holding the number of rows written.
 #[derive(Debug)]
 pub struct CsvWriterExec {
     input: Arc<dyn ExecutionPlan>,
     sink: Arc<CsvSink>,
     _order_requirements: Option<LexRequirement>,
+    properties: PlanProperties,
 }
 
 impl CsvWriterExec {
@@ -124,8 +248,29 @@ impl CsvWriterExec {
             input,
             sink,
             _order_requirements: order_requirements,
+            properties: PlanProperties::new(
+                EquivalenceProperties::new(count_schema()),
+                Partitioning::UnknownPartitioning(1),
+                EmissionType::Final,
+                Boundedness::Bounded,
+            ),
         }
     }
+
+    /// Get the sink this plan writes to
+    #[must_use]
+    pub fn sink(&self) -> &Arc<CsvSink> {
+        &self.sink
+    }
+}
+
+/// Schema of the batch returned by [`CsvWriterExec`]
+fn count_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![Field::new(
+        "count",
+        DataType::UInt64,
+        false,
+    )]))
 }
 
 impl DisplayAs for CsvWriterExec {
@@ -149,8 +294,12 @@ impl ExecutionPlan for CsvWriterExec {
         self
     }
 
-    fn properties(&self) -> &datafusion::physical_plan::PlanProperties {
-        self.input.properties()
+    fn properties(&self) -> &PlanProperties {
+        &self.properties
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::SinglePartition]
     }
 
     fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
@@ -172,6 +321,7 @@ impl ExecutionPlan for CsvWriterExec {
             input: Arc::clone(&children[0]),
             sink: Arc::clone(&self.sink),
             _order_requirements: self._order_requirements.clone(),
+            properties: self.properties.clone(),
         }))
     }
 
@@ -186,12 +336,29 @@ impl ExecutionPlan for CsvWriterExec {
             ));
         }
 
-        // Execute input and get stream
-        let input_stream = self.input.execute(partition, Arc::clone(&context))?;
-
-        // For now, we'll return the input stream
-        // A full implementation would write and return a count stream
-        Ok(input_stream)
+        // All input partitions are funnelled into the single output file
+        let input: Arc<dyn ExecutionPlan> =
+            if self.input.output_partitioning().partition_count() > 1 {
+                Arc::new(CoalescePartitionsExec::new(Arc::clone(&self.input)))
+            } else {
+                Arc::clone(&self.input)
+            };
+        let data = input.execute(0, Arc::clone(&context))?;
+
+        let sink = Arc::clone(&self.sink);
+        let stream = futures::stream::once(async move {
+            let count = sink.write_all(data, &context).await?;
+            RecordBatch::try_new(
+                count_schema(),
+                vec![Arc::new(UInt64Array::from(vec![count]))],
+            )
+            .map_err(DataFusionError::from)
+        });
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            count_schema(),
+            stream,
+        )))
     }
 }
 
@@ -1,10 +1,17 @@
 //! CSV writer implementation for converting Arrow record batches to CSV format
 
 use std::io::Write as IoWrite;
+use std::sync::Arc;
 
 use arrow_array::RecordBatch;
-use arrow_csv::WriterBuilder;
+use arrow_csv::{Writer, WriterBuilder};
+use arrow_schema::{DataType, Field, Schema};
 use datafusion_common::{DataFusionError, Result};
+use datafusion_execution::SendableRecordBatchStream;
+use futures::StreamExt;
+use geoarrow_array::GeoArrowArray;
+use geoarrow_array::array::{WktArray, from_arrow_array};
+use geoarrow_array::cast::to_wkt;
 
 /// Options for CSV writing
 #[derive(Debug, Clone)]
@@ -96,20 +103,11 @@ impl CsvWriterOptions {
     }
 }
 
-/// Write record batches to CSV format
-///
-/// # Errors
+/// Create an incremental CSV writer configured from `options`
 ///
-/// Returns an error if writing to the output fails or if CSV serialization fails
-pub fn write_csv<W: IoWrite>(
-    writer: &mut W,
-    batches: &[RecordBatch],
-    options: &CsvWriterOptions,
-) -> Result<()> {
-    if batches.is_empty() {
-        return Ok(());
-    }
-
+/// Each call to [`arrow_csv::Writer::write`] serialises one batch; the header is written
+/// before the first batch when enabled.
+pub fn csv_stream_writer<W: IoWrite>(writer: W, options: &CsvWriterOptions) -> Writer<W> {
     let mut builder = WriterBuilder::new()
         .with_delimiter(options.delimiter)
         .with_header(options.has_header);
@@ -130,17 +128,128 @@ pub fn write_csv<W: IoWrite>(
         builder = builder.with_null(options.null_value.clone());
     }
 
-    let mut csv_writer = builder.build(writer);
+    builder.build(writer)
+}
+
+/// Encode every `GeoArrow` geometry column of a batch as WKT text
+///
+/// Columns carrying a `geoarrow.*` extension type are replaced by plain `Utf8` columns so the
+/// batch can be serialised by the Arrow CSV writer. Columns that are already WKT strings and
+/// batches without geometry are returned unchanged.
+///
+/// # Errors
+///
+/// Returns an error if a geometry column cannot be interpreted as `GeoArrow` or encoded as WKT
+pub fn geometry_columns_to_wkt(batch: &RecordBatch) -> Result<RecordBatch> {
+    let schema = batch.schema();
+    let geometry_indices: Vec<usize> = schema
+        .fields()
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| is_binary_geometry_field(field))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    if geometry_indices.is_empty() {
+        return Ok(batch.clone());
+    }
+
+    let mut fields = schema.fields().to_vec();
+    let mut columns = batch.columns().to_vec();
+
+    for idx in geometry_indices {
+        let field = schema.field(idx);
+        let geometry = from_arrow_array(columns[idx].as_ref(), field).map_err(|e| {
+            DataFusionError::Execution(format!(
+                "Failed to read geometry column '{}': {e}",
+                field.name()
+            ))
+        })?;
+        let wkt: WktArray = to_wkt(&geometry).map_err(|e| {
+            DataFusionError::Execution(format!(
+                "Failed to encode geometry column '{}' as WKT: {e}",
+                field.name()
+            ))
+        })?;
+
+        fields[idx] = Arc::new(Field::new(field.name(), DataType::Utf8, true));
+        columns[idx] = wkt.to_array_ref();
+    }
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+        .map_err(|e| DataFusionError::ArrowError(Box::new(e), None))
+}
+
+/// Whether a field holds geometries that need WKT encoding before CSV serialisation
+pub(crate) fn is_binary_geometry_field(field: &Field) -> bool {
+    field
+        .extension_type_name()
+        .is_some_and(|name| name.starts_with("geoarrow."))
+        && !matches!(
+            field.data_type(),
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+        )
+}
+
+/// Write record batches to CSV format
+///
+/// `GeoArrow` geometry columns are written as WKT.
+///
+/// # Errors
+///
+/// Returns an error if writing to the output fails or if CSV serialization fails
+pub fn write_csv<W: IoWrite>(
+    writer: &mut W,
+    batches: &[RecordBatch],
+    options: &CsvWriterOptions,
+) -> Result<()> {
+    if batches.is_empty() {
+        return Ok(());
+    }
+
+    let mut csv_writer = csv_stream_writer(writer, options);
 
     for batch in batches {
         csv_writer
-            .write(batch)
+            .write(&geometry_columns_to_wkt(batch)?)
             .map_err(|e| DataFusionError::External(Box::new(e)))?;
     }
 
     Ok(())
 }
 
+/// Write a stream of record batches to CSV format, one batch at a time
+///
+/// `GeoArrow` geometry columns are written as WKT. Returns the number of rows written.
+///
+/// # Errors
+///
+/// Returns an error if the input stream fails, if writing to the output fails or if CSV
+/// serialization fails
+pub async fn write_csv_stream<W: IoWrite>(
+    mut stream: SendableRecordBatchStream,
+    writer: W,
+    options: &CsvWriterOptions,
+) -> Result<u64> {
+    let mut csv_writer = csv_stream_writer(writer, options);
+    let mut rows = 0u64;
+
+    while let Some(batch) = stream.next().await {
+        let batch = geometry_columns_to_wkt(&batch?)?;
+        csv_writer
+            .write(&batch)
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        rows += batch.num_rows() as u64;
+    }
+
+    csv_writer
+        .into_inner()
+        .flush()
+        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+    Ok(rows)
+}
+
 /// Write record batches to CSV bytes
 ///
 /// # Errors
@@ -261,4 +370,65 @@ mod tests {
         let lines: Vec<&str> = csv_str.lines().collect();
         assert_eq!(lines.len(), 7); // 1 header + 6 data rows
     }
+
+    #[tokio::test]
+    async fn test_write_csv_stream() {
+        use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+
+        let batch = create_test_batch();
+        let stream = Box::pin(RecordBatchStreamAdapter::new(
+            batch.schema(),
+            futures::stream::iter(vec![Ok(batch.clone()), Ok(batch)]),
+        ));
+
+        let mut buffer = Vec::new();
+        let rows = write_csv_stream(stream, &mut buffer, &CsvWriterOptions::default())
+            .await
+            .unwrap();
+        let csv_str = String::from_utf8(buffer).unwrap();
+
+        assert_eq!(rows, 6);
+        assert_eq!(csv_str.matches("id,name,value,active").count(), 1);
+        assert_eq!(csv_str.lines().count(), 7);
+    }
+
+    #[test]
+    fn test_write_csv_encodes_geometry_as_wkt() {
+        use geoarrow_array::cast::from_wkt;
+        use geoarrow_schema::{Dimension, GeoArrowType, PointType, WktType};
+
+        let wkt = WktArray::from((
+            StringArray::from(vec![Some("POINT(1 2)"), None]),
+            WktType::new(Arc::default()),
+        ));
+        let geoarrow_type = GeoArrowType::Point(PointType::new(Dimension::XY, Arc::default()));
+        let points = from_wkt(&wkt, geoarrow_type.clone()).unwrap();
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            geoarrow_type.to_field("geometry", true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef,
+                points.into_array_ref(),
+            ],
+        )
+        .unwrap();
+
+        let converted = geometry_columns_to_wkt(&batch).unwrap();
+        assert_eq!(converted.schema().field(1).data_type(), &DataType::Utf8);
+
+        let result = write_csv_to_bytes(&[batch], &CsvWriterOptions::default()).unwrap();
+        let csv_str = String::from_utf8(result).unwrap();
+        assert_eq!(csv_str, "id,geometry\n1,POINT(1 2)\n2,\n");
+    }
+
+    #[test]
+    fn test_geometry_columns_to_wkt_without_geometry() {
+        let batch = create_test_batch();
+        let converted = geometry_columns_to_wkt(&batch).unwrap();
+        assert_eq!(converted.schema(), batch.schema());
+    }
 }
@@ -125,6 +125,46 @@ async fn test_countries_wkt_to_geoarrow_multipolygon() -> Result<()> {
     Ok(())
 }
 
+/// Test that an `st_intersects` filter against a constant is pushed into the CSV scan
+#[tokio::test]
+async fn test_spatial_filter_pushed_into_scan() -> Result<()> {
+    let ctx = SessionContext::new();
+    geoetl_operations::register_spatial_functions(&ctx);
+
+    let options = CsvFormatOptions::default().with_geometry_from_wkt(
+        "Geolocation",
+        GeoArrowType::Point(PointType::new(Dimension::XY, Arc::default())),
+    );
+    ctx.register_csv_with_options("places", "tests/e2e_data/spatial/places.csv", options)
+        .await?;
+
+    let query = r#"SELECT count(*) AS n FROM places
+        WHERE st_intersects("Geolocation", 'POLYGON((-88 30, -86 30, -86 32, -88 32, -88 30))')"#;
+    let plan = ctx
+        .sql(&format!("EXPLAIN {query}"))
+        .await?
+        .collect()
+        .await?;
+    let plan = datafusion::arrow::util::pretty::pretty_format_batches(&plan)?.to_string();
+    assert!(
+        plan.contains("spatial_filter=Geolocation intersects BOX(-88 30, -86 32)"),
+        "{plan}"
+    );
+
+    // The scan only drops features outside the box; the filter still decides the rest
+    let filtered = ctx.sql(query).await?.collect().await?;
+    let expected = ctx
+        .sql(
+            r#"SELECT count(*) AS n FROM places
+            WHERE st_x("Geolocation") BETWEEN -88 AND -86 AND st_y("Geolocation") BETWEEN 30 AND 32"#,
+        )
+        .await?
+        .collect()
+        .await?;
+    assert_eq!(filtered, expected);
+    Ok(())
+}
+
 /// Test parsing WKT geometries from remote HTTP object store
 #[tokio::test]
 async fn test_remote_cities_wkt_to_geoarrow() -> Result<()> {
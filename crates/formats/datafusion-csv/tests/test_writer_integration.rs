@@ -16,13 +16,11 @@ use datafusion_execution::{SendableRecordBatchStream, TaskContext};
 use futures::stream;
 use tempfile::TempDir;
 
-#[ignore = "Requires proper object store integration"]
 #[tokio::test]
 async fn test_csv_sink_write_all() {
     let temp_dir = TempDir::new().unwrap();
     let output_path = temp_dir.path().to_str().unwrap();
 
-    // Pre-create the directory since sink expects it to exist
     fs::create_dir_all(output_path).unwrap();
 
     let schema = Arc::new(Schema::new(vec![
@@ -45,7 +43,7 @@ async fn test_csv_sink_write_all() {
         original_url: format!("file://{output_path}/output.csv"),
         object_store_url: ObjectStoreUrl::local_filesystem(),
         file_group: FileGroup::default(),
-        table_paths: vec![ListingTableUrl::parse(format!("file://{output_path}")).unwrap()],
+        table_paths: vec![ListingTableUrl::parse(format!("file://{output_path}/")).unwrap()],
         output_schema: schema.clone(),
         table_partition_cols: vec![],
         insert_op: InsertOp::Append,
@@ -145,3 +143,169 @@ fn test_csv_sink_config_accessors() {
     assert_eq!(sink.config().original_url, config.original_url);
     assert_eq!(sink.writer_options().delimiter, writer_options.delimiter);
 }
+
+#[tokio::test]
+async fn test_csv_factory_writer_plan_writes_file() {
+    use datafusion::datasource::memory::MemorySourceConfig;
+    use datafusion::physical_plan::collect;
+    use datafusion_csv::factory::CsvFormatFactory;
+    use geoetl_core_common::FormatFactory;
+
+    let temp_dir = TempDir::new().unwrap();
+    let output_path = temp_dir.path().join("output.csv");
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+    ]));
+    let batches = vec![
+        RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef,
+                Arc::new(StringArray::from(vec!["Alice", "Bob"])) as ArrayRef,
+            ],
+        )
+        .unwrap(),
+        RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![3])) as ArrayRef,
+                Arc::new(StringArray::from(vec!["Charlie"])) as ArrayRef,
+            ],
+        )
+        .unwrap(),
+    ];
+    let input = MemorySourceConfig::try_new_exec(&[batches], schema, None).unwrap();
+
+    let writer = CsvFormatFactory.create_writer().unwrap();
+    let plan = writer
+        .create_writer_plan(
+            input,
+            output_path.to_str().unwrap(),
+            geoetl_core_common::WriteMode::Overwrite,
+            &geoetl_core_common::OptionValues::new(),
+        )
+        .await
+        .unwrap();
+
+    let results = collect(plan, Arc::new(TaskContext::default()))
+        .await
+        .unwrap();
+    let count = results[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<arrow_array::UInt64Array>()
+        .unwrap()
+        .value(0);
+    assert_eq!(count, 3);
+
+    let contents = fs::read_to_string(&output_path).unwrap();
+    assert_eq!(contents, "id,name\n1,Alice\n2,Bob\n3,Charlie\n");
+}
+
+#[tokio::test]
+async fn test_csv_factory_writer_plan_appends() {
+    use datafusion::datasource::memory::MemorySourceConfig;
+    use datafusion::physical_plan::collect;
+    use datafusion_csv::factory::CsvFormatFactory;
+    use geoetl_core_common::{FormatFactory, WriteMode};
+
+    let temp_dir = TempDir::new().unwrap();
+    let output_path = temp_dir.path().join("output.csv");
+    let path = output_path.to_str().unwrap();
+
+    let write = |id: ArrayRef, names: Vec<&'static str>, mode| async move {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", id.data_type().clone(), false),
+            Field::new(names[0], DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![id, Arc::new(StringArray::from(vec![names[1]])) as ArrayRef],
+        )
+        .unwrap();
+        let input = MemorySourceConfig::try_new_exec(&[vec![batch]], schema, None).unwrap();
+        let plan = CsvFormatFactory
+            .create_writer()
+            .unwrap()
+            .create_writer_plan(input, path, mode, &geoetl_core_common::OptionValues::new())
+            .await
+            .unwrap();
+        collect(plan, Arc::new(TaskContext::default())).await
+    };
+    let id = || Arc::new(Int64Array::from(vec![1])) as ArrayRef;
+
+    // Appending to a missing file creates it with a header
+    write(id(), vec!["name", "Alice"], WriteMode::Append)
+        .await
+        .unwrap();
+    // Later appends add rows without repeating the header
+    write(id(), vec!["name", "Bob"], WriteMode::Append)
+        .await
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(&output_path).unwrap(),
+        "id,name\n1,Alice\n1,Bob\n"
+    );
+
+    // Appended columns must match the file's header, and a rejected append leaves the file
+    // untouched, even when its last line is not terminated
+    fs::write(&output_path, "id,name\n1,Alice").unwrap();
+    let err = write(id(), vec!["city", "Paris"], WriteMode::Append)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("its columns (id, name) do not match the written columns (id, city)"),
+        "{err}"
+    );
+    assert_eq!(
+        fs::read_to_string(&output_path).unwrap(),
+        "id,name\n1,Alice"
+    );
+
+    // The file's column types must hold the appended values
+    let text_id = Arc::new(StringArray::from(vec!["A-1"])) as ArrayRef;
+    let err = write(text_id, vec!["name", "Carol"], WriteMode::Append)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("its column 'id' holds Int64 values, which cannot hold the written Utf8"),
+        "{err}"
+    );
+    let float_id = Arc::new(arrow_array::Float64Array::from(vec![2.5])) as ArrayRef;
+    let err = write(float_id, vec!["name", "Carol"], WriteMode::Append)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("holds Int64 values"), "{err}");
+    write(id(), vec!["name", "Carol"], WriteMode::Append)
+        .await
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(&output_path).unwrap(),
+        "id,name\n1,Alice\n1,Carol\n"
+    );
+
+    write(id(), vec!["city", "Paris"], WriteMode::Overwrite)
+        .await
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(&output_path).unwrap(),
+        "id,city\n1,Paris\n"
+    );
+
+    // Failing refuses to replace the file
+    let err = write(id(), vec!["name", "Dave"], WriteMode::Fail)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err.find_root(), datafusion::error::DataFusionError::IoError(e) if e.kind() == std::io::ErrorKind::AlreadyExists),
+        "{err}"
+    );
+    assert_eq!(
+        fs::read_to_string(&output_path).unwrap(),
+        "id,city\n1,Paris\n"
+    );
+}
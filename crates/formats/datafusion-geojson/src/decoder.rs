@@ -0,0 +1,684 @@
+//! Incremental `GeoJSON` decoding over byte streams.
+//!
+//! [`GeoJsonDecoder`] consumes arbitrary byte chunks and yields one [`FeatureRecord`] at a time.
+//! Top-level `FeatureCollection` objects are not materialised: the decoder walks the
+//! `features` array and only buffers the bytes of the feature currently being decoded, so the
+//! memory footprint is bounded by the largest single feature rather than the file size. This
+//! needs the collection's `type` member to come before `features`, as it usually does; an
+//! object whose `type` is not known yet when its `features` array starts is buffered and
+//! parsed as a whole.
+//!
+//! Newline-delimited `GeoJSON` sequences (RFC 8142, with or without record separators) are
+//! handled by the same state machine, since every top-level value is decoded independently.
+//!
+//! The other members of a `FeatureCollection`, such as `bbox` or `name`, are kept aside and
+//! exposed through [`GeoJsonDecoder::collection_members`] once the collection is complete.
+#![allow(clippy::result_large_err)]
+
+use std::collections::VecDeque;
+
+use bytes::Bytes;
+use datafusion_shared::{SourcePosition, SpatialFormatReadError, SpatialFormatResult};
+use futures::StreamExt;
+use futures::stream::BoxStream;
+use geojson::{Feature, GeoJson, JsonObject, JsonValue};
+
+use crate::parser::{FeatureRecord, feature_to_record, geojson_to_features};
+
+/// ASCII record separator used by RFC 8142 `GeoJSON` text sequences.
+const RECORD_SEPARATOR: u8 = 0x1E;
+
+/// Key of the top-level member that holds the features of a `FeatureCollection`.
+const FEATURES_KEY: &[u8] = b"features";
+
+/// Key of the top-level member that holds the `GeoJSON` object type.
+const TYPE_KEY: &[u8] = b"type";
+
+/// Value of the `type` member of a `FeatureCollection`.
+const FEATURE_COLLECTION_TYPE: &[u8] = b"FeatureCollection";
+
+/// Position of the scanner relative to the top-level `GeoJSON` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// Between top-level values, skipping whitespace.
+    Idle,
+    /// Inside a top-level object, outside of the `features` array.
+    Object,
+    /// Inside the `features` array, between two elements.
+    Features,
+    /// Inside a single element of the `features` array.
+    Element,
+}
+
+/// Lexical state shared by the object and element scanners.
+#[derive(Debug, Default, Clone, Copy)]
+struct Lexer {
+    depth: usize,
+    in_string: bool,
+    escape: bool,
+}
+
+impl Lexer {
+    /// Advance over a single byte, returning `true` when a string literal just closed.
+    fn advance(&mut self, byte: u8) -> bool {
+        if self.in_string {
+            if self.escape {
+                self.escape = false;
+            } else if byte == b'\\' {
+                self.escape = true;
+            } else if byte == b'"' {
+                self.in_string = false;
+                return true;
+            }
+            return false;
+        }
+
+        match byte {
+            b'"' => self.in_string = true,
+            b'{' | b'[' => self.depth += 1,
+            b'}' | b']' => self.depth = self.depth.saturating_sub(1),
+            _ => {},
+        }
+        false
+    }
+}
+
+/// Push-based incremental `GeoJSON` decoder.
+///
+/// Feed bytes with [`GeoJsonDecoder::push`], drain records with
+/// [`GeoJsonDecoder::next_record`] (or raw features with [`GeoJsonDecoder::next_feature`]), and
+/// call [`GeoJsonDecoder::finish`] once the input is
+/// exhausted to detect truncated documents.
+#[derive(Debug)]
+pub struct GeoJsonDecoder {
+    context: String,
+    buffer: Vec<u8>,
+    /// Scan position within `buffer`.
+    pos: usize,
+    /// Number of bytes discarded from the front of `buffer` so far.
+    consumed: u64,
+    /// Current 1-based line number at `pos`.
+    line: u64,
+    mode: Mode,
+    lexer: Lexer,
+    /// Start of the current top-level value within `buffer`.
+    value_start: usize,
+    value_line: u64,
+    /// Whether the `features` array of the current top-level value is being streamed.
+    saw_features: bool,
+    /// Whether the next string at object depth 1 is a member name.
+    expect_key: bool,
+    /// Start of the contents of the last string opened at object depth 1.
+    string_start: usize,
+    /// Set after a `"features"` member name until its value starts.
+    pending_features: bool,
+    /// Set after a `"type"` member name until its value has been read.
+    pending_type: bool,
+    /// Whether the `type` member of the current top-level value, if read yet, is
+    /// `FeatureCollection`.
+    is_collection: Option<bool>,
+    /// Members of the current collection written before its `features` array.
+    collection_head: Vec<u8>,
+    /// Start of the members written after the `features` array within `buffer`.
+    tail_start: usize,
+    /// Members of the last completed `FeatureCollection` besides `type` and `features`.
+    collection_members: Option<JsonObject>,
+    /// Start of the current `features` element within `buffer`.
+    element_start: usize,
+    element_line: u64,
+    values_seen: usize,
+    pending: VecDeque<Feature>,
+}
+
+impl GeoJsonDecoder {
+    /// Create a decoder. `context` is attached to every reported error.
+    pub fn new(context: impl Into<String>) -> Self {
+        Self {
+            context: context.into(),
+            buffer: Vec::new(),
+            pos: 0,
+            consumed: 0,
+            line: 1,
+            mode: Mode::Idle,
+            lexer: Lexer::default(),
+            value_start: 0,
+            value_line: 1,
+            saw_features: false,
+            expect_key: false,
+            string_start: 0,
+            pending_features: false,
+            pending_type: false,
+            is_collection: None,
+            collection_head: Vec::new(),
+            tail_start: 0,
+            collection_members: None,
+            element_start: 0,
+            element_line: 1,
+            values_seen: 0,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Append a chunk of input bytes.
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.compact();
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Decode the next available record.
+    ///
+    /// Returns `Ok(None)` when more input is required to make progress.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffered input is not valid `GeoJSON`.
+    pub fn next_record(&mut self) -> SpatialFormatResult<Option<FeatureRecord>> {
+        self.next_feature()?
+            .map(|feature| feature_to_record(feature, &self.context))
+            .transpose()
+    }
+
+    /// Decode the next available feature without converting it into a record.
+    ///
+    /// Returns `Ok(None)` when more input is required to make progress.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffered input is not valid `GeoJSON`.
+    pub fn next_feature(&mut self) -> SpatialFormatResult<Option<Feature>> {
+        loop {
+            if let Some(feature) = self.pending.pop_front() {
+                return Ok(Some(feature));
+            }
+            if self.pos >= self.buffer.len() {
+                return Ok(None);
+            }
+            self.step()?;
+        }
+    }
+
+    /// Signal the end of input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ended in the middle of a value or contained no
+    /// `GeoJSON` values at all.
+    pub fn finish(&mut self) -> SpatialFormatResult<()> {
+        if self.mode != Mode::Idle {
+            return Err(self.parse_error(
+                "Unexpected end of input inside a GeoJSON value".to_string(),
+                self.value_line,
+                self.value_start,
+            ));
+        }
+        if self.values_seen == 0 {
+            return Err(SpatialFormatReadError::Parse {
+                message: "No GeoJSON features found".to_string(),
+                position: None,
+                context: Some(self.context.clone()),
+            });
+        }
+        Ok(())
+    }
+
+    /// Members of the last completed top-level `FeatureCollection` other than `type` and
+    /// `features`, or `None` if the last top-level value was not a collection.
+    pub fn collection_members(&self) -> Option<&JsonObject> {
+        self.collection_members.as_ref()
+    }
+
+    /// Scan a single byte and advance the state machine.
+    fn step(&mut self) -> SpatialFormatResult<()> {
+        let byte = self.buffer[self.pos];
+        match self.mode {
+            Mode::Idle => self.step_idle(byte)?,
+            Mode::Object => self.step_object(byte)?,
+            Mode::Features => self.step_features(byte)?,
+            Mode::Element => self.step_element(byte)?,
+        }
+        if byte == b'\n' {
+            self.line += 1;
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn step_idle(&mut self, byte: u8) -> SpatialFormatResult<()> {
+        if byte.is_ascii_whitespace() || byte == RECORD_SEPARATOR {
+            return Ok(());
+        }
+        if byte != b'{' {
+            return Err(self.parse_error(
+                "Failed to parse GeoJSON feature: expected a JSON object".to_string(),
+                self.line,
+                self.pos,
+            ));
+        }
+
+        self.mode = Mode::Object;
+        self.lexer = Lexer {
+            depth: 1,
+            ..Lexer::default()
+        };
+        self.value_start = self.pos;
+        self.value_line = self.line;
+        self.saw_features = false;
+        self.expect_key = true;
+        self.pending_features = false;
+        self.pending_type = false;
+        self.is_collection = None;
+        Ok(())
+    }
+
+    fn step_object(&mut self, byte: u8) -> SpatialFormatResult<()> {
+        let at_member_level = self.lexer.depth == 1 && !self.lexer.in_string;
+        let starts_value = at_member_level && !byte.is_ascii_whitespace() && byte != b':';
+
+        // Only the `features` array of an object already known to be a `FeatureCollection`
+        // is streamed. In any other object, such as a `Feature` with a `features` foreign
+        // member, or one whose `type` comes after the array, it stays part of the value
+        if self.pending_features && starts_value {
+            self.pending_features = false;
+            if byte == b'[' && self.is_collection == Some(true) {
+                self.mode = Mode::Features;
+                self.saw_features = true;
+                // Everything before the opening quote of the `features` name
+                self.collection_head =
+                    self.buffer[self.value_start..self.string_start - 1].to_vec();
+                return Ok(());
+            }
+        }
+        if self.pending_type && starts_value && byte != b'"' {
+            self.pending_type = false;
+            self.is_collection = Some(false);
+        }
+
+        if at_member_level && byte == b'"' {
+            self.string_start = self.pos + 1;
+        }
+
+        let closed_string = self.lexer.advance(byte);
+        let member_string = self.lexer.depth == 1 && closed_string;
+
+        if member_string && self.expect_key {
+            self.expect_key = false;
+            let key = &self.buffer[self.string_start..self.pos];
+            self.pending_features = key == FEATURES_KEY;
+            self.pending_type = key == TYPE_KEY;
+        } else if member_string && self.pending_type {
+            self.pending_type = false;
+            self.is_collection =
+                Some(&self.buffer[self.string_start..self.pos] == FEATURE_COLLECTION_TYPE);
+        } else if at_member_level && byte == b',' {
+            self.expect_key = true;
+        } else if self.lexer.depth == 0 {
+            self.complete_value()?;
+        }
+        Ok(())
+    }
+
+    fn step_features(&mut self, byte: u8) -> SpatialFormatResult<()> {
+        match byte {
+            b']' => {
+                self.mode = Mode::Object;
+                self.lexer.depth = 1;
+                self.expect_key = false;
+                self.tail_start = self.pos + 1;
+            },
+            b'{' => {
+                self.mode = Mode::Element;
+                self.element_start = self.pos;
+                self.element_line = self.line;
+                self.lexer = Lexer {
+                    depth: 1,
+                    ..Lexer::default()
+                };
+            },
+            b',' => {},
+            b if b.is_ascii_whitespace() => {},
+            _ => {
+                return Err(self.parse_error(
+                    "Failed to parse GeoJSON feature: expected a JSON object".to_string(),
+                    self.line,
+                    self.pos,
+                ));
+            },
+        }
+        Ok(())
+    }
+
+    fn step_element(&mut self, byte: u8) -> SpatialFormatResult<()> {
+        self.lexer.advance(byte);
+        if self.lexer.depth > 0 {
+            return Ok(());
+        }
+
+        self.mode = Mode::Features;
+        let value = self.parse_value(self.element_start, self.element_line)?;
+        let feature = Feature::from_json_value(value).map_err(|err| {
+            self.parse_error(
+                format!("Failed to parse GeoJSON feature: {err}"),
+                self.element_line,
+                self.element_start,
+            )
+        })?;
+        self.pending.push_back(feature);
+        Ok(())
+    }
+
+    /// Handle the end of a top-level value.
+    fn complete_value(&mut self) -> SpatialFormatResult<()> {
+        self.mode = Mode::Idle;
+        self.values_seen += 1;
+        self.collection_members = None;
+
+        if self.saw_features {
+            return self.complete_collection();
+        }
+
+        let value = self.parse_value(self.value_start, self.value_line)?;
+        if let JsonValue::Object(object) = &value
+            && object.get("type").and_then(JsonValue::as_str) == Some("FeatureCollection")
+        {
+            let mut members = object.clone();
+            members.remove("type");
+            members.remove("features");
+            self.collection_members = Some(members);
+        }
+        let geojson = GeoJson::from_json_value(value).map_err(|err| {
+            self.parse_error(
+                format!("Failed to parse GeoJSON feature: {err}"),
+                self.value_line,
+                self.value_start,
+            )
+        })?;
+        self.pending.extend(geojson_to_features(geojson));
+        Ok(())
+    }
+
+    /// Parse the members of a complete collection, around its already streamed features.
+    fn complete_collection(&mut self) -> SpatialFormatResult<()> {
+        let mut raw = std::mem::take(&mut self.collection_head);
+        raw.extend_from_slice(b"\"features\":[]");
+        raw.extend_from_slice(&self.buffer[self.tail_start..=self.pos]);
+        let mut members = serde_json::from_slice::<JsonObject>(&raw).map_err(|err| {
+            self.parse_error(
+                format!("Failed to parse GeoJSON feature collection: {err}"),
+                self.value_line,
+                self.value_start,
+            )
+        })?;
+        members.remove("type");
+        members.remove("features");
+        self.collection_members = Some(members);
+        Ok(())
+    }
+
+    /// Deserialize the bytes between `start` and the current position (inclusive).
+    fn parse_value(&self, start: usize, line: u64) -> SpatialFormatResult<JsonValue> {
+        let raw = &self.buffer[start..=self.pos];
+        let text = std::str::from_utf8(raw).map_err(|err| {
+            self.parse_error(
+                format!("GeoJSON value is not valid UTF-8: {err}"),
+                line,
+                start,
+            )
+        })?;
+        serde_json::from_str(text).map_err(|err| {
+            self.parse_error(
+                format!("Failed to parse GeoJSON feature: {err}"),
+                line,
+                start,
+            )
+        })
+    }
+
+    fn parse_error(&self, message: String, line: u64, offset: usize) -> SpatialFormatReadError {
+        SpatialFormatReadError::Parse {
+            message,
+            position: Some(SourcePosition {
+                line: Some(line),
+                byte_offset: Some(self.consumed + offset as u64),
+                ..SourcePosition::default()
+            }),
+            context: Some(self.context.clone()),
+        }
+    }
+
+    /// Discard buffered bytes that are no longer needed by the current value.
+    fn compact(&mut self) {
+        let keep_from = match self.mode {
+            Mode::Idle | Mode::Features => self.pos,
+            // The members after a streamed `features` array are kept for `collection_members`
+            Mode::Object if self.saw_features => self.tail_start,
+            Mode::Object => self.value_start,
+            Mode::Element => self.element_start,
+        };
+        if keep_from == 0 {
+            return;
+        }
+
+        self.buffer.drain(..keep_from);
+        self.consumed += keep_from as u64;
+        self.pos -= keep_from;
+        self.value_start = self.value_start.saturating_sub(keep_from);
+        self.element_start = self.element_start.saturating_sub(keep_from);
+        self.string_start = self.string_start.saturating_sub(keep_from);
+        self.tail_start = self.tail_start.saturating_sub(keep_from);
+    }
+}
+
+/// Pulls [`FeatureRecord`]s from an object store byte stream through a [`GeoJsonDecoder`].
+pub struct FeatureReader {
+    bytes: BoxStream<'static, object_store::Result<Bytes>>,
+    decoder: GeoJsonDecoder,
+    exhausted: bool,
+}
+
+impl FeatureReader {
+    pub fn new(
+        bytes: BoxStream<'static, object_store::Result<Bytes>>,
+        context: impl Into<String>,
+    ) -> Self {
+        Self {
+            bytes,
+            decoder: GeoJsonDecoder::new(context),
+            exhausted: false,
+        }
+    }
+
+    /// Read up to `max` records, returning fewer only once the input is exhausted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the object store fails or the input is not valid
+    /// `GeoJSON`.
+    pub async fn next_records(&mut self, max: usize) -> SpatialFormatResult<Vec<FeatureRecord>> {
+        let mut records = Vec::with_capacity(max.min(8192));
+
+        while records.len() < max {
+            if let Some(record) = self.decoder.next_record()? {
+                records.push(record);
+                continue;
+            }
+            if self.exhausted {
+                break;
+            }
+
+            if let Some(chunk) = self.bytes.next().await {
+                let chunk = chunk.map_err(|err| SpatialFormatReadError::Io {
+                    source: std::io::Error::other(err),
+                    context: Some(self.decoder.context.clone()),
+                })?;
+                self.decoder.push(&chunk);
+            } else {
+                self.exhausted = true;
+                self.decoder.finish()?;
+            }
+        }
+
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_chunked(data: &[u8], chunk_size: usize) -> SpatialFormatResult<Vec<FeatureRecord>> {
+        let mut decoder = GeoJsonDecoder::new("test");
+        let mut records = Vec::new();
+        for chunk in data.chunks(chunk_size) {
+            decoder.push(chunk);
+            while let Some(record) = decoder.next_record()? {
+                records.push(record);
+            }
+        }
+        decoder.finish()?;
+        Ok(records)
+    }
+
+    const COLLECTION: &[u8] = br#"{
+  "type": "FeatureCollection",
+  "name": "with \"features\" in a string",
+  "features": [
+    {"type":"Feature","geometry":{"type":"Point","coordinates":[1.0,2.0]},"properties":{"name":"A [x]"}},
+    {"type":"Feature","geometry":null,"properties":{"value":42,"nested":{"features":[1]}}},
+    {"type":"Feature","geometry":{"type":"LineString","coordinates":[[0,0],[1,1]]},"properties":{"s":"}\""}}
+  ],
+  "bbox": [0, 0, 1, 2]
+}"#;
+
+    #[test]
+    fn decodes_collection_in_any_chunking() {
+        for chunk_size in [1, 2, 7, 64, COLLECTION.len()] {
+            let records = decode_chunked(COLLECTION, chunk_size).expect("decode");
+            assert_eq!(records.len(), 3, "chunk size {chunk_size}");
+            assert_eq!(records[0].properties.get("name").unwrap(), "A [x]");
+            assert!(records[1].geometry.is_none());
+            assert_eq!(records[2].properties.get("s").unwrap(), "}\"");
+        }
+    }
+
+    #[test]
+    fn keeps_collection_members_in_any_chunking() {
+        for chunk_size in [1, 2, 7, 64, COLLECTION.len()] {
+            let mut decoder = GeoJsonDecoder::new("test");
+            for chunk in COLLECTION.chunks(chunk_size) {
+                decoder.push(chunk);
+                while decoder.next_feature().expect("decode").is_some() {}
+            }
+            decoder.finish().expect("finish");
+
+            let members = decoder.collection_members().expect("collection members");
+            assert_eq!(members.len(), 2, "chunk size {chunk_size}");
+            assert_eq!(members["name"], "with \"features\" in a string");
+            assert_eq!(members["bbox"], serde_json::json!([0, 0, 1, 2]));
+        }
+    }
+
+    #[test]
+    fn buffer_is_bounded_by_single_feature() {
+        let feature = br#"{"type":"Feature","geometry":{"type":"Point","coordinates":[1,2]},"properties":{"id":1}}"#;
+        let mut decoder = GeoJsonDecoder::new("test");
+        decoder.push(br#"{"type":"FeatureCollection","features":["#);
+        assert!(decoder.next_record().unwrap().is_none());
+
+        for _ in 0..1000 {
+            decoder.push(feature);
+            decoder.push(b",");
+            assert!(decoder.next_record().unwrap().is_some());
+            assert!(decoder.buffer.len() <= feature.len() + 2);
+        }
+    }
+
+    #[test]
+    fn decodes_sequence_with_record_separators() {
+        let data = b"\x1e{\"type\":\"Feature\",\"geometry\":null,\"properties\":{\"id\":1}}\n\
+                     \x1e{\"type\":\"Point\",\"coordinates\":[1,1]}\n";
+        let records = decode_chunked(data, 5).expect("decode");
+        assert_eq!(records.len(), 2);
+        assert!(records[1].geometry.is_some());
+    }
+
+    #[test]
+    fn empty_collection_yields_no_records() {
+        let records =
+            decode_chunked(br#"{"type":"FeatureCollection","features":[]}"#, 3).expect("decode");
+        assert!(records.is_empty());
+    }
+
+    #[test]
+    fn feature_with_features_member_is_not_a_collection() {
+        let data = br#"{"type":"Feature","geometry":null,"properties":{"id":1},
+            "features":[{"type":"Feature","geometry":null,"properties":{"id":2}}]}"#;
+        for chunk_size in [1, 5, data.len()] {
+            let records = decode_chunked(data, chunk_size).expect("decode");
+            assert_eq!(records.len(), 1, "chunk size {chunk_size}");
+            assert_eq!(records[0].properties.get("id").unwrap(), 1);
+        }
+
+        // The `features` member may also come before `type`
+        let data = br#"{"features":[{"type":"Feature","geometry":null,"properties":{"id":2}}],
+            "type":"Feature","geometry":null,"properties":{"id":1}}"#;
+        for chunk_size in [1, 5, data.len()] {
+            let records = decode_chunked(data, chunk_size).expect("decode");
+            assert_eq!(records.len(), 1, "chunk size {chunk_size}");
+            assert_eq!(records[0].properties.get("id").unwrap(), 1);
+        }
+    }
+
+    #[test]
+    fn decodes_collection_with_type_after_features() {
+        let data = br#"{"features":[{"type":"Feature","geometry":null,"properties":{"id":1}}],
+            "bbox":[0,0,1,1],"type":"FeatureCollection"}"#;
+        for chunk_size in [1, 5, data.len()] {
+            let mut decoder = GeoJsonDecoder::new("test");
+            let mut records = Vec::new();
+            for chunk in data.chunks(chunk_size) {
+                decoder.push(chunk);
+                while let Some(record) = decoder.next_record().expect("decode") {
+                    records.push(record);
+                }
+            }
+            decoder.finish().expect("finish");
+            assert_eq!(records.len(), 1, "chunk size {chunk_size}");
+            let members = decoder.collection_members().expect("collection members");
+            assert_eq!(members["bbox"], serde_json::json!([0, 0, 1, 1]));
+        }
+    }
+
+    #[test]
+    fn truncated_input_fails() {
+        let err = decode_chunked(&COLLECTION[..COLLECTION.len() / 2], 16).unwrap_err();
+        assert!(err.to_string().contains("Unexpected end of input"));
+    }
+
+    #[test]
+    fn invalid_feature_reports_line() {
+        let data = b"{\"type\":\"FeatureCollection\",\"features\":[\n{\"type\":\"Feature\",}\n]}";
+        let err = decode_chunked(data, 4).unwrap_err();
+        match err {
+            SpatialFormatReadError::Parse {
+                message, position, ..
+            } => {
+                assert!(message.contains("Failed to parse GeoJSON feature"));
+                assert_eq!(position.unwrap().line, Some(2));
+            },
+            _ => panic!("Expected Parse error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn feature_reader_batches_records() {
+        let chunks: Vec<object_store::Result<Bytes>> = COLLECTION
+            .chunks(10)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect();
+        let mut reader = FeatureReader::new(futures::stream::iter(chunks).boxed(), "test");
+
+        assert_eq!(reader.next_records(2).await.unwrap().len(), 2);
+        assert_eq!(reader.next_records(2).await.unwrap().len(), 1);
+        assert!(reader.next_records(2).await.unwrap().is_empty());
+    }
+}
@@ -6,19 +6,154 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use datafusion::datasource::TableProvider;
+use datafusion::datasource::listing::ListingTableUrl;
+use datafusion::datasource::physical_plan::{FileGroup, FileSinkConfig};
 use datafusion::execution::context::SessionState;
+use datafusion::execution::object_store::ObjectStoreUrl;
 use datafusion::physical_plan::ExecutionPlan;
 use geoetl_core_common::{
-    DataReader, DataWriter, Driver, FormatFactory, FormatOptions, SupportStatus,
+    DataReader, DataWriter, Driver, FormatFactory, FormatOption, FormatOptions, OptionType,
+    OptionValues, SupportStatus, WriteMode,
 };
 use std::sync::Arc;
 
-use crate::{GeoJsonFormatOptions, file_source};
+use crate::{
+    GeoJsonFormatOptions, GeoJsonSink, GeoJsonWriterExec, GeoJsonWriterOptions, file_source,
+    non_wgs84_crs,
+};
+
+/// Open options understood by the `GeoJSON` reader.
+const OPEN_OPTIONS: &[FormatOption] = &[
+    FormatOption::new(
+        "GEOMETRY_COLUMN",
+        OptionType::String,
+        "Name of the geometry column in the output schema",
+    )
+    .with_default("geometry"),
+    FormatOption::new(
+        "BATCH_SIZE",
+        OptionType::Integer,
+        "Number of features per record batch",
+    )
+    .with_default("8192"),
+    FormatOption::new(
+        "SCHEMA_INFER_MAX_FEATURES",
+        OptionType::Integer,
+        "Maximum number of features sampled for schema inference",
+    )
+    .with_default("1024"),
+    FormatOption::new(
+        "NESTED_TYPES",
+        OptionType::Boolean,
+        "Infer List, Struct and Map columns for array and object properties",
+    )
+    .with_default("NO"),
+    FormatOption::new(
+        "ID_COLUMN",
+        OptionType::String,
+        "Column receiving each feature's id member",
+    ),
+    FormatOption::new(
+        "FOREIGN_MEMBERS",
+        OptionType::Boolean,
+        "Expose foreign members as JSON text columns",
+    )
+    .with_default("NO"),
+];
+
+/// Creation options understood by the `GeoJSON` writer.
+const CREATION_OPTIONS: &[FormatOption] = &[
+    FormatOption::new(
+        "GEOMETRY_COLUMN",
+        OptionType::String,
+        "Column written as the feature geometry",
+    )
+    .with_default("geometry"),
+    FormatOption::new(
+        "FEATURE_COLLECTION",
+        OptionType::Boolean,
+        "Write a FeatureCollection; NO writes newline-delimited features",
+    )
+    .with_default("YES"),
+    FormatOption::new("PRETTY", OptionType::Boolean, "Pretty-print the output").with_default("NO"),
+    FormatOption::new(
+        "ID_COLUMN",
+        OptionType::String,
+        "Column written as the feature id member",
+    ),
+    FormatOption::new(
+        "FOREIGN_MEMBERS",
+        OptionType::String,
+        "Comma-separated columns of JSON text written as foreign members",
+    ),
+    FormatOption::new(
+        "WRITE_BBOX",
+        OptionType::Boolean,
+        "Write feature and collection bounding boxes",
+    )
+    .with_default("NO"),
+    FormatOption::new(
+        "COORDINATE_PRECISION",
+        OptionType::Integer,
+        "Number of decimal places coordinates are rounded to (full precision when unset)",
+    ),
+];
 
-/// `GeoJSON` format options wrapper for the factory system.
 impl FormatOptions for GeoJsonFormatOptions {
-    fn as_any(&self) -> Box<dyn std::any::Any + Send> {
-        Box::new(self.clone())
+    fn from_option_values(values: &OptionValues) -> Result<Self> {
+        let mut options = GeoJsonFormatOptions::default();
+        if let Some(name) = values.get("GEOMETRY_COLUMN") {
+            options = options.with_geometry_column_name(name);
+        }
+        if let Some(batch_size) = values.get_usize("BATCH_SIZE")? {
+            options = options.with_batch_size(batch_size);
+        }
+        if let Some(limit) = values.get_usize("SCHEMA_INFER_MAX_FEATURES")? {
+            options = options.with_schema_infer_max_features(Some(limit));
+        }
+        if let Some(nested_types) = values.get_bool("NESTED_TYPES")? {
+            options = options.with_nested_types(nested_types);
+        }
+        if let Some(name) = values.get("ID_COLUMN") {
+            options = options.with_id_column(name);
+        }
+        if let Some(foreign_members) = values.get_bool("FOREIGN_MEMBERS")? {
+            options = options.with_foreign_members(foreign_members);
+        }
+        Ok(options)
+    }
+}
+
+impl FormatOptions for GeoJsonWriterOptions {
+    fn from_option_values(values: &OptionValues) -> Result<Self> {
+        let mut options = GeoJsonWriterOptions::default();
+        if let Some(name) = values.get("GEOMETRY_COLUMN") {
+            options = options.with_geometry_column(name);
+        }
+        if let Some(feature_collection) = values.get_bool("FEATURE_COLLECTION")? {
+            options = options.with_feature_collection(feature_collection);
+        }
+        if let Some(pretty_print) = values.get_bool("PRETTY")? {
+            options = options.with_pretty_print(pretty_print);
+        }
+        if let Some(name) = values.get("ID_COLUMN") {
+            options = options.with_id_column(name);
+        }
+        if let Some(columns) = values.get("FOREIGN_MEMBERS") {
+            options = options.with_foreign_member_columns(
+                columns
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|column| !column.is_empty()),
+            );
+        }
+        if let Some(bbox) = values.get_bool("WRITE_BBOX")? {
+            options = options.with_bbox(bbox);
+        }
+        if let Some(decimals) = values.get_usize("COORDINATE_PRECISION")? {
+            options = options.with_coordinate_precision(u32::try_from(decimals)?);
+        }
+        Ok(options)
     }
 }
 
@@ -31,14 +166,12 @@ impl DataReader for GeoJsonReader {
         &self,
         state: &SessionState,
         path: &str,
-        options: Box<dyn std::any::Any + Send>,
+        options: &OptionValues,
     ) -> Result<Arc<dyn TableProvider>> {
-        let geojson_options = options
-            .downcast::<GeoJsonFormatOptions>()
-            .map_err(|_| anyhow::anyhow!("Invalid options type for GeoJSON reader"))?;
+        let geojson_options = GeoJsonFormatOptions::from_option_values(options)?;
 
         let table =
-            file_source::create_geojson_table_provider(state, path, *geojson_options).await?;
+            file_source::create_geojson_table_provider(state, path, geojson_options).await?;
         Ok(table)
     }
 }
@@ -50,15 +183,36 @@ struct GeoJsonWriter;
 impl DataWriter for GeoJsonWriter {
     async fn create_writer_plan(
         &self,
-        _input: Arc<dyn ExecutionPlan>,
-        _path: &str,
-        _options: Box<dyn std::any::Any + Send>,
+        input: Arc<dyn ExecutionPlan>,
+        path: &str,
+        mode: WriteMode,
+        options: &OptionValues,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        // TODO: Implement writer plan creation
-        // This requires creating a GeoJsonSink with FileSinkConfig
-        Err(anyhow::anyhow!(
-            "GeoJSON writer not yet implemented in factory"
-        ))
+        let writer_options = GeoJsonWriterOptions::from_option_values(options)?;
+        if let Some(crs) = non_wgs84_crs(&input.schema(), &writer_options.geometry_column_name) {
+            log::warn!(
+                "Geometry column '{}' is in {crs}, but GeoJSON requires WGS 84 coordinates; \
+                 reproject with --t-srs EPSG:4326",
+                writer_options.geometry_column_name
+            );
+        }
+
+        let config = FileSinkConfig {
+            original_url: path.to_string(),
+            object_store_url: ObjectStoreUrl::local_filesystem(),
+            file_group: FileGroup::default(),
+            table_paths: vec![ListingTableUrl::parse(path)?],
+            output_schema: input.schema(),
+            table_partition_cols: vec![],
+            insert_op: mode.insert_op(),
+            keep_partition_by_columns: false,
+            file_extension: "geojson".to_string(),
+        };
+        let sink = Arc::new(
+            GeoJsonSink::new(config, writer_options).with_create_new(mode == WriteMode::Fail),
+        );
+
+        Ok(Arc::new(GeoJsonWriterExec::new(input, sink, None)))
     }
 }
 
@@ -83,6 +237,45 @@ impl FormatFactory for GeoJsonFormatFactory {
     fn create_writer(&self) -> Option<Arc<dyn DataWriter>> {
         Some(Arc::new(GeoJsonWriter))
     }
+
+    fn open_options(&self) -> Vec<FormatOption> {
+        OPEN_OPTIONS.to_vec()
+    }
+
+    fn creation_options(&self) -> Vec<FormatOption> {
+        CREATION_OPTIONS.to_vec()
+    }
+
+    fn extensions(&self) -> Vec<&'static str> {
+        vec!["geojson", "json"]
+    }
+
+    fn probe(&self, header: &[u8]) -> bool {
+        is_geojson_header(header)
+    }
+}
+
+/// Returns `true` if `header` opens a `GeoJSON` feature collection or feature.
+///
+/// The document must start with an object (after an optional BOM and whitespace) that has a
+/// `"type"` member naming `FeatureCollection` or `Feature`, or a `"features"` array. Members
+/// are matched textually, so the probe works on a truncated header.
+fn is_geojson_header(header: &[u8]) -> bool {
+    let header = header.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(header);
+    let text = String::from_utf8_lossy(header);
+    if !text.trim_start().starts_with('{') {
+        return false;
+    }
+
+    let member_starts_with = |key: &str, value: &str| {
+        text.match_indices(key).any(|(start, _)| {
+            text[start + key.len()..]
+                .trim_start()
+                .strip_prefix(':')
+                .is_some_and(|rest| rest.trim_start().starts_with(value))
+        })
+    };
+    member_starts_with("\"type\"", "\"Feature") || member_starts_with("\"features\"", "[")
 }
 
 /// Registers the `GeoJSON` format with the global driver registry.
@@ -92,3 +285,25 @@ pub fn register_geojson_format() {
     let registry = geoetl_core_common::driver_registry();
     registry.register(Arc::new(GeoJsonFormatFactory));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probe_recognises_geojson() {
+        let factory = GeoJsonFormatFactory;
+        assert!(factory.probe(br#"{"type":"FeatureCollection","features":[]}"#));
+        assert!(factory.probe(b"\xEF\xBB\xBF\n  {\n  \"type\" : \"Feature\", \"geometry\": null"));
+        assert!(factory.probe(br#"{"name":"roads","features":[{"#));
+    }
+
+    #[test]
+    fn test_probe_rejects_other_content() {
+        let factory = GeoJsonFormatFactory;
+        assert!(!factory.probe(b"id,name,wkt\n1,a,\"POINT(1 1)\""));
+        assert!(!factory.probe(br#"{"type":"Point","coordinates":[1,2]}"#));
+        assert!(!factory.probe(br#"[{"type":"Feature"}]"#));
+        assert!(!factory.probe(b"PAR1"));
+    }
+}
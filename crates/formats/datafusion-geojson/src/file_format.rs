@@ -14,11 +14,12 @@ use datafusion::physical_plan::ExecutionPlan;
 use datafusion_common::Statistics;
 use datafusion_session::Session;
 use datafusion_shared::SpatialFormatReadError;
-use geoarrow_schema::{CoordType, GeometryType};
+use geoarrow_schema::{CoordType, Crs, GeometryType, Metadata};
 use object_store::{ObjectMeta, ObjectStore};
 
+use crate::decoder::FeatureReader;
 use crate::file_source::{GeoJsonExec, GeoJsonFileSource};
-use crate::parser::{FeatureRecord, parse_geojson_bytes};
+use crate::parser::FeatureRecord;
 
 /// Options controlling `GeoJSON` reading behaviour.
 #[derive(Debug, Clone)]
@@ -33,6 +34,23 @@ pub struct GeoJsonFormatOptions {
     pub geometry_column_name: String,
     /// `GeoArrow` geometry type to emit.
     pub geometry_type: GeometryType,
+    /// Infer nested Arrow types for array and object properties.
+    ///
+    /// When enabled, homogeneous arrays become `List` columns, objects with a consistent set
+    /// of keys become `Struct` columns and objects with varying keys but uniform values become
+    /// `Map` columns. Anything else falls back to JSON text. When disabled (the default), all
+    /// arrays and objects are stored as JSON text in `Utf8` columns.
+    pub nested_types: bool,
+    /// Name of the column that receives each feature's `id` member, if any.
+    ///
+    /// The column is `Int64` when all sampled ids are integers and `Utf8` otherwise. It takes
+    /// precedence over a property with the same name.
+    pub id_column: Option<String>,
+    /// Expose foreign members (top-level feature members other than `type`, `id`, `bbox`,
+    /// `geometry` and `properties`) as `Utf8` columns holding JSON text.
+    ///
+    /// Properties take precedence over foreign members with the same name.
+    pub foreign_members: bool,
 }
 
 impl Default for GeoJsonFormatOptions {
@@ -42,8 +60,15 @@ impl Default for GeoJsonFormatOptions {
             batch_size: 8192,
             file_extension: ".geojson".to_string(),
             geometry_column_name: "geometry".to_string(),
-            geometry_type: GeometryType::new(Arc::default())
-                .with_coord_type(CoordType::Interleaved),
+            // RFC 7946 GeoJSON is always WGS 84 longitude/latitude
+            geometry_type: GeometryType::new(Arc::new(Metadata::new(
+                Crs::from_authority_code("OGC:CRS84".to_string()),
+                None,
+            )))
+            .with_coord_type(CoordType::Interleaved),
+            nested_types: false,
+            id_column: None,
+            foreign_members: false,
         }
     }
 }
@@ -84,6 +109,24 @@ impl GeoJsonFormatOptions {
         self
     }
 
+    #[must_use]
+    pub fn with_nested_types(mut self, nested_types: bool) -> Self {
+        self.nested_types = nested_types;
+        self
+    }
+
+    #[must_use]
+    pub fn with_id_column(mut self, name: impl Into<String>) -> Self {
+        self.id_column = Some(name.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_foreign_members(mut self, foreign_members: bool) -> Self {
+        self.foreign_members = foreign_members;
+        self
+    }
+
     pub(crate) fn file_extension_with_dot(&self) -> String {
         if self.file_extension.starts_with('.') {
             self.file_extension.clone()
@@ -142,7 +185,7 @@ impl FileFormat for GeoJsonFormat {
         let object = &objects[0];
         let location = object.location.clone();
 
-        let bytes = store
+        let stream = store
             .get(&object.location)
             .await
             .map_err(|err| {
@@ -151,23 +194,25 @@ impl FileFormat for GeoJsonFormat {
                     context: Some(location.to_string()),
                 })
             })?
-            .bytes()
-            .await
-            .map_err(|err| {
-                datafusion::error::DataFusionError::from(SpatialFormatReadError::Io {
-                    source: std::io::Error::other(err),
-                    context: Some(location.to_string()),
-                })
-            })?;
-
-        let records = parse_geojson_bytes(
-            &bytes,
-            self.options.schema_infer_max_features,
-            location.to_string(),
-        )
-        .map_err(datafusion::error::DataFusionError::from)?;
+            .into_stream();
+
+        let mut reader = FeatureReader::new(stream, location.to_string());
+        let mut remaining = self.options.schema_infer_max_features.unwrap_or(usize::MAX);
+        let mut inferred = InferredColumns::default();
+
+        while remaining > 0 {
+            let records = reader
+                .next_records(remaining.min(self.options.batch_size))
+                .await
+                .map_err(datafusion::error::DataFusionError::from)?;
+            if records.is_empty() {
+                break;
+            }
+            remaining -= records.len();
+            update_inferred_types(&mut inferred, &records, &self.options);
+        }
 
-        let schema = infer_schema_from_records(&records, &self.options);
+        let schema = schema_from_inferred_types(&inferred, &self.options);
 
         Ok(Arc::new(schema))
     }
@@ -204,9 +249,10 @@ impl FileFormat for GeoJsonFormat {
     ) -> Result<Arc<dyn ExecutionPlan>> {
         use datafusion::logical_expr::dml::InsertOp;
 
-        if conf.insert_op != InsertOp::Append {
+        // Appends and overwrites are handled by the sink
+        if conf.insert_op == InsertOp::Replace {
             return Err(datafusion::error::DataFusionError::NotImplemented(
-                "Overwrites are not implemented yet for GeoJSON".to_string(),
+                "Replacing rows is not supported for GeoJSON".to_string(),
             ));
         }
 
@@ -227,22 +273,38 @@ impl FileFormat for GeoJsonFormat {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum InferredScalarType {
+/// Type inferred for a single `GeoJSON` property while sampling features.
+///
+/// Arrays and objects are only tracked structurally when nested type inference is enabled;
+/// otherwise they collapse to `Utf8` and are stored as JSON text.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum InferredType {
     Null,
     Boolean,
     Int64,
     Float64,
     Utf8,
+    List(Box<InferredType>),
+    Object {
+        /// Union of all keys seen, with the type inferred for each of them.
+        fields: BTreeMap<String, InferredType>,
+        /// Type inferred across every value, regardless of key.
+        values: Box<InferredType>,
+        /// Whether every object observed so far had exactly the same key set.
+        consistent_keys: bool,
+    },
+    /// Structurally incompatible values; stored as JSON text.
+    Json,
 }
 
-impl InferredScalarType {
-    fn update(self, value: &geojson::JsonValue) -> Self {
+impl InferredType {
+    pub(crate) fn update(&mut self, value: &geojson::JsonValue, nested: bool) {
         use geojson::JsonValue;
-        match value {
-            JsonValue::Null => self,
+        let next = match value {
+            JsonValue::Null => return,
             JsonValue::Bool(_) => match self {
                 Self::Null | Self::Boolean => Self::Boolean,
+                Self::List(_) | Self::Object { .. } | Self::Json => Self::Json,
                 _ => Self::Utf8,
             },
             JsonValue::Number(n) => {
@@ -256,39 +318,196 @@ impl InferredScalarType {
                         }
                     },
                     Self::Float64 => Self::Float64,
+                    Self::List(_) | Self::Object { .. } | Self::Json => Self::Json,
                     _ => Self::Utf8,
                 }
             },
-            JsonValue::String(_) | JsonValue::Array(_) | JsonValue::Object(_) => Self::Utf8,
-        }
+            JsonValue::String(_) => match self {
+                Self::List(_) | Self::Object { .. } | Self::Json => Self::Json,
+                _ => Self::Utf8,
+            },
+            JsonValue::Array(_) | JsonValue::Object(_) if !nested => Self::Utf8,
+            JsonValue::Array(items) => match self {
+                Self::Null => {
+                    let mut element = Self::Null;
+                    for item in items {
+                        element.update(item, nested);
+                    }
+                    Self::List(Box::new(element))
+                },
+                Self::List(element) => {
+                    for item in items {
+                        element.update(item, nested);
+                    }
+                    return;
+                },
+                _ => Self::Json,
+            },
+            JsonValue::Object(object) => match self {
+                Self::Null => {
+                    let mut fields = BTreeMap::new();
+                    let mut values = Self::Null;
+                    for (key, value) in object {
+                        let mut field = Self::Null;
+                        field.update(value, nested);
+                        fields.insert(key.clone(), field);
+                        values.update(value, nested);
+                    }
+                    Self::Object {
+                        fields,
+                        values: Box::new(values),
+                        consistent_keys: true,
+                    }
+                },
+                Self::Object {
+                    fields,
+                    values,
+                    consistent_keys,
+                } => {
+                    if fields.len() != object.len()
+                        || !object.keys().all(|key| fields.contains_key(key))
+                    {
+                        *consistent_keys = false;
+                    }
+                    for (key, value) in object {
+                        fields
+                            .entry(key.clone())
+                            .or_insert(Self::Null)
+                            .update(value, nested);
+                        values.update(value, nested);
+                    }
+                    return;
+                },
+                _ => Self::Json,
+            },
+        };
+        *self = next;
     }
 
-    fn to_datatype(self) -> DataType {
+    pub(crate) fn to_datatype(&self) -> DataType {
+        self.structured_datatype().unwrap_or(DataType::Utf8)
+    }
+
+    /// Arrow type of this inferred type, or `None` when it (or anything nested in it) fell
+    /// back to JSON text, in which case the enclosing array or object is stored as JSON text
+    /// as a whole.
+    ///
+    /// Objects with a consistent key set become a `Struct` when every field is structured,
+    /// and otherwise a `Map` when their values share a structured type.
+    fn structured_datatype(&self) -> Option<DataType> {
         match self {
-            Self::Null | Self::Utf8 => DataType::Utf8,
-            Self::Boolean => DataType::Boolean,
-            Self::Int64 => DataType::Int64,
-            Self::Float64 => DataType::Float64,
+            Self::Null | Self::Utf8 => Some(DataType::Utf8),
+            Self::Boolean => Some(DataType::Boolean),
+            Self::Int64 => Some(DataType::Int64),
+            Self::Float64 => Some(DataType::Float64),
+            Self::Json => None,
+            Self::List(element) => element
+                .structured_datatype()
+                .map(|element| DataType::List(Arc::new(Field::new_list_field(element, true)))),
+            Self::Object {
+                fields,
+                values,
+                consistent_keys,
+            } => {
+                if *consistent_keys && !fields.is_empty() {
+                    let struct_fields = fields
+                        .iter()
+                        .map(|(name, ty)| {
+                            ty.structured_datatype()
+                                .map(|data_type| Field::new(name, data_type, true))
+                        })
+                        .collect::<Option<Vec<_>>>();
+                    if let Some(struct_fields) = struct_fields {
+                        return Some(DataType::Struct(struct_fields.into()));
+                    }
+                }
+                let values = values.structured_datatype()?;
+                let entries = Field::new(
+                    "entries",
+                    DataType::Struct(
+                        vec![
+                            Field::new("keys", DataType::Utf8, false),
+                            Field::new("values", values, true),
+                        ]
+                        .into(),
+                    ),
+                    false,
+                );
+                Some(DataType::Map(Arc::new(entries), false))
+            },
         }
     }
 }
 
-fn infer_schema_from_records(records: &[FeatureRecord], options: &GeoJsonFormatOptions) -> Schema {
-    let mut inferred: BTreeMap<String, InferredScalarType> = BTreeMap::new();
+/// Column types inferred while sampling features.
+#[derive(Debug, Default)]
+struct InferredColumns {
+    properties: BTreeMap<String, InferredType>,
+    foreign_members: BTreeMap<String, InferredType>,
+    id: Option<InferredType>,
+}
 
+fn update_inferred_types(
+    inferred: &mut InferredColumns,
+    records: &[FeatureRecord],
+    options: &GeoJsonFormatOptions,
+) {
     for record in records {
         for (key, value) in &record.properties {
-            let entry = inferred
+            inferred
+                .properties
                 .entry(key.clone())
-                .or_insert(InferredScalarType::Null);
-            *entry = entry.update(value);
+                .or_insert(InferredType::Null)
+                .update(value, options.nested_types);
+        }
+        if options.foreign_members {
+            for key in record.foreign_members.keys() {
+                inferred
+                    .foreign_members
+                    .entry(key.clone())
+                    .or_insert(InferredType::Json);
+            }
+        }
+        if options.id_column.is_some() {
+            let id = inferred.id.get_or_insert(InferredType::Null);
+            if let Some(value) = &record.id {
+                id.update(value, false);
+            }
         }
     }
+}
+
+fn schema_from_inferred_types(
+    inferred: &InferredColumns,
+    options: &GeoJsonFormatOptions,
+) -> Schema {
+    let id_column = options.id_column.as_deref();
+    let mut fields = Vec::new();
+
+    if let (Some(name), Some(ty)) = (id_column, &inferred.id) {
+        let data_type = match ty {
+            InferredType::Int64 => DataType::Int64,
+            _ => DataType::Utf8,
+        };
+        fields.push(Field::new(name, data_type, true));
+    }
 
-    let mut fields: Vec<Field> = inferred
-        .into_iter()
-        .map(|(name, ty)| Field::new(name, ty.to_datatype(), true))
-        .collect();
+    fields.extend(
+        inferred
+            .properties
+            .iter()
+            .filter(|(name, _)| Some(name.as_str()) != id_column)
+            .map(|(name, ty)| Field::new(name, ty.to_datatype(), true)),
+    );
+    fields.extend(
+        inferred
+            .foreign_members
+            .iter()
+            .filter(|(name, _)| {
+                Some(name.as_str()) != id_column && !inferred.properties.contains_key(*name)
+            })
+            .map(|(name, ty)| Field::new(name, ty.to_datatype(), true)),
+    );
 
     let geometry_field = options
         .geometry_type
@@ -298,6 +517,13 @@ fn infer_schema_from_records(records: &[FeatureRecord], options: &GeoJsonFormatO
     Schema::new(fields)
 }
 
+#[cfg(test)]
+fn infer_schema_from_records(records: &[FeatureRecord], options: &GeoJsonFormatOptions) -> Schema {
+    let mut inferred = InferredColumns::default();
+    update_inferred_types(&mut inferred, records, options);
+    schema_from_inferred_types(&inferred, options)
+}
+
 /// Helper to detect file extensions from a provided path.
 pub(crate) fn detect_file_extension(path: &str) -> Option<String> {
     std::path::Path::new(path)
@@ -343,10 +569,12 @@ mod tests {
             FeatureRecord {
                 properties: props_a,
                 geometry: None,
+                ..FeatureRecord::default()
             },
             FeatureRecord {
                 properties: props_b,
                 geometry: None,
+                ..FeatureRecord::default()
             },
         ];
 
@@ -359,4 +587,169 @@ mod tests {
         assert_eq!(schema.field(2).data_type(), &DataType::Float64);
         assert_eq!(schema.field(3).name(), "geometry");
     }
+
+    fn records_with(values: &[JsonValue]) -> Vec<FeatureRecord> {
+        values
+            .iter()
+            .map(|value| FeatureRecord {
+                properties: [("prop".to_string(), value.clone())].into_iter().collect(),
+                geometry: None,
+                ..FeatureRecord::default()
+            })
+            .collect()
+    }
+
+    fn infer_nested(values: &[JsonValue]) -> DataType {
+        let options = GeoJsonFormatOptions::default().with_nested_types(true);
+        let schema = infer_schema_from_records(&records_with(values), &options);
+        schema.field(0).data_type().clone()
+    }
+
+    #[test]
+    fn nested_values_are_json_text_by_default() {
+        let records = records_with(&[serde_json::json!(["a", "b"]), serde_json::json!({"key": 1})]);
+        let schema = infer_schema_from_records(&records, &GeoJsonFormatOptions::default());
+
+        assert_eq!(schema.field(0).data_type(), &DataType::Utf8);
+    }
+
+    #[test]
+    fn infer_list_from_homogeneous_arrays() {
+        let data_type = infer_nested(&[
+            serde_json::json!(["a", "b"]),
+            serde_json::json!([]),
+            JsonValue::Null,
+            serde_json::json!(["c"]),
+        ]);
+
+        assert_eq!(
+            data_type,
+            DataType::List(Arc::new(Field::new_list_field(DataType::Utf8, true)))
+        );
+        assert_eq!(
+            infer_nested(&[serde_json::json!([1, 2]), serde_json::json!([2.5])]),
+            DataType::List(Arc::new(Field::new_list_field(DataType::Float64, true)))
+        );
+    }
+
+    #[test]
+    fn infer_struct_from_consistent_objects() {
+        let data_type = infer_nested(&[
+            serde_json::json!({"height": 10, "tags": ["a"]}),
+            serde_json::json!({"height": 12.5, "tags": []}),
+        ]);
+
+        let DataType::Struct(fields) = data_type else {
+            panic!("expected struct, got {data_type:?}");
+        };
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].name(), "height");
+        assert_eq!(fields[0].data_type(), &DataType::Float64);
+        assert!(matches!(fields[1].data_type(), DataType::List(_)));
+    }
+
+    #[test]
+    fn infer_map_from_objects_with_varying_keys() {
+        let data_type = infer_nested(&[
+            serde_json::json!({"en": "Tower", "de": "Turm"}),
+            serde_json::json!({"fr": "Tour"}),
+        ]);
+
+        let DataType::Map(entries, false) = data_type else {
+            panic!("expected map, got {data_type:?}");
+        };
+        let DataType::Struct(fields) = entries.data_type() else {
+            panic!("expected struct entries");
+        };
+        assert_eq!(fields[0].data_type(), &DataType::Utf8);
+        assert_eq!(fields[1].data_type(), &DataType::Utf8);
+    }
+
+    #[test]
+    fn map_objects_are_structured_inside_lists_and_structs() {
+        let map = InferredType::Object {
+            fields: [("en".to_string(), InferredType::Json)]
+                .into_iter()
+                .collect(),
+            values: Box::new(InferredType::Utf8),
+            consistent_keys: true,
+        };
+        assert!(matches!(
+            map.structured_datatype(),
+            Some(DataType::Map(_, false))
+        ));
+
+        let data_type = infer_nested(&[serde_json::json!([{"en": "Tower"}, {"fr": "Tour"}])]);
+        let DataType::List(element) = data_type else {
+            panic!("expected list, got {data_type:?}");
+        };
+        assert!(matches!(element.data_type(), DataType::Map(_, false)));
+
+        let data_type = infer_nested(&[
+            serde_json::json!({"names": {"en": "Tower"}}),
+            serde_json::json!({"names": {"fr": "Tour"}}),
+        ]);
+        let DataType::Struct(fields) = data_type else {
+            panic!("expected struct, got {data_type:?}");
+        };
+        assert!(matches!(fields[0].data_type(), DataType::Map(_, false)));
+    }
+
+    #[test]
+    fn heterogeneous_values_fall_back_to_json_text() {
+        assert_eq!(
+            infer_nested(&[serde_json::json!([1]), serde_json::json!({"a": 1})]),
+            DataType::Utf8
+        );
+        assert_eq!(
+            infer_nested(&[serde_json::json!("text"), serde_json::json!([1])]),
+            DataType::Utf8
+        );
+        assert_eq!(
+            infer_nested(&[serde_json::json!([[1], {"a": 1}])]),
+            DataType::Utf8
+        );
+        assert_eq!(
+            infer_nested(&[serde_json::json!({"a": 1}), serde_json::json!({"b": [1]})]),
+            DataType::Utf8
+        );
+    }
+
+    #[test]
+    fn infer_id_and_foreign_member_columns() {
+        let mut foreign = JsonObject::new();
+        foreign.insert("source".to_string(), JsonValue::String("{}".to_string()));
+        let records = vec![
+            FeatureRecord {
+                id: Some(JsonValue::Number(1.into())),
+                foreign_members: foreign,
+                ..FeatureRecord::default()
+            },
+            FeatureRecord {
+                id: Some(JsonValue::Number(2.into())),
+                ..FeatureRecord::default()
+            },
+        ];
+
+        let options = GeoJsonFormatOptions::default()
+            .with_id_column("fid")
+            .with_foreign_members(true);
+        let schema = infer_schema_from_records(&records, &options);
+
+        assert_eq!(schema.fields().len(), 3);
+        assert_eq!(schema.field(0).name(), "fid");
+        assert_eq!(schema.field(0).data_type(), &DataType::Int64);
+        assert_eq!(schema.field(1).name(), "source");
+        assert_eq!(schema.field(1).data_type(), &DataType::Utf8);
+
+        let mut records = records;
+        records[1].id = Some(JsonValue::String("b".to_string()));
+        let schema = infer_schema_from_records(&records, &GeoJsonFormatOptions::default());
+        assert_eq!(schema.fields().len(), 1);
+        let schema = infer_schema_from_records(
+            &records,
+            &GeoJsonFormatOptions::default().with_id_column("fid"),
+        );
+        assert_eq!(schema.field(0).data_type(), &DataType::Utf8);
+    }
 }
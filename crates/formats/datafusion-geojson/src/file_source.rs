@@ -11,19 +11,23 @@ use datafusion::datasource::listing::{
     ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
 };
 use datafusion::datasource::physical_plan::{
-    FileGroup, FileOpener, FileScanConfig, FileSource, FileStream,
+    FileGroup, FileOpener, FileScanConfig, FileScanConfigBuilder, FileSource, FileStream,
 };
 use datafusion::error::Result;
 use datafusion::execution::TaskContext;
 use datafusion::execution::context::SessionState;
 use datafusion::physical_plan::execution_plan::{Boundedness, EmissionType};
+use datafusion::physical_plan::filter_pushdown::{
+    ChildPushdownResult, FilterPushdownPhase, FilterPushdownPropagation, PushedDown,
+};
 use datafusion::physical_plan::metrics::ExecutionPlanMetricsSet;
 use datafusion::physical_plan::{
     DisplayAs, DisplayFormatType, ExecutionPlan, PlanProperties, SendableRecordBatchStream,
 };
+use datafusion_common::config::ConfigOptions;
 use datafusion_common::{DataFusionError, Statistics};
-use datafusion_physical_expr::EquivalenceProperties;
-use datafusion_shared::SpatialFormatReadError;
+use datafusion_physical_expr::{EquivalenceProperties, PhysicalExpr};
+use datafusion_shared::{SpatialFilter, SpatialFormatReadError};
 use object_store::ObjectStore;
 use object_store::aws::AmazonS3Builder;
 use object_store::azure::MicrosoftAzureBuilder;
@@ -118,6 +122,7 @@ pub struct GeoJsonFileSource {
     projection: Option<Vec<usize>>,
     statistics: Option<Statistics>,
     metrics: ExecutionPlanMetricsSet,
+    spatial_filters: Vec<SpatialFilter>,
 }
 
 impl GeoJsonFileSource {
@@ -129,6 +134,7 @@ impl GeoJsonFileSource {
             projection: None,
             statistics: None,
             metrics: ExecutionPlanMetricsSet::new(),
+            spatial_filters: Vec::new(),
         }
     }
 
@@ -163,7 +169,8 @@ impl FileSource for GeoJsonFileSource {
         let batch_size = self.resolve_batch_size(base_config);
 
         let opener = GeoJsonOpener::new(self.options.clone(), schema, projection, object_store)
-            .with_batch_size(batch_size);
+            .with_batch_size(batch_size)
+            .with_spatial_filters(self.spatial_filters.clone());
 
         Arc::new(opener)
     }
@@ -210,10 +217,40 @@ impl FileSource for GeoJsonFileSource {
         "geojson"
     }
 
+    /// Features whose geometry lies outside an `st_intersects` filter on the geometry column
+    /// are skipped before they are converted to Arrow. The filters are inexact, so they are
+    /// still reported as not pushed down.
+    fn try_pushdown_filters(
+        &self,
+        filters: Vec<Arc<dyn PhysicalExpr>>,
+        _config: &ConfigOptions,
+    ) -> Result<FilterPushdownPropagation<Arc<dyn FileSource>>> {
+        let result = FilterPushdownPropagation::with_parent_pushdown_result(vec![
+            PushedDown::No;
+            filters.len()
+        ]);
+        let spatial_filters: Vec<SpatialFilter> = filters
+            .iter()
+            .filter_map(SpatialFilter::from_predicate)
+            .filter(|filter| filter.column == self.options.geometry_column_name)
+            .collect();
+        if spatial_filters.is_empty() {
+            return Ok(result);
+        }
+
+        let mut source = self.clone();
+        source.spatial_filters.extend(spatial_filters);
+        Ok(result.with_updated_node(Arc::new(source)))
+    }
+
     fn fmt_extra(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
         match t {
             DisplayFormatType::Default | DisplayFormatType::Verbose => {
-                write!(f, ", geometry_column={}", self.options.geometry_column_name)
+                write!(f, ", geometry_column={}", self.options.geometry_column_name)?;
+                for filter in &self.spatial_filters {
+                    write!(f, ", spatial_filter={filter}")?;
+                }
+                Ok(())
             },
             DisplayFormatType::TreeRender => Ok(()),
         }
@@ -251,7 +288,8 @@ impl DisplayAs for GeoJsonExec {
         match t {
             DisplayFormatType::Default | DisplayFormatType::Verbose => {
                 let count: usize = self.config.file_groups.iter().map(FileGroup::len).sum();
-                write!(f, "GeoJsonExec: file_groups={{count={count}}}")
+                write!(f, "GeoJsonExec: file_groups={{count={count}}}")?;
+                self.config.file_source.fmt_extra(t, f)
             },
             DisplayFormatType::TreeRender => Ok(()),
         }
@@ -286,6 +324,34 @@ impl ExecutionPlan for GeoJsonExec {
         Ok(self)
     }
 
+    /// Offers the filters above the scan to the file source, as `DataSourceExec` does
+    fn handle_child_pushdown_result(
+        &self,
+        _phase: FilterPushdownPhase,
+        child_pushdown_result: ChildPushdownResult,
+        config: &ConfigOptions,
+    ) -> Result<FilterPushdownPropagation<Arc<dyn ExecutionPlan>>> {
+        let filters = child_pushdown_result
+            .parent_filters
+            .into_iter()
+            .map(|f| f.filter)
+            .collect();
+        let result = self
+            .config
+            .file_source
+            .try_pushdown_filters(filters, config)?;
+        let updated_node = result.updated_node.map(|file_source| {
+            let config = FileScanConfigBuilder::from(self.config.clone())
+                .with_source(file_source)
+                .build();
+            Arc::new(Self::new(config)) as Arc<dyn ExecutionPlan>
+        });
+        Ok(FilterPushdownPropagation {
+            filters: result.filters,
+            updated_node,
+        })
+    }
+
     fn execute(
         &self,
         partition: usize,
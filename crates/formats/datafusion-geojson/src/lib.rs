@@ -1,3 +1,4 @@
+mod decoder;
 pub mod factory;
 mod file_format;
 mod file_source;
@@ -10,7 +11,10 @@ pub use factory::register_geojson_format;
 pub use file_format::GeoJsonFormatOptions;
 pub use file_source::GeoJsonSourceBuilder;
 pub use sink::{GeoJsonSink, GeoJsonWriterExec};
-pub use writer::{GeoJsonWriterOptions, write_geojson, write_geojson_to_bytes};
+pub use writer::{
+    GeoJsonStreamWriter, GeoJsonWriterOptions, non_wgs84_crs, write_geojson, write_geojson_stream,
+    write_geojson_to_bytes,
+};
 
 use datafusion::prelude::*;
 use datafusion_common::Result;
@@ -4,88 +4,59 @@
 use std::convert::TryInto;
 use std::fmt;
 
-use datafusion_shared::{SourcePosition, SpatialFormatReadError, SpatialFormatResult};
+use datafusion_shared::{SpatialFormatReadError, SpatialFormatResult};
 use geo_types::Geometry;
-use geojson::{
-    Feature, FeatureCollection, GeoJson, Geometry as GeoJsonGeometry, JsonObject, JsonValue,
-};
+use geojson::feature::Id;
+use geojson::{Feature, GeoJson, Geometry as GeoJsonGeometry, JsonObject, JsonValue};
 
 /// Parsed `GeoJSON` feature with materialized properties and geometry.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct FeatureRecord {
     pub properties: JsonObject,
     pub geometry: Option<Geometry<f64>>,
+    /// Feature `id` member, either a string or a number.
+    pub id: Option<JsonValue>,
+    /// Foreign members of the feature, each serialised as JSON text.
+    pub foreign_members: JsonObject,
 }
 
-/// Parse raw bytes into a vector of `FeatureRecord`s.
-pub fn parse_geojson_bytes(
-    bytes: &[u8],
-    limit: Option<usize>,
-    context: impl Into<String>,
-) -> SpatialFormatResult<Vec<FeatureRecord>> {
-    let context = context.into();
-    let reader = std::io::Cursor::new(bytes);
-
-    match GeoJson::from_reader(reader) {
-        Ok(geojson) => geojson_to_records(geojson, limit, &context),
-        Err(primary_err) => {
-            let primary_err_message = primary_err.to_string();
-            match parse_geojson_sequence(bytes, limit, &context) {
-                Ok(records) => Ok(records),
-                Err(sequence_err) => {
-                    Err(combine_errors(&primary_err_message, &sequence_err, context))
-                },
-            }
-        },
+/// Split a parsed top-level `GeoJSON` value into its features; a bare geometry becomes a
+/// feature without properties.
+pub(crate) fn geojson_to_features(geojson: GeoJson) -> Vec<Feature> {
+    match geojson {
+        GeoJson::FeatureCollection(collection) => collection.features,
+        GeoJson::Feature(feature) => vec![feature],
+        GeoJson::Geometry(geometry) => vec![Feature::from(geometry)],
     }
 }
 
-fn geojson_to_records(
-    geojson: GeoJson,
-    limit: Option<usize>,
+/// Convert a single `GeoJSON` feature into a record.
+pub(crate) fn feature_to_record(
+    feature: Feature,
     context: &str,
-) -> SpatialFormatResult<Vec<FeatureRecord>> {
-    let mut records = match geojson {
-        GeoJson::FeatureCollection(collection) => feature_collection_to_records(collection)?,
-        GeoJson::Feature(feature) => vec![feature_to_record(feature)?],
-        GeoJson::Geometry(geometry) => {
-            let geometry = convert_geometry(geometry, context)?;
-            vec![FeatureRecord {
-                properties: JsonObject::new(),
-                geometry: Some(geometry),
-            }]
-        },
-    };
-
-    if let Some(max) = limit
-        && records.len() > max
-    {
-        records.truncate(max);
-    }
-    Ok(records)
-}
-
-fn feature_collection_to_records(
-    collection: FeatureCollection,
-) -> SpatialFormatResult<Vec<FeatureRecord>> {
-    collection
-        .features
-        .into_iter()
-        .map(feature_to_record)
-        .collect()
-}
-
-fn feature_to_record(feature: Feature) -> SpatialFormatResult<FeatureRecord> {
+) -> SpatialFormatResult<FeatureRecord> {
     let geometry = match feature.geometry {
-        Some(geometry) => Some(convert_geometry(geometry, "feature")?),
+        Some(geometry) => Some(convert_geometry(geometry, context)?),
         None => None,
     };
 
     let properties = feature.properties.unwrap_or_default();
+    let id = feature.id.map(|id| match id {
+        Id::String(value) => JsonValue::String(value),
+        Id::Number(value) => JsonValue::Number(value),
+    });
+    let foreign_members = feature
+        .foreign_members
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(key, value)| (key, JsonValue::String(value.to_string())))
+        .collect();
 
     Ok(FeatureRecord {
         properties,
         geometry,
+        id,
+        foreign_members,
     })
 }
 
@@ -102,81 +73,6 @@ fn convert_geometry(
         })
 }
 
-fn parse_geojson_sequence(
-    bytes: &[u8],
-    limit: Option<usize>,
-    context: &str,
-) -> SpatialFormatResult<Vec<FeatureRecord>> {
-    let mut records = Vec::new();
-    for (line_idx, raw_line) in bytes.split(|b| *b == b'\n').enumerate() {
-        let line_number = (line_idx + 1) as u64;
-        let line = match std::str::from_utf8(raw_line) {
-            Ok(line) => line.trim(),
-            Err(err) => {
-                return Err(SpatialFormatReadError::Parse {
-                    message: format!("GeoJSON line is not valid UTF-8: {err}"),
-                    position: Some(SourcePosition {
-                        line: Some(line_number),
-                        ..SourcePosition::default()
-                    }),
-                    context: Some(context.to_string()),
-                });
-            },
-        };
-
-        if line.is_empty() {
-            continue;
-        }
-
-        let geojson = line
-            .parse::<GeoJson>()
-            .map_err(|err| SpatialFormatReadError::Parse {
-                message: format!("Failed to parse GeoJSON feature: {err}"),
-                position: Some(SourcePosition {
-                    line: Some(line_number),
-                    ..SourcePosition::default()
-                }),
-                context: Some(context.to_string()),
-            })?;
-
-        let mut parsed = geojson_to_records(geojson, None, context)?;
-        records.append(&mut parsed);
-
-        if let Some(max) = limit
-            && records.len() >= max
-        {
-            records.truncate(max);
-            break;
-        }
-    }
-
-    if records.is_empty() {
-        Err(SpatialFormatReadError::Parse {
-            message: "No GeoJSON features found".to_string(),
-            position: None,
-            context: Some(context.to_string()),
-        })
-    } else {
-        Ok(records)
-    }
-}
-
-fn combine_errors(
-    collection_err: &str,
-    sequence_err: &SpatialFormatReadError,
-    context: String,
-) -> SpatialFormatReadError {
-    let message = format!(
-        "Failed to parse GeoJSON as FeatureCollection ({collection_err}); \
-         also failed to parse as GeoJSON sequence: {sequence_err}"
-    );
-    SpatialFormatReadError::Parse {
-        message,
-        position: None,
-        context: Some(context),
-    }
-}
-
 /// Helper to describe JSON value kinds for error messages.
 pub(crate) fn describe_value(value: &JsonValue) -> &'static str {
     match value {
@@ -207,6 +103,28 @@ impl fmt::Display for FeatureRecord {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::decoder::GeoJsonDecoder;
+
+    /// Decode an in-memory buffer, stopping early once `limit` records were produced.
+    fn parse_geojson_bytes(
+        bytes: &[u8],
+        limit: Option<usize>,
+        context: impl Into<String>,
+    ) -> SpatialFormatResult<Vec<FeatureRecord>> {
+        let mut decoder = GeoJsonDecoder::new(context);
+        decoder.push(bytes);
+
+        let mut records = Vec::new();
+        while let Some(record) = decoder.next_record()? {
+            records.push(record);
+            if limit.is_some_and(|max| records.len() >= max) {
+                return Ok(records);
+            }
+        }
+
+        decoder.finish()?;
+        Ok(records)
+    }
 
     #[test]
     fn parse_feature_collection() {
@@ -353,17 +271,19 @@ not valid json"#;
     }
 
     #[test]
-    fn parse_invalid_json_combines_errors() {
+    fn parse_invalid_json_reports_context() {
         let data = b"not valid json at all";
 
         let err = parse_geojson_bytes(data, None, "invalid").unwrap_err();
         match err {
             SpatialFormatReadError::Parse {
-                message, context, ..
+                message,
+                context,
+                position,
             } => {
-                assert!(message.contains("Failed to parse GeoJSON as FeatureCollection"));
-                assert!(message.contains("also failed to parse as GeoJSON sequence"));
+                assert!(message.contains("Failed to parse GeoJSON feature"));
                 assert_eq!(context.as_deref(), Some("invalid"));
+                assert_eq!(position.and_then(|pos| pos.line), Some(1));
             },
             _ => panic!("Expected Parse error"),
         }
@@ -407,6 +327,7 @@ not valid json"#;
                 .cloned()
                 .collect(),
             geometry: Some(Geometry::Point(geo_types::Point::new(1.0, 2.0))),
+            ..FeatureRecord::default()
         };
 
         let display = format!("{record}");
@@ -419,10 +340,28 @@ not valid json"#;
         let record = FeatureRecord {
             properties: JsonObject::new(),
             geometry: None,
+            ..FeatureRecord::default()
         };
 
         let display = format!("{record}");
         assert!(display.contains("properties=0 keys"));
         assert!(display.contains("None"));
     }
+
+    #[test]
+    fn parse_feature_id_and_foreign_members() {
+        let data = br#"{"type":"FeatureCollection","features":[
+    {"type":"Feature","id":"a-1","geometry":null,"properties":{},"source":{"name":"survey"}},
+    {"type":"Feature","id":7,"bbox":[0,0,1,1],"geometry":null,"properties":null}
+]}"#;
+
+        let records = parse_geojson_bytes(data, None, "test").expect("parse");
+        assert_eq!(records[0].id, Some(JsonValue::String("a-1".into())));
+        assert_eq!(
+            records[0].foreign_members.get("source").unwrap(),
+            r#"{"name":"survey"}"#
+        );
+        assert_eq!(records[1].id, Some(JsonValue::Number(7.into())));
+        assert!(records[1].foreign_members.is_empty());
+    }
 }
@@ -1,24 +1,30 @@
 //! Physical execution for `GeoJSON` reading.
 //!
 //! This module wires `GeoJSON` parsing into `DataFusion`'s `FileOpener` abstraction and produces
-//! `GeoArrow`-backed record batches.
+//! `GeoArrow`-backed record batches. Files are decoded incrementally from the object store byte
+//! stream, so at most one batch of features is held in memory at a time.
 
 use std::sync::Arc;
 
 use arrow_array::builder::{BooleanBuilder, Float64Builder, Int64Builder, StringBuilder};
-use arrow_array::{ArrayRef, RecordBatch, RecordBatchOptions};
-use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use arrow_array::{
+    ArrayRef, ListArray, MapArray, RecordBatch, RecordBatchOptions, StringArray, StructArray,
+};
+use arrow_buffer::{NullBuffer, OffsetBuffer};
+use arrow_schema::{DataType, Field, FieldRef, Fields, Schema, SchemaRef};
 use datafusion::datasource::listing::PartitionedFile;
 use datafusion::datasource::physical_plan::{FileMeta, FileOpenFuture, FileOpener};
 use datafusion::error::{DataFusionError, Result};
-use datafusion_shared::SpatialFormatReadError;
+use datafusion_shared::{SpatialFilter, SpatialFormatReadError};
 use futures::TryStreamExt;
 use geoarrow_array::GeoArrowArray;
 use geoarrow_array::builder::GeometryBuilder;
+use geojson::JsonValue;
 use object_store::ObjectStore;
 
+use crate::decoder::FeatureReader;
 use crate::file_format::GeoJsonFormatOptions;
-use crate::parser::{FeatureRecord, describe_value, parse_geojson_bytes};
+use crate::parser::{FeatureRecord, describe_value};
 
 /// `GeoJSON` file opener that produces record batches using `GeoArrow` arrays.
 #[derive(Clone)]
@@ -28,6 +34,7 @@ pub struct GeoJsonOpener {
     projection: Option<Vec<usize>>,
     batch_size: usize,
     object_store: Arc<dyn ObjectStore>,
+    spatial_filters: Vec<SpatialFilter>,
 }
 
 impl GeoJsonOpener {
@@ -43,6 +50,7 @@ impl GeoJsonOpener {
             projection,
             batch_size: 8192,
             object_store,
+            spatial_filters: Vec::new(),
         }
     }
 
@@ -50,6 +58,12 @@ impl GeoJsonOpener {
         self.batch_size = batch_size;
         self
     }
+
+    /// Skip features whose geometry cannot satisfy the given filters on the geometry column
+    pub fn with_spatial_filters(mut self, filters: Vec<SpatialFilter>) -> Self {
+        self.spatial_filters = filters;
+        self
+    }
 }
 
 impl FileOpener for GeoJsonOpener {
@@ -68,15 +82,7 @@ impl FileOpener for GeoJsonOpener {
                 })
             })?;
 
-            let bytes = get_result.bytes().await.map_err(|err| {
-                DataFusionError::from(SpatialFormatReadError::Io {
-                    source: std::io::Error::other(err),
-                    context: Some(source_path.to_string()),
-                })
-            })?;
-
-            let records = parse_geojson_bytes(&bytes, None, source_path.to_string())
-                .map_err(DataFusionError::from)?;
+            let reader = FeatureReader::new(get_result.into_stream(), source_path.to_string());
 
             let output_schema = if let Some(ref proj) = opener.projection {
                 let fields: Vec<Field> = proj
@@ -91,21 +97,30 @@ impl FileOpener for GeoJsonOpener {
             let state = GeoJsonReadState {
                 schema: output_schema,
                 options: opener.options.clone(),
-                features: Arc::new(records),
+                reader,
                 batch_size: opener.batch_size,
-                offset: 0,
                 source: Arc::clone(&source_path),
+                spatial_filters: opener.spatial_filters,
             };
 
             let stream = futures::stream::try_unfold(state, |mut state| async move {
-                if state.offset >= state.features.len() {
-                    return Ok(None);
-                }
-
-                let end = (state.offset + state.batch_size).min(state.features.len());
-                let slice = &state.features[state.offset..end];
-                let batch = records_to_batch(&state.schema, &state.options, &state.source, slice)?;
-                state.offset = end;
+                let records = loop {
+                    let mut records = state
+                        .reader
+                        .next_records(state.batch_size)
+                        .await
+                        .map_err(DataFusionError::from)?;
+                    if records.is_empty() {
+                        return Ok(None);
+                    }
+                    records.retain(|record| state.keeps(record));
+                    if !records.is_empty() {
+                        break records;
+                    }
+                };
+
+                let batch =
+                    records_to_batch(&state.schema, &state.options, &state.source, &records)?;
                 Ok(Some((batch, state)))
             })
             .into_stream();
@@ -118,10 +133,21 @@ impl FileOpener for GeoJsonOpener {
 struct GeoJsonReadState {
     schema: SchemaRef,
     options: GeoJsonFormatOptions,
-    features: Arc<Vec<FeatureRecord>>,
+    reader: FeatureReader,
     batch_size: usize,
-    offset: usize,
     source: Arc<str>,
+    spatial_filters: Vec<SpatialFilter>,
+}
+
+impl GeoJsonReadState {
+    fn keeps(&self, record: &FeatureRecord) -> bool {
+        self.spatial_filters.iter().all(|filter| {
+            record
+                .geometry
+                .as_ref()
+                .is_some_and(|geometry| filter.matches(geometry))
+        })
+    }
 }
 
 fn records_to_batch(
@@ -153,22 +179,8 @@ fn records_to_batch(
             continue;
         }
 
-        let array = match field.data_type() {
-            DataType::Boolean => build_boolean_array(field, records, source),
-            DataType::Int64 => build_int64_array(field, records, source),
-            DataType::Float64 => build_float64_array(field, records, source),
-            DataType::Utf8 => Ok(build_utf8_array(field, records)),
-            other => Err(DataFusionError::from(SpatialFormatReadError::Parse {
-                message: format!(
-                    "Unsupported data type {other:?} for GeoJSON property '{}'",
-                    field.name()
-                ),
-                position: None,
-                context: Some(source.to_string()),
-            })),
-        }?;
-
-        columns.push(array);
+        let values = column_values(field, records, options);
+        columns.push(build_array(field, &values, source)?);
     }
 
     RecordBatch::try_new(schema.clone(), columns).map_err(|err| {
@@ -180,17 +192,68 @@ fn records_to_batch(
     })
 }
 
+/// Collect the value of `field` from every record; missing values become `None`.
+///
+/// A column is backed by the feature `id`, a property or a foreign member depending on
+/// `options`.
+fn column_values<'a>(
+    field: &Field,
+    records: &'a [FeatureRecord],
+    options: &GeoJsonFormatOptions,
+) -> Vec<Option<&'a JsonValue>> {
+    if options.id_column.as_deref() == Some(field.name().as_str()) {
+        return records.iter().map(|record| record.id.as_ref()).collect();
+    }
+
+    records
+        .iter()
+        .map(|record| {
+            record.properties.get(field.name()).or_else(|| {
+                if options.foreign_members {
+                    record.foreign_members.get(field.name())
+                } else {
+                    None
+                }
+            })
+        })
+        .collect()
+}
+
+fn build_array(
+    field: &Field,
+    values: &[Option<&JsonValue>],
+    source: &Arc<str>,
+) -> Result<ArrayRef> {
+    match field.data_type() {
+        DataType::Boolean => build_boolean_array(field, values, source),
+        DataType::Int64 => build_int64_array(field, values, source),
+        DataType::Float64 => build_float64_array(field, values, source),
+        DataType::Utf8 => Ok(build_utf8_array(values)),
+        DataType::List(element) => build_list_array(field, element, values, source),
+        DataType::Struct(fields) => build_struct_array(field, fields, values, source),
+        DataType::Map(entries, sorted) => build_map_array(field, entries, *sorted, values, source),
+        other => Err(DataFusionError::from(SpatialFormatReadError::Parse {
+            message: format!(
+                "Unsupported data type {other:?} for GeoJSON property '{}'",
+                field.name()
+            ),
+            position: None,
+            context: Some(source.to_string()),
+        })),
+    }
+}
+
 fn build_boolean_array(
     field: &Field,
-    records: &[FeatureRecord],
+    values: &[Option<&JsonValue>],
     source: &Arc<str>,
 ) -> Result<ArrayRef> {
-    let mut builder = BooleanBuilder::with_capacity(records.len());
+    let mut builder = BooleanBuilder::with_capacity(values.len());
 
-    for feature in records {
-        match feature.properties.get(field.name()) {
-            Some(geojson::JsonValue::Bool(value)) => builder.append_value(*value),
-            Some(geojson::JsonValue::Null) | None => builder.append_null(),
+    for &value in values {
+        match value {
+            Some(JsonValue::Bool(value)) => builder.append_value(*value),
+            Some(JsonValue::Null) | None => builder.append_null(),
             Some(other) => return Err(property_type_error(field, "bool", other, source)),
         }
     }
@@ -200,26 +263,26 @@ fn build_boolean_array(
 
 fn build_int64_array(
     field: &Field,
-    records: &[FeatureRecord],
+    values: &[Option<&JsonValue>],
     source: &Arc<str>,
 ) -> Result<ArrayRef> {
-    let mut builder = Int64Builder::with_capacity(records.len());
+    let mut builder = Int64Builder::with_capacity(values.len());
 
-    for feature in records {
-        match feature.properties.get(field.name()) {
-            Some(geojson::JsonValue::Number(value)) => {
+    for &value in values {
+        match value {
+            Some(JsonValue::Number(value)) => {
                 if let Some(int_val) = value.as_i64() {
                     builder.append_value(int_val);
                 } else {
                     return Err(property_type_error(
                         field,
                         "integer",
-                        &geojson::JsonValue::Number(value.clone()),
+                        &JsonValue::Number(value.clone()),
                         source,
                     ));
                 }
             },
-            Some(geojson::JsonValue::Null) | None => builder.append_null(),
+            Some(JsonValue::Null) | None => builder.append_null(),
             Some(other) => return Err(property_type_error(field, "integer", other, source)),
         }
     }
@@ -229,26 +292,26 @@ fn build_int64_array(
 
 fn build_float64_array(
     field: &Field,
-    records: &[FeatureRecord],
+    values: &[Option<&JsonValue>],
     source: &Arc<str>,
 ) -> Result<ArrayRef> {
-    let mut builder = Float64Builder::with_capacity(records.len());
+    let mut builder = Float64Builder::with_capacity(values.len());
 
-    for feature in records {
-        match feature.properties.get(field.name()) {
-            Some(geojson::JsonValue::Number(value)) => {
+    for &value in values {
+        match value {
+            Some(JsonValue::Number(value)) => {
                 if let Some(float_val) = value.as_f64() {
                     builder.append_value(float_val);
                 } else {
                     return Err(property_type_error(
                         field,
                         "float",
-                        &geojson::JsonValue::Number(value.clone()),
+                        &JsonValue::Number(value.clone()),
                         source,
                     ));
                 }
             },
-            Some(geojson::JsonValue::Null) | None => builder.append_null(),
+            Some(JsonValue::Null) | None => builder.append_null(),
             Some(other) => return Err(property_type_error(field, "float", other, source)),
         }
     }
@@ -256,29 +319,178 @@ fn build_float64_array(
     Ok(Arc::new(builder.finish()))
 }
 
-fn build_utf8_array(field: &Field, records: &[FeatureRecord]) -> ArrayRef {
-    let mut builder = StringBuilder::with_capacity(records.len(), records.len() * 4);
+fn build_utf8_array(values: &[Option<&JsonValue>]) -> ArrayRef {
+    let mut builder = StringBuilder::with_capacity(values.len(), values.len() * 4);
 
-    for feature in records {
-        match feature.properties.get(field.name()) {
-            Some(geojson::JsonValue::String(value)) => builder.append_value(value),
-            Some(geojson::JsonValue::Number(value)) => builder.append_value(value.to_string()),
-            Some(geojson::JsonValue::Bool(value)) => {
+    for &value in values {
+        match value {
+            Some(JsonValue::String(value)) => builder.append_value(value),
+            Some(JsonValue::Number(value)) => builder.append_value(value.to_string()),
+            Some(JsonValue::Bool(value)) => {
                 builder.append_value(if *value { "true" } else { "false" });
             },
-            Some(geojson::JsonValue::Array(value)) => {
-                builder.append_value(geojson::JsonValue::Array(value.clone()).to_string());
+            Some(value @ (JsonValue::Array(_) | JsonValue::Object(_))) => {
+                builder.append_value(value.to_string());
             },
-            Some(geojson::JsonValue::Object(value)) => {
-                builder.append_value(geojson::JsonValue::Object(value.clone()).to_string());
-            },
-            Some(geojson::JsonValue::Null) | None => builder.append_null(),
+            Some(JsonValue::Null) | None => builder.append_null(),
         }
     }
 
     Arc::new(builder.finish())
 }
 
+fn build_list_array(
+    field: &Field,
+    element: &FieldRef,
+    values: &[Option<&JsonValue>],
+    source: &Arc<str>,
+) -> Result<ArrayRef> {
+    let mut lengths = Vec::with_capacity(values.len());
+    let mut validity = Vec::with_capacity(values.len());
+    let mut items = Vec::new();
+
+    for &value in values {
+        match value {
+            Some(JsonValue::Array(array)) => {
+                lengths.push(array.len());
+                validity.push(true);
+                items.extend(array.iter().map(Some));
+            },
+            Some(JsonValue::Null) | None => {
+                lengths.push(0);
+                validity.push(false);
+            },
+            Some(other) => return Err(property_type_error(field, "array", other, source)),
+        }
+    }
+
+    let child = build_array(element, &items, source)?;
+    let array = ListArray::try_new(
+        element.clone(),
+        OffsetBuffer::from_lengths(lengths),
+        child,
+        Some(NullBuffer::from(validity)),
+    )
+    .map_err(|err| nested_array_error(field, &err, source))?;
+
+    Ok(Arc::new(array))
+}
+
+fn build_struct_array(
+    field: &Field,
+    fields: &Fields,
+    values: &[Option<&JsonValue>],
+    source: &Arc<str>,
+) -> Result<ArrayRef> {
+    let mut objects = Vec::with_capacity(values.len());
+
+    for &value in values {
+        match value {
+            Some(JsonValue::Object(object)) => objects.push(Some(object)),
+            Some(JsonValue::Null) | None => objects.push(None),
+            Some(other) => return Err(property_type_error(field, "object", other, source)),
+        }
+    }
+
+    let children = fields
+        .iter()
+        .map(|child| {
+            let child_values: Vec<Option<&JsonValue>> = objects
+                .iter()
+                .map(|object| object.and_then(|object| object.get(child.name())))
+                .collect();
+            build_array(child, &child_values, source)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let validity = NullBuffer::from_iter(objects.iter().map(Option::is_some));
+
+    let array = StructArray::try_new(fields.clone(), children, Some(validity))
+        .map_err(|err| nested_array_error(field, &err, source))?;
+
+    Ok(Arc::new(array))
+}
+
+fn build_map_array(
+    field: &Field,
+    entries: &FieldRef,
+    sorted: bool,
+    values: &[Option<&JsonValue>],
+    source: &Arc<str>,
+) -> Result<ArrayRef> {
+    let DataType::Struct(entry_fields) = entries.data_type() else {
+        return Err(nested_array_error(
+            field,
+            &format!(
+                "map entries must be a struct, found {:?}",
+                entries.data_type()
+            ),
+            source,
+        ));
+    };
+    let [key_field, value_field] = entry_fields.iter().collect::<Vec<_>>()[..] else {
+        return Err(nested_array_error(
+            field,
+            &"map entries must have exactly two fields",
+            source,
+        ));
+    };
+
+    let mut lengths = Vec::with_capacity(values.len());
+    let mut validity = Vec::with_capacity(values.len());
+    let mut keys = Vec::new();
+    let mut items = Vec::new();
+
+    for &value in values {
+        match value {
+            Some(JsonValue::Object(object)) => {
+                lengths.push(object.len());
+                validity.push(true);
+                for (key, item) in object {
+                    keys.push(key.as_str());
+                    items.push(Some(item));
+                }
+            },
+            Some(JsonValue::Null) | None => {
+                lengths.push(0);
+                validity.push(false);
+            },
+            Some(other) => return Err(property_type_error(field, "object", other, source)),
+        }
+    }
+
+    let key_array: ArrayRef = Arc::new(StringArray::from(keys));
+    let value_array = build_array(value_field, &items, source)?;
+    let entries_array =
+        StructArray::try_new(entry_fields.clone(), vec![key_array, value_array], None)
+            .map_err(|err| nested_array_error(key_field, &err, source))?;
+
+    let array = MapArray::try_new(
+        entries.clone(),
+        OffsetBuffer::from_lengths(lengths),
+        entries_array,
+        Some(NullBuffer::from(validity)),
+        sorted,
+    )
+    .map_err(|err| nested_array_error(field, &err, source))?;
+
+    Ok(Arc::new(array))
+}
+
+fn nested_array_error(
+    field: &Field,
+    err: &dyn std::fmt::Display,
+    source: &Arc<str>,
+) -> DataFusionError {
+    DataFusionError::from(SpatialFormatReadError::Parse {
+        message: format!(
+            "Failed to build nested array for property '{}': {err}",
+            field.name()
+        ),
+        position: None,
+        context: Some(source.to_string()),
+    })
+}
+
 fn build_geometry_array(
     records: &[FeatureRecord],
     options: &GeoJsonFormatOptions,
@@ -304,7 +516,7 @@ fn build_geometry_array(
 fn property_type_error(
     field: &Field,
     expected: &str,
-    actual: &geojson::JsonValue,
+    actual: &JsonValue,
     source: &Arc<str>,
 ) -> DataFusionError {
     DataFusionError::from(SpatialFormatReadError::Parse {
@@ -326,34 +538,40 @@ mod tests {
     use geo_types::{Geometry, Point};
     use geoarrow_schema::{CoordType, GeometryType};
 
+    fn property_values<'a>(
+        field: &Field,
+        records: &'a [FeatureRecord],
+    ) -> Vec<Option<&'a JsonValue>> {
+        column_values(field, records, &GeoJsonFormatOptions::default())
+    }
+
     fn make_feature_records() -> Vec<FeatureRecord> {
         vec![
             FeatureRecord {
                 properties: [
-                    ("bool_col".to_string(), geojson::JsonValue::Bool(true)),
+                    ("bool_col".to_string(), JsonValue::Bool(true)),
                     ("int_col".to_string(), serde_json::json!(42)),
                     ("float_col".to_string(), serde_json::json!(2.5)),
-                    (
-                        "string_col".to_string(),
-                        geojson::JsonValue::String("hello".into()),
-                    ),
+                    ("string_col".to_string(), JsonValue::String("hello".into())),
                 ]
                 .iter()
                 .cloned()
                 .collect(),
                 geometry: Some(Geometry::Point(Point::new(1.0, 2.0))),
+                ..FeatureRecord::default()
             },
             FeatureRecord {
                 properties: [
-                    ("bool_col".to_string(), geojson::JsonValue::Null),
-                    ("int_col".to_string(), geojson::JsonValue::Null),
-                    ("float_col".to_string(), geojson::JsonValue::Null),
-                    ("string_col".to_string(), geojson::JsonValue::Null),
+                    ("bool_col".to_string(), JsonValue::Null),
+                    ("int_col".to_string(), JsonValue::Null),
+                    ("float_col".to_string(), JsonValue::Null),
+                    ("string_col".to_string(), JsonValue::Null),
                 ]
                 .iter()
                 .cloned()
                 .collect(),
                 geometry: None,
+                ..FeatureRecord::default()
             },
         ]
     }
@@ -364,7 +582,8 @@ mod tests {
         let field = Field::new("bool_col", DataType::Boolean, true);
         let source = Arc::from("test");
 
-        let array = build_boolean_array(&field, &records, &source).expect("build bool array");
+        let array = build_boolean_array(&field, &property_values(&field, &records), &source)
+            .expect("build bool array");
         let bool_array = array
             .as_any()
             .downcast_ref::<arrow_array::BooleanArray>()
@@ -380,17 +599,19 @@ mod tests {
         let records = vec![FeatureRecord {
             properties: [(
                 "bool_col".to_string(),
-                geojson::JsonValue::String("not a bool".into()),
+                JsonValue::String("not a bool".into()),
             )]
             .iter()
             .cloned()
             .collect(),
             geometry: None,
+            ..FeatureRecord::default()
         }];
         let field = Field::new("bool_col", DataType::Boolean, true);
         let source = Arc::from("test");
 
-        let err = build_boolean_array(&field, &records, &source).unwrap_err();
+        let err =
+            build_boolean_array(&field, &property_values(&field, &records), &source).unwrap_err();
         assert!(err.to_string().contains("expected bool"));
     }
 
@@ -400,7 +621,8 @@ mod tests {
         let field = Field::new("int_col", DataType::Int64, true);
         let source = Arc::from("test");
 
-        let array = build_int64_array(&field, &records, &source).expect("build int64 array");
+        let array = build_int64_array(&field, &property_values(&field, &records), &source)
+            .expect("build int64 array");
         let int_array = array
             .as_any()
             .downcast_ref::<arrow_array::Int64Array>()
@@ -416,17 +638,19 @@ mod tests {
         let records = vec![FeatureRecord {
             properties: [(
                 "int_col".to_string(),
-                geojson::JsonValue::String("not an int".into()),
+                JsonValue::String("not an int".into()),
             )]
             .iter()
             .cloned()
             .collect(),
             geometry: None,
+            ..FeatureRecord::default()
         }];
         let field = Field::new("int_col", DataType::Int64, true);
         let source = Arc::from("test");
 
-        let err = build_int64_array(&field, &records, &source).unwrap_err();
+        let err =
+            build_int64_array(&field, &property_values(&field, &records), &source).unwrap_err();
         assert!(err.to_string().contains("expected integer"));
     }
 
@@ -438,11 +662,13 @@ mod tests {
                 .cloned()
                 .collect(),
             geometry: None,
+            ..FeatureRecord::default()
         }];
         let field = Field::new("int_col", DataType::Int64, true);
         let source = Arc::from("test");
 
-        let err = build_int64_array(&field, &records, &source).unwrap_err();
+        let err =
+            build_int64_array(&field, &property_values(&field, &records), &source).unwrap_err();
         assert!(err.to_string().contains("expected integer"));
     }
 
@@ -452,7 +678,8 @@ mod tests {
         let field = Field::new("float_col", DataType::Float64, true);
         let source = Arc::from("test");
 
-        let array = build_float64_array(&field, &records, &source).expect("build float64 array");
+        let array = build_float64_array(&field, &property_values(&field, &records), &source)
+            .expect("build float64 array");
         let float_array = array
             .as_any()
             .downcast_ref::<arrow_array::Float64Array>()
@@ -468,17 +695,19 @@ mod tests {
         let records = vec![FeatureRecord {
             properties: [(
                 "float_col".to_string(),
-                geojson::JsonValue::String("not a float".into()),
+                JsonValue::String("not a float".into()),
             )]
             .iter()
             .cloned()
             .collect(),
             geometry: None,
+            ..FeatureRecord::default()
         }];
         let field = Field::new("float_col", DataType::Float64, true);
         let source = Arc::from("test");
 
-        let err = build_float64_array(&field, &records, &source).unwrap_err();
+        let err =
+            build_float64_array(&field, &property_values(&field, &records), &source).unwrap_err();
         assert!(err.to_string().contains("expected float"));
     }
 
@@ -487,7 +716,7 @@ mod tests {
         let records = make_feature_records();
         let field = Field::new("string_col", DataType::Utf8, true);
 
-        let array = build_utf8_array(&field, &records);
+        let array = build_utf8_array(&property_values(&field, &records));
         let string_array = array
             .as_any()
             .downcast_ref::<arrow_array::StringArray>()
@@ -506,10 +735,11 @@ mod tests {
                 .cloned()
                 .collect(),
             geometry: None,
+            ..FeatureRecord::default()
         }];
         let field = Field::new("string_col", DataType::Utf8, true);
 
-        let array = build_utf8_array(&field, &records);
+        let array = build_utf8_array(&property_values(&field, &records));
         let string_array = array
             .as_any()
             .downcast_ref::<arrow_array::StringArray>()
@@ -523,23 +753,25 @@ mod tests {
     fn test_build_utf8_array_with_bool() {
         let records = vec![
             FeatureRecord {
-                properties: [("string_col".to_string(), geojson::JsonValue::Bool(true))]
+                properties: [("string_col".to_string(), JsonValue::Bool(true))]
                     .iter()
                     .cloned()
                     .collect(),
                 geometry: None,
+                ..FeatureRecord::default()
             },
             FeatureRecord {
-                properties: [("string_col".to_string(), geojson::JsonValue::Bool(false))]
+                properties: [("string_col".to_string(), JsonValue::Bool(false))]
                     .iter()
                     .cloned()
                     .collect(),
                 geometry: None,
+                ..FeatureRecord::default()
             },
         ];
         let field = Field::new("string_col", DataType::Utf8, true);
 
-        let array = build_utf8_array(&field, &records);
+        let array = build_utf8_array(&property_values(&field, &records));
         let string_array = array
             .as_any()
             .downcast_ref::<arrow_array::StringArray>()
@@ -558,10 +790,11 @@ mod tests {
                 .cloned()
                 .collect(),
             geometry: None,
+            ..FeatureRecord::default()
         }];
         let field = Field::new("string_col", DataType::Utf8, true);
 
-        let array = build_utf8_array(&field, &records);
+        let array = build_utf8_array(&property_values(&field, &records));
         let string_array = array
             .as_any()
             .downcast_ref::<arrow_array::StringArray>()
@@ -582,10 +815,11 @@ mod tests {
             .cloned()
             .collect(),
             geometry: None,
+            ..FeatureRecord::default()
         }];
         let field = Field::new("string_col", DataType::Utf8, true);
 
-        let array = build_utf8_array(&field, &records);
+        let array = build_utf8_array(&property_values(&field, &records));
         let string_array = array
             .as_any()
             .downcast_ref::<arrow_array::StringArray>()
@@ -668,4 +902,94 @@ mod tests {
 
         assert_eq!(opener.batch_size, 1024);
     }
+
+    fn nested_values() -> Vec<JsonValue> {
+        vec![
+            serde_json::json!({"tags": ["a", "b"], "info": {"height": 10}, "names": {"en": "A"}}),
+            serde_json::json!({"tags": [], "info": null, "names": {"de": "B", "fr": "C"}}),
+            serde_json::json!({"tags": null, "info": {"height": null}, "names": null}),
+        ]
+    }
+
+    #[test]
+    fn test_build_list_array() {
+        let values = nested_values();
+        let tags: Vec<Option<&JsonValue>> = values.iter().map(|v| v.get("tags")).collect();
+        let field = Field::new(
+            "tags",
+            DataType::List(Arc::new(Field::new_list_field(DataType::Utf8, true))),
+            true,
+        );
+        let source = Arc::from("test");
+
+        let array = build_array(&field, &tags, &source).expect("build list array");
+        let list = array.as_any().downcast_ref::<ListArray>().unwrap();
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.value_length(0), 2);
+        assert_eq!(list.value_length(1), 0);
+        assert!(list.is_valid(1));
+        assert!(list.is_null(2));
+    }
+
+    #[test]
+    fn test_build_struct_array() {
+        let values = nested_values();
+        let info: Vec<Option<&JsonValue>> = values.iter().map(|v| v.get("info")).collect();
+        let field = Field::new(
+            "info",
+            DataType::Struct(vec![Field::new("height", DataType::Int64, true)].into()),
+            true,
+        );
+        let source = Arc::from("test");
+
+        let array = build_array(&field, &info, &source).expect("build struct array");
+        let structs = array.as_any().downcast_ref::<StructArray>().unwrap();
+
+        assert_eq!(structs.len(), 3);
+        assert!(structs.is_null(1));
+        assert!(structs.is_valid(2));
+        assert!(structs.column(0).is_null(2));
+    }
+
+    #[test]
+    fn test_build_map_array() {
+        let values = nested_values();
+        let names: Vec<Option<&JsonValue>> = values.iter().map(|v| v.get("names")).collect();
+        let entries = Field::new(
+            "entries",
+            DataType::Struct(
+                vec![
+                    Field::new("keys", DataType::Utf8, false),
+                    Field::new("values", DataType::Utf8, true),
+                ]
+                .into(),
+            ),
+            false,
+        );
+        let field = Field::new("names", DataType::Map(Arc::new(entries), false), true);
+        let source = Arc::from("test");
+
+        let array = build_array(&field, &names, &source).expect("build map array");
+        let map = array.as_any().downcast_ref::<MapArray>().unwrap();
+
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.value_length(0), 1);
+        assert_eq!(map.value_length(1), 2);
+        assert!(map.is_null(2));
+    }
+
+    #[test]
+    fn test_build_list_array_type_error() {
+        let value = serde_json::json!("not a list");
+        let field = Field::new(
+            "tags",
+            DataType::List(Arc::new(Field::new_list_field(DataType::Utf8, true))),
+            true,
+        );
+        let source = Arc::from("test");
+
+        let err = build_array(&field, &[Some(&value)], &source).unwrap_err();
+        assert!(err.to_string().contains("expected array"));
+    }
 }
@@ -1,25 +1,47 @@
 //! `GeoJSON` Data Sink implementation for writing data to `GeoJSON` files
 
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Read};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use arrow_schema::SchemaRef;
+use arrow_array::{RecordBatch, UInt64Array};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
 use async_trait::async_trait;
 use datafusion::datasource::physical_plan::FileSinkConfig;
 use datafusion::datasource::sink::DataSink;
+use datafusion::logical_expr::dml::InsertOp;
+use datafusion::physical_expr::EquivalenceProperties;
+use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::execution_plan::{Boundedness, EmissionType};
 use datafusion::physical_plan::metrics::MetricsSet;
-use datafusion::physical_plan::{DisplayAs, DisplayFormatType, ExecutionPlan};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, Distribution, ExecutionPlan, ExecutionPlanProperties,
+    Partitioning, PlanProperties,
+};
 use datafusion_common::{DataFusionError, Result};
 use datafusion_execution::{SendableRecordBatchStream, TaskContext};
 use datafusion_physical_expr::LexRequirement;
+use datafusion_shared::{create_output, holds_written_values, open_for_append};
 use futures::StreamExt;
+use geojson::Feature;
+use tempfile::NamedTempFile;
 
-use crate::writer::{GeoJsonWriterOptions, write_geojson};
+use crate::decoder::GeoJsonDecoder;
+use crate::file_format::InferredType;
+use crate::writer::{GeoJsonStreamWriter, GeoJsonWriterOptions, write_geojson_stream};
+
+/// Size of the chunks in which an existing `FeatureCollection` is read when appending to it
+const APPEND_READ_CHUNK_SIZE: usize = 64 * 1024;
 
 /// `GeoJSON` data sink that implements the `DataSink` trait
 #[derive(Debug)]
 pub struct GeoJsonSink {
     config: FileSinkConfig,
     writer_options: GeoJsonWriterOptions,
+    create_new: bool,
 }
 
 impl GeoJsonSink {
@@ -29,9 +51,19 @@ impl GeoJsonSink {
         Self {
             config,
             writer_options,
+            create_new: false,
         }
     }
 
+    /// Fail instead of replacing the output file when it already exists (default: false)
+    ///
+    /// Only applies when overwriting; the file is checked and created in one operation.
+    #[must_use]
+    pub fn with_create_new(mut self, create_new: bool) -> Self {
+        self.create_new = create_new;
+        self
+    }
+
     /// Get the sink configuration
     #[must_use]
     pub fn config(&self) -> &FileSinkConfig {
@@ -43,6 +75,220 @@ impl GeoJsonSink {
     pub fn writer_options(&self) -> &GeoJsonWriterOptions {
         &self.writer_options
     }
+
+    /// Resolve the local file the sink writes to
+    ///
+    /// A table path ending in `/` is treated as a directory and receives a `data.geojson`
+    /// file; any other path is written as-is.
+    fn output_file(&self) -> Result<PathBuf> {
+        let output_path = self
+            .config
+            .table_paths
+            .first()
+            .ok_or_else(|| DataFusionError::Internal("No output path specified".to_string()))?;
+
+        // For now, write to local filesystem
+        let path = output_path.get_url().to_file_path().map_err(|()| {
+            DataFusionError::NotImplemented(format!(
+                "GeoJSON sink only supports local files, got '{}'",
+                output_path.as_str()
+            ))
+        })?;
+
+        if output_path.is_collection() {
+            Ok(path.join("data.geojson"))
+        } else {
+            Ok(path)
+        }
+    }
+
+    /// Append newline-delimited features to `path`, after checking the properties of its
+    /// existing features
+    ///
+    /// Every existing feature is decoded, so that a line that is not `GeoJSON` is reported
+    /// and the property types are inferred over the whole file.
+    async fn append_features(&self, path: &Path, data: SendableRecordBatchStream) -> Result<u64> {
+        if path.exists() && std::fs::metadata(path)?.len() > 0 {
+            let not_sequence = |message: String| {
+                DataFusionError::Execution(format!(
+                    "Cannot append to '{}': it is not newline-delimited GeoJSON: {message}",
+                    path.display()
+                ))
+            };
+            let mut found = BTreeMap::new();
+            let decoder = decode_existing(path, &not_sequence, |feature| {
+                infer_property_types(&mut found, &feature);
+                Ok(())
+            })?;
+            if decoder.collection_members().is_some() {
+                return Err(not_sequence("found a FeatureCollection".to_string()));
+            }
+            self.check_appended_properties(path, &found)?;
+        }
+
+        // Only opened once the check passed, since it may add a missing final newline
+        let file = match open_for_append(path)? {
+            Some(file) => file,
+            None => File::create(path)?,
+        };
+        write_geojson_stream(data, BufWriter::new(file), &self.writer_options).await
+    }
+
+    /// Merge the written features into the `FeatureCollection` at `path`
+    ///
+    /// The features of the collection are streamed into a temporary file next to it, followed
+    /// by the new ones, and the temporary file then replaces the collection. Its other members
+    /// are kept, and its `bbox`, if any, is recomputed to cover the new features. The
+    /// temporary file, which takes the permissions of the collection, is removed if anything
+    /// fails.
+    async fn append_to_collection(
+        &self,
+        path: &Path,
+        mut data: SendableRecordBatchStream,
+    ) -> Result<u64> {
+        if !path.exists() || std::fs::metadata(path)?.len() == 0 {
+            return write_geojson_stream(
+                data,
+                BufWriter::new(File::create(path)?),
+                &self.writer_options,
+            )
+            .await;
+        }
+
+        let not_collection = |message: String| {
+            DataFusionError::Execution(format!(
+                "Cannot append to '{}': it is not a GeoJSON FeatureCollection: {message}",
+                path.display()
+            ))
+        };
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let mut writer = GeoJsonStreamWriter::new(
+            BufWriter::new(NamedTempFile::new_in(directory)?),
+            self.writer_options.clone(),
+        );
+        writer.set_collection_bbox(true);
+
+        let mut found = BTreeMap::new();
+        let decoder = decode_existing(path, &not_collection, |feature| {
+            infer_property_types(&mut found, &feature);
+            writer.write_feature(&feature)
+        })?;
+
+        let members = decoder
+            .collection_members()
+            .cloned()
+            .ok_or_else(|| not_collection("found standalone features".to_string()))?;
+        let existing_features = writer.features_written();
+        if existing_features > 0 {
+            self.check_appended_properties(path, &found)?;
+        }
+
+        writer.set_collection_bbox(self.writer_options.bbox || members.contains_key("bbox"));
+        writer.set_collection_members(members);
+        while let Some(batch) = data.next().await {
+            writer.write_batch(&batch?)?;
+        }
+        let rows = writer.features_written() - existing_features;
+        let temp_file = writer
+            .finish()?
+            .into_inner()
+            .map_err(|e| DataFusionError::from(e.into_error()))?;
+        std::fs::set_permissions(temp_file.path(), std::fs::metadata(path)?.permissions())?;
+        temp_file
+            .persist(path)
+            .map_err(|e| DataFusionError::from(e.error))?;
+
+        Ok(rows)
+    }
+
+    /// Check that the properties `found` in the existing features are the written property
+    /// columns, and that the types inferred for them hold the written values
+    ///
+    /// Every column other than the geometry, id and foreign member columns is written as a
+    /// property.
+    fn check_appended_properties(
+        &self,
+        path: &Path,
+        found: &BTreeMap<String, InferredType>,
+    ) -> Result<()> {
+        let options = &self.writer_options;
+        let written: BTreeMap<&str, &Field> = self
+            .config
+            .output_schema()
+            .fields()
+            .iter()
+            .map(|field| (field.name().as_str(), field.as_ref()))
+            .filter(|(name, _)| {
+                *name != options.geometry_column_name
+                    && options.id_column.as_deref() != Some(*name)
+                    && !options.foreign_member_columns.iter().any(|c| c == name)
+            })
+            .collect();
+
+        if !found.keys().map(String::as_str).eq(written.keys().copied()) {
+            let found: Vec<&str> = found.keys().map(String::as_str).collect();
+            let written: Vec<&str> = written.into_keys().collect();
+            return Err(DataFusionError::Execution(format!(
+                "Cannot append to '{}': its feature properties ({}) do not match the written \
+                 columns ({})",
+                path.display(),
+                found.join(", "),
+                written.join(", ")
+            )));
+        }
+
+        let mismatch = found
+            .iter()
+            .map(|(name, inferred)| (name, inferred.to_datatype(), written[name.as_str()]))
+            .find(|(_, existing, written)| !holds_written_values(existing, written.data_type()));
+        match mismatch {
+            None => Ok(()),
+            Some((name, existing, written)) => Err(DataFusionError::Execution(format!(
+                "Cannot append to '{}': its property '{name}' holds {existing} values, which \
+                 cannot hold the written {} values",
+                path.display(),
+                written.data_type()
+            ))),
+        }
+    }
+}
+
+/// Stream the features of the existing `GeoJSON` file at `path` into `on_feature`
+///
+/// Returns the decoder once the whole file has been read, to inspect the collection members.
+/// Decoding errors are reported through `invalid`.
+fn decode_existing(
+    path: &Path,
+    invalid: &impl Fn(String) -> DataFusionError,
+    mut on_feature: impl FnMut(Feature) -> Result<()>,
+) -> Result<GeoJsonDecoder> {
+    let mut existing = File::open(path)?;
+    let mut decoder = GeoJsonDecoder::new(path.display().to_string());
+    let mut chunk = vec![0; APPEND_READ_CHUNK_SIZE];
+    loop {
+        let read = existing.read(&mut chunk)?;
+        if read == 0 {
+            decoder.finish().map_err(|e| invalid(e.to_string()))?;
+            return Ok(decoder);
+        }
+        decoder.push(&chunk[..read]);
+        while let Some(feature) = decoder.next_feature().map_err(|e| invalid(e.to_string()))? {
+            on_feature(feature)?;
+        }
+    }
+}
+
+/// Merge the types of the properties of `feature` into those `found` so far
+fn infer_property_types(found: &mut BTreeMap<String, InferredType>, feature: &Feature) {
+    for (key, value) in feature.properties.iter().flatten() {
+        found
+            .entry(key.clone())
+            .or_insert(InferredType::Null)
+            .update(value, false);
+    }
 }
 
 #[async_trait]
@@ -61,38 +307,25 @@ impl DataSink for GeoJsonSink {
 
     async fn write_all(
         &self,
-        mut data: SendableRecordBatchStream,
+        data: SendableRecordBatchStream,
         _context: &Arc<TaskContext>,
     ) -> Result<u64> {
-        let mut batches = Vec::new();
-        let mut row_count = 0u64;
-
-        // Collect all batches from the stream
-        while let Some(batch_result) = data.next().await {
-            let batch = batch_result?;
-            row_count += batch.num_rows() as u64;
-            batches.push(batch);
+        let file_path = self.output_file()?;
+        match self.config.insert_op {
+            InsertOp::Append if self.writer_options.feature_collection => {
+                self.append_to_collection(&file_path, data).await
+            },
+            InsertOp::Append => self.append_features(&file_path, data).await,
+            InsertOp::Overwrite => {
+                let file = create_output(&file_path, self.create_new)?;
+
+                // Features are written batch by batch as they arrive
+                write_geojson_stream(data, BufWriter::new(file), &self.writer_options).await
+            },
+            InsertOp::Replace => Err(DataFusionError::NotImplemented(
+                "GeoJSON sink cannot replace features; use append or overwrite".to_string(),
+            )),
         }
-
-        // Write to output - for now write to a single file
-        let output_path = self
-            .config
-            .table_paths
-            .first()
-            .ok_or_else(|| DataFusionError::Internal("No output path specified".to_string()))?;
-
-        let file_path = format!(
-            "{}/data.geojson",
-            <datafusion::datasource::listing::ListingTableUrl as AsRef<str>>::as_ref(output_path)
-        );
-
-        // For now, write to local filesystem
-        let mut file = std::fs::File::create(&file_path)
-            .map_err(|e| DataFusionError::External(Box::new(e)))?;
-
-        write_geojson(&mut file, &batches, &self.writer_options)?;
-
-        Ok(row_count)
     }
 }
 
@@ -103,11 +336,15 @@ impl DisplayAs for GeoJsonSink {
 }
 
 /// `GeoJSON` writer physical execution plan
+///
+/// Drains its input into a [`GeoJsonSink`] and produces a single batch with a `count` column
+/// holding the number of rows written.
 #[derive(Debug)]
 pub struct GeoJsonWriterExec {
     input: Arc<dyn ExecutionPlan>,
     sink: Arc<GeoJsonSink>,
     _order_requirements: Option<LexRequirement>,
+    properties: PlanProperties,
 }
 
 impl GeoJsonWriterExec {
@@ -121,8 +358,29 @@ impl GeoJsonWriterExec {
             input,
             sink,
             _order_requirements: order_requirements,
+            properties: PlanProperties::new(
+                EquivalenceProperties::new(count_schema()),
+                Partitioning::UnknownPartitioning(1),
+                EmissionType::Final,
+                Boundedness::Bounded,
+            ),
         }
     }
+
+    /// Get the sink this plan writes to
+    #[must_use]
+    pub fn sink(&self) -> &Arc<GeoJsonSink> {
+        &self.sink
+    }
+}
+
+/// Schema of the batch returned by [`GeoJsonWriterExec`]
+fn count_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![Field::new(
+        "count",
+        DataType::UInt64,
+        false,
+    )]))
 }
 
 impl DisplayAs for GeoJsonWriterExec {
@@ -146,8 +404,12 @@ impl ExecutionPlan for GeoJsonWriterExec {
         self
     }
 
-    fn properties(&self) -> &datafusion::physical_plan::PlanProperties {
-        self.input.properties()
+    fn properties(&self) -> &PlanProperties {
+        &self.properties
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::SinglePartition]
     }
 
     fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
@@ -169,6 +431,7 @@ impl ExecutionPlan for GeoJsonWriterExec {
             input: Arc::clone(&children[0]),
             sink: Arc::clone(&self.sink),
             _order_requirements: self._order_requirements.clone(),
+            properties: self.properties.clone(),
         }))
     }
 
@@ -183,11 +446,29 @@ impl ExecutionPlan for GeoJsonWriterExec {
             ));
         }
 
-        // Execute input and get stream
-        let input_stream = self.input.execute(partition, Arc::clone(&context))?;
-
-        // For now, we'll return the input stream
-        Ok(input_stream)
+        // All input partitions are funnelled into the single output file
+        let input: Arc<dyn ExecutionPlan> =
+            if self.input.output_partitioning().partition_count() > 1 {
+                Arc::new(CoalescePartitionsExec::new(Arc::clone(&self.input)))
+            } else {
+                Arc::clone(&self.input)
+            };
+        let data = input.execute(0, Arc::clone(&context))?;
+
+        let sink = Arc::clone(&self.sink);
+        let stream = futures::stream::once(async move {
+            let count = sink.write_all(data, &context).await?;
+            RecordBatch::try_new(
+                count_schema(),
+                vec![Arc::new(UInt64Array::from(vec![count]))],
+            )
+            .map_err(DataFusionError::from)
+        });
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            count_schema(),
+            stream,
+        )))
     }
 }
 
@@ -1,12 +1,29 @@
 //! `GeoJSON` writer implementation for converting Arrow record batches to `GeoJSON` format
 
 use std::io::Write as IoWrite;
+use std::sync::Arc;
 
+use arrow_array::cast::AsArray;
+use arrow_array::temporal_conversions::as_date;
+use arrow_array::types::{
+    Date64Type, Float16Type, Float32Type, Float64Type, Int8Type, Int16Type, Int32Type, Int64Type,
+    UInt8Type, UInt16Type, UInt32Type, UInt64Type,
+};
 use arrow_array::{Array, RecordBatch};
-use arrow_schema::DataType;
+use arrow_cast::display::array_value_to_string;
+use arrow_schema::{DataType, Schema};
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
 use datafusion_common::{DataFusionError, Result};
+use datafusion_execution::SendableRecordBatchStream;
+use datafusion_shared::{DEFAULT_CRS, field_crs};
+use futures::StreamExt;
+use geoarrow_array::array::{GeometryArray, from_arrow_array};
+use geoarrow_array::cast::{AsGeoArrowArray, from_wkb, to_wkb};
 use geoarrow_array::{GeoArrowArray, GeoArrowArrayAccessor};
-use geojson::{Feature, FeatureCollection, GeoJson, JsonObject, JsonValue};
+use geoarrow_schema::GeometryType;
+use geojson::feature::Id;
+use geojson::{Bbox, Feature, JsonObject, JsonValue, Value as GeoJsonValue};
 use geozero::ToJson;
 
 /// Options for `GeoJSON` writing
@@ -19,6 +36,15 @@ pub struct GeoJsonWriterOptions {
     pub feature_collection: bool,
     /// Pretty-print JSON output (default: false)
     pub pretty_print: bool,
+    /// Column written as the feature `id` member instead of a property (default: none)
+    pub id_column: Option<String>,
+    /// Columns holding JSON text written as foreign members instead of properties
+    /// (default: none)
+    pub foreign_member_columns: Vec<String>,
+    /// Write a `bbox` member on each feature and on the `FeatureCollection` (default: false)
+    pub bbox: bool,
+    /// Number of decimal places coordinates are rounded to (default: full precision)
+    pub coordinate_precision: Option<u32>,
 }
 
 impl Default for GeoJsonWriterOptions {
@@ -27,6 +53,10 @@ impl Default for GeoJsonWriterOptions {
             geometry_column_name: "geometry".to_string(),
             feature_collection: true,
             pretty_print: false,
+            id_column: None,
+            foreign_member_columns: Vec::new(),
+            bbox: false,
+            coordinate_precision: None,
         }
     }
 }
@@ -58,125 +88,238 @@ impl GeoJsonWriterOptions {
         self.pretty_print = pretty_print;
         self
     }
+
+    /// Set the column written as the feature `id`
+    #[must_use]
+    pub fn with_id_column(mut self, name: impl Into<String>) -> Self {
+        self.id_column = Some(name.into());
+        self
+    }
+
+    /// Set the columns written as foreign members
+    #[must_use]
+    pub fn with_foreign_member_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.foreign_member_columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set whether to write feature and collection bounding boxes
+    #[must_use]
+    pub fn with_bbox(mut self, bbox: bool) -> Self {
+        self.bbox = bbox;
+        self
+    }
+
+    /// Round coordinates to this many decimal places
+    ///
+    /// Six decimal places of longitude/latitude are about 10 cm at the equator, which is
+    /// enough for most web maps and much shorter than full `f64` precision.
+    #[must_use]
+    pub fn with_coordinate_precision(mut self, decimals: u32) -> Self {
+        self.coordinate_precision = Some(decimals);
+        self
+    }
 }
 
-/// Convert `GeoArrow` geometry to `GeoJSON` geometry using geozero
-fn geoarrow_to_geojson_geometry(
+/// Read a geometry column as a `geoarrow.geometry` array
+///
+/// Columns of any other `GeoArrow` type are cast once per batch. Columns that are not
+/// `GeoArrow` geometries yield `None`, and every feature is written without a geometry.
+fn geometry_column(
     geom_array: &dyn Array,
     geom_field: &arrow_schema::Field,
-    row_idx: usize,
-) -> Result<Option<geojson::Geometry>> {
-    use geoarrow_array::array::GeometryArray;
+) -> Result<Option<GeometryArray>> {
+    if let Ok(geometries) = GeometryArray::try_from((geom_array, geom_field)) {
+        return Ok(Some(geometries));
+    }
+    let is_geoarrow = geom_field
+        .extension_type_name()
+        .is_some_and(|name| name.starts_with("geoarrow."));
+    let Some(geometries) = is_geoarrow
+        .then(|| from_arrow_array(geom_array, geom_field).ok())
+        .flatten()
+    else {
+        return Ok(None);
+    };
 
-    // Try to convert from Arrow array to GeometryArray (supports all geometry types)
-    let geometry_array_result = GeometryArray::try_from((geom_array, geom_field));
+    let to_geoarrow_error = |e| DataFusionError::External(Box::new(e));
+    let metadata = Arc::clone(geometries.data_type().metadata());
+    let wkb = to_wkb::<i32>(geometries.as_ref()).map_err(to_geoarrow_error)?;
+    let geometries =
+        from_wkb(&wkb, GeometryType::new(metadata).into()).map_err(to_geoarrow_error)?;
+    Ok(Some(geometries.as_geometry().clone()))
+}
 
-    match geometry_array_result {
-        Ok(geom_arr) => {
-            // Check if the value at this row is null
-            if geom_arr.is_null(row_idx) {
-                return Ok(None);
-            }
+/// The CRS of the geometry column in `schema`, when it is known not to be WGS 84
+///
+/// RFC 7946 `GeoJSON` is always WGS 84 longitude/latitude, so such data needs reprojecting
+/// before it is written. Columns without CRS metadata are assumed to be WGS 84.
+#[must_use]
+pub fn non_wgs84_crs(schema: &Schema, geometry_column: &str) -> Option<String> {
+    let field = schema.field_with_name(geometry_column).ok()?;
+    field_crs(field).filter(|definition| definition != DEFAULT_CRS)
+}
 
-            // Get the geometry scalar at this row index using GeoArrowArrayAccessor
-            let geom = geom_arr
-                .value(row_idx)
-                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+/// Convert a `GeoArrow` geometry to a `GeoJSON` geometry using geozero
+fn geoarrow_to_geojson_geometry(
+    geometries: &GeometryArray,
+    row_idx: usize,
+) -> Result<Option<geojson::Geometry>> {
+    if geometries.is_null(row_idx) {
+        return Ok(None);
+    }
 
-            // Convert to GeoJSON string using geozero's ToJson trait
-            let geojson_string = geom
-                .to_json()
-                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+    // Get the geometry scalar at this row index using GeoArrowArrayAccessor
+    let geom = geometries
+        .value(row_idx)
+        .map_err(|e| DataFusionError::External(Box::new(e)))?;
 
-            // Parse the GeoJSON string into a geojson::Geometry
-            let geometry: geojson::Geometry = serde_json::from_str(&geojson_string)
-                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+    // Convert to GeoJSON string using geozero's ToJson trait
+    let geojson_string = geom
+        .to_json()
+        .map_err(|e| DataFusionError::External(Box::new(e)))?;
 
-            Ok(Some(geometry))
-        },
-        Err(e) => {
-            // Log the error for debugging
-            eprintln!("DEBUG: Failed to convert to GeometryArray: {e:?}");
-            // Not a GeoArrow geometry column
-            Ok(None)
-        },
-    }
+    // Parse the GeoJSON string into a geojson::Geometry
+    let geometry: geojson::Geometry = serde_json::from_str(&geojson_string)
+        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+    Ok(Some(geometry))
 }
 
 /// Convert Arrow value to JSON value
+///
+/// Numbers, strings and booleans map to their JSON counterparts and nested types to JSON
+/// arrays and objects. Dates, times and timestamps are written as RFC 3339 strings (naive
+/// timestamps without an offset), binary values as base64 strings and decimals as numbers
+/// unless the number would not read back as the same decimal, in which case they are written
+/// as strings. Any other type is written using Arrow's display formatting.
 fn arrow_value_to_json(array: &dyn Array, row: usize) -> Result<JsonValue> {
     if array.is_null(row) {
         return Ok(JsonValue::Null);
     }
 
-    match array.data_type() {
-        DataType::Boolean => {
-            let arr = array
-                .as_any()
-                .downcast_ref::<arrow_array::BooleanArray>()
-                .ok_or_else(|| {
-                    DataFusionError::Internal("Failed to downcast to BooleanArray".to_string())
-                })?;
-            Ok(JsonValue::Bool(arr.value(row)))
+    let value = match array.data_type() {
+        DataType::Null => JsonValue::Null,
+        DataType::Boolean => JsonValue::Bool(array.as_boolean().value(row)),
+        DataType::Int8 => array.as_primitive::<Int8Type>().value(row).into(),
+        DataType::Int16 => array.as_primitive::<Int16Type>().value(row).into(),
+        DataType::Int32 => array.as_primitive::<Int32Type>().value(row).into(),
+        DataType::Int64 => array.as_primitive::<Int64Type>().value(row).into(),
+        DataType::UInt8 => array.as_primitive::<UInt8Type>().value(row).into(),
+        DataType::UInt16 => array.as_primitive::<UInt16Type>().value(row).into(),
+        DataType::UInt32 => array.as_primitive::<UInt32Type>().value(row).into(),
+        DataType::UInt64 => array.as_primitive::<UInt64Type>().value(row).into(),
+        DataType::Float16 => float_to_json(array.as_primitive::<Float16Type>().value(row).to_f64()),
+        DataType::Float32 => {
+            float_to_json(f64::from(array.as_primitive::<Float32Type>().value(row)))
         },
-        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
-            let arr = array
-                .as_any()
-                .downcast_ref::<arrow_array::Int64Array>()
-                .or_else(|| {
-                    array
-                        .as_any()
-                        .downcast_ref::<arrow_array::Int32Array>()
-                        .map(|_| None::<&arrow_array::Int64Array>);
-                    None
-                });
-
-            if let Some(arr) = arr {
-                Ok(JsonValue::Number(arr.value(row).into()))
-            } else {
-                // Handle Int8, Int16, Int32
-                let arr32 = array
-                    .as_any()
-                    .downcast_ref::<arrow_array::Int32Array>()
-                    .ok_or_else(|| {
-                        DataFusionError::Internal("Failed to downcast to Int32Array".to_string())
-                    })?;
-                Ok(JsonValue::Number(i64::from(arr32.value(row)).into()))
+        DataType::Float64 => float_to_json(array.as_primitive::<Float64Type>().value(row)),
+        DataType::Decimal32(..)
+        | DataType::Decimal64(..)
+        | DataType::Decimal128(..)
+        | DataType::Decimal256(..) => decimal_to_json(array, row)?,
+        DataType::Utf8 => JsonValue::String(array.as_string::<i32>().value(row).to_string()),
+        DataType::LargeUtf8 => JsonValue::String(array.as_string::<i64>().value(row).to_string()),
+        DataType::Utf8View => JsonValue::String(array.as_string_view().value(row).to_string()),
+        DataType::Binary => binary_to_json(array.as_binary::<i32>().value(row)),
+        DataType::LargeBinary => binary_to_json(array.as_binary::<i64>().value(row)),
+        DataType::BinaryView => binary_to_json(array.as_binary_view().value(row)),
+        DataType::FixedSizeBinary(_) => binary_to_json(array.as_fixed_size_binary().value(row)),
+        DataType::Date64 => {
+            let millis = array.as_primitive::<Date64Type>().value(row);
+            match as_date::<Date64Type>(millis) {
+                Some(date) => JsonValue::String(date.to_string()),
+                None => JsonValue::String(display_value(array, row)?),
             }
         },
-        DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
-            #[allow(clippy::cast_possible_wrap)]
-            if let Some(arr) = array.as_any().downcast_ref::<arrow_array::UInt64Array>() {
-                Ok(JsonValue::Number((arr.value(row) as i64).into()))
-            } else if let Some(arr) = array.as_any().downcast_ref::<arrow_array::UInt32Array>() {
-                Ok(JsonValue::Number(i64::from(arr.value(row)).into()))
-            } else {
-                Err(DataFusionError::Internal(
-                    "Failed to downcast unsigned integer array".to_string(),
-                ))
-            }
+        DataType::Date32
+        | DataType::Time32(_)
+        | DataType::Time64(_)
+        | DataType::Timestamp(_, _) => JsonValue::String(display_value(array, row)?),
+        DataType::List(_) => list_to_json(array.as_list::<i32>().value(row).as_ref())?,
+        DataType::LargeList(_) => list_to_json(array.as_list::<i64>().value(row).as_ref())?,
+        DataType::FixedSizeList(_, _) => {
+            list_to_json(array.as_fixed_size_list().value(row).as_ref())?
         },
-        DataType::Float32 | DataType::Float64 => {
-            let arr = array
-                .as_any()
-                .downcast_ref::<arrow_array::Float64Array>()
-                .ok_or_else(|| {
-                    DataFusionError::Internal("Failed to downcast to Float64Array".to_string())
-                })?;
-            let val = arr.value(row);
-            Ok(serde_json::Number::from_f64(val).map_or(JsonValue::Null, JsonValue::Number))
+        DataType::Struct(_) => {
+            let arr = array.as_struct();
+            let mut object = JsonObject::new();
+            for (field, column) in arr.fields().iter().zip(arr.columns()) {
+                object.insert(
+                    field.name().clone(),
+                    arrow_value_to_json(column.as_ref(), row)?,
+                );
+            }
+            JsonValue::Object(object)
         },
-        DataType::Utf8 | DataType::LargeUtf8 => {
-            let arr = array
-                .as_any()
-                .downcast_ref::<arrow_array::StringArray>()
-                .ok_or_else(|| {
-                    DataFusionError::Internal("Failed to downcast to StringArray".to_string())
-                })?;
-            Ok(JsonValue::String(arr.value(row).to_string()))
+        DataType::Map(_, _) => {
+            let entries = array.as_map().value(row);
+            let keys = entries.column(0);
+            let values = entries.column(1);
+            let mut object = JsonObject::new();
+            for idx in 0..entries.len() {
+                let key = match arrow_value_to_json(keys.as_ref(), idx)? {
+                    JsonValue::String(key) => key,
+                    other => other.to_string(),
+                };
+                object.insert(key, arrow_value_to_json(values.as_ref(), idx)?);
+            }
+            JsonValue::Object(object)
         },
-        _ => Ok(JsonValue::String(format!("{array:?}"))),
+        _ => JsonValue::String(display_value(array, row)?),
+    };
+
+    Ok(value)
+}
+
+/// Convert a float to a JSON number; NaN and infinities become null
+fn float_to_json(value: f64) -> JsonValue {
+    serde_json::Number::from_f64(value).map_or(JsonValue::Null, JsonValue::Number)
+}
+
+/// Convert a decimal to a JSON number when the number is written as exactly the same
+/// decimal, otherwise to a string
+///
+/// JSON numbers are written with the shortest representation that round-trips through `f64`,
+/// so decimals with more significant digits than `f64` holds are written as strings.
+fn decimal_to_json(array: &dyn Array, row: usize) -> Result<JsonValue> {
+    let text = display_value(array, row)?;
+    let canonical = if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        text.as_str()
+    };
+
+    if let Ok(value) = text.parse::<f64>()
+        && value.to_string() == canonical
+        && let Some(number) = serde_json::Number::from_f64(value)
+    {
+        return Ok(JsonValue::Number(number));
     }
+
+    Ok(JsonValue::String(text))
+}
+
+/// Encode binary data as a base64 JSON string
+fn binary_to_json(bytes: &[u8]) -> JsonValue {
+    JsonValue::String(BASE64_STANDARD.encode(bytes))
+}
+
+/// Convert the elements of a single list value to a JSON array
+fn list_to_json(values: &dyn Array) -> Result<JsonValue> {
+    (0..values.len())
+        .map(|idx| arrow_value_to_json(values, idx))
+        .collect::<Result<Vec<_>>>()
+        .map(JsonValue::Array)
+}
+
+/// Format a single value using Arrow's display formatting
+fn display_value(array: &dyn Array, row: usize) -> Result<String> {
+    array_value_to_string(array, row).map_err(DataFusionError::from)
 }
 
 /// Convert a record batch to `GeoJSON` features
@@ -191,27 +334,39 @@ pub fn batch_to_features(
     let schema = batch.schema();
     let num_rows = batch.num_rows();
 
+    let column_index = |name: &str, role: &str| {
+        schema
+            .fields()
+            .iter()
+            .position(|f| f.name() == name)
+            .ok_or_else(|| {
+                DataFusionError::Plan(format!("{role} column '{name}' not found in schema"))
+            })
+    };
+
     // Find geometry column index
-    let geom_idx = schema
-        .fields()
+    let geom_idx = column_index(&options.geometry_column_name, "Geometry")?;
+    let id_idx = options
+        .id_column
+        .as_deref()
+        .map(|name| column_index(name, "Id"))
+        .transpose()?;
+    let foreign_idx = options
+        .foreign_member_columns
         .iter()
-        .position(|f| f.name() == &options.geometry_column_name)
-        .ok_or_else(|| {
-            DataFusionError::Plan(format!(
-                "Geometry column '{}' not found in schema",
-                options.geometry_column_name
-            ))
-        })?;
+        .map(|name| column_index(name, "Foreign member"))
+        .collect::<Result<Vec<_>>>()?;
 
+    let geometries = geometry_column(batch.column(geom_idx).as_ref(), schema.field(geom_idx))?;
     let mut features = Vec::with_capacity(num_rows);
 
     for row_idx in 0..num_rows {
         let mut properties = JsonObject::new();
 
-        // Extract properties (all columns except geometry)
+        // Extract properties (all columns except geometry, id and foreign members)
         for (col_idx, field) in schema.fields().iter().enumerate() {
-            if col_idx == geom_idx {
-                continue; // Skip geometry column for properties
+            if col_idx == geom_idx || Some(col_idx) == id_idx || foreign_idx.contains(&col_idx) {
+                continue;
             }
 
             let column = batch.column(col_idx);
@@ -219,17 +374,45 @@ pub fn batch_to_features(
             properties.insert(field.name().clone(), value);
         }
 
-        // Extract geometry - use geoarrow_to_geojson_geometry helper
-        let geom_column = batch.column(geom_idx);
-        let geom_field = schema.field(geom_idx);
-        let geometry = geoarrow_to_geojson_geometry(geom_column.as_ref(), geom_field, row_idx)?;
+        let mut geometry = match &geometries {
+            Some(geometries) => geoarrow_to_geojson_geometry(geometries, row_idx)?,
+            None => None,
+        };
+        if let (Some(geometry), Some(decimals)) = (&mut geometry, options.coordinate_precision) {
+            round_coordinates(&mut geometry.value, decimals);
+        }
+
+        let id = match id_idx {
+            Some(idx) => {
+                json_to_feature_id(arrow_value_to_json(batch.column(idx).as_ref(), row_idx)?)
+            },
+            None => None,
+        };
+
+        let mut foreign_members = JsonObject::new();
+        for &idx in &foreign_idx {
+            let value = match arrow_value_to_json(batch.column(idx).as_ref(), row_idx)? {
+                JsonValue::Null => continue,
+                JsonValue::String(text) => {
+                    serde_json::from_str(&text).unwrap_or(JsonValue::String(text))
+                },
+                other => other,
+            };
+            foreign_members.insert(schema.field(idx).name().clone(), value);
+        }
+
+        let bbox = if options.bbox {
+            geometry.as_ref().and_then(geometry_bbox)
+        } else {
+            None
+        };
 
         let feature = Feature {
-            bbox: None,
+            bbox,
             geometry,
-            id: None,
+            id,
             properties: Some(properties),
-            foreign_members: None,
+            foreign_members: (!foreign_members.is_empty()).then_some(foreign_members),
         };
 
         features.push(feature);
@@ -238,8 +421,284 @@ pub fn batch_to_features(
     Ok(features)
 }
 
+/// Convert a JSON value to a feature `id`; numbers and strings are kept as-is
+fn json_to_feature_id(value: JsonValue) -> Option<Id> {
+    match value {
+        JsonValue::Null => None,
+        JsonValue::Number(number) => Some(Id::Number(number)),
+        JsonValue::String(text) => Some(Id::String(text)),
+        other => Some(Id::String(other.to_string())),
+    }
+}
+
+/// Round every coordinate of a geometry to `decimals` decimal places
+fn round_coordinates(value: &mut GeoJsonValue, decimals: u32) {
+    let scale = 10f64.powi(i32::try_from(decimals).unwrap_or(i32::MAX));
+    let round = |position: &mut Vec<f64>| {
+        for coordinate in position {
+            // Adding zero turns -0.0 into 0.0
+            let rounded = (*coordinate * scale).round() / scale + 0.0;
+            // Scaling very large coordinates can overflow; they are kept as they are
+            if rounded.is_finite() {
+                *coordinate = rounded;
+            }
+        }
+    };
+
+    match value {
+        GeoJsonValue::Point(position) => round(position),
+        GeoJsonValue::MultiPoint(positions) | GeoJsonValue::LineString(positions) => {
+            positions.iter_mut().for_each(round);
+        },
+        GeoJsonValue::MultiLineString(lines) | GeoJsonValue::Polygon(lines) => {
+            lines.iter_mut().flatten().for_each(round);
+        },
+        GeoJsonValue::MultiPolygon(polygons) => {
+            polygons.iter_mut().flatten().flatten().for_each(round);
+        },
+        GeoJsonValue::GeometryCollection(geometries) => {
+            for geometry in geometries {
+                round_coordinates(&mut geometry.value, decimals);
+            }
+        },
+    }
+}
+
+/// Compute the 2D bounding box of a `GeoJSON` geometry
+fn geometry_bbox(geometry: &geojson::Geometry) -> Option<Bbox> {
+    fn visit_positions(value: &GeoJsonValue, visit: &mut impl FnMut(&[f64])) {
+        match value {
+            GeoJsonValue::Point(position) => visit(position),
+            GeoJsonValue::MultiPoint(positions) | GeoJsonValue::LineString(positions) => {
+                positions.iter().for_each(|position| visit(position));
+            },
+            GeoJsonValue::MultiLineString(lines) | GeoJsonValue::Polygon(lines) => {
+                lines.iter().flatten().for_each(|position| visit(position))
+            },
+            GeoJsonValue::MultiPolygon(polygons) => polygons
+                .iter()
+                .flatten()
+                .flatten()
+                .for_each(|position| visit(position)),
+            GeoJsonValue::GeometryCollection(geometries) => {
+                for geometry in geometries {
+                    visit_positions(&geometry.value, visit);
+                }
+            },
+        }
+    }
+
+    let mut bbox: Option<Bbox> = None;
+    visit_positions(&geometry.value, &mut |position| {
+        if let [x, y, ..] = *position {
+            bbox = Some(merge_bbox(bbox.take(), &[x, y, x, y]));
+        }
+    });
+    bbox
+}
+
+/// Extend a 2D bounding box to cover another one
+fn merge_bbox(current: Option<Bbox>, other: &[f64]) -> Bbox {
+    match current {
+        Some(current) => vec![
+            current[0].min(other[0]),
+            current[1].min(other[1]),
+            current[2].max(other[2]),
+            current[3].max(other[3]),
+        ],
+        None => other.to_vec(),
+    }
+}
+
+/// Incremental `GeoJSON` writer
+///
+/// Features are serialised as each batch is written, so memory use is bounded by the largest
+/// batch rather than the dataset. In `FeatureCollection` mode the collection header is written
+/// before the first feature and the closing bracket, together with the collection `bbox` when
+/// enabled and any other collection members, by [`GeoJsonStreamWriter::finish`].
+pub struct GeoJsonStreamWriter<W: IoWrite> {
+    writer: W,
+    options: GeoJsonWriterOptions,
+    started: bool,
+    features_written: u64,
+    collection_bbox: bool,
+    bbox: Option<Bbox>,
+    collection_members: JsonObject,
+}
+
+impl<W: IoWrite> GeoJsonStreamWriter<W> {
+    /// Create a writer that serialises features into `writer`
+    pub fn new(writer: W, options: GeoJsonWriterOptions) -> Self {
+        Self {
+            writer,
+            started: false,
+            features_written: 0,
+            collection_bbox: options.bbox,
+            bbox: None,
+            collection_members: JsonObject::new(),
+            options,
+        }
+    }
+
+    /// Set whether the collection `bbox` is written (default: the `bbox` option)
+    ///
+    /// The `bbox` covers the features written while this is enabled, including those that
+    /// get no `bbox` member themselves.
+    pub fn set_collection_bbox(&mut self, collection_bbox: bool) {
+        self.collection_bbox = collection_bbox;
+    }
+
+    /// Set members, such as `name` or `crs`, written after the features of the collection
+    ///
+    /// A `type`, `features` or `bbox` member is ignored.
+    pub fn set_collection_members(&mut self, mut members: JsonObject) {
+        for reserved in ["type", "features", "bbox"] {
+            members.remove(reserved);
+        }
+        self.collection_members = members;
+    }
+
+    /// Number of features written so far
+    #[must_use]
+    pub fn features_written(&self) -> u64 {
+        self.features_written
+    }
+
+    /// Serialise the features of a record batch
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if type conversion, serialisation or writing fails
+    pub fn write_batch(&mut self, batch: &RecordBatch) -> Result<()> {
+        self.write_header()?;
+
+        for feature in batch_to_features(batch, &self.options)? {
+            self.write_feature(&feature)?;
+        }
+        Ok(())
+    }
+
+    /// Serialise a single feature
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialisation or writing fails
+    pub fn write_feature(&mut self, feature: &Feature) -> Result<()> {
+        self.write_header()?;
+
+        if self.collection_bbox {
+            let computed = match feature.bbox {
+                Some(_) => None,
+                None => feature.geometry.as_ref().and_then(geometry_bbox),
+            };
+            if let Some(bbox) = feature.bbox.as_ref().or(computed.as_ref()) {
+                self.bbox = Some(merge_bbox(self.bbox.take(), bbox));
+            }
+        }
+
+        if self.options.feature_collection {
+            let separator: &[u8] = match (self.features_written, self.options.pretty_print) {
+                (0, _) => b"",
+                (_, true) => b",\n",
+                (_, false) => b",",
+            };
+            self.write_bytes(separator)?;
+        }
+
+        if self.options.pretty_print && self.options.feature_collection {
+            let json = serde_json::to_string_pretty(feature)
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+            for (idx, line) in json.lines().enumerate() {
+                if idx > 0 {
+                    self.write_bytes(b"\n")?;
+                }
+                self.write_bytes(b"    ")?;
+                self.write_bytes(line.as_bytes())?;
+            }
+        } else {
+            serde_json::to_writer(&mut self.writer, feature)
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        }
+
+        if !self.options.feature_collection {
+            // Newline-delimited GeoJSON
+            self.write_bytes(b"\n")?;
+        }
+
+        self.features_written += 1;
+        Ok(())
+    }
+
+    /// Write the collection footer and flush, returning the underlying writer
+    ///
+    /// A `FeatureCollection` is always completed, even if no batch was written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing or flushing fails
+    pub fn finish(mut self) -> Result<W> {
+        self.write_header()?;
+
+        if self.options.feature_collection {
+            let mut members = std::mem::take(&mut self.collection_members);
+            if let Some(bbox) = self.bbox.take().filter(|_| self.collection_bbox) {
+                members.insert("bbox".to_string(), JsonValue::from(bbox));
+            }
+
+            let pretty = self.options.pretty_print;
+            let close: &[u8] = match (pretty, self.features_written) {
+                (true, 0) | (false, _) => b"]",
+                (true, _) => b"\n  ]",
+            };
+            self.write_bytes(close)?;
+            for (key, value) in &members {
+                let key = serde_json::to_string(key)
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?;
+                let member = if pretty {
+                    format!(",\n  {key}: {}", format!("{value:#}").replace('\n', "\n  "))
+                } else {
+                    format!(",{key}:{value}")
+                };
+                self.write_bytes(member.as_bytes())?;
+            }
+            self.write_bytes(if pretty { b"\n}" } else { b"}" })?;
+        }
+
+        self.writer
+            .flush()
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        Ok(self.writer)
+    }
+
+    fn write_header(&mut self) -> Result<()> {
+        if self.started {
+            return Ok(());
+        }
+        self.started = true;
+
+        if self.options.feature_collection {
+            let header: &[u8] = if self.options.pretty_print {
+                b"{\n  \"type\": \"FeatureCollection\",\n  \"features\": [\n"
+            } else {
+                b"{\"type\":\"FeatureCollection\",\"features\":["
+            };
+            self.write_bytes(header)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.writer
+            .write_all(bytes)
+            .map_err(|e| DataFusionError::External(Box::new(e)))
+    }
+}
+
 /// Write record batches to `GeoJSON` format
 ///
+/// Nothing is written when `batches` is empty.
+///
 /// # Errors
 ///
 /// Returns an error if writing to the output fails or if `GeoJSON` serialization fails
@@ -252,48 +711,36 @@ pub fn write_geojson<W: IoWrite>(
         return Ok(());
     }
 
-    let mut all_features = Vec::new();
-
+    let mut stream_writer = GeoJsonStreamWriter::new(writer, options.clone());
     for batch in batches {
-        let features = batch_to_features(batch, options)?;
-        all_features.extend(features);
+        stream_writer.write_batch(batch)?;
     }
+    stream_writer.finish()?;
 
-    if options.feature_collection {
-        let collection = FeatureCollection {
-            bbox: None,
-            features: all_features,
-            foreign_members: None,
-        };
-
-        let geojson = GeoJson::FeatureCollection(collection);
-        let json_str = if options.pretty_print {
-            serde_json::to_string_pretty(&geojson)
-        } else {
-            serde_json::to_string(&geojson)
-        }
-        .map_err(|e| DataFusionError::External(Box::new(e)))?;
-
-        writer
-            .write_all(json_str.as_bytes())
-            .map_err(|e| DataFusionError::External(Box::new(e)))?;
-    } else {
-        // Newline-delimited GeoJSON
-        for feature in all_features {
-            let geojson = GeoJson::Feature(feature);
-            let json_str = serde_json::to_string(&geojson)
-                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+    Ok(())
+}
 
-            writer
-                .write_all(json_str.as_bytes())
-                .map_err(|e| DataFusionError::External(Box::new(e)))?;
-            writer
-                .write_all(b"\n")
-                .map_err(|e| DataFusionError::External(Box::new(e)))?;
-        }
+/// Write a stream of record batches to `GeoJSON` format, one batch at a time
+///
+/// Returns the number of rows written.
+///
+/// # Errors
+///
+/// Returns an error if the input stream fails, if writing to the output fails or if `GeoJSON`
+/// serialization fails
+pub async fn write_geojson_stream<W: IoWrite>(
+    mut stream: SendableRecordBatchStream,
+    writer: W,
+    options: &GeoJsonWriterOptions,
+) -> Result<u64> {
+    let mut stream_writer = GeoJsonStreamWriter::new(writer, options.clone());
+    while let Some(batch) = stream.next().await {
+        stream_writer.write_batch(&batch?)?;
     }
+    let rows = stream_writer.features_written();
+    stream_writer.finish()?;
 
-    Ok(())
+    Ok(rows)
 }
 
 /// Write record batches to `GeoJSON` bytes
@@ -313,6 +760,7 @@ pub fn write_geojson_to_bytes(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use arrow_array::builder::{Float64Builder, Int64Builder};
     use arrow_array::{ArrayRef, Int64Array, StringArray};
     use arrow_schema::{Field, Schema};
     use std::sync::Arc;
@@ -348,6 +796,32 @@ mod tests {
         assert!(json_str.contains("\"features\""));
     }
 
+    #[test]
+    fn test_write_collection_members() {
+        let members: JsonObject = serde_json::from_str(
+            r#"{"name":"places","crs":{"type":"name","properties":{"name":"CRS84"}},"bbox":[0,0,0,0]}"#,
+        )
+        .unwrap();
+        let feature = Feature {
+            geometry: Some(geojson::Geometry::new(GeoJsonValue::Point(vec![1.0, 2.0]))),
+            ..Feature::default()
+        };
+
+        for pretty_print in [false, true] {
+            let options = GeoJsonWriterOptions::default().with_pretty_print(pretty_print);
+            let mut writer = GeoJsonStreamWriter::new(Vec::new(), options);
+            writer.set_collection_bbox(true);
+            writer.set_collection_members(members.clone());
+            writer.write_feature(&feature).unwrap();
+            let written: JsonValue = serde_json::from_slice(&writer.finish().unwrap()).unwrap();
+
+            assert_eq!(written["features"].as_array().unwrap().len(), 1);
+            assert_eq!(written["name"], "places");
+            assert_eq!(written["crs"], members["crs"]);
+            assert_eq!(written["bbox"], serde_json::json!([1.0, 2.0, 1.0, 2.0]));
+        }
+    }
+
     #[test]
     fn test_write_newline_delimited() {
         let batch = create_test_batch();
@@ -361,6 +835,71 @@ mod tests {
         assert!(lines[0].contains("\"type\":\"Feature\""));
     }
 
+    #[test]
+    fn test_write_any_geoarrow_type() {
+        use geoarrow_schema::{GeoArrowType, WktType};
+
+        let schema = Arc::new(Schema::new(vec![
+            GeoArrowType::Wkt(WktType::new(Arc::default())).to_field("geometry", true),
+        ]));
+        let geometries: ArrayRef = Arc::new(StringArray::from(vec![
+            Some("POINT(1 2)"),
+            None,
+            Some("LINESTRING(0 0,1 1)"),
+        ]));
+        let batch = RecordBatch::try_new(schema, vec![geometries]).unwrap();
+
+        let features = batch_to_features(&batch, &GeoJsonWriterOptions::default()).unwrap();
+        let geometries: Vec<Option<GeoJsonValue>> = features
+            .into_iter()
+            .map(|feature| feature.geometry.map(|geometry| geometry.value))
+            .collect();
+        assert_eq!(
+            geometries,
+            vec![
+                Some(GeoJsonValue::Point(vec![1.0, 2.0])),
+                None,
+                Some(GeoJsonValue::LineString(vec![
+                    vec![0.0, 0.0],
+                    vec![1.0, 1.0]
+                ])),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_non_wgs84_crs() {
+        use geoarrow_schema::{Crs, GeoArrowType, Metadata, WktType};
+
+        let schema_with_crs = |crs: Crs| {
+            Schema::new(vec![
+                GeoArrowType::Wkt(WktType::new(Arc::new(Metadata::new(crs, None))))
+                    .to_field("geometry", true),
+            ])
+        };
+
+        let projected = schema_with_crs(Crs::from_authority_code("EPSG:32618".to_string()));
+        assert_eq!(
+            non_wgs84_crs(&projected, "geometry"),
+            Some("EPSG:32618".to_string())
+        );
+
+        let crs84 = schema_with_crs(Crs::from_authority_code("OGC:CRS84".to_string()));
+        assert_eq!(non_wgs84_crs(&crs84, "geometry"), None);
+        let projjson = schema_with_crs(Crs::from_projjson(serde_json::json!({
+            "id": {"authority": "EPSG", "code": 4326}
+        })));
+        assert_eq!(non_wgs84_crs(&projjson, "geometry"), None);
+        let proj_string = schema_with_crs(Crs::from_unknown_crs_type(
+            "+proj=longlat +datum=WGS84 +no_defs".to_string(),
+        ));
+        assert_eq!(non_wgs84_crs(&proj_string, "geometry"), None);
+        assert_eq!(
+            non_wgs84_crs(&schema_with_crs(Crs::default()), "geometry"),
+            None
+        );
+    }
+
     #[test]
     fn test_empty_batches() {
         let batches: Vec<RecordBatch> = vec![];
@@ -369,4 +908,436 @@ mod tests {
         let result = write_geojson_to_bytes(&batches, &options).unwrap();
         assert!(result.is_empty());
     }
+
+    #[test]
+    fn test_nested_values_to_json() {
+        use arrow_array::StructArray;
+        use arrow_array::builder::{ListBuilder, MapBuilder, StringBuilder};
+
+        let mut tags = ListBuilder::new(StringBuilder::new());
+        tags.values().append_value("a");
+        tags.values().append_value("b");
+        tags.append(true);
+        tags.append(false);
+        let tags = tags.finish();
+
+        let info = StructArray::from(vec![
+            (
+                Arc::new(Field::new("height", DataType::Int64, true)),
+                Arc::new(Int64Array::from(vec![Some(10), None])) as ArrayRef,
+            ),
+            (
+                Arc::new(Field::new("kind", DataType::Utf8, true)),
+                Arc::new(StringArray::from(vec![Some("tower"), Some("hut")])) as ArrayRef,
+            ),
+        ]);
+
+        let mut names = MapBuilder::new(None, StringBuilder::new(), Int64Builder::new());
+        names.keys().append_value("en");
+        names.values().append_value(1);
+        names.append(true).unwrap();
+        names.append(true).unwrap();
+        let names = names.finish();
+
+        assert_eq!(
+            arrow_value_to_json(&tags, 0).unwrap(),
+            serde_json::json!(["a", "b"])
+        );
+        assert_eq!(arrow_value_to_json(&tags, 1).unwrap(), JsonValue::Null);
+        assert_eq!(
+            arrow_value_to_json(&info, 0).unwrap(),
+            serde_json::json!({"height": 10, "kind": "tower"})
+        );
+        assert_eq!(
+            arrow_value_to_json(&info, 1).unwrap(),
+            serde_json::json!({"height": null, "kind": "hut"})
+        );
+        assert_eq!(
+            arrow_value_to_json(&names, 0).unwrap(),
+            serde_json::json!({"en": 1})
+        );
+        assert_eq!(
+            arrow_value_to_json(&names, 1).unwrap(),
+            serde_json::json!({})
+        );
+    }
+
+    #[test]
+    fn test_write_id_and_foreign_members() {
+        let batch = create_test_batch();
+        let batch = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![
+                batch.schema().field(0).clone(),
+                batch.schema().field(1).clone(),
+                batch.schema().field(2).clone(),
+                Field::new("source", DataType::Utf8, true),
+            ])),
+            vec![
+                batch.column(0).clone(),
+                batch.column(1).clone(),
+                batch.column(2).clone(),
+                Arc::new(StringArray::from(vec![
+                    Some(r#"{"name":"survey"}"#),
+                    Some("not json"),
+                    None,
+                ])),
+            ],
+        )
+        .unwrap();
+        let options = GeoJsonWriterOptions::default()
+            .with_id_column("id")
+            .with_foreign_member_columns(["source"]);
+
+        let features = batch_to_features(&batch, &options).unwrap();
+
+        assert_eq!(features[0].id, Some(Id::Number(1.into())));
+        let properties = features[0].properties.as_ref().unwrap();
+        assert!(!properties.contains_key("id"));
+        assert!(!properties.contains_key("source"));
+        assert_eq!(
+            features[0].foreign_members.as_ref().unwrap()["source"],
+            serde_json::json!({"name": "survey"})
+        );
+        assert_eq!(
+            features[1].foreign_members.as_ref().unwrap()["source"],
+            serde_json::json!("not json")
+        );
+        assert!(features[2].foreign_members.is_none());
+    }
+
+    #[test]
+    fn test_missing_id_column() {
+        let batch = create_test_batch();
+        let options = GeoJsonWriterOptions::default().with_id_column("missing");
+
+        let err = batch_to_features(&batch, &options).unwrap_err();
+        assert!(err.to_string().contains("Id column 'missing' not found"));
+    }
+
+    #[test]
+    fn test_geometry_bbox() {
+        let polygon = geojson::Geometry::new(GeoJsonValue::Polygon(vec![vec![
+            vec![0.0, 0.0],
+            vec![4.0, -1.0],
+            vec![2.0, 3.0],
+            vec![0.0, 0.0],
+        ]]));
+        let collection = geojson::Geometry::new(GeoJsonValue::GeometryCollection(vec![
+            polygon.clone(),
+            geojson::Geometry::new(GeoJsonValue::Point(vec![-2.0, 1.0, 100.0])),
+        ]));
+
+        assert_eq!(geometry_bbox(&polygon), Some(vec![0.0, -1.0, 4.0, 3.0]));
+        assert_eq!(geometry_bbox(&collection), Some(vec![-2.0, -1.0, 4.0, 3.0]));
+        assert_eq!(
+            geometry_bbox(&geojson::Geometry::new(GeoJsonValue::MultiPoint(vec![]))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_round_coordinates() {
+        let mut collection = GeoJsonValue::GeometryCollection(vec![
+            geojson::Geometry::new(GeoJsonValue::Point(vec![12.453_386_5, 41.903_282_2, 0.5])),
+            geojson::Geometry::new(GeoJsonValue::LineString(vec![
+                vec![-0.000_04, 1.0],
+                vec![1e300, 2.123_45],
+            ])),
+        ]);
+        round_coordinates(&mut collection, 4);
+
+        assert_eq!(
+            serde_json::to_string(&collection).unwrap(),
+            r#"[{"type":"Point","coordinates":[12.4534,41.9033,0.5]},{"type":"LineString","coordinates":[[0.0,1.0],[1e300,2.1235]]}]"#
+        );
+    }
+
+    fn json_values(array: &dyn Array) -> Vec<JsonValue> {
+        (0..array.len())
+            .map(|row| arrow_value_to_json(array, row).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_integer_values_to_json() {
+        use arrow_array::{
+            Int8Array, Int16Array, Int32Array, UInt8Array, UInt16Array, UInt32Array, UInt64Array,
+        };
+
+        let json = serde_json::json!([i8::MIN, null]);
+        assert_eq!(
+            json_values(&Int8Array::from(vec![Some(i8::MIN), None])),
+            json.as_array().unwrap().clone()
+        );
+        assert_eq!(
+            json_values(&Int16Array::from(vec![i16::MIN])),
+            vec![serde_json::json!(i16::MIN)]
+        );
+        assert_eq!(
+            json_values(&Int32Array::from(vec![i32::MIN])),
+            vec![serde_json::json!(i32::MIN)]
+        );
+        assert_eq!(
+            json_values(&Int64Array::from(vec![i64::MIN])),
+            vec![serde_json::json!(i64::MIN)]
+        );
+        assert_eq!(
+            json_values(&UInt8Array::from(vec![u8::MAX])),
+            vec![serde_json::json!(u8::MAX)]
+        );
+        assert_eq!(
+            json_values(&UInt16Array::from(vec![u16::MAX])),
+            vec![serde_json::json!(u16::MAX)]
+        );
+        assert_eq!(
+            json_values(&UInt32Array::from(vec![u32::MAX])),
+            vec![serde_json::json!(u32::MAX)]
+        );
+        assert_eq!(
+            json_values(&UInt64Array::from(vec![u64::MAX])),
+            vec![serde_json::json!(u64::MAX)]
+        );
+    }
+
+    #[test]
+    fn test_float_values_to_json() {
+        use arrow_array::{Float32Array, Float64Array};
+
+        let half = arrow_cast::cast(&Float32Array::from(vec![1.5]), &DataType::Float16).unwrap();
+        assert_eq!(json_values(&half), vec![serde_json::json!(1.5)]);
+        assert_eq!(
+            json_values(&Float32Array::from(vec![2.25, f32::NAN])),
+            vec![serde_json::json!(2.25), JsonValue::Null]
+        );
+        assert_eq!(
+            json_values(&Float64Array::from(vec![
+                Some(-0.125),
+                Some(f64::INFINITY),
+                None
+            ])),
+            vec![serde_json::json!(-0.125), JsonValue::Null, JsonValue::Null]
+        );
+    }
+
+    #[test]
+    fn test_decimal_values_to_json() {
+        use arrow_array::{Decimal32Array, Decimal64Array, Decimal128Array, Decimal256Array};
+        use arrow_buffer::i256;
+
+        let decimals = Decimal128Array::from(vec![Some(12345), Some(-5), None, Some(i128::MAX)])
+            .with_precision_and_scale(38, 2)
+            .unwrap();
+        assert_eq!(
+            json_values(&decimals),
+            vec![
+                serde_json::json!(123.45),
+                serde_json::json!(-0.05),
+                JsonValue::Null,
+                serde_json::json!("170141183460469231731687303715884105.72"),
+            ]
+        );
+
+        // `f64` cannot hold every sixteen-digit decimal; those are written as strings
+        let digits = Decimal128Array::from(vec![9_007_199_254_740_991, 900_719_925_474_099])
+            .with_precision_and_scale(38, 2)
+            .unwrap();
+        assert_eq!(
+            json_values(&digits),
+            vec![
+                serde_json::json!("90071992547409.91"),
+                serde_json::json!(9_007_199_254_740.99),
+            ]
+        );
+
+        let small = Decimal32Array::from(vec![-15])
+            .with_precision_and_scale(9, 1)
+            .unwrap();
+        assert_eq!(json_values(&small), vec![serde_json::json!(-1.5)]);
+        let medium = Decimal64Array::from(vec![42])
+            .with_precision_and_scale(18, -2)
+            .unwrap();
+        assert_eq!(json_values(&medium), vec![serde_json::json!(4200.0)]);
+
+        let wide = Decimal256Array::from(vec![i256::from_i128(250), i256::MAX])
+            .with_precision_and_scale(76, 1)
+            .unwrap();
+        let values = json_values(&wide);
+        assert_eq!(values[0], serde_json::json!(25.0));
+        assert!(values[1].as_str().unwrap().starts_with(
+            "57896044618658097711785492504343953926634992332820282019728792003956564819"
+        ));
+    }
+
+    #[test]
+    fn test_string_values_to_json() {
+        use arrow_array::{LargeStringArray, StringViewArray};
+
+        assert_eq!(
+            json_values(&StringArray::from(vec![Some("a"), None])),
+            vec![serde_json::json!("a"), JsonValue::Null]
+        );
+        assert_eq!(
+            json_values(&LargeStringArray::from(vec!["large"])),
+            vec![serde_json::json!("large")]
+        );
+        assert_eq!(
+            json_values(&StringViewArray::from(vec![
+                "a string longer than twelve bytes"
+            ])),
+            vec![serde_json::json!("a string longer than twelve bytes")]
+        );
+    }
+
+    #[test]
+    fn test_binary_values_to_json() {
+        use arrow_array::{BinaryArray, BinaryViewArray, FixedSizeBinaryArray, LargeBinaryArray};
+
+        let expected = vec![serde_json::json!("aGVsbG8=")];
+        assert_eq!(
+            json_values(&BinaryArray::from(vec![b"hello".as_ref()])),
+            expected
+        );
+        assert_eq!(
+            json_values(&LargeBinaryArray::from(vec![b"hello".as_ref()])),
+            expected
+        );
+        assert_eq!(
+            json_values(&BinaryViewArray::from(vec![b"hello".as_ref()])),
+            expected
+        );
+        let fixed = FixedSizeBinaryArray::try_from_iter(vec![b"hello"].into_iter()).unwrap();
+        assert_eq!(json_values(&fixed), expected);
+    }
+
+    #[test]
+    fn test_temporal_values_to_json() {
+        use arrow_array::{
+            Date32Array, Date64Array, Time32SecondArray, Time64MicrosecondArray,
+            TimestampMillisecondArray, TimestampSecondArray,
+        };
+
+        assert_eq!(
+            json_values(&Date32Array::from(vec![19_000])),
+            vec![serde_json::json!("2022-01-08")]
+        );
+        assert_eq!(
+            json_values(&Date64Array::from(vec![1_641_600_000_000])),
+            vec![serde_json::json!("2022-01-08")]
+        );
+        assert_eq!(
+            json_values(&Time32SecondArray::from(vec![3_723])),
+            vec![serde_json::json!("01:02:03")]
+        );
+        assert_eq!(
+            json_values(&Time64MicrosecondArray::from(vec![3_723_500_000])),
+            vec![serde_json::json!("01:02:03.500")]
+        );
+        assert_eq!(
+            json_values(&TimestampSecondArray::from(vec![1_641_600_000])),
+            vec![serde_json::json!("2022-01-08T00:00:00")]
+        );
+        assert_eq!(
+            json_values(
+                &TimestampMillisecondArray::from(vec![1_641_600_000_250]).with_timezone("+02:00")
+            ),
+            vec![serde_json::json!("2022-01-08T02:00:00.250+02:00")]
+        );
+        assert_eq!(
+            json_values(&TimestampSecondArray::from(vec![1_641_600_000]).with_timezone("UTC")),
+            vec![serde_json::json!("2022-01-08T00:00:00Z")]
+        );
+    }
+
+    #[test]
+    fn test_list_values_to_json() {
+        use arrow_array::builder::{FixedSizeListBuilder, LargeListBuilder};
+
+        let mut large = LargeListBuilder::new(Int64Builder::new());
+        large.values().append_value(1);
+        large.values().append_null();
+        large.append(true);
+        let large = large.finish();
+        assert_eq!(json_values(&large), vec![serde_json::json!([1, null])]);
+
+        let mut fixed = FixedSizeListBuilder::new(Float64Builder::new(), 2);
+        fixed.values().append_value(1.5);
+        fixed.values().append_value(2.5);
+        fixed.append(true);
+        let fixed = fixed.finish();
+        assert_eq!(json_values(&fixed), vec![serde_json::json!([1.5, 2.5])]);
+    }
+
+    #[test]
+    fn test_other_values_use_display_formatting() {
+        use arrow_array::DictionaryArray;
+        use arrow_array::types::Int32Type;
+
+        let dictionary: DictionaryArray<Int32Type> = vec!["x", "y", "x"].into_iter().collect();
+        assert_eq!(
+            json_values(&dictionary),
+            vec![
+                serde_json::json!("x"),
+                serde_json::json!("y"),
+                serde_json::json!("x")
+            ]
+        );
+        assert_eq!(
+            json_values(&arrow_array::NullArray::new(1)),
+            vec![JsonValue::Null]
+        );
+    }
+
+    #[test]
+    fn test_stream_writer_multiple_batches() {
+        for pretty_print in [false, true] {
+            let options = GeoJsonWriterOptions::default().with_pretty_print(pretty_print);
+            let mut writer = GeoJsonStreamWriter::new(Vec::new(), options);
+            writer.write_batch(&create_test_batch()).unwrap();
+            writer.write_batch(&create_test_batch()).unwrap();
+            assert_eq!(writer.features_written(), 6);
+
+            let bytes = writer.finish().unwrap();
+            let json: JsonValue = serde_json::from_slice(&bytes).unwrap();
+            assert_eq!(json["type"], "FeatureCollection");
+            assert_eq!(json["features"].as_array().unwrap().len(), 6);
+            assert_eq!(json["features"][3]["properties"]["id"], 1);
+        }
+    }
+
+    #[test]
+    fn test_stream_writer_empty_collection() {
+        let writer = GeoJsonStreamWriter::new(Vec::new(), GeoJsonWriterOptions::default());
+        let bytes = writer.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            r#"{"type":"FeatureCollection","features":[]}"#
+        );
+
+        let options = GeoJsonWriterOptions::default().with_pretty_print(true);
+        let bytes = GeoJsonStreamWriter::new(Vec::new(), options)
+            .finish()
+            .unwrap();
+        let json: JsonValue = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["features"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_write_geojson_stream() {
+        use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+
+        let batch = create_test_batch();
+        let stream = Box::pin(RecordBatchStreamAdapter::new(
+            batch.schema(),
+            futures::stream::iter(vec![Ok(batch.clone()), Ok(batch)]),
+        ));
+        let options = GeoJsonWriterOptions::default().with_feature_collection(false);
+
+        let mut buffer = Vec::new();
+        let rows = write_geojson_stream(stream, &mut buffer, &options)
+            .await
+            .unwrap();
+
+        assert_eq!(rows, 6);
+        assert_eq!(String::from_utf8(buffer).unwrap().lines().count(), 6);
+    }
 }
@@ -354,3 +354,125 @@ async fn test_null_geometries() -> Result<()> {
 
     Ok(())
 }
+
+/// Test reading nested properties as Arrow list/struct/map columns and writing them back
+#[tokio::test]
+async fn test_nested_properties_round_trip() -> Result<()> {
+    let ctx = SessionContext::new();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("nested.geojson");
+
+    std::fs::write(
+        &path,
+        r#"{
+  "type": "FeatureCollection",
+  "features": [
+    {"type": "Feature", "geometry": {"type": "Point", "coordinates": [0.0, 1.0]},
+     "properties": {"tags": ["park", "public"], "info": {"height": 10}, "names": {"en": "Park"}}},
+    {"type": "Feature", "geometry": {"type": "Point", "coordinates": [2.0, 3.0]},
+     "properties": {"tags": ["shop"], "info": {"height": 4}, "names": {"de": "Laden", "fr": "Boutique"}}}
+  ]
+}
+"#,
+    )
+    .unwrap();
+
+    let options = GeoJsonFormatOptions::default().with_nested_types(true);
+    ctx.register_geojson_with_options("nested", path.to_str().unwrap(), options)
+        .await?;
+
+    let df = ctx
+        .sql(r"SELECT array_length(tags) AS tag_count FROM nested WHERE array_has(tags, 'shop')")
+        .await?;
+    let batches = df.collect().await?;
+    assert_eq!(batches[0].num_rows(), 1);
+
+    let df = ctx
+        .sql(r"SELECT tags, info, names, geometry FROM nested")
+        .await?;
+    let schema = df.schema().clone();
+    assert!(matches!(
+        schema.field_with_name(None, "tags")?.data_type(),
+        arrow_schema::DataType::List(_)
+    ));
+    assert!(matches!(
+        schema.field_with_name(None, "info")?.data_type(),
+        arrow_schema::DataType::Struct(_)
+    ));
+    assert!(matches!(
+        schema.field_with_name(None, "names")?.data_type(),
+        arrow_schema::DataType::Map(_, _)
+    ));
+
+    let batches = df.collect().await?;
+    let bytes = datafusion_geojson::write_geojson_to_bytes(&batches, &Default::default())?;
+    let written: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    let properties = &written["features"][1]["properties"];
+    assert_eq!(properties["tags"], serde_json::json!(["shop"]));
+    assert_eq!(properties["info"], serde_json::json!({"height": 4}));
+    assert_eq!(
+        properties["names"],
+        serde_json::json!({"de": "Laden", "fr": "Boutique"})
+    );
+
+    Ok(())
+}
+
+/// Test preserving feature ids, foreign members and bounding boxes on round-trip
+#[tokio::test]
+async fn test_feature_id_and_foreign_members_round_trip() -> Result<()> {
+    let ctx = SessionContext::new();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("ids.geojson");
+
+    std::fs::write(
+        &path,
+        r#"{
+  "type": "FeatureCollection",
+  "features": [
+    {"type": "Feature", "id": 10, "source": {"survey": 2024},
+     "geometry": {"type": "LineString", "coordinates": [[0.0, 1.0], [2.0, -1.0]]}, "properties": {"name": "A"}},
+    {"type": "Feature", "id": 20,
+     "geometry": {"type": "Point", "coordinates": [5.0, 3.0]}, "properties": {"name": "B"}}
+  ]
+}
+"#,
+    )
+    .unwrap();
+
+    let options = GeoJsonFormatOptions::default()
+        .with_id_column("fid")
+        .with_foreign_members(true);
+    ctx.register_geojson_with_options("ids", path.to_str().unwrap(), options)
+        .await?;
+
+    let df = ctx
+        .sql(r"SELECT fid, name, source, geometry FROM ids ORDER BY fid")
+        .await?;
+    assert_eq!(
+        df.schema().field_with_name(None, "fid")?.data_type(),
+        &arrow_schema::DataType::Int64
+    );
+
+    let batches = df.collect().await?;
+    let writer_options = datafusion_geojson::GeoJsonWriterOptions::default()
+        .with_id_column("fid")
+        .with_foreign_member_columns(["source"])
+        .with_bbox(true);
+    let bytes = datafusion_geojson::write_geojson_to_bytes(&batches, &writer_options)?;
+    let written: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+    assert_eq!(written["bbox"], serde_json::json!([0.0, -1.0, 5.0, 3.0]));
+    let first = &written["features"][0];
+    assert_eq!(first["id"], serde_json::json!(10));
+    assert_eq!(first["source"], serde_json::json!({"survey": 2024}));
+    assert_eq!(first["bbox"], serde_json::json!([0.0, -1.0, 2.0, 1.0]));
+    assert_eq!(first["properties"], serde_json::json!({"name": "A"}));
+    let second = &written["features"][1];
+    assert_eq!(second["id"], serde_json::json!(20));
+    assert!(second.get("source").is_none());
+
+    Ok(())
+}
@@ -16,13 +16,11 @@ use datafusion_geojson::{GeoJsonSink, GeoJsonWriterOptions, write_geojson_to_byt
 use futures::stream;
 use tempfile::TempDir;
 
-#[ignore = "Requires proper object store integration"]
 #[tokio::test]
 async fn test_geojson_sink_write_all() {
     let temp_dir = TempDir::new().unwrap();
     let output_path = temp_dir.path().to_str().unwrap();
 
-    // Pre-create the directory since sink expects it to exist
     fs::create_dir_all(output_path).unwrap();
 
     let schema = Arc::new(Schema::new(vec![
@@ -52,7 +50,7 @@ async fn test_geojson_sink_write_all() {
         original_url: format!("file://{output_path}/output.geojson"),
         object_store_url: ObjectStoreUrl::local_filesystem(),
         file_group: FileGroup::default(),
-        table_paths: vec![ListingTableUrl::parse(format!("file://{output_path}")).unwrap()],
+        table_paths: vec![ListingTableUrl::parse(format!("file://{output_path}/")).unwrap()],
         output_schema: schema.clone(),
         table_partition_cols: vec![],
         insert_op: InsertOp::Append,
@@ -203,3 +201,239 @@ fn test_write_geojson_pretty_print() {
     assert!(json_str.contains('\n'));
     assert!(json_str.contains("  ")); // Indentation
 }
+
+#[tokio::test]
+async fn test_geojson_factory_writer_plan_writes_file() {
+    use datafusion::datasource::memory::MemorySourceConfig;
+    use datafusion::physical_plan::collect;
+    use datafusion_geojson::factory::GeoJsonFormatFactory;
+    use geoetl_core_common::FormatFactory;
+
+    let temp_dir = TempDir::new().unwrap();
+    let output_path = temp_dir.path().join("output.geojson");
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("geometry", DataType::Utf8, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef,
+            Arc::new(StringArray::from(vec!["Alice", "Bob"])) as ArrayRef,
+            Arc::new(StringArray::from(vec!["POINT(0 0)", "POINT(1 1)"])) as ArrayRef,
+        ],
+    )
+    .unwrap();
+    let input = MemorySourceConfig::try_new_exec(&[vec![batch]], schema, None).unwrap();
+
+    let writer = GeoJsonFormatFactory.create_writer().unwrap();
+    let plan = writer
+        .create_writer_plan(
+            input,
+            output_path.to_str().unwrap(),
+            geoetl_core_common::WriteMode::Overwrite,
+            &geoetl_core_common::OptionValues::new(),
+        )
+        .await
+        .unwrap();
+
+    let results = collect(plan, Arc::new(TaskContext::default()))
+        .await
+        .unwrap();
+    let count = results[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<arrow_array::UInt64Array>()
+        .unwrap()
+        .value(0);
+    assert_eq!(count, 2);
+
+    let contents = fs::read_to_string(&output_path).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    assert_eq!(parsed["type"], "FeatureCollection");
+    assert_eq!(parsed["features"].as_array().unwrap().len(), 2);
+    assert_eq!(parsed["features"][1]["properties"]["name"], "Bob");
+}
+
+#[tokio::test]
+async fn test_geojson_factory_writer_plan_appends() {
+    use datafusion::datasource::memory::MemorySourceConfig;
+    use datafusion::physical_plan::collect;
+    use datafusion_geojson::factory::GeoJsonFormatFactory;
+    use geoetl_core_common::{FormatFactory, OptionValues, WriteMode};
+
+    let temp_dir = TempDir::new().unwrap();
+
+    let write = |path: String, property: &'static str, options: OptionValues| async move {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(property, DataType::Utf8, true),
+            Field::new("geometry", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["Alice"])) as ArrayRef,
+                Arc::new(StringArray::from(vec!["POINT(0 0)"])) as ArrayRef,
+            ],
+        )
+        .unwrap();
+        let input = MemorySourceConfig::try_new_exec(&[vec![batch]], schema, None).unwrap();
+        let plan = GeoJsonFormatFactory
+            .create_writer()
+            .unwrap()
+            .create_writer_plan(input, &path, WriteMode::Append, &options)
+            .await
+            .unwrap();
+        let results = collect(plan, Arc::new(TaskContext::default())).await?;
+        Ok::<_, datafusion::error::DataFusionError>(
+            results[0]
+                .column(0)
+                .as_any()
+                .downcast_ref::<arrow_array::UInt64Array>()
+                .unwrap()
+                .value(0),
+        )
+    };
+
+    // Features appended to a FeatureCollection are merged into it
+    let collection = temp_dir.path().join("collection.geojson");
+    let path = collection.to_str().unwrap().to_string();
+    let options = OptionValues::new();
+    for _ in 0..2 {
+        let count = write(path.clone(), "name", options.clone()).await.unwrap();
+        assert_eq!(count, 1);
+    }
+    let parsed: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&collection).unwrap()).unwrap();
+    assert_eq!(parsed["type"], "FeatureCollection");
+    assert_eq!(parsed["features"].as_array().unwrap().len(), 2);
+
+    // The properties of the existing features must match the written columns, and a
+    // rejected append leaves neither changes nor a temporary file behind
+    let before = fs::read_to_string(&collection).unwrap();
+    let err = write(path, "city", options.clone()).await.unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("its feature properties (name) do not match the written columns (city)"),
+        "{err}"
+    );
+    assert_eq!(fs::read_to_string(&collection).unwrap(), before);
+    assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+
+    // The types inferred for the existing properties must hold the written values
+    let numbers = temp_dir.path().join("numbers.geojson");
+    fs::write(
+        &numbers,
+        r#"{"type":"FeatureCollection","features":[
+            {"type":"Feature","geometry":{"type":"Point","coordinates":[1,1]},"properties":{"name":1}},
+            {"type":"Feature","geometry":{"type":"Point","coordinates":[3,2]},"properties":{"name":2.5}}
+        ]}"#,
+    )
+    .unwrap();
+    let err = write(
+        numbers.to_str().unwrap().to_string(),
+        "name",
+        options.clone(),
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        err.to_string().contains(
+            "its property 'name' holds Float64 values, which cannot hold the written Utf8 values"
+        ),
+        "{err}"
+    );
+
+    // Other collection members are kept and the collection `bbox` is recomputed
+    let members = temp_dir.path().join("members.geojson");
+    fs::write(
+        &members,
+        r#"{"type":"FeatureCollection","name":"places","bbox":[9,9,9,9],"features":[
+            {"type":"Feature","geometry":{"type":"Point","coordinates":[1,1]},"properties":{"name":"A"}},
+            {"type":"Feature","geometry":{"type":"Point","coordinates":[3,2]},"properties":{"name":"B"}}
+        ],"crs":{"type":"name","properties":{"name":"urn:ogc:def:crs:OGC:1.3:CRS84"}}}"#,
+    )
+    .unwrap();
+    let count = write(
+        members.to_str().unwrap().to_string(),
+        "name",
+        options.clone(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(count, 1);
+    let parsed: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&members).unwrap()).unwrap();
+    assert_eq!(parsed["features"].as_array().unwrap().len(), 3);
+    assert_eq!(parsed["features"][2]["properties"]["name"], "Alice");
+    assert_eq!(parsed["name"], "places");
+    assert_eq!(
+        parsed["crs"]["properties"]["name"],
+        "urn:ogc:def:crs:OGC:1.3:CRS84"
+    );
+    assert_eq!(parsed["bbox"], serde_json::json!([1.0, 1.0, 3.0, 2.0]));
+
+    // The merged collection keeps the permissions of the original file
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::set_permissions(&members, fs::Permissions::from_mode(0o644)).unwrap();
+        write(members.to_str().unwrap().to_string(), "name", options)
+            .await
+            .unwrap();
+        let mode = fs::metadata(&members).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o644);
+    }
+
+    // Newline-delimited features are appended as lines
+    let lines = temp_dir.path().join("lines.geojson");
+    let path = lines.to_str().unwrap().to_string();
+    let options = OptionValues::new().with("FEATURE_COLLECTION", "NO");
+    for _ in 0..2 {
+        write(path.clone(), "name", options.clone()).await.unwrap();
+    }
+    assert_eq!(fs::read_to_string(&lines).unwrap().lines().count(), 2);
+
+    // The property types are inferred over every existing line, and every line must be a
+    // feature
+    let boolean_lines = temp_dir.path().join("boolean_lines.geojson");
+    fs::write(
+        &boolean_lines,
+        "{\"type\":\"Feature\",\"geometry\":null,\"properties\":{\"name\":null}}\n\
+         {\"type\":\"Feature\",\"geometry\":null,\"properties\":{\"name\":true}}\n",
+    )
+    .unwrap();
+    let err = write(
+        boolean_lines.to_str().unwrap().to_string(),
+        "name",
+        options.clone(),
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("its property 'name' holds Boolean values"),
+        "{err}"
+    );
+
+    let corrupt_lines = temp_dir.path().join("corrupt_lines.geojson");
+    fs::write(
+        &corrupt_lines,
+        "{\"type\":\"Feature\",\"geometry\":null,\"properties\":{\"name\":\"A\"}}\n\
+         {\"type\":\"Feature\",\"geometry\":null,\"properties\":{\"name\":\n",
+    )
+    .unwrap();
+    let before = fs::read_to_string(&corrupt_lines).unwrap();
+    let err = write(corrupt_lines.to_str().unwrap().to_string(), "name", options)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("it is not newline-delimited GeoJSON"),
+        "{err}"
+    );
+    assert_eq!(fs::read_to_string(&corrupt_lines).unwrap(), before);
+}
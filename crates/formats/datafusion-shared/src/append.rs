@@ -0,0 +1,127 @@
+//! Opening output files to write them from scratch or append lines to them.
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use datafusion_common::arrow::datatypes::DataType;
+
+/// Create the file at `path` to write it from scratch.
+///
+/// An existing file is truncated, unless `create_new` is set: the call then fails with
+/// [`std::io::ErrorKind::AlreadyExists`], checking and creating the file in one operation.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created.
+pub fn create_output(path: &Path, create_new: bool) -> std::io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.write(true);
+    if create_new {
+        options.create_new(true);
+    } else {
+        options.create(true).truncate(true);
+    }
+    options.open(path)
+}
+
+/// Open an existing, non-empty file to append lines to it.
+///
+/// Returns `None` when the file does not exist or is empty, so that the caller writes it
+/// from scratch (with a header, if any). Otherwise the file is positioned at its end, after
+/// a newline terminating its last line.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be read or written.
+pub fn open_for_append(path: &Path) -> std::io::Result<Option<File>> {
+    let mut file = match OpenOptions::new().read(true).append(true).open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    if file.metadata()?.len() == 0 {
+        return Ok(None);
+    }
+
+    let mut last = [0u8];
+    file.seek(SeekFrom::End(-1))?;
+    file.read_exact(&mut last)?;
+    if last[0] != b'\n' {
+        file.write_all(b"\n")?;
+    }
+    Ok(Some(file))
+}
+
+/// Whether a column whose existing values read back as `existing` keeps that type once
+/// values of the `written` type are appended to it.
+///
+/// Sinks appending to text formats, which infer column types when read, use this to reject
+/// appends that would change the type of an existing column.
+#[must_use]
+pub fn holds_written_values(existing: &DataType, written: &DataType) -> bool {
+    if *written == DataType::Null {
+        return true;
+    }
+
+    match existing {
+        DataType::Int64 => written.is_integer(),
+        DataType::Float64 => written.is_numeric(),
+        DataType::Boolean => *written == DataType::Boolean,
+        existing if existing.is_temporal() => written.is_temporal(),
+        // Text and empty columns hold anything
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_for_append() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+
+        let missing = dir.join("missing.txt");
+        assert!(open_for_append(&missing).unwrap().is_none());
+
+        let empty = dir.join("empty.txt");
+        std::fs::write(&empty, "").unwrap();
+        assert!(open_for_append(&empty).unwrap().is_none());
+
+        // An unterminated last line is ended before appending
+        let path = dir.join("lines.txt");
+        std::fs::write(&path, "a\nb").unwrap();
+        let mut file = open_for_append(&path).unwrap().unwrap();
+        file.write_all(b"c\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a\nb\nc\n");
+    }
+
+    #[test]
+    fn test_create_output() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("output.txt");
+        std::fs::write(&path, "old").unwrap();
+
+        let err = create_output(&path, true).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "old");
+
+        create_output(&path, false)
+            .unwrap()
+            .write_all(b"new")
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+    }
+
+    #[test]
+    fn test_holds_written_values() {
+        assert!(holds_written_values(&DataType::Int64, &DataType::Int32));
+        assert!(!holds_written_values(&DataType::Int64, &DataType::Float64));
+        assert!(holds_written_values(&DataType::Float64, &DataType::Int64));
+        assert!(!holds_written_values(&DataType::Boolean, &DataType::Utf8));
+        assert!(holds_written_values(&DataType::Boolean, &DataType::Null));
+        assert!(holds_written_values(&DataType::Utf8, &DataType::Float64));
+    }
+}
@@ -0,0 +1,175 @@
+//! Normalising coordinate reference system definitions and recording them as `GeoArrow`
+//! metadata.
+//!
+//! Every reader, writer and operation compares CRS definitions in their normalised form, so
+//! that aliases of the same CRS, such as `4326`, `epsg:4326`, `OGC:CRS84` or a WGS 84 PROJ
+//! string, are recognised as one.
+
+use datafusion_common::arrow::datatypes::Field;
+use geoarrow_schema::{Crs, Metadata};
+
+/// The CRS assumed for geometries that do not declare one
+pub const DEFAULT_CRS: &str = "EPSG:4326";
+
+/// PROJ parameters that may accompany `+proj=longlat` in a WGS 84 definition
+const WGS84_PROJ_PARAMETERS: &[&str] = &[
+    "+datum=wgs84",
+    "+ellps=wgs84",
+    "+towgs84=0,0,0",
+    "+towgs84=0,0,0,0,0,0,0",
+    "+no_defs",
+    "+type=crs",
+];
+
+/// Normalise a user supplied CRS to `AUTHORITY:CODE` where possible
+///
+/// Bare codes (`3857`) are taken as EPSG codes and authorities are upper-cased
+/// (`epsg:3857`). The WGS 84 aliases, including longitude/latitude PROJ strings on the WGS 84
+/// datum, become [`DEFAULT_CRS`]; any other definition is returned trimmed but otherwise
+/// unchanged.
+#[must_use]
+pub fn normalize_crs(definition: &str) -> String {
+    let definition = definition.trim();
+    if definition.eq_ignore_ascii_case("WGS84")
+        || definition.eq_ignore_ascii_case("CRS84")
+        || definition.eq_ignore_ascii_case("OGC:CRS84")
+        || is_wgs84_proj_string(definition)
+    {
+        return DEFAULT_CRS.to_string();
+    }
+    if !definition.is_empty() && definition.chars().all(|c| c.is_ascii_digit()) {
+        return format!("EPSG:{definition}");
+    }
+    match authority_code(definition) {
+        Some((authority, code)) => format!("{}:{code}", authority.to_ascii_uppercase()),
+        None => definition.to_string(),
+    }
+}
+
+/// The definition carried by `GeoArrow` CRS metadata, if it can be expressed as a string,
+/// normalised like [`normalize_crs`]
+///
+/// PROJJSON objects are reduced to their `id` (for example `EPSG:4326`).
+#[must_use]
+pub fn crs_definition(crs: &Crs) -> Option<String> {
+    let value = crs.crs_value()?;
+    if let Some(text) = value.as_str() {
+        return Some(normalize_crs(text));
+    }
+
+    let id = value.get("id")?;
+    let authority = id.get("authority")?.as_str()?;
+    let code = id.get("code")?;
+    let code = code
+        .as_str()
+        .map(str::to_string)
+        .or_else(|| code.as_u64().map(|c| c.to_string()))?;
+    Some(normalize_crs(&format!("{authority}:{code}")))
+}
+
+/// The CRS recorded in a geometry field's `GeoArrow` metadata, normalised like
+/// [`crs_definition`]
+#[must_use]
+pub fn field_crs(field: &Field) -> Option<String> {
+    Metadata::try_from(field)
+        .ok()
+        .and_then(|metadata| crs_definition(metadata.crs()))
+}
+
+/// `GeoArrow` CRS metadata for a user supplied definition
+///
+/// `AUTHORITY:CODE` identifiers and bare codes (taken as EPSG) are stored as authority
+/// codes; anything else, such as a PROJ string, is stored as an unknown CRS type.
+#[must_use]
+pub fn crs_metadata(definition: &str) -> Crs {
+    let normalized = normalize_crs(definition);
+    if authority_code(&normalized).is_some() {
+        Crs::from_authority_code(normalized)
+    } else {
+        Crs::from_unknown_crs_type(normalized)
+    }
+}
+
+/// `GeoArrow` metadata recording a CRS assigned by the user, such as a `CRS` open option,
+/// stored like [`crs_metadata`]
+#[must_use]
+pub fn assigned_crs_metadata(definition: &str) -> Metadata {
+    Metadata::new(crs_metadata(definition), None)
+}
+
+/// The authority and code of an `AUTHORITY:CODE` identifier
+fn authority_code(definition: &str) -> Option<(&str, &str)> {
+    let (authority, code) = definition.split_once(':')?;
+    let code = code.trim();
+    (!authority.is_empty()
+        && authority.chars().all(|c| c.is_ascii_alphanumeric())
+        && !code.is_empty()
+        && !code.contains(char::is_whitespace))
+    .then_some((authority, code))
+}
+
+/// Whether a PROJ string is longitude/latitude on the WGS 84 datum or ellipsoid
+fn is_wgs84_proj_string(definition: &str) -> bool {
+    let parameters: Vec<String> = definition
+        .split_whitespace()
+        .map(str::to_ascii_lowercase)
+        .collect();
+    let is_longlat = |p: &String| p == "+proj=longlat" || p == "+proj=latlong";
+    parameters.iter().filter(|p| is_longlat(p)).count() == 1
+        && parameters
+            .iter()
+            .any(|p| p == "+datum=wgs84" || p == "+ellps=wgs84")
+        && parameters
+            .iter()
+            .all(|p| is_longlat(p) || WGS84_PROJ_PARAMETERS.contains(&p.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_aliases() {
+        assert_eq!(normalize_crs("3857"), "EPSG:3857");
+        assert_eq!(normalize_crs("epsg:4326"), "EPSG:4326");
+        assert_eq!(normalize_crs("OGC:CRS84"), "EPSG:4326");
+        assert_eq!(normalize_crs("esri:102100"), "ESRI:102100");
+        assert_eq!(normalize_crs(" +proj=longlat "), "+proj=longlat");
+        assert_eq!(
+            normalize_crs("+proj=longlat +datum=WGS84 +no_defs"),
+            "EPSG:4326"
+        );
+        assert_eq!(
+            normalize_crs("+proj=longlat +datum=NAD83 +no_defs"),
+            "+proj=longlat +datum=NAD83 +no_defs"
+        );
+    }
+
+    #[test]
+    fn reads_projjson_identifiers() {
+        let crs = Crs::from_projjson(serde_json::json!({
+            "id": {"authority": "OGC", "code": "CRS84"}
+        }));
+        assert_eq!(crs_definition(&crs).as_deref(), Some("EPSG:4326"));
+        assert_eq!(crs_definition(&Crs::default()), None);
+    }
+
+    #[test]
+    fn assigned_crs_as_authority_code() {
+        let crs_value = |definition: &str| {
+            assigned_crs_metadata(definition)
+                .crs()
+                .crs_value()
+                .cloned()
+                .unwrap()
+        };
+
+        assert_eq!(crs_value("32618"), "EPSG:32618");
+        assert_eq!(crs_value("epsg:3857"), "EPSG:3857");
+        assert_eq!(crs_value("OGC:CRS84"), "EPSG:4326");
+        assert_eq!(
+            crs_value("+proj=utm +zone=18 +datum=WGS84"),
+            "+proj=utm +zone=18 +datum=WGS84"
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! Geometry type names.
+//!
+//! Formats and transforms accept geometry types by name, such as a CSV `GEOMETRY_TYPE` open
+//! option or the target type of a conversion. Names follow WKT, case-insensitively, with an
+//! optional `Z`, `M` or `ZM` suffix for the coordinate dimension: `Point`, `MULTIPOLYGON Z`,
+//! `LineStringZM`.
+
+use std::sync::Arc;
+
+use datafusion_common::{Result, plan_err};
+use geoarrow_schema::{
+    Dimension, GeoArrowType, GeometryCollectionType, GeometryType, LineStringType, Metadata,
+    MultiLineStringType, MultiPointType, MultiPolygonType, PointType, PolygonType,
+};
+
+/// Parse a geometry type name into a `GeoArrowType` carrying `metadata`.
+///
+/// Names without a dimension suffix are XY. `Geometry` is the mixed `geoarrow.geometry` type,
+/// which holds every dimension, so it takes no suffix.
+///
+/// # Errors
+///
+/// Returns an error if the name is not a known geometry type.
+pub fn parse_geometry_type(name: &str, metadata: Arc<Metadata>) -> Result<GeoArrowType> {
+    let normalized = name.trim().to_lowercase();
+    let (base, dimension) = [
+        ("zm", Dimension::XYZM),
+        ("z", Dimension::XYZ),
+        ("m", Dimension::XYM),
+    ]
+    .into_iter()
+    .find_map(|(suffix, dimension)| {
+        normalized
+            .strip_suffix(suffix)
+            .map(|base| (base.trim_end(), dimension))
+    })
+    .unwrap_or((normalized.as_str(), Dimension::XY));
+
+    let geoarrow_type = match base {
+        "geometry" if dimension == Dimension::XY => {
+            GeoArrowType::Geometry(GeometryType::new(metadata))
+        },
+        "point" => GeoArrowType::Point(PointType::new(dimension, metadata)),
+        "linestring" => GeoArrowType::LineString(LineStringType::new(dimension, metadata)),
+        "polygon" => GeoArrowType::Polygon(PolygonType::new(dimension, metadata)),
+        "multipoint" => GeoArrowType::MultiPoint(MultiPointType::new(dimension, metadata)),
+        "multilinestring" => {
+            GeoArrowType::MultiLineString(MultiLineStringType::new(dimension, metadata))
+        },
+        "multipolygon" => GeoArrowType::MultiPolygon(MultiPolygonType::new(dimension, metadata)),
+        "geometrycollection" => {
+            GeoArrowType::GeometryCollection(GeometryCollectionType::new(dimension, metadata))
+        },
+        _ => return plan_err!("Unsupported geometry type: {name}"),
+    };
+    Ok(geoarrow_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(name: &str) -> Result<GeoArrowType> {
+        parse_geometry_type(name, Arc::default())
+    }
+
+    #[test]
+    fn parses_names_and_dimensions() {
+        assert!(matches!(
+            parse("Geometry").unwrap(),
+            GeoArrowType::Geometry(_)
+        ));
+        assert_eq!(parse("point").unwrap().dimension(), Some(Dimension::XY));
+        assert!(matches!(
+            parse("MULTIPOLYGON Z").unwrap(),
+            GeoArrowType::MultiPolygon(t) if t.dimension() == Dimension::XYZ
+        ));
+        assert_eq!(
+            parse("LineStringZM").unwrap().dimension(),
+            Some(Dimension::XYZM)
+        );
+        assert_eq!(parse("Point M").unwrap().dimension(), Some(Dimension::XYM));
+        assert!(parse("Geometry Z").is_err());
+        assert!(parse("Circle").is_err());
+    }
+}
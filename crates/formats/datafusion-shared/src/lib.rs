@@ -3,6 +3,18 @@ use std::fmt;
 
 use datafusion_common::DataFusionError;
 
+mod append;
+mod crs;
+mod geometry_type;
+mod spatial_filter;
+
+pub use append::{create_output, holds_written_values, open_for_append};
+pub use crs::{
+    DEFAULT_CRS, assigned_crs_metadata, crs_definition, crs_metadata, field_crs, normalize_crs,
+};
+pub use geometry_type::parse_geometry_type;
+pub use spatial_filter::{BoundingBox, SpatialFilter};
+
 /// A position within a source file, such as a CSV record.
 ///
 /// All indices are 1-based where possible to align with human expectations.
@@ -0,0 +1,237 @@
+//! Spatial filters pushed into file scans.
+//!
+//! A query filtering on `st_intersects(<geometry column>, <constant geometry>)` can be
+//! answered in part by the scan itself: a feature whose bounding box does not overlap the
+//! constant geometry's bounding box cannot match. Readers use [`SpatialFilter`] to drop such
+//! features while decoding, before they are converted to Arrow. The filter is inexact, so
+//! the query still evaluates the predicate on the features that remain.
+
+use std::fmt;
+use std::sync::Arc;
+
+use datafusion_common::ScalarValue;
+use datafusion_physical_expr::expressions::{Column, Literal};
+use datafusion_physical_expr::{PhysicalExpr, ScalarFunctionExpr};
+use geozero::error::Result as GeozeroResult;
+use geozero::wkb::Wkb;
+use geozero::wkt::Wkt;
+use geozero::{GeomProcessor, GeozeroGeometry};
+
+/// An axis-aligned bounding box
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+impl BoundingBox {
+    /// The bounding box of a geometry, or `None` if it has no coordinates
+    pub fn of(geometry: &impl GeozeroGeometry) -> Option<Self> {
+        let mut bounds = Bounds(None);
+        geometry.process_geom(&mut bounds).ok()?;
+        bounds.0
+    }
+
+    /// The bounding box of a WKT geometry, or `None` if it is empty or cannot be parsed
+    #[must_use]
+    pub fn of_wkt(wkt: &str) -> Option<Self> {
+        Self::of(&Wkt(wkt))
+    }
+
+    /// Whether the boxes share any point
+    #[must_use]
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min_x <= other.max_x
+            && other.min_x <= self.max_x
+            && self.min_y <= other.max_y
+            && other.min_y <= self.max_y
+    }
+
+    fn extend(self, x: f64, y: f64) -> Self {
+        Self {
+            min_x: self.min_x.min(x),
+            min_y: self.min_y.min(y),
+            max_x: self.max_x.max(x),
+            max_y: self.max_y.max(y),
+        }
+    }
+}
+
+impl fmt::Display for BoundingBox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "BOX({} {}, {} {})",
+            self.min_x, self.min_y, self.max_x, self.max_y
+        )
+    }
+}
+
+/// Collects the bounding box of the coordinates a geometry reader emits
+struct Bounds(Option<BoundingBox>);
+
+impl GeomProcessor for Bounds {
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> GeozeroResult<()> {
+        self.0 = Some(match self.0 {
+            Some(bbox) => bbox.extend(x, y),
+            None => BoundingBox {
+                min_x: x,
+                min_y: y,
+                max_x: x,
+                max_y: y,
+            },
+        });
+        Ok(())
+    }
+}
+
+/// A filter keeping the features whose geometry in `column` may intersect `bbox`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpatialFilter {
+    /// Name of the geometry column the filter applies to
+    pub column: String,
+    /// Bounding box of the filter geometry
+    pub bbox: BoundingBox,
+}
+
+impl SpatialFilter {
+    /// Recognise `st_intersects(column, geometry)`, with the arguments in either order, where
+    /// `geometry` is a WKT string or WKB constant
+    #[must_use]
+    pub fn from_predicate(predicate: &Arc<dyn PhysicalExpr>) -> Option<Self> {
+        let function = predicate.as_any().downcast_ref::<ScalarFunctionExpr>()?;
+        let [a, b] = function.args() else {
+            return None;
+        };
+        if function.name() != "st_intersects" {
+            return None;
+        }
+
+        let (column, literal) = match (
+            a.as_any().downcast_ref::<Column>(),
+            b.as_any().downcast_ref::<Literal>(),
+        ) {
+            (Some(column), Some(literal)) => (column, literal),
+            _ => (
+                b.as_any().downcast_ref::<Column>()?,
+                a.as_any().downcast_ref::<Literal>()?,
+            ),
+        };
+        let bbox = match literal.value() {
+            ScalarValue::Utf8(Some(wkt))
+            | ScalarValue::LargeUtf8(Some(wkt))
+            | ScalarValue::Utf8View(Some(wkt)) => BoundingBox::of_wkt(wkt),
+            ScalarValue::Binary(Some(wkb))
+            | ScalarValue::LargeBinary(Some(wkb))
+            | ScalarValue::BinaryView(Some(wkb)) => BoundingBox::of(&Wkb(wkb)),
+            _ => None,
+        }?;
+
+        Some(Self {
+            column: column.name().to_string(),
+            bbox,
+        })
+    }
+
+    /// Whether a geometry may match; geometries without coordinates never do
+    pub fn matches(&self, geometry: &impl GeozeroGeometry) -> bool {
+        BoundingBox::of(geometry).is_some_and(|bbox| bbox.intersects(&self.bbox))
+    }
+
+    /// Whether a WKT value may match
+    ///
+    /// Empty values never match. Values that are not valid WKT are kept, so that the reader
+    /// reports them as it would without the filter.
+    #[must_use]
+    pub fn matches_wkt(&self, wkt: &str) -> bool {
+        if wkt.trim().is_empty() {
+            return false;
+        }
+        let mut bounds = Bounds(None);
+        match Wkt(wkt).process_geom(&mut bounds) {
+            Ok(()) => bounds.0.is_some_and(|bbox| bbox.intersects(&self.bbox)),
+            Err(_) => true,
+        }
+    }
+}
+
+impl fmt::Display for SpatialFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} intersects {}", self.column, self.bbox)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::arrow::datatypes::{DataType, Field};
+    use datafusion::logical_expr::{
+        ColumnarValue, ScalarFunctionArgs, ScalarUDF, ScalarUDFImpl, Signature, Volatility,
+    };
+    use datafusion_common::config::ConfigOptions;
+
+    #[derive(Debug, PartialEq, Eq, Hash)]
+    struct Intersects(Signature);
+
+    impl ScalarUDFImpl for Intersects {
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn name(&self) -> &str {
+            "st_intersects"
+        }
+
+        fn signature(&self) -> &Signature {
+            &self.0
+        }
+
+        fn return_type(&self, _arg_types: &[DataType]) -> datafusion_common::Result<DataType> {
+            Ok(DataType::Boolean)
+        }
+
+        fn invoke_with_args(
+            &self,
+            _args: ScalarFunctionArgs,
+        ) -> datafusion_common::Result<ColumnarValue> {
+            datafusion_common::internal_err!("not evaluated")
+        }
+    }
+
+    fn intersects(args: Vec<Arc<dyn PhysicalExpr>>) -> Arc<dyn PhysicalExpr> {
+        let udf = ScalarUDF::new_from_impl(Intersects(Signature::any(2, Volatility::Immutable)));
+        Arc::new(ScalarFunctionExpr::new(
+            "st_intersects",
+            Arc::new(udf),
+            args,
+            Arc::new(Field::new("st_intersects", DataType::Boolean, true)),
+            Arc::new(ConfigOptions::default()),
+        ))
+    }
+
+    #[test]
+    fn recognises_intersects_with_a_constant() {
+        let column: Arc<dyn PhysicalExpr> = Arc::new(Column::new("geometry", 2));
+        let polygon: Arc<dyn PhysicalExpr> = Arc::new(Literal::new(ScalarValue::from(
+            "POLYGON((0 0, 10 0, 10 5, 0 5, 0 0))",
+        )));
+
+        let filter = SpatialFilter::from_predicate(&intersects(vec![
+            Arc::clone(&polygon),
+            Arc::clone(&column),
+        ]))
+        .unwrap();
+        assert_eq!(filter.column, "geometry");
+        assert_eq!(filter.to_string(), "geometry intersects BOX(0 0, 10 5)");
+
+        assert!(filter.matches_wkt("POINT(5 5)"));
+        assert!(filter.matches_wkt("LINESTRING(-5 -5, 20 1)"));
+        assert!(!filter.matches_wkt("POINT(11 1)"));
+        assert!(!filter.matches_wkt(""));
+        assert!(filter.matches_wkt("not wkt"));
+
+        assert!(SpatialFilter::from_predicate(&intersects(vec![column.clone(), column])).is_none());
+    }
+}
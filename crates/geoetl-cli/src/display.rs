@@ -1,11 +1,77 @@
 //! Display utilities for formatting CLI output.
 //!
 //! This module provides table row structures and formatting functions
-//! for presenting geospatial data information in a human-readable format.
+//! for presenting geospatial data information in a human-readable format,
+//! and prints the same information as JSON or YAML documents for scripts.
 
+use clap::ValueEnum;
+use serde::Serialize;
+use tabled::builder::Builder;
 use tabled::{Table, Tabled};
 
-use geoetl_core::types::DatasetInfo;
+use geoetl_core::drivers::{Driver, FormatOption};
+use geoetl_core::error::GeoEtlError;
+use geoetl_core::types::{DatasetInfo, QueryResult, SCHEMA_VERSION, ValidationReport};
+
+/// Output formats accepted by `--format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable tables
+    #[default]
+    Table,
+    /// A JSON document
+    Json,
+    /// A YAML document
+    Yaml,
+}
+
+/// A machine-readable document: the content's fields next to the schema version they follow.
+#[derive(Serialize)]
+struct Document<'a, T: Serialize> {
+    schema_version: u32,
+    #[serde(flatten)]
+    content: &'a T,
+}
+
+/// The drivers listed by `geoetl drivers`, as a document.
+#[derive(Serialize)]
+pub struct DriverList<'a> {
+    /// Available drivers.
+    pub drivers: &'a [Driver],
+}
+
+/// A driver and its options, shown by `geoetl drivers <DRIVER>`, as a document.
+#[derive(Serialize)]
+pub struct DriverDetails<'a> {
+    /// The driver and its capabilities.
+    pub driver: &'a Driver,
+    /// Options accepted by its reader through `--oo`.
+    pub open_options: &'a [FormatOption],
+    /// Options accepted by its writer through `--lco`.
+    pub creation_options: &'a [FormatOption],
+}
+
+/// Print `content` as a JSON or YAML document with a `schema_version` field.
+///
+/// Tables have no document form, so `OutputFormat::Table` prints JSON as well.
+///
+/// # Errors
+///
+/// Returns an error if `content` cannot be serialised.
+pub fn print_document(format: OutputFormat, content: &impl Serialize) -> Result<(), GeoEtlError> {
+    let document = Document {
+        schema_version: SCHEMA_VERSION,
+        content,
+    };
+    let text = match format {
+        OutputFormat::Yaml => serde_yaml_ng::to_string(&document).map_err(anyhow::Error::from)?,
+        OutputFormat::Json | OutputFormat::Table => {
+            serde_json::to_string_pretty(&document).map_err(anyhow::Error::from)?
+        },
+    };
+    println!("{}", text.trim_end());
+    Ok(())
+}
 
 /// Table row representation for displaying geometry column information.
 #[derive(Tabled)]
@@ -35,6 +101,57 @@ pub struct FieldRow {
     pub nullable: String,
 }
 
+/// Table row representation for displaying the value statistics of a field.
+#[derive(Tabled)]
+pub struct FieldStatsRow {
+    /// Name of the field.
+    #[tabled(rename = "Field")]
+    pub name: String,
+    /// Number of null values.
+    #[tabled(rename = "Nulls")]
+    pub null_count: u64,
+    /// Smallest value.
+    #[tabled(rename = "Min")]
+    pub min: String,
+    /// Largest value.
+    #[tabled(rename = "Max")]
+    pub max: String,
+    /// Estimated number of distinct values.
+    #[tabled(rename = "Distinct (approx.)")]
+    pub distinct_count: String,
+    /// Most frequent values with their counts.
+    #[tabled(rename = "Top Values")]
+    pub top_values: String,
+}
+
+/// Table row representation for displaying the number of geometries of a type.
+#[derive(Tabled)]
+pub struct GeometryTypeRow {
+    /// Geometry type name.
+    #[tabled(rename = "Type")]
+    pub name: String,
+    /// Number of geometries of the type.
+    #[tabled(rename = "Count")]
+    pub count: u64,
+}
+
+/// Table row representation for displaying an invalid geometry.
+#[derive(Tabled)]
+pub struct ValidationRow {
+    /// Zero-based index of the feature.
+    #[tabled(rename = "Feature")]
+    pub feature: u64,
+    /// Geometry column holding the invalid geometry.
+    #[tabled(rename = "Column")]
+    pub column: String,
+    /// Why the geometry is invalid.
+    #[tabled(rename = "Reason")]
+    pub reason: String,
+    /// Where the geometry is invalid.
+    #[tabled(rename = "Location")]
+    pub location: String,
+}
+
 /// Table row representation for displaying driver information.
 #[derive(Tabled)]
 pub struct DriverRow {
@@ -55,10 +172,55 @@ pub struct DriverRow {
     pub write: String,
 }
 
+/// Table row representation for displaying a driver open or creation option.
+#[derive(Tabled)]
+pub struct OptionRow {
+    /// Option name, as passed to `--oo`/`--lco`.
+    #[tabled(rename = "Option")]
+    pub name: String,
+    /// Type of the accepted values.
+    #[tabled(rename = "Type")]
+    pub option_type: String,
+    /// Value used when the option is not given.
+    #[tabled(rename = "Default")]
+    pub default: String,
+    /// Description of the option.
+    #[tabled(rename = "Description")]
+    pub description: String,
+}
+
+/// Display driver options under a section title in a formatted table.
+///
+/// # Arguments
+///
+/// * `title` - Section title (e.g., "Open Options (--oo)")
+/// * `options` - The options declared by the driver
+pub fn display_format_options(title: &str, options: &[FormatOption]) {
+    println!("\n=== {title} ===");
+
+    if options.is_empty() {
+        println!("None");
+        return;
+    }
+
+    let rows: Vec<OptionRow> = options
+        .iter()
+        .map(|o| OptionRow {
+            name: o.name.to_string(),
+            option_type: o.option_type.to_string(),
+            default: o.default.unwrap_or("").to_string(),
+            description: o.description.to_string(),
+        })
+        .collect();
+
+    println!("{}", Table::new(rows));
+}
+
 /// Display dataset information in a formatted table.
 ///
 /// This function presents dataset metadata, geometry columns, and field schema
-/// in a human-readable table format written to standard output.
+/// in a human-readable table format written to standard output, followed by the
+/// geometry types and field statistics when they were computed.
 ///
 /// # Arguments
 ///
@@ -67,6 +229,19 @@ pub fn display_dataset_info(info: &DatasetInfo) {
     // Display dataset path and driver
     println!("\nDataset: {}", info.dataset);
     println!("Driver: {} ({})", info.driver, info.driver_long_name);
+    if info.layers.len() > 1 {
+        println!("Layers: {}", info.layers.join(", "));
+    }
+    println!("Layer: {}", info.layer);
+    if let Some(count) = info.feature_count {
+        println!("Feature Count: {count}");
+    }
+    if let Some(extent) = info.extent {
+        println!(
+            "Extent: ({}, {}) - ({}, {})",
+            extent.min_x, extent.min_y, extent.max_x, extent.max_y
+        );
+    }
 
     // Display geometry columns
     if !info.geometry_columns.is_empty() {
@@ -103,12 +278,114 @@ pub fn display_dataset_info(info: &DatasetInfo) {
         let field_table = Table::new(field_rows).to_string();
         println!("{field_table}");
     }
+
+    // Display statistics
+    if !info.geometry_types.is_empty() {
+        println!("\n=== Geometry Types ===");
+
+        let type_rows: Vec<GeometryTypeRow> = info
+            .geometry_types
+            .iter()
+            .map(|(name, count)| GeometryTypeRow {
+                name: name.clone(),
+                count: *count,
+            })
+            .collect();
+        println!("{}", Table::new(type_rows));
+    }
+
+    let stats_rows: Vec<FieldStatsRow> = info
+        .fields
+        .iter()
+        .filter_map(|f| {
+            let stats = f.stats.as_ref()?;
+            Some(FieldStatsRow {
+                name: f.name.clone(),
+                null_count: stats.null_count,
+                min: stats.min.clone().unwrap_or_else(|| "N/A".to_string()),
+                max: stats.max.clone().unwrap_or_else(|| "N/A".to_string()),
+                distinct_count: stats
+                    .distinct_count
+                    .map_or_else(|| "N/A".to_string(), |count| count.to_string()),
+                top_values: stats
+                    .top_values
+                    .iter()
+                    .map(|(value, count)| format!("{value} ({count})"))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            })
+        })
+        .collect();
+    if !stats_rows.is_empty() {
+        println!("\n=== Field Statistics ===");
+        println!("{}", Table::new(stats_rows));
+    }
+}
+
+/// Display the invalid geometries of a dataset in a formatted table.
+///
+/// The table is followed by the number of invalid geometries and features checked.
+///
+/// # Arguments
+///
+/// * `report` - The validation report to display
+pub fn display_validation_report(report: &ValidationReport) {
+    println!("\nDataset: {}", report.dataset);
+    println!("Driver: {}", report.driver);
+    println!("Layer: {}", report.layer);
+
+    if !report.issues.is_empty() {
+        println!("\n=== Invalid Geometries ===");
+
+        let rows: Vec<ValidationRow> = report
+            .issues
+            .iter()
+            .map(|issue| ValidationRow {
+                feature: issue.feature,
+                column: issue.column.clone(),
+                reason: issue.reason.clone(),
+                location: issue.location.clone().unwrap_or_else(|| "N/A".to_string()),
+            })
+            .collect();
+        println!("{}", Table::new(rows));
+    }
+
+    println!(
+        "\n{} invalid of {} features checked",
+        report.issues.len(),
+        report.feature_count
+    );
+}
+
+/// Display the result of a SQL query in a formatted table.
+///
+/// The table has one column per result column and is followed by the number of rows.
+///
+/// # Arguments
+///
+/// * `result` - The query result to display
+pub fn display_query_result(result: &QueryResult) {
+    let mut builder = Builder::default();
+    builder.push_record(result.columns.iter().map(String::as_str));
+    for row in &result.rows {
+        builder.push_record(row.iter().map(String::as_str));
+    }
+
+    println!("{}", builder.build());
+    let noun = if result.rows.len() == 1 {
+        "row"
+    } else {
+        "rows"
+    };
+    println!("({} {noun})", result.rows.len());
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use geoetl_core::types::{DatasetInfo, FieldInfo, GeometryColumnInfo};
+    use geoetl_core::types::{
+        DatasetInfo, Extent, FieldInfo, FieldStats, GeometryColumnInfo, ValidationIssue,
+    };
 
     #[test]
     fn test_geometry_row_creation() {
@@ -150,10 +427,34 @@ mod tests {
         assert_eq!(row.write, "Yes");
     }
 
+    #[test]
+    fn test_display_validation_report() {
+        let report = ValidationReport {
+            dataset: "parcels.geojson".to_string(),
+            driver: "GeoJSON".to_string(),
+            layer: "parcels".to_string(),
+            feature_count: 2,
+            issues: vec![ValidationIssue {
+                feature: 1,
+                column: "geometry".to_string(),
+                reason: "exterior ring has a self-intersection".to_string(),
+                location: Some("POINT(1 1)".to_string()),
+            }],
+        };
+
+        // This test just ensures the function runs without panicking
+        display_validation_report(&report);
+    }
+
     #[test]
     fn test_display_dataset_info_with_geometry() {
         let info = DatasetInfo {
+            feature_count: None,
+            extent: None,
+            geometry_types: vec![],
             dataset: "test.geojson".to_string(),
+            layer: "test".to_string(),
+            layers: vec!["test".to_string()],
             driver: "GeoJSON".to_string(),
             driver_long_name: "GeoJSON File Format".to_string(),
             geometry_columns: vec![GeometryColumnInfo {
@@ -166,6 +467,42 @@ mod tests {
                 name: "id".to_string(),
                 data_type: "Int32".to_string(),
                 nullable: false,
+                stats: None,
+            }],
+        };
+
+        // This test just ensures the function runs without panicking
+        display_dataset_info(&info);
+    }
+
+    #[test]
+    fn test_display_dataset_info_with_stats() {
+        let info = DatasetInfo {
+            feature_count: Some(3),
+            extent: Some(Extent {
+                min_x: -1.0,
+                min_y: 0.0,
+                max_x: 2.5,
+                max_y: 4.0,
+            }),
+            geometry_types: vec![("Point".to_string(), 2), ("MultiPoint".to_string(), 1)],
+            dataset: "test.geojson".to_string(),
+            layer: "test".to_string(),
+            layers: vec!["test".to_string(), "other".to_string()],
+            driver: "GeoJSON".to_string(),
+            driver_long_name: "GeoJSON File Format".to_string(),
+            geometry_columns: vec![],
+            fields: vec![FieldInfo {
+                name: "name".to_string(),
+                data_type: "String".to_string(),
+                nullable: true,
+                stats: Some(FieldStats {
+                    null_count: 1,
+                    min: Some("a".to_string()),
+                    max: Some("b".to_string()),
+                    distinct_count: Some(2),
+                    top_values: vec![("a".to_string(), 1), ("b".to_string(), 1)],
+                }),
             }],
         };
 
@@ -176,7 +513,12 @@ mod tests {
     #[test]
     fn test_display_dataset_info_without_geometry() {
         let info = DatasetInfo {
+            feature_count: None,
+            extent: None,
+            geometry_types: vec![],
             dataset: "test.csv".to_string(),
+            layer: "test".to_string(),
+            layers: vec!["test".to_string()],
             driver: "CSV".to_string(),
             driver_long_name: "Comma Separated Values".to_string(),
             geometry_columns: vec![],
@@ -185,11 +527,13 @@ mod tests {
                     name: "name".to_string(),
                     data_type: "String".to_string(),
                     nullable: true,
+                    stats: None,
                 },
                 FieldInfo {
                     name: "value".to_string(),
                     data_type: "Float64".to_string(),
                     nullable: false,
+                    stats: None,
                 },
             ],
         };
@@ -201,7 +545,12 @@ mod tests {
     #[test]
     fn test_display_dataset_info_with_na_fields() {
         let info = DatasetInfo {
+            feature_count: None,
+            extent: None,
+            geometry_types: vec![],
             dataset: "test.geojson".to_string(),
+            layer: "test".to_string(),
+            layers: vec!["test".to_string()],
             driver: "GeoJSON".to_string(),
             driver_long_name: "GeoJSON File Format".to_string(),
             geometry_columns: vec![GeometryColumnInfo {
@@ -220,7 +569,12 @@ mod tests {
     #[test]
     fn test_display_dataset_info_empty() {
         let info = DatasetInfo {
+            feature_count: None,
+            extent: None,
+            geometry_types: vec![],
             dataset: "empty.csv".to_string(),
+            layer: "empty".to_string(),
+            layers: vec!["empty".to_string()],
             driver: "CSV".to_string(),
             driver_long_name: "Comma Separated Values".to_string(),
             geometry_columns: vec![],
@@ -230,4 +584,31 @@ mod tests {
         // This test ensures empty datasets are handled correctly
         display_dataset_info(&info);
     }
+
+    #[test]
+    fn test_display_query_result() {
+        let result = QueryResult {
+            columns: vec!["name".to_string(), "geometry".to_string()],
+            rows: vec![vec!["A".to_string(), "POINT(1 2)".to_string()]],
+        };
+
+        // This test ensures results with and without rows are displayed
+        display_query_result(&result);
+        display_query_result(&QueryResult::default());
+    }
+
+    #[test]
+    fn test_display_format_options() {
+        use geoetl_core::drivers::OptionType;
+
+        let options = [
+            FormatOption::new("HEADER", OptionType::Boolean, "Write a header row")
+                .with_default("YES"),
+            FormatOption::new("NULL_VALUE", OptionType::String, "Text for nulls"),
+        ];
+
+        // This test ensures options with and without defaults are displayed
+        display_format_options("Creation Options (--lco)", &options);
+        display_format_options("Open Options (--oo)", &[]);
+    }
 }
@@ -14,11 +14,13 @@
 //!
 //! - `convert` - Convert data between geospatial formats
 //! - `info` - Display dataset information and metadata
+//! - `validate` - Report invalid geometries in a dataset
+//! - `sql` - Run a SQL query over one or more datasets
 //! - `drivers` - List all available format drivers and their capabilities
 
 mod display;
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use tabled::Table;
 use tracing::{Level, info};
 use tracing_log::LogTracer;
@@ -27,7 +29,10 @@ use tracing_subscriber::FmtSubscriber;
 use geoetl_core::drivers::get_available_drivers;
 use geoetl_core::error::{self, GeoEtlError};
 
-use display::{DriverRow, display_dataset_info};
+use display::{
+    DriverDetails, DriverList, DriverRow, OutputFormat, display_dataset_info,
+    display_format_options, display_query_result, display_validation_report, print_document,
+};
 
 #[derive(Parser)]
 #[command(
@@ -60,11 +65,13 @@ struct Cli {
 /// geospatial datasets, such as format conversion, metadata inspection, or
 /// driver enumeration.
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)] // parsed once per run
 enum Commands {
     /// Converts data between different vector geospatial formats.
     ///
-    /// This command takes an input dataset and converts it to an output dataset,
-    /// specifying the input and output drivers.
+    /// This command takes an input dataset and converts it to an output dataset.
+    /// Drivers that are not specified are detected from the input's content or
+    /// extension and from the output's extension.
     Convert {
         /// Path to the input geospatial dataset.
         #[arg(short, long, value_name = "DATASET")]
@@ -75,12 +82,14 @@ enum Commands {
         output: String,
 
         /// The driver to use for reading the input dataset (e.g., "`GeoJSON`", "`Parquet`").
+        /// Detected from the input's content or extension when omitted.
         #[arg(long, value_name = "DRIVER")]
-        input_driver: String,
+        input_driver: Option<String>,
 
         /// The driver to use for writing the output dataset (e.g., "`GeoJSON`", "`Parquet`").
+        /// Detected from the output's extension when omitted.
         #[arg(long, value_name = "DRIVER")]
-        output_driver: String,
+        output_driver: Option<String>,
 
         /// Name of the geometry column in the input dataset (default: "geometry").
         /// For CSV files, this should be the column containing WKT geometry strings.
@@ -91,20 +100,121 @@ enum Commands {
         /// Only required when converting from CSV with WKT geometries to `GeoJSON`.
         #[arg(long, value_name = "TYPE")]
         geometry_type: Option<String>,
+
+        /// Input layer to convert (repeatable); every layer when omitted. Several layers
+        /// are written to one dataset, or to a directory of files for single-layer formats.
+        #[arg(long = "layer", value_name = "LAYER")]
+        layers: Vec<String>,
+
+        /// Input driver open option (repeatable). See `geoetl drivers <DRIVER>`.
+        #[arg(long = "oo", value_name = "KEY=VALUE")]
+        open_options: Vec<String>,
+
+        /// Output driver creation option (repeatable). See `geoetl drivers <DRIVER>`.
+        #[arg(long = "lco", value_name = "KEY=VALUE")]
+        creation_options: Vec<String>,
+
+        /// Only write rows matching this SQL expression (e.g., "population > 1000000").
+        #[arg(long = "where", value_name = "EXPR")]
+        filter: Option<String>,
+
+        /// Comma-separated columns to write, in output order.
+        #[arg(long, value_name = "COLUMNS", value_delimiter = ',')]
+        select: Option<Vec<String>>,
+
+        /// Rename a column in the output (repeatable).
+        #[arg(long, value_name = "OLD=NEW")]
+        rename: Vec<String>,
+
+        /// Reproject geometries to this CRS (e.g., "EPSG:3857", "32633" or a PROJ string).
+        #[arg(long = "t-srs", value_name = "SRS")]
+        t_srs: Option<String>,
+
+        /// CRS of the input geometries, overriding the one recorded in the dataset.
+        #[arg(long = "s-srs", value_name = "SRS", requires = "t_srs")]
+        s_srs: Option<String>,
+
+        /// Assign a CRS to input geometries that carry none (e.g., CSV), without reprojecting.
+        #[arg(long = "a-srs", value_name = "SRS")]
+        a_srs: Option<String>,
+
+        /// Only write features intersecting this bounding box, in the input CRS.
+        #[arg(
+            long,
+            value_name = "XMIN,YMIN,XMAX,YMAX",
+            group = "spatial",
+            allow_hyphen_values = true
+        )]
+        bbox: Option<String>,
+
+        /// Only write features intersecting this geometry, given as WKT or a dataset path.
+        #[arg(long, value_name = "WKT|DATASET", group = "spatial")]
+        spatial_filter: Option<String>,
+
+        /// Only write features intersecting this bounding box, geometry or dataset, clipping
+        /// their geometries to it.
+        #[arg(
+            long,
+            value_name = "BBOX|WKT|DATASET",
+            group = "spatial",
+            allow_hyphen_values = true
+        )]
+        clip_src: Option<String>,
+
+        /// Repair invalid geometries before writing them. See `geoetl validate`.
+        #[arg(long)]
+        make_valid: bool,
+
+        /// Simplify lines and polygons with this tolerance, in input CRS units: a distance
+        /// for `douglas-peucker`, an area for the Visvalingam methods.
+        #[arg(long, value_name = "TOLERANCE")]
+        simplify: Option<f64>,
+
+        /// Simplification algorithm.
+        #[arg(
+            long,
+            value_name = "METHOD",
+            value_enum,
+            default_value_t = SimplifyMethod::PreserveTopology,
+            requires = "simplify"
+        )]
+        simplify_method: SimplifyMethod,
+
+        /// Round output coordinates to multiples of this size, in output CRS units
+        /// (e.g., 0.00001 for about 1 m in degrees).
+        #[arg(long, value_name = "SIZE")]
+        snap_to_grid: Option<f64>,
+
+        /// Write each part of multi-geometries and collections as its own feature.
+        #[arg(long)]
+        explode: bool,
+
+        /// Geometry type to write (e.g., "`MultiPolygon`", "`Point Z`"), or `PROMOTE_TO_MULTI`.
+        /// Conversion fails, listing their types, if some geometries cannot be converted.
+        #[arg(long, value_name = "TYPE")]
+        nlt: Option<String>,
+
+        /// Coordinate dimension to write; missing Z or M values are set to 0.
+        #[arg(long, value_name = "DIM", value_enum, ignore_case = true)]
+        dim: Option<CoordinateDimension>,
+
+        #[command(flatten)]
+        write_mode: WriteModeArgs,
     },
 
     /// Displays information about a vector geospatial dataset.
     ///
-    /// This command shows general information, detailed layer information,
-    /// and statistics for each field within the dataset.
+    /// This command shows general information and detailed layer information and, with
+    /// `--stats`, statistics for the dataset and each field within it.
     Info {
         /// Path to the input geospatial dataset.
         #[arg(value_name = "DATASET")]
         input: String,
 
         /// Input driver (e.g., `GeoJSON`, `CSV`, `Parquet`).
+        /// Detected from the dataset's content or extension when omitted.
         #[arg(short = 'f', long, value_name = "DRIVER")]
-        driver: String,
+        driver: Option<String>,
 
         /// Name of the geometry column in the input dataset.
         /// For CSV files, this should be the column containing WKT geometry strings.
@@ -116,13 +226,235 @@ enum Commands {
         /// Only used when reading CSV files with WKT geometries.
         #[arg(long, value_name = "TYPE")]
         geometry_type: Option<String>,
+
+        /// Layer to describe; the first layer when omitted.
+        #[arg(long, value_name = "LAYER")]
+        layer: Option<String>,
+
+        /// Driver open option (repeatable). See `geoetl drivers <DRIVER>`.
+        #[arg(long = "oo", value_name = "KEY=VALUE")]
+        open_options: Vec<String>,
+
+        /// Count the features intersecting this bounding box, in the dataset's CRS.
+        #[arg(
+            long,
+            value_name = "XMIN,YMIN,XMAX,YMAX",
+            conflicts_with = "spatial_filter"
+        )]
+        bbox: Option<String>,
+
+        /// Count the features intersecting this geometry, given as WKT or a dataset path.
+        #[arg(long, value_name = "WKT|DATASET")]
+        spatial_filter: Option<String>,
+
+        /// Read every feature to report the feature count, extent, geometry types and, for
+        /// each field, null count, range, distinct values and most frequent strings.
+        #[arg(long)]
+        stats: bool,
+
+        /// Output format: tables, or a JSON or YAML document for scripts.
+        #[arg(long, value_enum, ignore_case = true, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+
+    /// Checks the geometries of a vector geospatial dataset for validity.
+    ///
+    /// Every geometry is checked against the OGC simple feature rules (too few points,
+    /// self-intersections, crossing rings, holes outside their shell, unclosed rings and ring
+    /// orientation). Each invalid geometry is listed with its feature index, the reason and a
+    /// location. Use `convert --make-valid` to repair them.
+    Validate {
+        /// Path to the input geospatial dataset.
+        #[arg(value_name = "DATASET")]
+        input: String,
+
+        /// Input driver (e.g., `GeoJSON`, `CSV`).
+        /// Detected from the dataset's content or extension when omitted.
+        #[arg(short = 'f', long, value_name = "DRIVER")]
+        driver: Option<String>,
+
+        /// Name of the geometry column in the input dataset.
+        /// Required for CSV format, optional for other formats (defaults to "geometry").
+        #[arg(long, value_name = "COLUMN")]
+        geometry_column: Option<String>,
+
+        /// Geometry type for the input geometry column (e.g., "`Point`", "`Polygon`").
+        /// Only used when reading CSV files with WKT geometries.
+        #[arg(long, value_name = "TYPE")]
+        geometry_type: Option<String>,
+
+        /// Layer to check; the first layer when omitted.
+        #[arg(long, value_name = "LAYER")]
+        layer: Option<String>,
+
+        /// Driver open option (repeatable). See `geoetl drivers <DRIVER>`.
+        #[arg(long = "oo", value_name = "KEY=VALUE")]
+        open_options: Vec<String>,
+    },
+
+    /// Runs a SQL query over one or more vector geospatial datasets.
+    ///
+    /// Each dataset is registered as a table under the given name, read with its own
+    /// driver. The results are printed as a table, or written to an output dataset.
+    Sql {
+        /// SQL query to run (e.g., "SELECT name FROM cities WHERE population > 1000000").
+        #[arg(value_name = "QUERY")]
+        query: String,
+
+        /// Dataset to register as a table, as NAME=PATH (repeatable).
+        #[arg(short, long = "table", value_name = "NAME=PATH", required = true)]
+        tables: Vec<String>,
+
+        /// Write the results to this dataset instead of printing them.
+        #[arg(short, long, value_name = "DATASET")]
+        output: Option<String>,
+
+        /// The driver used to read every table. Detected per dataset when omitted.
+        #[arg(long, value_name = "DRIVER")]
+        input_driver: Option<String>,
+
+        /// The driver used to write the output dataset. Detected from the output's
+        /// extension when omitted.
+        #[arg(long, value_name = "DRIVER", requires = "output")]
+        output_driver: Option<String>,
+
+        /// Name of the geometry column in the input datasets (default: "geometry").
+        /// For CSV files, this should be the column containing WKT geometry strings.
+        #[arg(long, value_name = "COLUMN", default_value = "geometry")]
+        geometry_column: String,
+
+        /// Geometry type for the input geometry columns (e.g., "`Point`", "`Polygon`").
+        /// Only used when reading CSV files with WKT geometries.
+        #[arg(long, value_name = "TYPE")]
+        geometry_type: Option<String>,
+
+        /// Open option passed to every input driver (repeatable).
+        #[arg(long = "oo", value_name = "KEY=VALUE")]
+        open_options: Vec<String>,
+
+        /// Output driver creation option (repeatable).
+        #[arg(long = "lco", value_name = "KEY=VALUE", requires = "output")]
+        creation_options: Vec<String>,
+
+        #[command(flatten)]
+        write_mode: WriteModeArgs,
     },
 
     /// Lists all available geospatial drivers and their capabilities.
     ///
     /// This command provides an overview of which formats can be read from,
     /// written to, and provide metadata information.
-    Drivers,
+    Drivers {
+        /// Show the capabilities and the open/creation options of a single driver.
+        #[arg(value_name = "DRIVER")]
+        driver: Option<String>,
+
+        /// Output format: tables, or a JSON or YAML document for scripts.
+        #[arg(long, value_enum, ignore_case = true, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+}
+
+/// Simplification algorithms accepted by `convert --simplify-method`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SimplifyMethod {
+    /// Douglas-Peucker, with a distance tolerance
+    DouglasPeucker,
+    /// Visvalingam-Whyatt, with an area tolerance
+    Visvalingam,
+    /// Visvalingam-Whyatt without introducing self-intersections, with an area tolerance
+    PreserveTopology,
+}
+
+impl From<SimplifyMethod> for Simplification {
+    fn from(method: SimplifyMethod) -> Self {
+        match method {
+            SimplifyMethod::DouglasPeucker => Self::DouglasPeucker,
+            SimplifyMethod::Visvalingam => Self::Visvalingam,
+            SimplifyMethod::PreserveTopology => Self::PreserveTopology,
+        }
+    }
+}
+
+/// Coordinate dimensions accepted by `convert --dim`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CoordinateDimension {
+    /// X and Y
+    Xy,
+    /// X, Y and Z
+    Xyz,
+    /// X, Y and M
+    Xym,
+    /// X, Y, Z and M
+    Xyzm,
+}
+
+impl From<CoordinateDimension> for Dimension {
+    fn from(dimension: CoordinateDimension) -> Self {
+        match dimension {
+            CoordinateDimension::Xy => Self::XY,
+            CoordinateDimension::Xyz => Self::XYZ,
+            CoordinateDimension::Xym => Self::XYM,
+            CoordinateDimension::Xyzm => Self::XYZM,
+        }
+    }
+}
+
+/// What `convert` and `sql` do when their output already exists.
+#[derive(Debug, Clone, Args)]
+struct WriteModeArgs {
+    /// What to do when the output exists. Appending checks that the output's columns
+    /// match the written ones.
+    #[arg(
+        long,
+        value_name = "ACTION",
+        value_enum,
+        ignore_case = true,
+        default_value_t = IfExists::Overwrite,
+        conflicts_with_all = ["append", "overwrite"],
+        requires = "output"
+    )]
+    if_exists: IfExists,
+
+    /// Add the features to an existing output (same as `--if-exists append`).
+    #[arg(long, conflicts_with = "overwrite", requires = "output")]
+    append: bool,
+
+    /// Replace an existing output (same as `--if-exists overwrite`, the default).
+    #[arg(long, requires = "output")]
+    overwrite: bool,
+}
+
+impl WriteModeArgs {
+    /// The write mode selected by `--if-exists`, `--append` or `--overwrite`.
+    fn write_mode(&self) -> WriteMode {
+        if self.append {
+            WriteMode::Append
+        } else {
+            self.if_exists.into()
+        }
+    }
+}
+
+/// Actions accepted by `--if-exists`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum IfExists {
+    /// Replace the output
+    Overwrite,
+    /// Add the features to the output
+    Append,
+    /// Fail without writing anything
+    Fail,
+}
+
+impl From<IfExists> for WriteMode {
+    fn from(action: IfExists) -> Self {
+        match action {
+            IfExists::Overwrite => Self::Overwrite,
+            IfExists::Append => Self::Append,
+            IfExists::Fail => Self::Fail,
+        }
+    }
 }
 
 /// Entry point for the `GeoETL` command-line interface.
@@ -135,9 +467,6 @@ enum Commands {
 /// Returns an error if command execution fails or if the logging system cannot be initialized.
 #[tokio::main]
 async fn main() {
-    // Initialize geoetl-core (registers all format drivers)
-    geoetl_core::init::initialize();
-
     let cli = Cli::parse();
 
     // Setup logging based on verbosity flags
@@ -155,9 +484,11 @@ async fn main() {
         std::process::exit(1);
     }
 
+    // Logs go to stderr so that JSON and YAML output on stdout stays parseable
     let subscriber = FmtSubscriber::builder()
         .with_max_level(log_level)
         .with_target(true) // Show module paths for better context
+        .with_writer(std::io::stderr)
         .finish();
 
     if let Err(e) = tracing::subscriber::set_global_default(subscriber) {
@@ -165,6 +496,10 @@ async fn main() {
         std::process::exit(1);
     }
 
+    // Initialize geoetl-core (registers all format drivers and plugins). Done after the
+    // logger is set up so that plugin loading failures are reported.
+    geoetl_core::init::initialize();
+
     // Execute the command and handle errors with user-friendly messages
     let result = match cli.command {
         Commands::Convert {
@@ -174,16 +509,80 @@ async fn main() {
             output_driver,
             geometry_column,
             geometry_type,
+            layers,
+            open_options,
+            creation_options,
+            filter,
+            select,
+            rename,
+            t_srs,
+            s_srs,
+            a_srs,
+            bbox,
+            spatial_filter,
+            clip_src,
+            make_valid,
+            simplify,
+            simplify_method,
+            snap_to_grid,
+            explode,
+            nlt,
+            dim,
+            write_mode,
         } => {
             info!("Converting {input} to {output}");
-            handle_convert(
-                &input,
-                &output,
-                &input_driver,
-                &output_driver,
-                &geometry_column,
-                geometry_type.as_deref(),
-            )
+            async {
+                let mut options = apply_transform_options(
+                    parse_convert_options(&open_options, &creation_options)?,
+                    filter,
+                    select,
+                    &rename,
+                )?;
+                for layer in layers {
+                    options = options.with_layer(layer);
+                }
+                if let Some(t_srs) = t_srs {
+                    options = options.with_target_crs(t_srs);
+                }
+                if let Some(s_srs) = s_srs {
+                    options = options.with_source_crs(s_srs);
+                }
+                if let Some(a_srs) = a_srs {
+                    options = options.with_assigned_crs(a_srs);
+                }
+                let clip = clip_src.is_some();
+                if let Some(spec) = bbox.or(spatial_filter).or(clip_src) {
+                    let geometry = operations::spatial_filter_geometry(&spec).await?;
+                    options = options.with_spatial_filter(geometry).with_clip(clip);
+                }
+                options = options.with_make_valid(make_valid);
+                if let Some(tolerance) = simplify {
+                    options = options
+                        .with_simplify(tolerance)
+                        .with_simplify_method(simplify_method.into());
+                }
+                if let Some(size) = snap_to_grid {
+                    options = options.with_snap_to_grid(size);
+                }
+                options = options.with_explode(explode);
+                if let Some(geometry_type) = nlt {
+                    options = options.with_geometry_type(geometry_type);
+                }
+                if let Some(dim) = dim {
+                    options = options.with_dimension(dim.into());
+                }
+                options = options.with_write_mode(write_mode.write_mode());
+                handle_convert(
+                    &input,
+                    &output,
+                    input_driver.as_deref(),
+                    output_driver.as_deref(),
+                    &geometry_column,
+                    geometry_type.as_deref(),
+                    &options,
+                )
+                .await
+            }
             .await
         },
         Commands::Info {
@@ -191,17 +590,103 @@ async fn main() {
             driver,
             geometry_column,
             geometry_type,
+            layer,
+            open_options,
+            bbox,
+            spatial_filter,
+            stats,
+            format,
         } => {
             info!("Displaying info for {input}");
-            handle_info(
-                &input,
-                &driver,
-                geometry_column.as_deref(),
-                geometry_type.as_deref(),
-            )
+            async {
+                let mut options = InfoOptions::new()
+                    .with_open_options(parse_option_values("open", &open_options)?)
+                    .with_stats(stats);
+                if let Some(layer) = layer {
+                    options = options.with_layer(layer);
+                }
+                if let Some(spec) = bbox.or(spatial_filter) {
+                    let geometry = operations::spatial_filter_geometry(&spec).await?;
+                    options = options.with_spatial_filter(geometry);
+                }
+                let dataset_info = handle_info(
+                    &input,
+                    driver.as_deref(),
+                    geometry_column.as_deref(),
+                    geometry_type.as_deref(),
+                    &options,
+                )
+                .await?;
+
+                // Display dataset information using tables or as a document
+                if format == OutputFormat::Table {
+                    display_dataset_info(&dataset_info);
+                    Ok(())
+                } else {
+                    print_document(format, &dataset_info)
+                }
+            }
+            .await
+        },
+        Commands::Validate {
+            input,
+            driver,
+            geometry_column,
+            geometry_type,
+            layer,
+            open_options,
+        } => {
+            info!("Validating {input}");
+            async {
+                let open_options = parse_option_values("open", &open_options)?;
+                handle_validate(
+                    &input,
+                    driver.as_deref(),
+                    geometry_column.as_deref(),
+                    geometry_type.as_deref(),
+                    layer.as_deref(),
+                    &open_options,
+                )
+                .await
+            }
+            .await
+        },
+        Commands::Sql {
+            query,
+            tables,
+            output,
+            input_driver,
+            output_driver,
+            geometry_column,
+            geometry_type,
+            open_options,
+            creation_options,
+            write_mode,
+        } => {
+            info!("Running SQL query over {} table(s)", tables.len());
+            async {
+                let open_options = parse_option_values("open", &open_options)?;
+                let creation_options = parse_option_values("creation", &creation_options)?;
+                let tables = parse_sql_tables(
+                    &tables,
+                    input_driver.as_deref(),
+                    &geometry_column,
+                    geometry_type.as_deref(),
+                    &open_options,
+                )?;
+                handle_sql(
+                    &query,
+                    &tables,
+                    output.as_deref(),
+                    output_driver.as_deref(),
+                    write_mode.write_mode(),
+                    &creation_options,
+                )
+                .await
+            }
             .await
         },
-        Commands::Drivers => handle_drivers(),
+        Commands::Drivers { driver, format } => handle_drivers(driver.as_deref(), format),
     };
 
     // Handle errors with user-friendly messages
@@ -218,42 +703,139 @@ async fn main() {
 
 use geoetl_core::drivers;
 use geoetl_core::operations;
+use geoetl_core::types::{
+    ConvertOptions, DatasetInfo, Dimension, InfoOptions, OptionValues, Simplification, SqlTable,
+    WriteMode,
+};
+
+/// Parses repeated `KEY=VALUE` arguments into driver options.
+///
+/// `kind` names the options in error messages (`"open"` or `"creation"`).
+fn parse_option_values(kind: &str, pairs: &[String]) -> Result<OptionValues, GeoEtlError> {
+    OptionValues::parse(pairs).map_err(|e| {
+        error::ConfigError::InvalidOption {
+            option: kind.to_string(),
+            message: e.to_string(),
+        }
+        .into()
+    })
+}
+
+/// Builds the conversion options from the `--oo` and `--lco` arguments.
+fn parse_convert_options(
+    open_options: &[String],
+    creation_options: &[String],
+) -> Result<ConvertOptions, GeoEtlError> {
+    Ok(ConvertOptions::new()
+        .with_open_options(parse_option_values("open", open_options)?)
+        .with_creation_options(parse_option_values("creation", creation_options)?))
+}
+
+/// Resolves the driver used to read `input`.
+///
+/// A named driver is looked up and checked against the format detected from the dataset's
+/// content, so that naming the wrong driver fails with a clear error. Without a name, the
+/// driver is detected from the content or extension.
+fn resolve_input_driver(input: &str, name: Option<&str>) -> Result<drivers::Driver, GeoEtlError> {
+    let Some(name) = name else {
+        let driver =
+            drivers::detect_driver(input).ok_or_else(|| error::DriverError::NotDetected {
+                path: input.to_string(),
+            })?;
+        info!("Detected input driver: {}", driver.short_name);
+        return Ok(driver);
+    };
+
+    let driver = drivers::find_driver(name).ok_or_else(|| error::driver_not_found(name))?;
+    if let Some(detected) = drivers::detect_driver_from_content(input)
+        && detected.short_name != driver.short_name
+    {
+        return Err(error::DriverError::FormatMismatch {
+            path: input.to_string(),
+            driver: driver.short_name.to_string(),
+            detected: detected.short_name.to_string(),
+        }
+        .into());
+    }
+    Ok(driver)
+}
+
+/// Resolves the driver used to write `output`, detecting it from the extension if not named.
+fn resolve_output_driver(output: &str, name: Option<&str>) -> Result<drivers::Driver, GeoEtlError> {
+    if let Some(name) = name {
+        return drivers::find_driver(name).ok_or_else(|| error::driver_not_found(name).into());
+    }
+
+    let driver = drivers::detect_driver_from_extension(output).ok_or_else(|| {
+        error::DriverError::NotDetected {
+            path: output.to_string(),
+        }
+    })?;
+    info!("Detected output driver: {}", driver.short_name);
+    Ok(driver)
+}
+
+/// Adds the `--where`, `--select` and `--rename` arguments to the conversion options.
+fn apply_transform_options(
+    mut options: ConvertOptions,
+    filter: Option<String>,
+    select: Option<Vec<String>>,
+    renames: &[String],
+) -> Result<ConvertOptions, GeoEtlError> {
+    if let Some(filter) = filter {
+        options = options.with_filter(filter);
+    }
+    if let Some(columns) = select {
+        options = options.with_columns(columns.iter().map(|column| column.trim()));
+    }
+    for rename in renames {
+        let (old, new) = rename
+            .split_once('=')
+            .map(|(old, new)| (old.trim(), new.trim()))
+            .filter(|(old, new)| !old.is_empty() && !new.is_empty())
+            .ok_or_else(|| error::ConfigError::InvalidOption {
+                option: "rename".to_string(),
+                message: format!("expected OLD=NEW, got '{rename}'"),
+            })?;
+        options = options.with_rename(old, new);
+    }
+    Ok(options)
+}
 
 async fn handle_convert(
     input: &str,
     output: &str,
-    input_driver_name: &str,
-    output_driver_name: &str,
+    input_driver_name: Option<&str>,
+    output_driver_name: Option<&str>,
     geometry_column: &str,
     geometry_type: Option<&str>,
+    options: &ConvertOptions,
 ) -> Result<(), GeoEtlError> {
     info!("Validating convert command:");
     info!("Input: {input}");
     info!("Output: {output}");
-    info!("Input driver: {input_driver_name}");
-    info!("Output driver: {output_driver_name}");
     info!("Geometry column: {geometry_column}");
     if let Some(geom_type) = geometry_type {
         info!("Geometry type: {geom_type}");
     }
 
-    let input_driver = drivers::find_driver(input_driver_name)
-        .ok_or_else(|| error::driver_not_found(input_driver_name))?;
+    let input_driver = resolve_input_driver(input, input_driver_name)?;
+    info!("Input driver: {}", input_driver.short_name);
 
     if !input_driver.capabilities.read.is_supported() {
         return Err(error::DriverError::OperationNotSupported {
-            driver: input_driver_name.to_string(),
+            driver: input_driver.short_name.to_string(),
             operation: "reading".to_string(),
         }
         .into());
     }
 
-    let output_driver = drivers::find_driver(output_driver_name)
-        .ok_or_else(|| error::driver_not_found(output_driver_name))?;
+    let output_driver = resolve_output_driver(output, output_driver_name)?;
+    info!("Output driver: {}", output_driver.short_name);
 
     if !output_driver.capabilities.write.is_supported() {
         return Err(error::DriverError::OperationNotSupported {
-            driver: output_driver_name.to_string(),
+            driver: output_driver.short_name.to_string(),
             operation: "writing".to_string(),
         }
         .into());
@@ -267,6 +849,7 @@ async fn handle_convert(
         &output_driver,
         geometry_column,
         geometry_type,
+        options,
     )
     .await?;
     info!("Conversion complete.");
@@ -275,14 +858,77 @@ async fn handle_convert(
 
 async fn handle_info(
     input: &str,
-    driver_name: &str,
+    driver_name: Option<&str>,
     geometry_column: Option<&str>,
     geometry_type: Option<&str>,
-) -> Result<(), GeoEtlError> {
+    options: &InfoOptions,
+) -> Result<DatasetInfo, GeoEtlError> {
     info!("Info command:");
     info!("Input: {input}");
-    info!("Driver: {driver_name}");
 
+    let (resolved_input, driver) = resolve_dataset(input, driver_name)?;
+
+    // Validate driver supports info or read operations
+    if !driver.capabilities.info.is_supported() && !driver.capabilities.read.is_supported() {
+        return Err(error::DriverError::OperationNotSupported {
+            driver: driver.short_name.to_string(),
+            operation: "info or read".to_string(),
+        }
+        .into());
+    }
+    let geometry_col = required_geometry_column(&driver, geometry_column, &options.open_options)?;
+
+    // Get dataset information
+    operations::info(
+        &resolved_input,
+        &driver,
+        geometry_col,
+        geometry_type,
+        options,
+    )
+    .await
+}
+
+async fn handle_validate(
+    input: &str,
+    driver_name: Option<&str>,
+    geometry_column: Option<&str>,
+    geometry_type: Option<&str>,
+    layer: Option<&str>,
+    open_options: &OptionValues,
+) -> Result<(), GeoEtlError> {
+    info!("Validate command:");
+    info!("Input: {input}");
+
+    let (resolved_input, driver) = resolve_dataset(input, driver_name)?;
+    if !driver.capabilities.read.is_supported() {
+        return Err(error::DriverError::OperationNotSupported {
+            driver: driver.short_name.to_string(),
+            operation: "read".to_string(),
+        }
+        .into());
+    }
+    let geometry_col = required_geometry_column(&driver, geometry_column, open_options)?;
+
+    let report = operations::validate(
+        &resolved_input,
+        &driver,
+        geometry_col,
+        geometry_type,
+        layer,
+        open_options,
+    )
+    .await?;
+    display_validation_report(&report);
+
+    Ok(())
+}
+
+/// Resolves an existing input dataset to an absolute path and the driver that reads it.
+fn resolve_dataset(
+    input: &str,
+    driver_name: Option<&str>,
+) -> Result<(String, drivers::Driver), GeoEtlError> {
     // Resolve the input path to an absolute path
     let input_path = std::path::Path::new(input);
     let absolute_path = if input_path.is_absolute() {
@@ -302,7 +948,8 @@ async fn handle_info(
         .ok_or_else(|| error::IoError::InvalidPath {
             path: absolute_path.clone(),
             reason: "Path contains invalid UTF-8 characters".to_string(),
-        })?;
+        })?
+        .to_string();
 
     // Verify file exists
     if !absolute_path.exists() {
@@ -312,64 +959,166 @@ async fn handle_info(
         .into());
     }
 
-    // Find the specified driver
-    let driver =
-        drivers::find_driver(driver_name).ok_or_else(|| error::driver_not_found(driver_name))?;
+    // Find the specified driver, or detect it from the dataset
+    let driver = resolve_input_driver(&resolved_input, driver_name)?;
+    info!("Driver: {}", driver.short_name);
+    Ok((resolved_input, driver))
+}
 
-    // Validate driver supports info or read operations
-    if !driver.capabilities.info.is_supported() && !driver.capabilities.read.is_supported() {
-        return Err(error::DriverError::OperationNotSupported {
-            driver: driver.short_name.to_string(),
-            operation: "info or read".to_string(),
+/// Returns the geometry column to read, which must be given for CSV files.
+fn required_geometry_column<'a>(
+    driver: &drivers::Driver,
+    geometry_column: Option<&'a str>,
+    open_options: &OptionValues,
+) -> Result<&'a str, GeoEtlError> {
+    if driver.short_name == "CSV"
+        && geometry_column.is_none()
+        && !open_options.contains("GEOMETRY_COLUMN")
+    {
+        return Err(error::ConfigError::MissingRequired {
+            option: "geometry-column (required for CSV files)".to_string(),
         }
         .into());
     }
+    Ok(geometry_column.unwrap_or("geometry"))
+}
 
-    // Validate geometry column is provided for CSV
-    let geometry_col = if driver.short_name == "CSV" {
-        geometry_column.ok_or_else(|| error::ConfigError::MissingRequired {
-            option: "geometry-column (required for CSV files)".to_string(),
-        })?
-    } else {
-        geometry_column.unwrap_or("geometry")
+/// Builds the SQL tables from repeated `NAME=PATH` arguments.
+///
+/// Each table is read with `input_driver` if given, otherwise with the driver detected from
+/// its dataset; the geometry and open options apply to every table.
+fn parse_sql_tables(
+    tables: &[String],
+    input_driver: Option<&str>,
+    geometry_column: &str,
+    geometry_type: Option<&str>,
+    open_options: &OptionValues,
+) -> Result<Vec<SqlTable>, GeoEtlError> {
+    tables
+        .iter()
+        .map(|table| {
+            let (name, path) = table
+                .split_once('=')
+                .filter(|(name, path)| !name.trim().is_empty() && !path.is_empty())
+                .ok_or_else(|| error::ConfigError::InvalidOption {
+                    option: "table".to_string(),
+                    message: format!("expected NAME=PATH, got '{table}'"),
+                })?;
+
+            let driver = resolve_input_driver(path, input_driver)?;
+            if !driver.capabilities.read.is_supported() {
+                return Err(error::DriverError::OperationNotSupported {
+                    driver: driver.short_name.to_string(),
+                    operation: "reading".to_string(),
+                }
+                .into());
+            }
+
+            Ok(SqlTable::new(name.trim(), path, driver)
+                .with_geometry_column(geometry_column)
+                .with_geometry_type(geometry_type.map(str::to_string))
+                .with_open_options(open_options.clone()))
+        })
+        .collect()
+}
+
+/// Handles the `sql` subcommand by running `query` over `tables`.
+///
+/// Without an output, the results are printed as a table. With an output, they are written
+/// through the output driver, named or detected from the output's extension, replacing,
+/// appending to or refusing to replace an existing output as `write_mode` says.
+async fn handle_sql(
+    query: &str,
+    tables: &[SqlTable],
+    output: Option<&str>,
+    output_driver_name: Option<&str>,
+    write_mode: WriteMode,
+    creation_options: &OptionValues,
+) -> Result<(), GeoEtlError> {
+    info!("SQL command:");
+    info!("Query: {query}");
+
+    let Some(output) = output else {
+        let result = operations::sql(tables, query).await?;
+        display_query_result(&result);
+        return Ok(());
     };
 
-    // Get dataset information
-    let dataset_info =
-        operations::info(resolved_input, &driver, geometry_col, geometry_type).await?;
+    let output_driver = resolve_output_driver(output, output_driver_name)?;
+    info!("Output: {output} (Driver: {})", output_driver.short_name);
 
-    // Display dataset information using tables
-    display_dataset_info(&dataset_info);
+    if !output_driver.capabilities.write.is_supported() {
+        return Err(error::DriverError::OperationNotSupported {
+            driver: output_driver.short_name.to_string(),
+            operation: "writing".to_string(),
+        }
+        .into());
+    }
 
+    let rows = operations::sql_to_file(
+        tables,
+        query,
+        output,
+        &output_driver,
+        write_mode,
+        creation_options,
+    )
+    .await?;
+    info!("Wrote {rows} rows to {output}");
     Ok(())
 }
 
 /// Handles the `drivers` subcommand by displaying a formatted table of available drivers.
 ///
-/// Retrieves all drivers with at least one supported operation from the driver registry
-/// and presents their capabilities (info, read, write) in a human-readable table format
-/// written to standard output.
+/// Without a driver name, retrieves all drivers with at least one supported operation from
+/// the driver registry and presents their capabilities (info, read, write) in a
+/// human-readable table. With a driver name, shows that driver's capabilities followed by
+/// the open and creation options it accepts through `--oo` and `--lco`. Other output
+/// formats print the same information as a document.
 ///
 /// # Errors
 ///
-/// This function returns a `Result` for consistency with other command handlers,
-/// but does not currently perform any operations that fail, so it always returns `Ok(())`.
-#[allow(clippy::unnecessary_wraps)]
-fn handle_drivers() -> Result<(), GeoEtlError> {
+/// Returns a `DriverError::NotFound` if `driver_name` does not name a known driver.
+fn handle_drivers(driver_name: Option<&str>, format: OutputFormat) -> Result<(), GeoEtlError> {
+    let to_row = |d: &drivers::Driver| DriverRow {
+        short_name: d.short_name.to_string(),
+        long_name: d.long_name.to_string(),
+        info: d.capabilities.info.as_str().to_string(),
+        read: d.capabilities.read.as_str().to_string(),
+        write: d.capabilities.write.as_str().to_string(),
+    };
+
+    if let Some(name) = driver_name {
+        let driver = drivers::find_driver(name).ok_or_else(|| error::driver_not_found(name))?;
+        let open_options = drivers::get_open_options(driver.short_name);
+        let creation_options = drivers::get_creation_options(driver.short_name);
+
+        if format != OutputFormat::Table {
+            return print_document(
+                format,
+                &DriverDetails {
+                    driver: &driver,
+                    open_options: &open_options,
+                    creation_options: &creation_options,
+                },
+            );
+        }
+
+        println!("\nDriver: {} ({})\n", driver.short_name, driver.long_name);
+        println!("{}", Table::new([to_row(&driver)]));
+        display_format_options("Open Options (--oo)", &open_options);
+        display_format_options("Creation Options (--lco)", &creation_options);
+        return Ok(());
+    }
+
     let drivers = get_available_drivers();
+    if format != OutputFormat::Table {
+        return print_document(format, &DriverList { drivers: &drivers });
+    }
 
     println!("\nAvailable Drivers ({} total):\n", drivers.len());
 
-    let rows: Vec<DriverRow> = drivers
-        .iter()
-        .map(|d| DriverRow {
-            short_name: d.short_name.to_string(),
-            long_name: d.long_name.to_string(),
-            info: d.capabilities.info.as_str().to_string(),
-            read: d.capabilities.read.as_str().to_string(),
-            write: d.capabilities.write.as_str().to_string(),
-        })
-        .collect();
+    let rows: Vec<DriverRow> = drivers.iter().map(to_row).collect();
 
     let table = Table::new(rows).to_string();
     println!("{table}");
@@ -409,10 +1158,11 @@ mod tests {
         let result = handle_convert(
             "input.csv",
             "output.geojson",
-            input_driver_name,
-            output_driver_name,
+            Some(input_driver_name),
+            Some(output_driver_name),
             "geometry",
             None,
+            &ConvertOptions::default(),
         )
         .await;
         assert!(result.is_err());
@@ -432,10 +1182,11 @@ mod tests {
         let result = handle_convert(
             "input.gml",
             "output.geojson",
-            input_driver_name,
-            output_driver_name,
+            Some(input_driver_name),
+            Some(output_driver_name),
             "geometry",
             None,
+            &ConvertOptions::default(),
         )
         .await;
         assert!(result.is_err());
@@ -455,10 +1206,11 @@ mod tests {
         let result = handle_convert(
             "input.csv",
             "output.geojson",
-            input_driver_name,
-            output_driver_name,
+            Some(input_driver_name),
+            Some(output_driver_name),
             "geometry",
             None,
+            &ConvertOptions::default(),
         )
         .await;
         assert!(result.is_err());
@@ -478,10 +1230,11 @@ mod tests {
         let result = handle_convert(
             "input.csv",
             "output.gml",
-            input_driver_name,
-            output_driver_name,
+            Some(input_driver_name),
+            Some(output_driver_name),
             "geometry",
             None,
+            &ConvertOptions::default(),
         )
         .await;
         assert!(result.is_err());
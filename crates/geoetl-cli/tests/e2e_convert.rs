@@ -352,3 +352,770 @@ fn test_cli_convert_geojson_output_format() {
         json_result.err()
     );
 }
+
+#[test]
+fn test_cli_convert_with_creation_options() {
+    let temp_dir = TempDir::new().unwrap();
+    let output_path = temp_dir.path().join("output.csv");
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--input-driver")
+        .arg("GeoJSON")
+        .arg("--output-driver")
+        .arg("CSV")
+        .arg("--lco")
+        .arg("SEPARATOR=;")
+        .arg("--lco")
+        .arg("header=no")
+        .assert()
+        .success();
+
+    let output_content = fs::read_to_string(&output_path).unwrap();
+    let first_line = output_content.lines().next().unwrap();
+    assert_eq!(first_line, "Vatican City;POINT(12.4533865 41.9032822)");
+}
+
+#[test]
+fn test_cli_convert_with_open_options() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("input.csv");
+    let output_path = temp_dir.path().join("output.geojson");
+    fs::write(&input_path, "1;POINT(1 2)\n2;POINT(3 4)\n").unwrap();
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(&input_path)
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--input-driver")
+        .arg("CSV")
+        .arg("--output-driver")
+        .arg("GeoJSON")
+        .arg("--oo")
+        .arg("SEPARATOR=;")
+        .arg("--oo")
+        .arg("HEADER=NO")
+        .arg("--oo")
+        .arg("GEOMETRY_COLUMN=column_1")
+        .assert()
+        .success();
+
+    let output: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+    let features = output["features"].as_array().unwrap();
+    assert_eq!(features.len(), 2);
+    assert_eq!(features[1]["geometry"]["coordinates"][1], 4.0);
+    assert_eq!(features[1]["properties"]["column_0"], 2);
+}
+
+#[test]
+fn test_cli_convert_unknown_option() {
+    let temp_dir = TempDir::new().unwrap();
+    let output_path = temp_dir.path().join("output.csv");
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_CSV)
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--input-driver")
+        .arg("CSV")
+        .arg("--output-driver")
+        .arg("CSV")
+        .arg("--lco")
+        .arg("COLOUR=red")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown option 'COLOUR'"));
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_CSV)
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--input-driver")
+        .arg("CSV")
+        .arg("--output-driver")
+        .arg("CSV")
+        .arg("--oo")
+        .arg("HEADER")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected KEY=VALUE"));
+}
+
+#[test]
+fn test_cli_convert_detects_drivers() {
+    let temp_dir = TempDir::new().unwrap();
+    let output_path = temp_dir.path().join("output.csv");
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--output")
+        .arg(&output_path)
+        .assert()
+        .success();
+
+    let output_content = fs::read_to_string(&output_path).unwrap();
+    assert!(output_content.contains("Vatican City"));
+    assert!(output_content.contains("POINT"));
+}
+
+#[test]
+fn test_cli_convert_undetectable_output_driver() {
+    let temp_dir = TempDir::new().unwrap();
+    let output_path = temp_dir.path().join("output.xyz");
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--output")
+        .arg(&output_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Could not detect the format"))
+        .stderr(predicate::str::contains("Name the driver explicitly"));
+}
+
+#[test]
+fn test_cli_convert_driver_mismatch() {
+    let temp_dir = TempDir::new().unwrap();
+    let output_path = temp_dir.path().join("output.csv");
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--input-driver")
+        .arg("CSV")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("its content is GeoJSON"))
+        .stderr(predicate::str::contains("Use the 'GeoJSON' driver"));
+}
+
+#[test]
+fn test_cli_info_detects_driver() {
+    geoetl_cmd()
+        .arg("info")
+        .arg(TEST_DATA_GEOJSON)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("GeoJSON"))
+        .stdout(predicate::str::contains("name"));
+}
+
+#[test]
+fn test_cli_info_stats() {
+    geoetl_cmd()
+        .arg("info")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--stats")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Feature Count:"))
+        .stdout(predicate::str::contains("Extent: ("))
+        .stdout(predicate::str::contains("=== Geometry Types ==="))
+        .stdout(predicate::str::contains("Point"))
+        .stdout(predicate::str::contains("=== Field Statistics ==="));
+}
+
+#[test]
+fn test_cli_info_json_and_yaml() {
+    let output = geoetl_cmd()
+        .arg("info")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--stats")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(document["schema_version"], 1);
+    assert_eq!(document["driver"], "GeoJSON");
+    assert_eq!(
+        document["layers"],
+        serde_json::json!(["natural-earth_cities"])
+    );
+    assert_eq!(document["geometry_columns"][0]["crs"], "EPSG:4326");
+    assert_eq!(document["fields"][0]["name"], "name");
+    assert_eq!(document["fields"][0]["stats"]["null_count"], 0);
+    assert_eq!(document["geometry_types"][0]["type"], "Point");
+    assert!(document["feature_count"].as_u64().unwrap() > 0);
+    assert!(document["extent"]["min_x"].as_f64().unwrap() < 0.0);
+
+    geoetl_cmd()
+        .arg("info")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--format")
+        .arg("YAML")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("schema_version: 1\n"))
+        .stdout(predicate::str::contains("driver: GeoJSON"))
+        .stdout(predicate::str::contains("stats: null"));
+}
+
+#[test]
+fn test_cli_layer_selection() {
+    geoetl_cmd()
+        .arg("info")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--layer")
+        .arg("Natural-Earth_Cities")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Layer: natural-earth_cities"));
+
+    geoetl_cmd()
+        .arg("info")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--layer")
+        .arg("rivers")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("layer 'rivers' not found"))
+        .stderr(predicate::str::contains("natural-earth_cities"));
+
+    geoetl_cmd()
+        .arg("validate")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--layer")
+        .arg("natural-earth_cities")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Layer: natural-earth_cities"));
+    geoetl_cmd()
+        .arg("validate")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--layer")
+        .arg("rivers")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("layer 'rivers' not found"));
+
+    let temp_dir = TempDir::new().unwrap();
+    let output_path = temp_dir.path().join("cities.csv");
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--layer")
+        .arg("natural-earth_cities")
+        .assert()
+        .success();
+    assert!(output_path.is_file());
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--output")
+        .arg(temp_dir.path().join("rivers.csv"))
+        .arg("--layer")
+        .arg("rivers")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("layer 'rivers' not found"));
+}
+
+#[test]
+fn test_cli_convert_with_where_select_and_rename() {
+    let temp_dir = TempDir::new().unwrap();
+    let output_path = temp_dir.path().join("output.geojson");
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--where")
+        .arg("name LIKE 'San %'")
+        .arg("--select")
+        .arg("geometry,name")
+        .arg("--rename")
+        .arg("name=city")
+        .assert()
+        .success();
+
+    let output: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+    let features = output["features"].as_array().unwrap();
+    assert!(!features.is_empty());
+    for feature in features {
+        assert!(
+            feature["properties"]["city"]
+                .as_str()
+                .unwrap()
+                .starts_with("San ")
+        );
+        assert!(feature["properties"].get("name").is_none());
+    }
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--rename")
+        .arg("name")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected OLD=NEW"));
+}
+
+#[test]
+fn test_cli_convert_with_t_srs() {
+    let temp_dir = TempDir::new().unwrap();
+    let output_path = temp_dir.path().join("mercator.geojson");
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--t-srs")
+        .arg("EPSG:3857")
+        .assert()
+        .success();
+
+    let output: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+    let features = output["features"].as_array().unwrap();
+    assert!(!features.is_empty());
+    for feature in features {
+        // Web Mercator coordinates are in metres, far outside the range of degrees
+        let x = feature["geometry"]["coordinates"][0].as_f64().unwrap();
+        assert!(x.abs() > 180.0, "{feature}");
+    }
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--t-srs")
+        .arg("EPSG:9999")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("t-srs"));
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--s-srs")
+        .arg("EPSG:4326")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_convert_with_a_srs() {
+    let temp_dir = TempDir::new().unwrap();
+    let output_path = temp_dir.path().join("mercator.geojson");
+
+    // Assigning WGS 84 to CSV input lets it be reprojected
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_CSV)
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--geometry-column")
+        .arg("geometry")
+        .arg("--a-srs")
+        .arg("EPSG:4326")
+        .arg("--t-srs")
+        .arg("EPSG:3857")
+        .assert()
+        .success();
+    assert!(output_path.exists());
+
+    // GeoJSON records its own CRS
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--output")
+        .arg(temp_dir.path().join("out.csv"))
+        .arg("--a-srs")
+        .arg("EPSG:3857")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("a-srs"));
+}
+
+#[test]
+fn test_cli_convert_with_bbox_and_clip_src() {
+    let temp_dir = TempDir::new().unwrap();
+    let output_path = temp_dir.path().join("europe.geojson");
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--bbox")
+        .arg("-10,35,30,60")
+        .assert()
+        .success();
+    let output = std::fs::read_to_string(&output_path).unwrap();
+    assert!(output.contains("Paris"));
+    assert!(output.contains("Berlin"));
+    assert!(!output.contains("Tokyo"));
+
+    geoetl_cmd()
+        .arg("info")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--spatial-filter")
+        .arg("POLYGON((-10 35, 30 35, 30 60, -10 60, -10 35))")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Feature Count:"));
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--output")
+        .arg(temp_dir.path().join("clipped.geojson"))
+        .arg("--clip-src")
+        .arg("-10,35,30,60")
+        .assert()
+        .success();
+
+    // Only one spatial filter can be given, and it must be valid
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--output")
+        .arg(temp_dir.path().join("out.geojson"))
+        .arg("--bbox")
+        .arg("-10,35,30,60")
+        .arg("--clip-src")
+        .arg("-10,35,30,60")
+        .assert()
+        .failure();
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("--output")
+        .arg(temp_dir.path().join("out.geojson"))
+        .arg("--bbox")
+        .arg("30,35,-10,60")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("spatial filter"));
+}
+
+#[test]
+fn test_cli_validate_and_make_valid() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("bowtie.geojson");
+    let output_path = temp_dir.path().join("repaired.geojson");
+    std::fs::write(
+        &input_path,
+        r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "bowtie"},
+             "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [2, 2], [2, 0], [0, 2], [0, 0]]]}}
+        ]}"#,
+    )
+    .unwrap();
+
+    geoetl_cmd()
+        .arg("validate")
+        .arg(&input_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("self-intersection"))
+        .stdout(predicate::str::contains("POINT(1 1)"))
+        .stdout(predicate::str::contains("1 invalid of 1 features checked"));
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(&input_path)
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--make-valid")
+        .assert()
+        .success();
+
+    geoetl_cmd()
+        .arg("validate")
+        .arg(&output_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0 invalid of 1 features checked"));
+}
+
+#[test]
+fn test_cli_convert_simplify_and_precision() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("road.geojson");
+    let output_path = temp_dir.path().join("web.geojson");
+    std::fs::write(
+        &input_path,
+        r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "road"},
+             "geometry": {"type": "LineString", "coordinates": [
+                [0.123456789, 0.0], [1.0, 0.000012345], [2.0, 0.0], [2.0, 1.987654321]]}}
+        ]}"#,
+    )
+    .unwrap();
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(&input_path)
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--simplify")
+        .arg("0.001")
+        .arg("--simplify-method")
+        .arg("visvalingam")
+        .arg("--lco")
+        .arg("COORDINATE_PRECISION=2")
+        .assert()
+        .success();
+    let mut output = fs::read_to_string(&output_path).unwrap();
+    output.retain(|c| !c.is_whitespace());
+    assert!(
+        output.contains(r#""coordinates":[[0.12,0.0],[2.0,0.0],[2.0,1.99]]"#),
+        "{output}"
+    );
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(&input_path)
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--snap-to-grid")
+        .arg("0")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("snap-to-grid"));
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(&input_path)
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--simplify-method")
+        .arg("visvalingam")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_convert_explode_and_nlt() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("islands.geojson");
+    let exploded_path = temp_dir.path().join("exploded.geojson");
+    let multi_path = temp_dir.path().join("multi.geojson");
+    std::fs::write(
+        &input_path,
+        r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "archipelago"},
+             "geometry": {"type": "MultiPolygon", "coordinates": [
+                [[[0, 0], [1, 0], [1, 1], [0, 0]]], [[[5, 5], [6, 5], [6, 6], [5, 5]]]]}},
+            {"type": "Feature", "properties": {"name": "island"},
+             "geometry": {"type": "Polygon", "coordinates": [[[9, 9], [10, 9], [10, 10], [9, 9]]]}}
+        ]}"#,
+    )
+    .unwrap();
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(&input_path)
+        .arg("--output")
+        .arg(&exploded_path)
+        .arg("--explode")
+        .assert()
+        .success();
+    let exploded: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&exploded_path).unwrap()).unwrap();
+    let features = exploded["features"].as_array().unwrap();
+    let names: Vec<_> = features
+        .iter()
+        .map(|f| f["properties"]["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["archipelago", "archipelago", "island"]);
+    assert!(features.iter().all(|f| f["geometry"]["type"] == "Polygon"));
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(&exploded_path)
+        .arg("--output")
+        .arg(&multi_path)
+        .arg("--nlt")
+        .arg("MultiPolygon")
+        .arg("--dim")
+        .arg("XYZ")
+        .assert()
+        .success();
+    let multi: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&multi_path).unwrap()).unwrap();
+    let geometry = &multi["features"][2]["geometry"];
+    assert_eq!(geometry["type"], "MultiPolygon");
+    assert_eq!(
+        geometry["coordinates"][0][0][0],
+        serde_json::json!([9.0, 9.0, 0.0])
+    );
+
+    geoetl_cmd()
+        .arg("convert")
+        .arg("--input")
+        .arg(&input_path)
+        .arg("--output")
+        .arg(temp_dir.path().join("points.geojson"))
+        .arg("--nlt")
+        .arg("Point")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "1 MultiPolygon, 1 Polygon in 2 features",
+        ))
+        .stderr(predicate::str::contains(
+            "feature 0 (geometry): MultiPolygon",
+        ));
+    assert!(!temp_dir.path().join("points.geojson").exists());
+}
+
+#[test]
+fn test_cli_drivers_lists_options() {
+    geoetl_cmd()
+        .arg("drivers")
+        .arg("geojson")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Open Options (--oo)"))
+        .stdout(predicate::str::contains("NESTED_TYPES"))
+        .stdout(predicate::str::contains("Creation Options (--lco)"))
+        .stdout(predicate::str::contains("WRITE_BBOX"));
+
+    geoetl_cmd()
+        .arg("drivers")
+        .arg("NoSuchDriver")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not found"));
+}
+
+#[test]
+fn test_cli_drivers_json_and_yaml() {
+    let output = geoetl_cmd()
+        .arg("drivers")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(document["schema_version"], 1);
+    let drivers = document["drivers"].as_array().unwrap();
+    assert!(drivers.iter().any(|driver| {
+        driver["short_name"] == "GeoJSON" && driver["capabilities"]["write"] == "supported"
+    }));
+
+    geoetl_cmd()
+        .arg("drivers")
+        .arg("CSV")
+        .arg("--format")
+        .arg("yaml")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("schema_version: 1\n"))
+        .stdout(predicate::str::contains("short_name: CSV"))
+        .stdout(predicate::str::contains("- name: GEOMETRY_COLUMN"));
+}
+
+#[test]
+fn test_cli_skips_invalid_plugin() {
+    let temp_dir = TempDir::new().unwrap();
+    let plugin = temp_dir
+        .path()
+        .join(format!("broken.{}", std::env::consts::DLL_EXTENSION));
+    fs::write(&plugin, b"not a shared library").unwrap();
+
+    geoetl_cmd()
+        .env("GEOETL_PLUGIN_PATH", temp_dir.path())
+        .arg("drivers")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("GeoJSON"))
+        .stderr(predicate::str::contains("Failed to load plugin"));
+}
+
+#[test]
+fn test_cli_convert_write_modes() {
+    let temp_dir = TempDir::new().unwrap();
+    let output_path = temp_dir.path().join("all.csv");
+
+    let convert = |mode: &[&str]| {
+        let mut cmd = geoetl_cmd();
+        cmd.arg("convert")
+            .arg("--input")
+            .arg(TEST_DATA_CSV)
+            .arg("--output")
+            .arg(&output_path)
+            .arg("--input-driver")
+            .arg("CSV")
+            .arg("--output-driver")
+            .arg("CSV")
+            .args(mode);
+        cmd.assert()
+    };
+
+    convert(&["--if-exists", "fail"]).success();
+    convert(&["--append"]).success();
+    let output_content = fs::read_to_string(&output_path).unwrap();
+    assert_eq!(
+        output_content.lines().count(),
+        487,
+        "header + 2 * 243 cities"
+    );
+    assert_eq!(output_content.matches("geometry,name").count(), 1);
+
+    convert(&["--if-exists", "fail"])
+        .failure()
+        .stderr(predicate::str::contains("Output already exists"));
+    convert(&["--append", "--overwrite"])
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    convert(&["--overwrite"]).success();
+    assert_eq!(
+        fs::read_to_string(&output_path).unwrap().lines().count(),
+        244
+    );
+}
@@ -0,0 +1,159 @@
+//! End-to-end CLI tests for the sql command
+//!
+//! These tests register real-world test datasets as tables and verify that
+//! queries are printed or written through an output driver.
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const TEST_DATA_CSV: &str = "tests/e2e_data/csv/natural-earth_cities_native_AS_WKT.csv";
+const TEST_DATA_GEOJSON: &str = "tests/e2e_data/geojson/natural-earth_cities.geojson";
+
+/// Helper to create a command instance for the CLI
+fn geoetl_cmd() -> Command {
+    Command::new(assert_cmd::cargo::cargo_bin!("geoetl-cli"))
+}
+
+#[test]
+fn test_cli_sql_prints_results() {
+    geoetl_cmd()
+        .arg("sql")
+        .arg("--table")
+        .arg(format!("cities={TEST_DATA_GEOJSON}"))
+        .arg("SELECT name, geometry FROM cities WHERE name = 'Vatican City'")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Vatican City"))
+        .stdout(predicate::str::contains("POINT"))
+        .stdout(predicate::str::contains("(1 row)"));
+}
+
+#[test]
+fn test_cli_sql_joins_tables_of_different_formats() {
+    geoetl_cmd()
+        .arg("sql")
+        .arg("-t")
+        .arg(format!("gj={TEST_DATA_GEOJSON}"))
+        .arg("-t")
+        .arg(format!("wkt={TEST_DATA_CSV}"))
+        .arg("SELECT count(*) AS matches FROM gj JOIN wkt ON gj.name = wkt.name")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("matches"))
+        .stdout(predicate::str::contains("243"));
+}
+
+#[test]
+fn test_cli_sql_writes_output() {
+    let temp_dir = TempDir::new().unwrap();
+    let output_path = temp_dir.path().join("output.geojson");
+
+    geoetl_cmd()
+        .arg("sql")
+        .arg("--table")
+        .arg(format!("cities={TEST_DATA_GEOJSON}"))
+        .arg("--output")
+        .arg(&output_path)
+        .arg("SELECT name, geometry FROM cities WHERE name LIKE 'San%' ORDER BY name")
+        .assert()
+        .success();
+
+    let output: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+    let features = output["features"].as_array().unwrap();
+    assert!(!features.is_empty());
+    assert_eq!(features[0]["geometry"]["type"], "Point");
+    assert!(
+        features
+            .iter()
+            .all(|f| f["properties"]["name"].as_str().unwrap().starts_with("San"))
+    );
+}
+
+#[test]
+fn test_cli_sql_write_modes() {
+    let temp_dir = TempDir::new().unwrap();
+    let output_path = temp_dir.path().join("san.geojson");
+
+    let sql = |mode: &[&str]| {
+        let mut cmd = geoetl_cmd();
+        cmd.arg("sql")
+            .arg("--table")
+            .arg(format!("cities={TEST_DATA_GEOJSON}"))
+            .arg("--output")
+            .arg(&output_path)
+            .args(mode)
+            .arg("SELECT name, geometry FROM cities WHERE name LIKE 'San%'");
+        cmd.assert()
+    };
+    let feature_count = || {
+        let output: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+        output["features"].as_array().unwrap().len()
+    };
+
+    sql(&["--if-exists", "fail"]).success();
+    let written = feature_count();
+    sql(&["--append"]).success();
+    assert_eq!(feature_count(), 2 * written);
+
+    sql(&["--if-exists", "fail"])
+        .failure()
+        .stderr(predicate::str::contains("Output already exists"));
+    sql(&["--overwrite"]).success();
+    assert_eq!(feature_count(), written);
+
+    // The write mode only applies to an output
+    geoetl_cmd()
+        .arg("sql")
+        .arg("--table")
+        .arg(format!("cities={TEST_DATA_GEOJSON}"))
+        .arg("--append")
+        .arg("SELECT 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--output"));
+}
+
+#[test]
+fn test_cli_sql_invalid_table_argument() {
+    geoetl_cmd()
+        .arg("sql")
+        .arg("--table")
+        .arg(TEST_DATA_GEOJSON)
+        .arg("SELECT 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected NAME=PATH"));
+}
+
+#[test]
+fn test_cli_sql_invalid_query() {
+    geoetl_cmd()
+        .arg("sql")
+        .arg("--table")
+        .arg(format!("cities={TEST_DATA_GEOJSON}"))
+        .arg("SELECT missing_column FROM cities")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Query error"));
+}
+
+#[test]
+fn test_cli_sql_spatial_functions() {
+    geoetl_cmd()
+        .arg("sql")
+        .arg("--table")
+        .arg(format!("cities={TEST_DATA_GEOJSON}"))
+        .arg(
+            "SELECT name, ST_AsText(ST_Buffer(geometry, 1.0)) LIKE '%POLYGON%' AS buffered \
+             FROM cities WHERE ST_Intersects(geometry, ST_GeomFromText('POINT(12.4533 41.9033)')) \
+             OR ST_Distance(geometry, 'POINT(12.4533 41.9033)') < 0.01",
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Vatican City"))
+        .stdout(predicate::str::contains("true"));
+}
@@ -1,15 +1,21 @@
-//! Driver registry for geospatial data format support and capabilities.
+//! Driver metadata for geospatial data format support and capabilities.
 //!
-//! This module provides a static registry of geospatial data format drivers, including
-//! their current support status (supported, planned, or not supported) for various operations
-//! (info, read, write). The registry is modeled after GDAL's driver system but designed for
-//! modern Rust-based ETL workflows.
+//! This module defines the driver description shared by the runtime registry
+//! ([`crate::DriverRegistry`]) and the informational catalog of known formats in
+//! `geoetl-core`: a short and long name plus the support status (supported, planned, or
+//! not supported) of each operation (info, read, write). The model follows GDAL's driver
+//! system but is designed for modern Rust-based ETL workflows.
+
+use serde::Serialize;
 
 /// Support status for a specific driver operation.
 ///
 /// Indicates whether a driver operation (info, read, or write) is currently supported,
 /// planned for future implementation, or not supported at all.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Serialises as `"supported"`, `"not_supported"` or `"planned"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SupportStatus {
     /// The feature is fully supported and implemented.
     Supported,
@@ -21,18 +27,50 @@ pub enum SupportStatus {
 
 impl SupportStatus {
     /// Returns `true` if the operation is fully supported and implemented.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geoetl_core_common::drivers::SupportStatus;
+    ///
+    /// assert!(SupportStatus::Supported.is_supported());
+    /// assert!(!SupportStatus::Planned.is_supported());
+    /// assert!(!SupportStatus::NotSupported.is_supported());
+    /// ```
     #[must_use]
     pub fn is_supported(&self) -> bool {
         matches!(self, SupportStatus::Supported)
     }
 
     /// Returns `true` if the operation is supported or planned (i.e., not explicitly unsupported).
+    ///
+    /// This is useful for filtering drivers that have current or future support.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geoetl_core_common::drivers::SupportStatus;
+    ///
+    /// assert!(SupportStatus::Supported.is_available());
+    /// assert!(SupportStatus::Planned.is_available());
+    /// assert!(!SupportStatus::NotSupported.is_available());
+    /// ```
     #[must_use]
     pub fn is_available(&self) -> bool {
         !matches!(self, SupportStatus::NotSupported)
     }
 
     /// Returns the string representation of this support status.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geoetl_core_common::drivers::SupportStatus;
+    ///
+    /// assert_eq!(SupportStatus::Supported.as_str(), "Supported");
+    /// assert_eq!(SupportStatus::Planned.as_str(), "Planned");
+    /// assert_eq!(SupportStatus::NotSupported.as_str(), "Not Supported");
+    /// ```
     #[must_use]
     pub fn as_str(&self) -> &str {
         match self {
@@ -48,7 +86,7 @@ impl SupportStatus {
 /// Each driver can support three types of operations: reading metadata (info),
 /// reading data (read), and writing data (write). Each capability has an associated
 /// [`SupportStatus`] indicating its current implementation status.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize)]
 pub struct DriverCapabilities {
     /// Support status for reading dataset metadata and information.
     pub info: SupportStatus,
@@ -60,12 +98,41 @@ pub struct DriverCapabilities {
 
 impl DriverCapabilities {
     /// Returns `true` if at least one operation is supported or planned.
+    ///
+    /// This is useful for identifying drivers that have any level of functionality,
+    /// either current or planned for the future.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geoetl_core_common::drivers::{DriverCapabilities, SupportStatus};
+    ///
+    /// let caps = DriverCapabilities {
+    ///     info: SupportStatus::Planned,
+    ///     read: SupportStatus::NotSupported,
+    ///     write: SupportStatus::NotSupported,
+    /// };
+    /// assert!(caps.has_any_support());
+    /// ```
     #[must_use]
     pub fn has_any_support(&self) -> bool {
         self.info.is_available() || self.read.is_available() || self.write.is_available()
     }
 
     /// Returns `true` if at least one operation is fully supported and implemented.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geoetl_core_common::drivers::{DriverCapabilities, SupportStatus};
+    ///
+    /// let caps = DriverCapabilities {
+    ///     info: SupportStatus::Supported,
+    ///     read: SupportStatus::Supported,
+    ///     write: SupportStatus::Planned,
+    /// };
+    /// assert!(caps.has_supported_operation());
+    /// ```
     #[must_use]
     pub fn has_supported_operation(&self) -> bool {
         self.info.is_supported() || self.read.is_supported() || self.write.is_supported()
@@ -77,7 +144,24 @@ impl DriverCapabilities {
 /// A driver represents support for a specific geospatial data format (e.g., `GeoJSON`, `Shapefile`).
 /// Each driver has a short name (used in the CLI), a descriptive long name, and a set of
 /// capabilities indicating what operations are supported.
-#[derive(Debug, Clone)]
+///
+/// # Examples
+///
+/// ```
+/// use geoetl_core_common::drivers::{Driver, SupportStatus};
+///
+/// let driver = Driver::new(
+///     "GeoJSON",
+///     "GeoJSON",
+///     SupportStatus::Supported,
+///     SupportStatus::Supported,
+///     SupportStatus::Supported,
+/// );
+///
+/// assert_eq!(driver.short_name, "GeoJSON");
+/// assert!(driver.capabilities.read.is_supported());
+/// ```
+#[derive(Debug, Clone, Serialize)]
 pub struct Driver {
     /// Short name used in the CLI and for driver identification (e.g., `"GeoJSON"`).
     pub short_name: &'static str,
@@ -89,6 +173,20 @@ pub struct Driver {
 
 impl Driver {
     /// Creates a new driver definition with specified capabilities.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geoetl_core_common::drivers::{Driver, SupportStatus};
+    ///
+    /// let driver = Driver::new(
+    ///     "CSV",
+    ///     "Comma Separated Value",
+    ///     SupportStatus::Planned,
+    ///     SupportStatus::Planned,
+    ///     SupportStatus::Planned,
+    /// );
+    /// ```
     #[must_use]
     pub const fn new(
         short_name: &'static str,
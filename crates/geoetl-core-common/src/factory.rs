@@ -3,17 +3,27 @@
 //! This module implements the Abstract Factory pattern to decouple the driver registry
 //! from concrete format implementations, eliminating hard-coded dependencies.
 
-use crate::drivers::Driver;
+use crate::drivers::{Driver, SupportStatus};
 use crate::io::{DataReader, DataWriter};
+use crate::options::{FormatOption, OptionValues};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::{Arc, RwLock};
 
-/// Marker trait for format-specific options.
+/// Number of leading bytes of a dataset handed to [`FormatFactory::probe`].
+pub const PROBE_HEADER_LEN: usize = 4096;
+
+/// Format-specific options that can be built from string key/value options.
 ///
-/// Format implementations should define their own option types and implement this trait.
-pub trait FormatOptions: Send + Sync + 'static {
-    /// Converts options to a boxed Any for dynamic dispatch.
-    fn as_any(&self) -> Box<dyn std::any::Any + Send>;
+/// Format implementations define their own typed option structs and implement this trait
+/// so readers and writers can be configured generically (e.g., from the CLI).
+pub trait FormatOptions: Sized + Send + Sync + 'static {
+    /// Builds typed options from key/value options, starting from the defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a value cannot be interpreted.
+    fn from_option_values(values: &OptionValues) -> anyhow::Result<Self>;
 }
 
 /// Factory trait for creating format-specific readers and writers.
@@ -33,6 +43,29 @@ pub trait FormatFactory: Send + Sync {
     ///
     /// Returns `None` if writing is not supported by this format.
     fn create_writer(&self) -> Option<Arc<dyn DataWriter>>;
+
+    /// Returns the open options understood by this format's reader.
+    fn open_options(&self) -> Vec<FormatOption> {
+        Vec::new()
+    }
+
+    /// Returns the creation options understood by this format's writer.
+    fn creation_options(&self) -> Vec<FormatOption> {
+        Vec::new()
+    }
+
+    /// Returns the file extensions of this format, lowercase and without the leading dot.
+    fn extensions(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// Returns `true` if `header` starts a dataset of this format.
+    ///
+    /// `header` holds at most [`PROBE_HEADER_LEN`] leading bytes of the dataset. Formats
+    /// without a recognisable signature keep the default and are only detected by extension.
+    fn probe(&self, _header: &[u8]) -> bool {
+        false
+    }
 }
 
 /// Global registry of format factories.
@@ -61,14 +94,31 @@ impl DriverRegistry {
     ///
     /// Panics if a factory with the same driver name is already registered.
     pub fn register(&self, factory: Arc<dyn FormatFactory>) {
-        let driver = factory.driver();
-        let name = driver.short_name.to_string();
+        if let Err(e) = self.try_register(factory) {
+            panic!("{e}");
+        }
+    }
+
+    /// Registers a format factory unless its driver name is already taken.
+    ///
+    /// Names are compared case-insensitively, as in [`DriverRegistry::find_factory`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a factory with the same driver name is already registered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned (another thread panicked while holding the lock).
+    pub fn try_register(&self, factory: Arc<dyn FormatFactory>) -> anyhow::Result<()> {
+        let name = factory.driver().short_name.to_string();
 
         let mut factories = self.factories.write().unwrap();
-        assert!(
-            factories.insert(name.clone(), factory).is_none(),
-            "Driver '{name}' is already registered"
-        );
+        if factories.keys().any(|key| key.eq_ignore_ascii_case(&name)) {
+            anyhow::bail!("Driver '{name}' is already registered");
+        }
+        factories.insert(name, factory);
+        Ok(())
     }
 
     /// Finds a factory by driver name (case-insensitive).
@@ -92,16 +142,82 @@ impl DriverRegistry {
             .map(|(_, factory)| Arc::clone(factory))
     }
 
-    /// Returns all registered drivers.
+    /// Finds a registered driver by name (case-insensitive).
     ///
-    /// This includes only drivers that have been registered via format factories.
+    /// The returned capabilities reflect what the factory actually provides; see
+    /// [`DriverRegistry::get_available_drivers`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned (another thread panicked while holding the lock).
+    pub fn find_driver(&self, name: &str) -> Option<Driver> {
+        self.find_factory(name)
+            .map(|factory| registered_driver(factory.as_ref()))
+    }
+
+    /// Returns all registered drivers, sorted by short name.
+    ///
+    /// This includes only drivers that have been registered via format factories. Read and
+    /// write capabilities are derived from whether the factory provides a reader or writer,
+    /// and info is available whenever reading is.
     ///
     /// # Panics
     ///
     /// Panics if the lock is poisoned (another thread panicked while holding the lock).
     pub fn get_available_drivers(&self) -> Vec<Driver> {
         let factories = self.factories.read().unwrap();
-        factories.values().map(|factory| factory.driver()).collect()
+        let mut drivers: Vec<Driver> = factories
+            .values()
+            .map(|factory| registered_driver(factory.as_ref()))
+            .collect();
+        drivers.sort_unstable_by_key(|driver| driver.short_name);
+        drivers
+    }
+
+    /// Detects the format of a dataset from its leading bytes.
+    ///
+    /// Factories are probed in driver name order and the first one recognising `header` wins.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned (another thread panicked while holding the lock).
+    pub fn detect_from_content(&self, header: &[u8]) -> Option<Arc<dyn FormatFactory>> {
+        if header.is_empty() {
+            return None;
+        }
+        self.sorted_factories()
+            .into_iter()
+            .find(|factory| factory.probe(header))
+    }
+
+    /// Detects the format of a dataset from the extension of its path (case-insensitive).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned (another thread panicked while holding the lock).
+    pub fn detect_from_extension(&self, path: &str) -> Option<Arc<dyn FormatFactory>> {
+        let extension = Path::new(path).extension()?.to_str()?;
+        self.sorted_factories().into_iter().find(|factory| {
+            factory
+                .extensions()
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(extension))
+        })
+    }
+
+    /// Detects the format of a dataset, preferring its content over its extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the dataset
+    /// * `header` - Leading bytes of the dataset; empty if it does not exist yet
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned (another thread panicked while holding the lock).
+    pub fn detect(&self, path: &str, header: &[u8]) -> Option<Arc<dyn FormatFactory>> {
+        self.detect_from_content(header)
+            .or_else(|| self.detect_from_extension(path))
     }
 
     /// Returns all registered driver names in sorted order.
@@ -115,6 +231,38 @@ impl DriverRegistry {
         names.sort_unstable();
         names
     }
+
+    /// Returns the registered factories sorted by driver name, so detection is deterministic.
+    fn sorted_factories(&self) -> Vec<Arc<dyn FormatFactory>> {
+        let factories = self.factories.read().unwrap();
+        let mut sorted: Vec<(&String, &Arc<dyn FormatFactory>)> = factories.iter().collect();
+        sorted.sort_unstable_by_key(|(name, _)| *name);
+        sorted
+            .into_iter()
+            .map(|(_, factory)| Arc::clone(factory))
+            .collect()
+    }
+}
+
+/// Builds the runtime view of a factory's driver.
+///
+/// Capabilities are derived from the reader and writer the factory actually creates, so a
+/// factory cannot advertise an operation it cannot perform.
+fn registered_driver(factory: &dyn FormatFactory) -> Driver {
+    let mut driver = factory.driver();
+    let status = |available: bool| {
+        if available {
+            SupportStatus::Supported
+        } else {
+            SupportStatus::NotSupported
+        }
+    };
+
+    let can_read = factory.create_reader().is_some();
+    driver.capabilities.read = status(can_read);
+    driver.capabilities.info = status(can_read);
+    driver.capabilities.write = status(factory.create_writer().is_some());
+    driver
 }
 
 /// Returns the global driver registry instance.
@@ -7,22 +7,69 @@ use anyhow::Result;
 use async_trait::async_trait;
 use datafusion::datasource::TableProvider;
 use datafusion::execution::context::SessionState;
+use datafusion::logical_expr::dml::InsertOp;
 use datafusion::physical_plan::ExecutionPlan;
+use std::path::Path;
 use std::sync::Arc;
 
+use crate::options::OptionValues;
+
+/// Name of the only layer of a single-layer dataset: the file name without its extension
+/// (e.g., `cities` for `data/cities.geojson`).
+#[must_use]
+pub fn default_layer_name(path: &str) -> String {
+    Path::new(path.trim_end_matches('/'))
+        .file_stem()
+        .map_or_else(
+            || path.to_string(),
+            |stem| stem.to_string_lossy().into_owned(),
+        )
+}
+
+/// What a write does when its output already exists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WriteMode {
+    /// Replace the output
+    #[default]
+    Overwrite,
+    /// Add the features to the output, whose columns must match the written ones
+    Append,
+    /// Fail without writing anything
+    ///
+    /// Writers create the output in the same operation that checks it does not exist, so
+    /// that a concurrent writer cannot create it in between.
+    Fail,
+}
+
+impl WriteMode {
+    /// The `DataFusion` insert operation the mode writes with; `Fail` writes like
+    /// `Overwrite` once the output is known not to exist
+    #[must_use]
+    pub fn insert_op(self) -> InsertOp {
+        match self {
+            Self::Append => InsertOp::Append,
+            Self::Overwrite | Self::Fail => InsertOp::Overwrite,
+        }
+    }
+}
+
 /// Trait for reading data from a geospatial format.
 ///
 /// Implementations create `DataFusion` `TableProvider` instances that can be
-/// queried using SQL or the `DataFrame` API.
+/// queried using SQL or the `DataFrame` API. A dataset holds one or more named layers,
+/// each read as its own table.
 #[async_trait]
 pub trait DataReader: Send + Sync {
     /// Creates a table provider for the given file path.
     ///
+    /// Multi-layer formats open the first layer listed by [`DataReader::layers`].
+    ///
     /// # Arguments
     ///
     /// * `state` - The `DataFusion` session state
     /// * `path` - Path to the data file
-    /// * `options` - Format-specific options (as dynamic trait object)
+    /// * `options` - Open options, already validated against the factory's
+    ///   [`crate::FormatFactory::open_options`]
     ///
     /// # Returns
     ///
@@ -31,8 +78,35 @@ pub trait DataReader: Send + Sync {
         &self,
         state: &SessionState,
         path: &str,
-        options: Box<dyn std::any::Any + Send>,
+        options: &OptionValues,
     ) -> Result<Arc<dyn TableProvider>>;
+
+    /// Lists the layers of the dataset at `path`, in dataset order.
+    ///
+    /// The default, for single-layer formats, is one layer named by
+    /// [`default_layer_name`].
+    async fn layers(
+        &self,
+        _state: &SessionState,
+        path: &str,
+        _options: &OptionValues,
+    ) -> Result<Vec<String>> {
+        Ok(vec![default_layer_name(path)])
+    }
+
+    /// Creates a table provider for one layer of the dataset at `path`.
+    ///
+    /// `layer` is one of the names listed by [`DataReader::layers`]. The default, for
+    /// single-layer formats, opens the dataset with [`DataReader::create_table_provider`].
+    async fn create_layer_provider(
+        &self,
+        state: &SessionState,
+        path: &str,
+        _layer: &str,
+        options: &OptionValues,
+    ) -> Result<Arc<dyn TableProvider>> {
+        self.create_table_provider(state, path, options).await
+    }
 }
 
 /// Trait for writing data to a geospatial format.
@@ -41,12 +115,19 @@ pub trait DataReader: Send + Sync {
 #[async_trait]
 pub trait DataWriter: Send + Sync {
     /// Creates an execution plan to write data.
-    ///``
+    ///
+    /// Executing the plan writes every input row to `path` and yields a single batch with a
+    /// `count` column holding the number of rows written.
+    ///
     /// # Arguments
     ///
     /// * `input` - The input execution plan providing data
     /// * `path` - Output file path
-    /// * `options` - Format-specific options (as dynamic trait object)
+    /// * `mode` - `WriteMode::Overwrite` replaces an existing file, `WriteMode::Append` adds
+    ///   the rows to it after checking its schema and `WriteMode::Fail` fails if it exists;
+    ///   formats reject modes they do not support when the plan is created or executed
+    /// * `options` - Creation options, already validated against the factory's
+    ///   [`crate::FormatFactory::creation_options`]
     ///
     /// # Returns
     ///
@@ -55,6 +136,31 @@ pub trait DataWriter: Send + Sync {
         &self,
         input: Arc<dyn ExecutionPlan>,
         path: &str,
-        options: Box<dyn std::any::Any + Send>,
+        mode: WriteMode,
+        options: &OptionValues,
     ) -> Result<Arc<dyn ExecutionPlan>>;
+
+    /// Returns `true` if the format stores several named layers in one dataset.
+    ///
+    /// Layers written to formats that do not are each written to their own file.
+    fn supports_layers(&self) -> bool {
+        false
+    }
+
+    /// Creates an execution plan to write data as layer `layer` of the dataset at `path`.
+    ///
+    /// The plans of the layers of one dataset are executed in turn, each adding its layer
+    /// to the dataset; `mode` applies to the layer. The default, for
+    /// single-layer formats, ignores the layer name and writes `path` with
+    /// [`DataWriter::create_writer_plan`].
+    async fn create_layer_writer_plan(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        path: &str,
+        _layer: &str,
+        mode: WriteMode,
+        options: &OptionValues,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.create_writer_plan(input, path, mode, options).await
+    }
 }
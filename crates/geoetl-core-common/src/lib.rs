@@ -6,8 +6,13 @@
 pub mod drivers;
 pub mod factory;
 pub mod io;
+pub mod options;
+pub mod plugin;
 
 // Re-export commonly used types
 pub use drivers::{Driver, DriverCapabilities, SupportStatus};
-pub use factory::{DriverRegistry, FormatFactory, FormatOptions, driver_registry};
-pub use io::{DataReader, DataWriter};
+pub use factory::{
+    DriverRegistry, FormatFactory, FormatOptions, PROBE_HEADER_LEN, driver_registry,
+};
+pub use io::{DataReader, DataWriter, WriteMode, default_layer_name};
+pub use options::{FormatOption, OptionType, OptionValues};
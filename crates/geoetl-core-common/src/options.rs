@@ -0,0 +1,323 @@
+//! Key/value options for format readers and writers.
+//!
+//! Format factories declare the open options understood by their reader and the creation
+//! options understood by their writer as [`FormatOption`] descriptors. Callers pass values as
+//! strings in an [`OptionValues`] map, in the spirit of GDAL's `-oo KEY=VALUE` and
+//! `-lco KEY=VALUE` flags, and each format converts them into its own typed options through
+//! [`crate::FormatOptions`].
+
+use anyhow::{Result, anyhow, bail};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Value type accepted by a format option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OptionType {
+    /// Free-form text.
+    String,
+    /// `YES`/`NO` flag (`TRUE`/`FALSE`, `ON`/`OFF` and `1`/`0` are also accepted).
+    Boolean,
+    /// Non-negative integer.
+    Integer,
+    /// A single character, such as a delimiter. `\t` denotes a tab.
+    Char,
+}
+
+impl OptionType {
+    /// Returns the string representation of this option type.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            OptionType::String => "String",
+            OptionType::Boolean => "Boolean",
+            OptionType::Integer => "Integer",
+            OptionType::Char => "Char",
+        }
+    }
+
+    /// Checks that `value` can be parsed as this type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the expected format if `value` is invalid.
+    pub fn validate(&self, value: &str) -> Result<()> {
+        match self {
+            OptionType::String => Ok(()),
+            OptionType::Boolean => parse_bool(value).map(|_| ()),
+            OptionType::Integer => parse_usize(value).map(|_| ()),
+            OptionType::Char => parse_char(value).map(|_| ()),
+        }
+    }
+}
+
+impl fmt::Display for OptionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Description of an option supported by a format reader or writer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct FormatOption {
+    /// Option name, conventionally upper case (e.g., `"GEOMETRY_COLUMN"`).
+    pub name: &'static str,
+    /// Type of the accepted values.
+    pub option_type: OptionType,
+    /// Value used when the option is not given, if any.
+    pub default: Option<&'static str>,
+    /// Human-readable description.
+    pub description: &'static str,
+}
+
+impl FormatOption {
+    /// Creates an option descriptor without a default value.
+    #[must_use]
+    pub const fn new(
+        name: &'static str,
+        option_type: OptionType,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            option_type,
+            default: None,
+            description,
+        }
+    }
+
+    /// Sets the value used when the option is not given.
+    #[must_use]
+    pub const fn with_default(mut self, default: &'static str) -> Self {
+        self.default = Some(default);
+        self
+    }
+}
+
+/// String key/value options passed to a format reader or writer.
+///
+/// Keys are case-insensitive and stored in upper case.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OptionValues {
+    values: BTreeMap<String, String>,
+}
+
+impl OptionValues {
+    /// Creates an empty set of options.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses `KEY=VALUE` pairs, as given on the command line.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a pair has no `=` or an empty key.
+    pub fn parse<S: AsRef<str>>(pairs: &[S]) -> Result<Self> {
+        let mut options = Self::new();
+        for pair in pairs {
+            let pair = pair.as_ref();
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| anyhow!("expected KEY=VALUE, got '{pair}'"))?;
+            if key.trim().is_empty() {
+                bail!("missing option name in '{pair}'");
+            }
+            options.insert(key.trim(), value);
+        }
+        Ok(options)
+    }
+
+    /// Sets an option, replacing any previous value.
+    pub fn insert(&mut self, key: impl AsRef<str>, value: impl Into<String>) {
+        self.values
+            .insert(key.as_ref().to_ascii_uppercase(), value.into());
+    }
+
+    /// Sets an option, returning the updated set.
+    #[must_use]
+    pub fn with(mut self, key: impl AsRef<str>, value: impl Into<String>) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    /// Sets an option only if it has not been given yet.
+    pub fn insert_default(&mut self, key: impl AsRef<str>, value: impl Into<String>) {
+        self.values
+            .entry(key.as_ref().to_ascii_uppercase())
+            .or_insert_with(|| value.into());
+    }
+
+    /// Returns `true` if the option has been given.
+    #[must_use]
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(&key.to_ascii_uppercase())
+    }
+
+    /// Returns `true` if no options have been given.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Iterates over the options in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Returns the raw value of an option.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values
+            .get(&key.to_ascii_uppercase())
+            .map(String::as_str)
+    }
+
+    /// Returns an option parsed as a boolean.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not a recognised boolean.
+    pub fn get_bool(&self, key: &str) -> Result<Option<bool>> {
+        self.get(key)
+            .map(|value| parse_bool(value).map_err(|e| anyhow!("{key}: {e}")))
+            .transpose()
+    }
+
+    /// Returns an option parsed as a non-negative integer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not a non-negative integer.
+    pub fn get_usize(&self, key: &str) -> Result<Option<usize>> {
+        self.get(key)
+            .map(|value| parse_usize(value).map_err(|e| anyhow!("{key}: {e}")))
+            .transpose()
+    }
+
+    /// Returns an option parsed as a single-byte character.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not a single ASCII character.
+    pub fn get_char(&self, key: &str) -> Result<Option<u8>> {
+        self.get(key)
+            .map(|value| parse_char(value).map_err(|e| anyhow!("{key}: {e}")))
+            .transpose()
+    }
+
+    /// Checks every option against the declared `supported` options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first option that is not supported or whose value does not
+    /// match the declared type.
+    pub fn validate(&self, supported: &[FormatOption]) -> Result<()> {
+        for (key, value) in self.iter() {
+            let Some(option) = supported
+                .iter()
+                .find(|option| option.name.eq_ignore_ascii_case(key))
+            else {
+                let names: Vec<&str> = supported.iter().map(|option| option.name).collect();
+                if names.is_empty() {
+                    bail!("unknown option '{key}' (this driver has no options)");
+                }
+                bail!("unknown option '{key}' (supported: {})", names.join(", "));
+            };
+            option
+                .option_type
+                .validate(value)
+                .map_err(|e| anyhow!("{key}: {e}"))?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_bool(value: &str) -> Result<bool> {
+    match value.trim().to_ascii_uppercase().as_str() {
+        "YES" | "TRUE" | "ON" | "1" => Ok(true),
+        "NO" | "FALSE" | "OFF" | "0" => Ok(false),
+        _ => bail!("expected YES or NO, got '{value}'"),
+    }
+}
+
+fn parse_usize(value: &str) -> Result<usize> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("expected a non-negative integer, got '{value}'"))
+}
+
+fn parse_char(value: &str) -> Result<u8> {
+    match value {
+        "\\t" => Ok(b'\t'),
+        _ if value.len() == 1 && value.is_ascii() => Ok(value.as_bytes()[0]),
+        _ => bail!("expected a single character, got '{value}'"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPTIONS: &[FormatOption] = &[
+        FormatOption::new("HEADER", OptionType::Boolean, "Write a header row").with_default("YES"),
+        FormatOption::new("SEPARATOR", OptionType::Char, "Field separator"),
+        FormatOption::new("BATCH_SIZE", OptionType::Integer, "Rows per batch"),
+    ];
+
+    #[test]
+    fn test_parse_pairs() {
+        let options = OptionValues::parse(&["header=NO", "Separator=;", "NAME=a=b"]).unwrap();
+        assert_eq!(options.get("HEADER"), Some("NO"));
+        assert_eq!(options.get("separator"), Some(";"));
+        assert_eq!(options.get("name"), Some("a=b"));
+
+        assert!(OptionValues::parse(&["HEADER"]).is_err());
+        assert!(OptionValues::parse(&["=YES"]).is_err());
+    }
+
+    #[test]
+    fn test_typed_getters() {
+        let options = OptionValues::new()
+            .with("HEADER", "off")
+            .with("SEPARATOR", "\\t")
+            .with("BATCH_SIZE", "42");
+        assert_eq!(options.get_bool("header").unwrap(), Some(false));
+        assert_eq!(options.get_char("SEPARATOR").unwrap(), Some(b'\t'));
+        assert_eq!(options.get_usize("BATCH_SIZE").unwrap(), Some(42));
+        assert_eq!(options.get_bool("MISSING").unwrap(), None);
+
+        let invalid = OptionValues::new().with("HEADER", "maybe");
+        assert!(invalid.get_bool("HEADER").is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let valid = OptionValues::new()
+            .with("header", "YES")
+            .with("SEPARATOR", ",");
+        assert!(valid.validate(OPTIONS).is_ok());
+
+        let unknown = OptionValues::new().with("COLOUR", "red");
+        let err = unknown.validate(OPTIONS).unwrap_err().to_string();
+        assert!(err.contains("COLOUR"));
+        assert!(err.contains("HEADER, SEPARATOR, BATCH_SIZE"));
+
+        let bad_type = OptionValues::new().with("BATCH_SIZE", "-1");
+        assert!(bad_type.validate(OPTIONS).is_err());
+    }
+
+    #[test]
+    fn test_insert_default_keeps_given_value() {
+        let mut options = OptionValues::new().with("HEADER", "NO");
+        options.insert_default("header", "YES");
+        options.insert_default("SEPARATOR", ";");
+        assert_eq!(options.get("HEADER"), Some("NO"));
+        assert_eq!(options.get("SEPARATOR"), Some(";"));
+    }
+}
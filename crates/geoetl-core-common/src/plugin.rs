@@ -0,0 +1,538 @@
+//! C ABI for format plugins loaded from shared libraries.
+//!
+//! Rust trait objects are not ABI-stable: a [`crate::FormatFactory`] built into a shared library
+//! is only usable by a host compiled with the same compiler *and* the exact same dependency
+//! graph. Plugins therefore talk to the host through `#[repr(C)]` function tables, and data
+//! crosses the boundary through the [Arrow C data and stream interfaces]. A plugin built against
+//! any compatible `geoetl-core-common` release, with any Rust compiler or even another language,
+//! can be loaded as long as [`PluginDescriptor::abi_version`] matches [`PLUGIN_ABI_VERSION`].
+//!
+//! A plugin library exports an `extern "C"` function named [`PLUGIN_ENTRY_SYMBOL`] that returns
+//! a pointer to a [`PluginDescriptor`] valid for the lifetime of the process. Rust plugins
+//! implement [`PluginFormat`] and export it with [`declare_plugin!`]:
+//!
+//! ```ignore
+//! use geoetl_core_common::plugin::PluginFormat;
+//!
+//! #[derive(Default)]
+//! struct MyFormat;
+//!
+//! impl PluginFormat for MyFormat {
+//!     const SHORT_NAME: &'static str = "MyFormat";
+//!     const LONG_NAME: &'static str = "My proprietary format";
+//!     const CAN_READ: bool = true;
+//!
+//!     fn read(
+//!         &self,
+//!         path: &str,
+//!         options: &OptionValues,
+//!     ) -> anyhow::Result<Box<dyn RecordBatchReader + Send>> {
+//!         // ...
+//!     }
+//! }
+//!
+//! geoetl_core_common::declare_plugin!(MyFormat);
+//! ```
+//!
+//! Errors are returned as nul-terminated strings allocated by the plugin and released with
+//! [`PluginFormatDescriptor::free_error`]; a null pointer means success.
+//!
+//! [Arrow C data and stream interfaces]: https://arrow.apache.org/docs/format/CDataInterface.html
+
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+
+use anyhow::{Result, anyhow, bail};
+use arrow::array::RecordBatchReader;
+use arrow::datatypes::SchemaRef;
+use arrow::ffi::FFI_ArrowSchema;
+use arrow::ffi_stream::{ArrowArrayStreamReader, FFI_ArrowArrayStream};
+
+use crate::options::{FormatOption, OptionType, OptionValues};
+
+/// Version of the plugin C ABI described by this module.
+///
+/// Incremented whenever [`PluginDescriptor`] or the structures it points to change in an
+/// incompatible way.
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Name of the entry point a plugin library exports, nul-terminated for symbol lookup.
+///
+/// The symbol is an `extern "C" fn() -> *const PluginDescriptor`.
+pub const PLUGIN_ENTRY_SYMBOL: &[u8] = b"geoetl_plugin_entry\0";
+
+/// Signature of the plugin entry point.
+pub type PluginEntryFn = unsafe extern "C" fn() -> *const PluginDescriptor;
+
+/// Exports the schema of a dataset into `out`.
+pub type ReadSchemaFn = unsafe extern "C" fn(
+    path: *const c_char,
+    options: *const PluginOptionValue,
+    option_count: usize,
+    out: *mut FFI_ArrowSchema,
+) -> *mut c_char;
+
+/// Exports the record batches of a dataset into `out`.
+pub type ReadFn = unsafe extern "C" fn(
+    path: *const c_char,
+    options: *const PluginOptionValue,
+    option_count: usize,
+    out: *mut FFI_ArrowArrayStream,
+) -> *mut c_char;
+
+/// Consumes `input` and writes it as a dataset, storing the number of rows in `rows`.
+pub type WriteFn = unsafe extern "C" fn(
+    path: *const c_char,
+    options: *const PluginOptionValue,
+    option_count: usize,
+    input: *mut FFI_ArrowArrayStream,
+    rows: *mut u64,
+) -> *mut c_char;
+
+/// Releases an error string returned by one of the plugin functions.
+pub type FreeErrorFn = unsafe extern "C" fn(error: *mut c_char);
+
+/// Top-level description of a plugin library.
+#[repr(C)]
+#[derive(Debug)]
+pub struct PluginDescriptor {
+    /// ABI version the plugin was built for; must equal [`PLUGIN_ABI_VERSION`].
+    pub abi_version: u32,
+    /// Formats provided by the plugin.
+    pub formats: *const PluginFormatDescriptor,
+    /// Number of entries in `formats`.
+    pub format_count: usize,
+}
+
+/// Description of a single format provided by a plugin.
+#[repr(C)]
+#[derive(Debug)]
+pub struct PluginFormatDescriptor {
+    /// Driver short name (e.g., `"MyFormat"`), nul-terminated UTF-8.
+    pub short_name: *const c_char,
+    /// Driver long name, nul-terminated UTF-8.
+    pub long_name: *const c_char,
+    /// Open options understood by the reader.
+    pub open_options: *const PluginOption,
+    /// Number of entries in `open_options`.
+    pub open_option_count: usize,
+    /// Creation options understood by the writer.
+    pub creation_options: *const PluginOption,
+    /// Number of entries in `creation_options`.
+    pub creation_option_count: usize,
+    /// Schema of a dataset, or `None` if the format cannot be read.
+    pub read_schema: Option<ReadSchemaFn>,
+    /// Record batches of a dataset, or `None` if the format cannot be read.
+    pub read: Option<ReadFn>,
+    /// Writes a dataset, or `None` if the format cannot be written.
+    pub write: Option<WriteFn>,
+    /// Releases error strings returned by `read_schema`, `read` and `write`.
+    pub free_error: FreeErrorFn,
+}
+
+/// Description of an open or creation option.
+#[repr(C)]
+#[derive(Debug)]
+pub struct PluginOption {
+    /// Option name, nul-terminated UTF-8.
+    pub name: *const c_char,
+    /// Value type: `0` string, `1` boolean, `2` integer, `3` character.
+    pub option_type: u32,
+    /// Default value, or null if the option has none.
+    pub default: *const c_char,
+    /// Human-readable description, nul-terminated UTF-8.
+    pub description: *const c_char,
+}
+
+/// A `KEY=VALUE` option passed to a plugin function.
+#[repr(C)]
+#[derive(Debug)]
+pub struct PluginOptionValue {
+    /// Option name, upper case, nul-terminated UTF-8.
+    pub key: *const c_char,
+    /// Option value, nul-terminated UTF-8.
+    pub value: *const c_char,
+}
+
+/// A format implemented by a Rust plugin.
+///
+/// Each call operates on a fresh `Self::default()` instance.
+pub trait PluginFormat: Default + 'static {
+    /// Driver short name used on the command line.
+    const SHORT_NAME: &'static str;
+    /// Driver long name used for display.
+    const LONG_NAME: &'static str;
+    /// Whether [`PluginFormat::read`] is implemented.
+    const CAN_READ: bool = false;
+    /// Whether [`PluginFormat::write`] is implemented.
+    const CAN_WRITE: bool = false;
+
+    /// Returns the open options understood by the reader.
+    fn open_options() -> Vec<FormatOption> {
+        Vec::new()
+    }
+
+    /// Returns the creation options understood by the writer.
+    fn creation_options() -> Vec<FormatOption> {
+        Vec::new()
+    }
+
+    /// Returns the schema of the dataset at `path`.
+    ///
+    /// The default implementation opens a reader and returns its schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dataset cannot be opened.
+    fn read_schema(&self, path: &str, options: &OptionValues) -> Result<SchemaRef> {
+        Ok(self.read(path, options)?.schema())
+    }
+
+    /// Opens the dataset at `path` for reading.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dataset cannot be opened or reading is not supported.
+    fn read(
+        &self,
+        _path: &str,
+        _options: &OptionValues,
+    ) -> Result<Box<dyn RecordBatchReader + Send>> {
+        bail!("{} does not support reading", Self::SHORT_NAME)
+    }
+
+    /// Writes every batch of `input` to `path` and returns the number of rows written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails or is not supported.
+    fn write(
+        &self,
+        _path: &str,
+        _options: &OptionValues,
+        _input: &mut dyn RecordBatchReader,
+    ) -> Result<u64> {
+        bail!("{} does not support writing", Self::SHORT_NAME)
+    }
+}
+
+/// Owns the descriptors exported by a Rust plugin and the strings they point to.
+///
+/// Created once by the entry point generated with [`declare_plugin!`].
+#[derive(Debug)]
+pub struct PluginExport {
+    descriptor: PluginDescriptor,
+    formats: Vec<PluginFormatDescriptor>,
+    option_tables: Vec<Vec<PluginOption>>,
+    strings: Vec<CString>,
+}
+
+// SAFETY: the export is immutable once built; its raw pointers refer to heap data it owns.
+unsafe impl Send for PluginExport {}
+// SAFETY: see above.
+unsafe impl Sync for PluginExport {}
+
+impl PluginExport {
+    /// Starts an export with no formats.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            descriptor: PluginDescriptor {
+                abi_version: PLUGIN_ABI_VERSION,
+                formats: ptr::null(),
+                format_count: 0,
+            },
+            formats: Vec::new(),
+            option_tables: Vec::new(),
+            strings: Vec::new(),
+        }
+    }
+
+    /// Adds the format `F`, returning the updated export.
+    #[must_use]
+    pub fn with_format<F: PluginFormat>(mut self) -> Self {
+        let open_options = self.options(&F::open_options());
+        let creation_options = self.options(&F::creation_options());
+        let format = PluginFormatDescriptor {
+            short_name: self.string(F::SHORT_NAME),
+            long_name: self.string(F::LONG_NAME),
+            open_options: open_options.as_ptr(),
+            open_option_count: open_options.len(),
+            creation_options: creation_options.as_ptr(),
+            creation_option_count: creation_options.len(),
+            read_schema: F::CAN_READ.then_some(read_schema_entry::<F> as ReadSchemaFn),
+            read: F::CAN_READ.then_some(read_entry::<F> as ReadFn),
+            write: F::CAN_WRITE.then_some(write_entry::<F> as WriteFn),
+            free_error: free_error_entry,
+        };
+        self.option_tables.push(open_options);
+        self.option_tables.push(creation_options);
+        self.formats.push(format);
+        self.descriptor.formats = self.formats.as_ptr();
+        self.descriptor.format_count = self.formats.len();
+        self
+    }
+
+    /// Returns the descriptor to hand to the host.
+    #[must_use]
+    pub fn descriptor(&self) -> *const PluginDescriptor {
+        &raw const self.descriptor
+    }
+
+    fn string(&mut self, value: &str) -> *const c_char {
+        let value = CString::new(value.replace('\0', "")).unwrap_or_default();
+        // The heap allocation does not move when the `CString` is moved into the vector
+        let pointer = value.as_ptr();
+        self.strings.push(value);
+        pointer
+    }
+
+    fn options(&mut self, options: &[FormatOption]) -> Vec<PluginOption> {
+        options
+            .iter()
+            .map(|option| PluginOption {
+                name: self.string(option.name),
+                option_type: option_type_code(option.option_type),
+                default: option
+                    .default
+                    .map_or(ptr::null(), |default| self.string(default)),
+                description: self.string(option.description),
+            })
+            .collect()
+    }
+}
+
+impl Default for PluginExport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Exports the plugin entry point for one or more [`PluginFormat`] types.
+#[macro_export]
+macro_rules! declare_plugin {
+    ($($format:ty),+ $(,)?) => {
+        #[unsafe(no_mangle)]
+        pub extern "C" fn geoetl_plugin_entry() -> *const $crate::plugin::PluginDescriptor {
+            static EXPORT: ::std::sync::OnceLock<$crate::plugin::PluginExport> =
+                ::std::sync::OnceLock::new();
+            EXPORT
+                .get_or_init(|| {
+                    $crate::plugin::PluginExport::new()$(.with_format::<$format>())+
+                })
+                .descriptor()
+        }
+    };
+}
+
+/// Returns the ABI code of an option type.
+#[must_use]
+pub fn option_type_code(option_type: OptionType) -> u32 {
+    match option_type {
+        OptionType::String => 0,
+        OptionType::Boolean => 1,
+        OptionType::Integer => 2,
+        OptionType::Char => 3,
+    }
+}
+
+/// Returns the option type for an ABI code.
+///
+/// # Errors
+///
+/// Returns an error for unknown codes.
+pub fn option_type_from_code(code: u32) -> Result<OptionType> {
+    match code {
+        0 => Ok(OptionType::String),
+        1 => Ok(OptionType::Boolean),
+        2 => Ok(OptionType::Integer),
+        3 => Ok(OptionType::Char),
+        _ => bail!("unknown option type code {code}"),
+    }
+}
+
+/// Runs a plugin function body, converting errors and panics into an owned error string.
+fn guarded(body: impl FnOnce() -> Result<()>) -> *mut c_char {
+    let message = match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => return ptr::null_mut(),
+        Ok(Err(e)) => format!("{e:#}"),
+        Err(_) => "plugin panicked".to_string(),
+    };
+    CString::new(message.replace('\0', ""))
+        .unwrap_or_default()
+        .into_raw()
+}
+
+/// Decodes the path and options passed to a plugin function.
+///
+/// # Safety
+///
+/// `path` must be a valid nul-terminated string and `options` must point to `option_count`
+/// valid entries (or be null when `option_count` is zero).
+unsafe fn decode_arguments(
+    path: *const c_char,
+    options: *const PluginOptionValue,
+    option_count: usize,
+) -> Result<(String, OptionValues)> {
+    // SAFETY: guaranteed by the caller.
+    let path = unsafe { c_str(path) }?.to_string();
+    let mut values = OptionValues::new();
+    if option_count > 0 {
+        // SAFETY: guaranteed by the caller.
+        let options = unsafe { std::slice::from_raw_parts(options, option_count) };
+        for option in options {
+            // SAFETY: guaranteed by the caller.
+            let (key, value) = unsafe { (c_str(option.key)?, c_str(option.value)?) };
+            values.insert(key, value);
+        }
+    }
+    Ok((path, values))
+}
+
+/// Borrows a nul-terminated UTF-8 string.
+///
+/// # Safety
+///
+/// `value` must be null or point to a valid nul-terminated string that outlives `'a`.
+pub unsafe fn c_str<'a>(value: *const c_char) -> Result<&'a str> {
+    if value.is_null() {
+        bail!("unexpected null string");
+    }
+    // SAFETY: guaranteed by the caller.
+    unsafe { CStr::from_ptr(value) }
+        .to_str()
+        .map_err(|e| anyhow!("invalid UTF-8 string: {e}"))
+}
+
+unsafe extern "C" fn read_schema_entry<F: PluginFormat>(
+    path: *const c_char,
+    options: *const PluginOptionValue,
+    option_count: usize,
+    out: *mut FFI_ArrowSchema,
+) -> *mut c_char {
+    guarded(|| {
+        // SAFETY: the host passes valid arguments.
+        let (path, options) = unsafe { decode_arguments(path, options, option_count) }?;
+        let schema = F::default().read_schema(&path, &options)?;
+        let schema = FFI_ArrowSchema::try_from(schema.as_ref())?;
+        // SAFETY: `out` points to writable, uninitialised-or-released schema storage.
+        unsafe { ptr::write(out, schema) };
+        Ok(())
+    })
+}
+
+unsafe extern "C" fn read_entry<F: PluginFormat>(
+    path: *const c_char,
+    options: *const PluginOptionValue,
+    option_count: usize,
+    out: *mut FFI_ArrowArrayStream,
+) -> *mut c_char {
+    guarded(|| {
+        // SAFETY: the host passes valid arguments.
+        let (path, options) = unsafe { decode_arguments(path, options, option_count) }?;
+        let reader = F::default().read(&path, &options)?;
+        // SAFETY: `out` points to writable, uninitialised-or-released stream storage.
+        unsafe { ptr::write(out, FFI_ArrowArrayStream::new(reader)) };
+        Ok(())
+    })
+}
+
+unsafe extern "C" fn write_entry<F: PluginFormat>(
+    path: *const c_char,
+    options: *const PluginOptionValue,
+    option_count: usize,
+    input: *mut FFI_ArrowArrayStream,
+    rows: *mut u64,
+) -> *mut c_char {
+    guarded(|| {
+        // SAFETY: the host passes valid arguments.
+        let (path, options) = unsafe { decode_arguments(path, options, option_count) }?;
+        // SAFETY: `input` is a valid `FFI_ArrowArrayStream` owned by the host, which hands
+        // its ownership to the reader exactly once.
+        let mut input = unsafe { ArrowArrayStreamReader::from_raw(input) }?;
+        let written = F::default().write(&path, &options, &mut input)?;
+        // SAFETY: `rows` points to writable storage.
+        unsafe { ptr::write(rows, written) };
+        Ok(())
+    })
+}
+
+unsafe extern "C" fn free_error_entry(error: *mut c_char) {
+    if !error.is_null() {
+        // SAFETY: error strings are created with `CString::into_raw` in `guarded`.
+        drop(unsafe { CString::from_raw(error) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::OptionType;
+
+    #[derive(Default)]
+    struct WriteOnly;
+
+    impl PluginFormat for WriteOnly {
+        const SHORT_NAME: &'static str = "WriteOnly";
+        const LONG_NAME: &'static str = "Write-only test format";
+        const CAN_WRITE: bool = true;
+
+        fn creation_options() -> Vec<FormatOption> {
+            vec![
+                FormatOption::new("PRETTY", OptionType::Boolean, "Indent output")
+                    .with_default("NO"),
+            ]
+        }
+    }
+
+    #[test]
+    fn test_export_describes_formats() {
+        let export = PluginExport::new().with_format::<WriteOnly>();
+        let descriptor = unsafe { &*export.descriptor() };
+        assert_eq!(descriptor.abi_version, PLUGIN_ABI_VERSION);
+        assert_eq!(descriptor.format_count, 1);
+
+        let format = unsafe { &*descriptor.formats };
+        assert_eq!(unsafe { c_str(format.short_name) }.unwrap(), "WriteOnly");
+        assert!(format.read.is_none() && format.read_schema.is_none());
+        assert!(format.write.is_some());
+        assert_eq!(format.open_option_count, 0);
+        assert_eq!(format.creation_option_count, 1);
+
+        let option = unsafe { &*format.creation_options };
+        assert_eq!(unsafe { c_str(option.name) }.unwrap(), "PRETTY");
+        assert_eq!(unsafe { c_str(option.default) }.unwrap(), "NO");
+        assert_eq!(
+            option_type_from_code(option.option_type).unwrap(),
+            OptionType::Boolean
+        );
+    }
+
+    #[test]
+    fn test_option_type_codes_round_trip() {
+        for option_type in [
+            OptionType::String,
+            OptionType::Boolean,
+            OptionType::Integer,
+            OptionType::Char,
+        ] {
+            let code = option_type_code(option_type);
+            assert_eq!(option_type_from_code(code).unwrap(), option_type);
+        }
+        assert!(option_type_from_code(42).is_err());
+    }
+
+    #[test]
+    fn test_guarded_reports_errors_and_panics() {
+        assert!(guarded(|| Ok(())).is_null());
+
+        let error = guarded(|| bail!("boom"));
+        assert_eq!(unsafe { c_str(error) }.unwrap(), "boom");
+        unsafe { free_error_entry(error) };
+
+        let error = guarded(|| panic!("unexpected"));
+        assert_eq!(unsafe { c_str(error) }.unwrap(), "plugin panicked");
+        unsafe { free_error_entry(error) };
+    }
+}
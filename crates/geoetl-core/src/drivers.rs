@@ -1,9 +1,9 @@
 //! Driver registry for geospatial data format support and capabilities.
 //!
-//! This module provides a static registry of geospatial data format drivers, including
-//! their current support status (supported, planned, or not supported) for various operations
-//! (info, read, write). The registry is modeled after GDAL's driver system but designed for
-//! modern Rust-based ETL workflows.
+//! This module combines the runtime [`geoetl_core_common::DriverRegistry`], which describes
+//! what registered format factories can actually do, with a catalog of other known formats
+//! and their planned support status (info, read, write). The registry is modeled after
+//! GDAL's driver system but designed for modern Rust-based ETL workflows.
 //!
 //! # Examples
 //!
@@ -21,206 +21,23 @@
 //! }
 //! ```
 
-/// Support status for a specific driver operation.
-///
-/// Indicates whether a driver operation (info, read, or write) is currently supported,
-/// planned for future implementation, or not supported at all.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum SupportStatus {
-    /// The feature is fully supported and implemented.
-    Supported,
-    /// The feature is not supported by the driver.
-    NotSupported,
-    /// The feature is planned for future implementation.
-    Planned,
-}
-
-impl SupportStatus {
-    /// Returns `true` if the operation is fully supported and implemented.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use geoetl_core::drivers::SupportStatus;
-    ///
-    /// assert!(SupportStatus::Supported.is_supported());
-    /// assert!(!SupportStatus::Planned.is_supported());
-    /// assert!(!SupportStatus::NotSupported.is_supported());
-    /// ```
-    #[must_use]
-    pub fn is_supported(&self) -> bool {
-        matches!(self, SupportStatus::Supported)
-    }
-
-    /// Returns `true` if the operation is supported or planned (i.e., not explicitly unsupported).
-    ///
-    /// This is useful for filtering drivers that have current or future support.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use geoetl_core::drivers::SupportStatus;
-    ///
-    /// assert!(SupportStatus::Supported.is_available());
-    /// assert!(SupportStatus::Planned.is_available());
-    /// assert!(!SupportStatus::NotSupported.is_available());
-    /// ```
-    #[must_use]
-    pub fn is_available(&self) -> bool {
-        !matches!(self, SupportStatus::NotSupported)
-    }
-
-    /// Returns the string representation of this support status.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use geoetl_core::drivers::SupportStatus;
-    ///
-    /// assert_eq!(SupportStatus::Supported.as_str(), "Supported");
-    /// assert_eq!(SupportStatus::Planned.as_str(), "Planned");
-    /// assert_eq!(SupportStatus::NotSupported.as_str(), "Not Supported");
-    /// ```
-    #[must_use]
-    pub fn as_str(&self) -> &str {
-        match self {
-            SupportStatus::Supported => "Supported",
-            SupportStatus::NotSupported => "Not Supported",
-            SupportStatus::Planned => "Planned",
-        }
-    }
-}
-
-/// Capabilities supported by a geospatial data format driver.
-///
-/// Each driver can support three types of operations: reading metadata (info),
-/// reading data (read), and writing data (write). Each capability has an associated
-/// [`SupportStatus`] indicating its current implementation status.
-#[derive(Debug, Clone, Copy)]
-pub struct DriverCapabilities {
-    /// Support status for reading dataset metadata and information.
-    pub info: SupportStatus,
-    /// Support status for reading data from this format.
-    pub read: SupportStatus,
-    /// Support status for writing data to this format.
-    pub write: SupportStatus,
-}
-
-impl DriverCapabilities {
-    /// Returns `true` if at least one operation is supported or planned.
-    ///
-    /// This is useful for identifying drivers that have any level of functionality,
-    /// either current or planned for the future.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use geoetl_core::drivers::{DriverCapabilities, SupportStatus};
-    ///
-    /// let caps = DriverCapabilities {
-    ///     info: SupportStatus::Planned,
-    ///     read: SupportStatus::NotSupported,
-    ///     write: SupportStatus::NotSupported,
-    /// };
-    /// assert!(caps.has_any_support());
-    /// ```
-    #[must_use]
-    pub fn has_any_support(&self) -> bool {
-        self.info.is_available() || self.read.is_available() || self.write.is_available()
-    }
+pub use geoetl_core_common::drivers::{Driver, DriverCapabilities, SupportStatus};
+pub use geoetl_core_common::options::{FormatOption, OptionType};
 
-    /// Returns `true` if at least one operation is fully supported and implemented.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use geoetl_core::drivers::{DriverCapabilities, SupportStatus};
-    ///
-    /// let caps = DriverCapabilities {
-    ///     info: SupportStatus::Supported,
-    ///     read: SupportStatus::Supported,
-    ///     write: SupportStatus::Planned,
-    /// };
-    /// assert!(caps.has_supported_operation());
-    /// ```
-    #[must_use]
-    pub fn has_supported_operation(&self) -> bool {
-        self.info.is_supported() || self.read.is_supported() || self.write.is_supported()
-    }
-}
+use geoetl_core_common::PROBE_HEADER_LEN;
+use std::io::Read;
+use std::path::Path;
 
-/// Geospatial data format driver definition.
+/// Returns every known vector format driver.
 ///
-/// A driver represents support for a specific geospatial data format (e.g., `GeoJSON`, `Shapefile`).
-/// Each driver has a short name (used in the CLI), a descriptive long name, and a set of
-/// capabilities indicating what operations are supported.
+/// Drivers registered with the global [`geoetl_core_common::DriverRegistry`] come first,
+/// sorted by short name; their capabilities reflect the readers and writers actually
+/// available at runtime, so custom format factories are included as soon as they are
+/// registered. They are followed by the catalog of formats that are not registered, whose
+/// [`SupportStatus::Planned`] or [`SupportStatus::NotSupported`] entries are informational
+/// only.
 ///
-/// # Examples
-///
-/// ```
-/// use geoetl_core::drivers::{Driver, SupportStatus};
-///
-/// let driver = Driver::new(
-///     "GeoJSON",
-///     "GeoJSON",
-///     SupportStatus::Supported,
-///     SupportStatus::Supported,
-///     SupportStatus::Supported,
-/// );
-///
-/// assert_eq!(driver.short_name, "GeoJSON");
-/// assert!(driver.capabilities.read.is_supported());
-/// ```
-#[derive(Debug, Clone)]
-pub struct Driver {
-    /// Short name used in the CLI and for driver identification (e.g., `"GeoJSON"`).
-    pub short_name: &'static str,
-    /// Long descriptive name for display purposes (e.g., `"GeoJSON"`).
-    pub long_name: &'static str,
-    /// Operations supported by this driver (info, read, write).
-    pub capabilities: DriverCapabilities,
-}
-
-impl Driver {
-    /// Creates a new driver definition with specified capabilities.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use geoetl_core::drivers::{Driver, SupportStatus};
-    ///
-    /// let driver = Driver::new(
-    ///     "CSV",
-    ///     "Comma Separated Value",
-    ///     SupportStatus::Planned,
-    ///     SupportStatus::Planned,
-    ///     SupportStatus::Planned,
-    /// );
-    /// ```
-    #[must_use]
-    pub const fn new(
-        short_name: &'static str,
-        long_name: &'static str,
-        info: SupportStatus,
-        read: SupportStatus,
-        write: SupportStatus,
-    ) -> Self {
-        Self {
-            short_name,
-            long_name,
-            capabilities: DriverCapabilities { info, read, write },
-        }
-    }
-}
-
-/// Returns the complete registry of all known vector format drivers.
-///
-/// This function returns every driver in the registry, regardless of support status.
-/// Each driver includes its short name, long name, and capabilities for info, read,
-/// and write operations.
-///
-/// The registry includes 68+ drivers covering formats like `GeoJSON`, `Shapefile`, `GeoPackage`,
-/// databases (PostgreSQL/PostGIS, `MySQL`), web services (WFS, OGC API), and many more.
+/// The built-in formats are registered on first use (see [`crate::init::initialize`]).
 ///
 /// # Examples
 ///
@@ -237,13 +54,31 @@ impl Driver {
 /// println!("Drivers with read support: {}", read_capable);
 /// ```
 #[must_use]
-#[allow(clippy::too_many_lines)]
 pub fn get_drivers() -> Vec<Driver> {
-    use SupportStatus::{NotSupported, Planned, Supported};
+    crate::init::initialize();
+
+    let registry = geoetl_core_common::driver_registry();
+    let mut drivers = registry.get_available_drivers();
+    drivers.extend(
+        known_drivers()
+            .into_iter()
+            .filter(|driver| registry.find_factory(driver.short_name).is_none()),
+    );
+    drivers
+}
+
+/// Catalog of vector formats without a registered implementation.
+///
+/// The catalog covers 60+ formats like `Shapefile`, `GeoPackage`, databases
+/// (PostgreSQL/PostGIS, `MySQL`), web services (WFS, OGC API), and many more. Entries are
+/// either planned or not supported; formats that can actually be read or written are
+/// described by their registered format factory instead.
+#[allow(clippy::too_many_lines)]
+fn known_drivers() -> Vec<Driver> {
+    use SupportStatus::{NotSupported, Planned};
 
     vec![
         // Core formats - Phase 2 implementation
-        Driver::new("GeoJSON", "GeoJSON", Supported, Supported, Supported),
         Driver::new(
             "GeoJSONSeq",
             "GeoJSONSeq: sequence of GeoJSON features",
@@ -297,13 +132,6 @@ pub fn get_drivers() -> Vec<Driver> {
             NotSupported,
             NotSupported,
         ),
-        Driver::new(
-            "CSV",
-            "Comma Separated Value (.csv)",
-            Supported,
-            Supported,
-            Supported,
-        ),
         Driver::new(
             "GeoRSS",
             "GeoRSS: Geographically Encoded Objects for RSS",
@@ -668,6 +496,178 @@ pub fn get_drivers() -> Vec<Driver> {
     ]
 }
 
+/// File signature of a catalog format, used to recognise formats that are not registered.
+struct CatalogSignature {
+    /// Short name of the catalog driver.
+    driver: &'static str,
+    /// File extensions, lowercase and without the leading dot.
+    extensions: &'static [&'static str],
+    /// Leading bytes of every dataset of this format; empty if it has none.
+    magic: &'static [u8],
+}
+
+/// Signatures of catalog formats, so that detection can name a format even before it is
+/// implemented and report that it cannot be read or written yet.
+const CATALOG_SIGNATURES: &[CatalogSignature] = &[
+    CatalogSignature {
+        driver: "FlatGeobuf",
+        extensions: &["fgb"],
+        magic: b"fgb\x03fgb",
+    },
+    CatalogSignature {
+        driver: "Parquet",
+        extensions: &["parquet", "geoparquet"],
+        magic: b"PAR1",
+    },
+    CatalogSignature {
+        driver: "Arrow",
+        extensions: &["arrow", "arrows", "feather", "ipc"],
+        magic: b"ARROW1",
+    },
+    CatalogSignature {
+        driver: "GPKG",
+        extensions: &["gpkg"],
+        magic: b"SQLite format 3\0",
+    },
+    CatalogSignature {
+        driver: "ESRI Shapefile",
+        extensions: &["shp", "dbf"],
+        magic: &[0x00, 0x00, 0x27, 0x0A],
+    },
+    CatalogSignature {
+        driver: "GeoJSONSeq",
+        extensions: &["geojsons", "geojsonl"],
+        magic: b"",
+    },
+    CatalogSignature {
+        driver: "GML",
+        extensions: &["gml"],
+        magic: b"",
+    },
+    CatalogSignature {
+        driver: "KML",
+        extensions: &["kml"],
+        magic: b"",
+    },
+    CatalogSignature {
+        driver: "LIBKML",
+        extensions: &["kmz"],
+        magic: b"",
+    },
+    CatalogSignature {
+        driver: "GPX",
+        extensions: &["gpx"],
+        magic: b"",
+    },
+    CatalogSignature {
+        driver: "DXF",
+        extensions: &["dxf"],
+        magic: b"",
+    },
+    CatalogSignature {
+        driver: "DWG",
+        extensions: &["dwg"],
+        magic: b"",
+    },
+    CatalogSignature {
+        driver: "DGN",
+        extensions: &["dgn"],
+        magic: b"",
+    },
+];
+
+/// Detects the driver of an existing dataset from its leading bytes.
+///
+/// Registered format factories are probed first (see
+/// [`geoetl_core_common::FormatFactory::probe`]), followed by the magic numbers of catalog
+/// formats such as `FlatGeobuf` and `Parquet`. Returns `None` if the file cannot be read or
+/// its content is not recognised.
+///
+/// # Examples
+///
+/// ```
+/// use geoetl_core::drivers::detect_driver_from_content;
+///
+/// let path = std::env::temp_dir().join("detect_driver_from_content.json");
+/// std::fs::write(&path, r#"{"type":"FeatureCollection","features":[]}"#).unwrap();
+///
+/// let driver = detect_driver_from_content(path.to_str().unwrap()).unwrap();
+/// assert_eq!(driver.short_name, "GeoJSON");
+/// ```
+#[must_use]
+pub fn detect_driver_from_content(path: &str) -> Option<Driver> {
+    crate::init::initialize();
+
+    let header = read_header(path)?;
+    if let Some(factory) = geoetl_core_common::driver_registry().detect_from_content(&header) {
+        return find_driver(factory.driver().short_name);
+    }
+    CATALOG_SIGNATURES
+        .iter()
+        .find(|signature| !signature.magic.is_empty() && header.starts_with(signature.magic))
+        .and_then(|signature| find_driver(signature.driver))
+}
+
+/// Detects the driver of a dataset from the extension of its path (case-insensitive).
+///
+/// Extensions declared by registered format factories take precedence over those of catalog
+/// formats. The dataset does not need to exist, so this also detects output drivers.
+///
+/// # Examples
+///
+/// ```
+/// use geoetl_core::drivers::detect_driver_from_extension;
+///
+/// assert_eq!(detect_driver_from_extension("out.CSV").unwrap().short_name, "CSV");
+/// assert_eq!(detect_driver_from_extension("out.fgb").unwrap().short_name, "FlatGeobuf");
+/// assert!(detect_driver_from_extension("out.unknown").is_none());
+/// ```
+#[must_use]
+pub fn detect_driver_from_extension(path: &str) -> Option<Driver> {
+    crate::init::initialize();
+
+    if let Some(factory) = geoetl_core_common::driver_registry().detect_from_extension(path) {
+        return find_driver(factory.driver().short_name);
+    }
+    let extension = Path::new(path).extension()?.to_str()?;
+    CATALOG_SIGNATURES
+        .iter()
+        .find(|signature| {
+            signature
+                .extensions
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(extension))
+        })
+        .and_then(|signature| find_driver(signature.driver))
+}
+
+/// Detects the driver of a dataset, preferring its content over its extension.
+///
+/// The content is only inspected if `path` is an existing file; see
+/// [`detect_driver_from_content`] and [`detect_driver_from_extension`].
+///
+/// # Examples
+///
+/// ```
+/// use geoetl_core::drivers::detect_driver;
+///
+/// assert_eq!(detect_driver("missing.geojson").unwrap().short_name, "GeoJSON");
+/// ```
+#[must_use]
+pub fn detect_driver(path: &str) -> Option<Driver> {
+    detect_driver_from_content(path).or_else(|| detect_driver_from_extension(path))
+}
+
+/// Reads the leading bytes of a file, or `None` if it cannot be read or is empty.
+fn read_header(path: &str) -> Option<Vec<u8>> {
+    let file = std::fs::File::open(path).ok()?;
+    let mut header = Vec::with_capacity(PROBE_HEADER_LEN);
+    file.take(PROBE_HEADER_LEN as u64)
+        .read_to_end(&mut header)
+        .ok()?;
+    (!header.is_empty()).then_some(header)
+}
+
 /// Returns all drivers that have at least one fully supported operation.
 ///
 /// This filters the driver registry to include only drivers where at least one
@@ -776,6 +776,48 @@ pub fn get_driver_names() -> Vec<&'static str> {
     names
 }
 
+/// Returns the open options declared by a registered driver (case-insensitive name).
+///
+/// Drivers without a registered format factory have no options.
+///
+/// # Examples
+///
+/// ```
+/// use geoetl_core::drivers::get_open_options;
+///
+/// let options = get_open_options("CSV");
+/// assert!(options.iter().any(|o| o.name == "GEOMETRY_COLUMN"));
+/// ```
+#[must_use]
+pub fn get_open_options(name: &str) -> Vec<FormatOption> {
+    crate::init::initialize();
+    geoetl_core_common::driver_registry()
+        .find_factory(name)
+        .map(|factory| factory.open_options())
+        .unwrap_or_default()
+}
+
+/// Returns the creation options declared by a registered driver (case-insensitive name).
+///
+/// Drivers without a registered format factory have no options.
+///
+/// # Examples
+///
+/// ```
+/// use geoetl_core::drivers::get_creation_options;
+///
+/// let options = get_creation_options("GeoJSON");
+/// assert!(options.iter().any(|o| o.name == "PRETTY"));
+/// ```
+#[must_use]
+pub fn get_creation_options(name: &str) -> Vec<FormatOption> {
+    crate::init::initialize();
+    geoetl_core_common::driver_registry()
+        .find_factory(name)
+        .map(|factory| factory.creation_options())
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -822,4 +864,67 @@ mod tests {
         assert!(!SupportStatus::NotSupported.is_available());
         assert!(SupportStatus::Planned.is_available());
     }
+
+    #[test]
+    fn test_catalog_has_no_supported_entries() {
+        for driver in known_drivers() {
+            assert!(
+                !driver.capabilities.has_supported_operation(),
+                "catalog entry '{}' claims support without a registered factory",
+                driver.short_name
+            );
+        }
+    }
+
+    #[test]
+    fn test_catalog_signatures_name_catalog_drivers() {
+        let catalog = known_drivers();
+        for signature in CATALOG_SIGNATURES {
+            assert!(
+                catalog.iter().any(|d| d.short_name == signature.driver),
+                "signature for unknown driver '{}'",
+                signature.driver
+            );
+        }
+    }
+
+    #[test]
+    fn test_detect_driver_prefers_content() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("points.csv");
+        std::fs::write(&path, r#"{"type":"FeatureCollection","features":[]}"#).unwrap();
+        let path = path.to_str().unwrap();
+
+        assert_eq!(detect_driver(path).unwrap().short_name, "GeoJSON");
+        assert_eq!(
+            detect_driver_from_extension(path).unwrap().short_name,
+            "CSV"
+        );
+    }
+
+    #[test]
+    fn test_detect_catalog_format_by_magic() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("data.bin");
+        std::fs::write(&path, b"PAR1\x15\x04").unwrap();
+
+        let driver = detect_driver(path.to_str().unwrap()).unwrap();
+        assert_eq!(driver.short_name, "Parquet");
+        assert_eq!(driver.capabilities.read, SupportStatus::Planned);
+    }
+
+    #[test]
+    fn test_detect_driver_unknown() {
+        assert!(detect_driver("missing.xyz").is_none());
+        assert!(detect_driver("no_extension").is_none());
+    }
+
+    #[test]
+    fn test_get_drivers_has_unique_names() {
+        let names = get_driver_names();
+        let mut unique = names.clone();
+        unique.dedup();
+        assert_eq!(names, unique);
+        assert!(names.contains(&"GML"));
+    }
 }
@@ -77,6 +77,33 @@ pub enum DriverError {
         /// The driver name
         driver: String,
     },
+
+    /// No driver could be detected from a dataset's content or extension
+    #[error("Could not detect the driver for '{path}'")]
+    NotDetected {
+        /// The dataset path
+        path: String,
+    },
+
+    /// The named driver does not match the format detected from the dataset's content
+    #[error("Dataset '{path}' looks like {detected}, not {driver}")]
+    FormatMismatch {
+        /// The dataset path
+        path: String,
+        /// The driver that was named
+        driver: String,
+        /// The driver detected from the content
+        detected: String,
+    },
+
+    /// A format plugin library could not be loaded
+    #[error("Failed to load plugin '{path}': {message}")]
+    PluginLoad {
+        /// Path of the plugin library
+        path: String,
+        /// Why loading failed
+        message: String,
+    },
 }
 
 /// I/O related errors.
@@ -125,6 +152,13 @@ pub enum IoError {
         path: PathBuf,
     },
 
+    /// Output already exists and may not be replaced
+    #[error("Output already exists: '{path}'")]
+    AlreadyExists {
+        /// The existing output path
+        path: PathBuf,
+    },
+
     /// Permission was denied
     #[error("Permission denied for '{path}'")]
     PermissionDenied {
@@ -297,7 +331,19 @@ impl DriverError {
             Self::OperationNotSupported { driver, operation } => {
                 format!("The '{driver}' driver does not support {operation} operation.")
             },
-            Self::InvalidConfiguration { .. } | Self::NotRegistered { .. } => self.to_string(),
+            Self::NotDetected { path } => {
+                format!("Could not detect the format of '{path}' from its content or extension.")
+            },
+            Self::FormatMismatch {
+                path,
+                driver,
+                detected,
+            } => format!(
+                "'{path}' was opened with the '{driver}' driver, but its content is {detected}."
+            ),
+            Self::InvalidConfiguration { .. }
+            | Self::NotRegistered { .. }
+            | Self::PluginLoad { .. } => self.to_string(),
         }
     }
 
@@ -312,6 +358,16 @@ impl DriverError {
             Self::NotRegistered { .. } => {
                 Some("This driver may not be enabled. Check your configuration.".to_string())
             },
+            Self::NotDetected { .. } => Some(
+                "Name the driver explicitly. Run 'geoetl drivers' to see all available drivers."
+                    .to_string(),
+            ),
+            Self::FormatMismatch { detected, .. } => Some(format!(
+                "Use the '{detected}' driver, or omit the driver to detect it automatically."
+            )),
+            Self::PluginLoad { .. } => Some(
+                "Check that the library is a GeoETL plugin built for this plugin ABI version, or remove it from GEOETL_PLUGIN_PATH.".to_string(),
+            ),
             Self::InvalidConfiguration { .. } => None,
         }
     }
@@ -323,9 +379,17 @@ impl IoError {
             Self::Read { format, path, .. } => {
                 format!("Failed to read {} file: {}", format, path.display())
             },
-            Self::Write { format, path, .. } => {
-                format!("Failed to write {} file: {}", format, path.display())
-            },
+            // The cause is kept: a write fails when executing the whole conversion, so it is
+            // often not about writing at all (e.g. a geometry that cannot be cast)
+            Self::Write {
+                format,
+                path,
+                source,
+            } => format!(
+                "Failed to write {} file: {}: {source}",
+                format,
+                path.display()
+            ),
             Self::FileNotFound { path } => {
                 format!("File not found: {}", path.display())
             },
@@ -344,6 +408,9 @@ impl IoError {
             Self::InvalidPath { .. } => {
                 Some("Ensure the path is valid and properly formatted.".to_string())
             },
+            Self::AlreadyExists { .. } => {
+                Some("Use --overwrite to replace the output or --append to add to it.".to_string())
+            },
             _ => None,
         }
     }
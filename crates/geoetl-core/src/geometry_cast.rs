@@ -0,0 +1,203 @@
+//! The geometry type check of `convert --nlt`.
+//!
+//! The geometries are checked as they stream to the writer, so the input is only read once.
+//! Batches pass through until one holds a geometry that does not fit the requested type; the
+//! rest of the input is then drained without being written, to report every feature that
+//! does not fit, and the stream ends with a [`GeometryCastError`].
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use datafusion::arrow::array::RecordBatch;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::{SendableRecordBatchStream, TaskContext};
+use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::streaming::PartitionStream;
+use datafusion::physical_plan::{ExecutionPlan, ExecutionPlanProperties};
+use futures::StreamExt;
+use geoetl_operations::cast_failures;
+
+/// Number of failing features listed in a [`GeometryCastError`]
+const LISTED_FEATURES: usize = 20;
+
+/// Geometries that do not fit the requested geometry type, or a type that is not known.
+#[derive(Debug)]
+pub(crate) struct GeometryCastError(pub(crate) String);
+
+impl fmt::Display for GeometryCastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for GeometryCastError {}
+
+/// The output of a plan, checked against a geometry type as a single partition.
+///
+/// Every `GeoArrow` column is checked; features are numbered from zero in the order their
+/// batches arrive, after filtering and explosion.
+#[derive(Debug)]
+pub(crate) struct GeometryCastCheck {
+    input: Arc<dyn ExecutionPlan>,
+    schema: SchemaRef,
+    geometry_type: String,
+    /// Indices of the `GeoArrow` columns of the input
+    columns: Vec<usize>,
+}
+
+impl GeometryCastCheck {
+    pub(crate) fn new(input: Arc<dyn ExecutionPlan>, geometry_type: &str) -> Self {
+        // Features are numbered across the whole input
+        let input: Arc<dyn ExecutionPlan> = if input.output_partitioning().partition_count() > 1 {
+            Arc::new(CoalescePartitionsExec::new(input))
+        } else {
+            input
+        };
+        let schema = input.schema();
+        let columns = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| {
+                field
+                    .metadata()
+                    .get("ARROW:extension:name")
+                    .is_some_and(|name| name.starts_with("geoarrow"))
+            })
+            .map(|(index, _)| index)
+            .collect();
+        Self {
+            input,
+            schema,
+            geometry_type: geometry_type.to_string(),
+            columns,
+        }
+    }
+}
+
+impl PartitionStream for GeometryCastCheck {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let schema = Arc::clone(&self.schema);
+        let input = match self.input.execute(0, ctx) {
+            Ok(input) => input,
+            Err(e) => {
+                return Box::pin(RecordBatchStreamAdapter::new(
+                    schema,
+                    futures::stream::once(async { Err(e) }),
+                ));
+            },
+        };
+
+        let report = CastReport::new(&self.geometry_type, self.columns.clone());
+        let stream = futures::stream::unfold(Some((input, report)), |state| async move {
+            let (mut input, mut report) = state?;
+            loop {
+                match input.next().await {
+                    Some(Ok(batch)) => {
+                        if let Err(e) = report.add(&batch) {
+                            return Some((Err(e), None));
+                        }
+                        if report.is_empty() {
+                            return Some((Ok(batch), Some((input, report))));
+                        }
+                        // The remaining batches are only read to report their features
+                    },
+                    Some(Err(e)) => return Some((Err(e), None)),
+                    None => {
+                        let error = report.into_error()?;
+                        return Some((Err(DataFusionError::External(Box::new(error))), None));
+                    },
+                }
+            }
+        });
+        Box::pin(RecordBatchStreamAdapter::new(schema, stream))
+    }
+}
+
+/// The features found so far whose geometries do not fit a geometry type.
+struct CastReport {
+    geometry_type: String,
+    columns: Vec<usize>,
+    feature_count: u64,
+    /// Number of failing geometries of each type
+    counts: BTreeMap<String, usize>,
+    /// The first failing features, with their column and geometry type
+    failures: Vec<(u64, String, String)>,
+}
+
+impl CastReport {
+    fn new(geometry_type: &str, columns: Vec<usize>) -> Self {
+        Self {
+            geometry_type: geometry_type.to_string(),
+            columns,
+            feature_count: 0,
+            counts: BTreeMap::new(),
+            failures: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, batch: &RecordBatch) -> Result<()> {
+        let schema = batch.schema();
+        let mut batch_failures = Vec::new();
+        for &index in &self.columns {
+            let field = schema.field(index);
+            let names = cast_failures(batch.column(index).as_ref(), field, &self.geometry_type)
+                .map_err(|e| {
+                    DataFusionError::External(Box::new(GeometryCastError(e.to_string())))
+                })?;
+            for (row, name) in names.into_iter().enumerate() {
+                if let Some(name) = name {
+                    *self.counts.entry(name.clone()).or_default() += 1;
+                    batch_failures.push((
+                        self.feature_count + row as u64,
+                        field.name().clone(),
+                        name,
+                    ));
+                }
+            }
+        }
+        batch_failures.sort_by_key(|(feature, ..)| *feature);
+        let listed = LISTED_FEATURES - self.failures.len();
+        self.failures
+            .extend(batch_failures.into_iter().take(listed));
+        self.feature_count += batch.num_rows() as u64;
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    fn into_error(self) -> Option<GeometryCastError> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let total: usize = self.counts.values().sum();
+        let counts: Vec<String> = self
+            .counts
+            .iter()
+            .map(|(name, count)| format!("{count} {name}"))
+            .collect();
+        let mut message = format!(
+            "cannot cast geometries to {}: {} in {} features",
+            self.geometry_type,
+            counts.join(", "),
+            self.feature_count
+        );
+        for (feature, column, name) in &self.failures {
+            message.push_str(&format!("\n  feature {feature} ({column}): {name}"));
+        }
+        if total > self.failures.len() {
+            message.push_str(&format!("\n  and {} more", total - self.failures.len()));
+        }
+        Some(GeometryCastError(message))
+    }
+}
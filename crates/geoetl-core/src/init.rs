@@ -10,8 +10,9 @@ static INIT: Once = Once::new();
 /// Initializes the `GeoETL` core library by registering all format drivers.
 ///
 /// This function registers all built-in format drivers (`CSV`, `GeoJSON`, etc.)
-/// with the global driver registry. It uses `Once` to ensure registration
-/// happens only once, even if called multiple times.
+/// with the global driver registry, followed by the format plugins listed in the
+/// `GEOETL_PLUGIN_PATH` environment variable (see [`crate::plugins`]). It uses `Once`
+/// to ensure registration happens only once, even if called multiple times.
 ///
 /// # Examples
 ///
@@ -31,5 +32,8 @@ pub fn initialize() {
         // Register all format drivers
         datafusion_csv::register_csv_format();
         datafusion_geojson::register_geojson_format();
+
+        // Register drivers provided by plugin libraries
+        crate::plugins::load_plugins_from_env();
     });
 }
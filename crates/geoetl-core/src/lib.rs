@@ -1,11 +1,12 @@
 //! Core library for the `GeoETL` project, providing fundamental functionalities for geospatial data processing.
 //!
 //! This crate includes:
-//! - **Driver Registry**: A static registry of supported geospatial data formats and their capabilities.
+//! - **Driver Registry**: The registered geospatial data formats and their runtime capabilities, alongside a catalog of planned formats.
+//! - **Plugins**: Format drivers loaded at startup from shared libraries listed in `GEOETL_PLUGIN_PATH`.
 //! - **Data Structures**: Core data structures for representing geospatial features and geometries (planned).
 //! - **ETL Operations**: Core Extract, Transform, Load operations (planned).
 //!
-//! The [`drivers`] module exposes the static driver registry consumed by the CLI and other parts of the system.
+//! The [`drivers`] module exposes the driver registry consumed by the CLI and other parts of the system.
 //!
 //! # Examples
 //!
@@ -24,7 +25,10 @@
 
 pub mod drivers;
 pub mod error;
+mod geometry_cast;
 pub mod init;
 pub mod operations;
+pub mod plugins;
 pub mod types;
 pub mod utils;
+mod value_counts;
@@ -3,14 +3,41 @@
 //! This module provides the main functions for Extract, Transform, and Load (ETL)
 //! operations on geospatial data, leveraging the driver registry for format support.
 
-use crate::drivers::Driver;
-use crate::error::{self, DriverError, GeoEtlError, IoErrorExt};
-use crate::types::{DatasetInfo, FieldInfo, GeometryColumnInfo};
+use crate::drivers::{Driver, SupportStatus};
+use crate::error::{self, ConfigError, DriverError, GeoEtlError};
+use crate::geometry_cast::{GeometryCastCheck, GeometryCastError};
+use crate::types::{
+    ConvertOptions, DatasetInfo, Dimension, Extent, FieldInfo, FieldStats, GeometryColumnInfo,
+    InfoOptions, QueryResult, Simplification, SqlTable, ValidationIssue, ValidationReport,
+    WriteMode,
+};
 use crate::utils::ArrowDataTypeExt;
-use datafusion::arrow::array::RecordBatch;
-use datafusion::prelude::SessionContext;
-use log::info;
-use std::fs::File;
+use crate::value_counts::ValueCountsUdf;
+use datafusion::arrow::array::{Array, AsArray, RecordBatch, StringArray, UInt64Array};
+use datafusion::arrow::datatypes::{DataType, Field, Float64Type, Schema, UInt64Type};
+use datafusion::arrow::util::display::{ArrayFormatter, FormatOptions};
+use datafusion::catalog::streaming::StreamingTable;
+use datafusion::common::UnnestOptions;
+use datafusion::functions::core::expr_fn::get_field;
+use datafusion::functions_aggregate::expr_fn::{approx_distinct, count, max, min};
+use datafusion::logical_expr::{AggregateUDF, Expr, ScalarUDF};
+use datafusion::physical_plan::collect;
+use datafusion::prelude::{DataFrame, SessionConfig, SessionContext, cast, col, ident, lit};
+use futures::StreamExt;
+use geoetl_core_common::{
+    DataReader, DataWriter, FormatFactory, FormatOption, OptionValues, default_layer_name,
+};
+use geoetl_operations::crs::{field_crs, resolve_crs};
+use geoetl_operations::geometry::read_geometries;
+use geoetl_operations::validity::validity_issues;
+use geoetl_operations::{
+    CastGeometryUdf, ConstructorUdf, DumpUdf, ForceDimensionUdf, GeometryTypeUdf, IntersectionUdf,
+    MakeValidUdf, MeasureUdf, MultiUdf, PredicateUdf, TransformUdf, field_geometry_type,
+};
+use log::{info, warn};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
 
 // Type alias for backward compatibility during migration
 type Result<T> = std::result::Result<T, GeoEtlError>;
@@ -18,8 +45,8 @@ type Result<T> = std::result::Result<T, GeoEtlError>;
 /// Initialize a `DataFusion` session context and register a dataset.
 ///
 /// This is a common entry point for all ETL operations that need to work with a dataset.
-/// It creates a new session context, registers the dataset with the specified parameters,
-/// and returns the context ready for use.
+/// It creates a new session context with the spatial SQL functions (`ST_*`) available,
+/// registers the dataset with the specified parameters, and returns the context ready for use.
 ///
 /// # Arguments
 ///
@@ -27,10 +54,11 @@ type Result<T> = std::result::Result<T, GeoEtlError>;
 /// * `driver` - The driver responsible for reading the format
 /// * `geometry_column` - Name of the geometry column (for CSV)
 /// * `geometry_type` - Optional geometry type hint (for CSV)
+/// * `open_options` - Driver-specific open options
 ///
 /// # Returns
 ///
-/// A `SessionContext` with the dataset registered as "dataset" table.
+/// A `SessionContext` with the first layer of the dataset registered as "dataset" table.
 ///
 /// # Errors
 ///
@@ -42,8 +70,9 @@ async fn initialize_context(
     driver: &Driver,
     geometry_column: &str,
     geometry_type: Option<&str>,
+    open_options: &OptionValues,
 ) -> Result<SessionContext> {
-    let ctx = SessionContext::new();
+    let ctx = geoetl_operations::spatial_session_context(SessionConfig::new());
     let table_name = "dataset";
     register_catalog(
         &ctx,
@@ -52,46 +81,204 @@ async fn initialize_context(
         table_name,
         geometry_column,
         geometry_type,
+        open_options,
     )
     .await?;
     Ok(ctx)
 }
 
-/// Prepare format-specific options for reading.
+/// Complete and validate the key/value options passed to a driver.
 ///
-/// This helper function creates the appropriate options object for each format type,
-/// encapsulating format-specific configuration logic.
+/// The common `GEOMETRY_COLUMN` and `GEOMETRY_TYPE` options are filled in from the
+/// operation's arguments when the driver declares them and the caller did not set them
+/// explicitly. Every option is then checked against the driver's declared options.
 ///
 /// # Arguments
 ///
-/// * `driver_name` - The short name of the driver (e.g., "`CSV`", "`GeoJSON`")
-/// * `geometry_column` - Name of the geometry column (used for CSV)
-/// * `geometry_type` - Optional geometry type hint (used for CSV)
+/// * `kind` - Kind of options, used in error messages (`"open"` or `"creation"`)
+/// * `supported` - Options declared by the driver
+/// * `given` - Options set by the caller
+/// * `geometry_column` - Name of the geometry column
+/// * `geometry_type` - Optional geometry type hint
 ///
 /// # Returns
 ///
-/// A boxed `Any` containing the format-specific options, or an error if the driver is unknown.
-fn prepare_reader_options(
-    driver_name: &str,
+/// The options to pass to the driver, or a `ConfigError::InvalidOption` if an option is
+/// unknown or has an invalid value.
+fn driver_options(
+    kind: &str,
+    supported: &[FormatOption],
+    given: &OptionValues,
     geometry_column: &str,
     geometry_type: Option<&str>,
-) -> Result<Box<dyn std::any::Any + Send>> {
-    match driver_name {
-        "CSV" => {
-            use datafusion_csv::CsvFormatOptions;
-            let mut options = CsvFormatOptions::new();
-            let geoarrow_type = parse_geometry_type(geometry_type.unwrap_or("Geometry"))?;
-            options = options.with_geometry_from_wkt(geometry_column, geoarrow_type);
-            Ok(Box::new(options))
-        },
-        "GeoJSON" => {
-            use datafusion_geojson::GeoJsonFormatOptions;
-            Ok(Box::new(GeoJsonFormatOptions::default()))
-        },
-        _ => Err(DriverError::NotRegistered {
-            driver: driver_name.to_string(),
+) -> Result<OptionValues> {
+    let declares = |name: &str| supported.iter().any(|option| option.name == name);
+
+    let mut options = given.clone();
+    if declares("GEOMETRY_COLUMN") {
+        options.insert_default("GEOMETRY_COLUMN", geometry_column);
+    }
+    if let Some(geometry_type) = geometry_type
+        && declares("GEOMETRY_TYPE")
+    {
+        options.insert_default("GEOMETRY_TYPE", geometry_type);
+    }
+
+    options
+        .validate(supported)
+        .map_err(|e| ConfigError::InvalidOption {
+            option: kind.to_string(),
+            message: e.to_string(),
+        })?;
+    Ok(options)
+}
+
+/// A dataset opened with its driver's reader, whose layers can be listed and registered.
+struct DatasetSource {
+    path: String,
+    driver: String,
+    reader: Arc<dyn DataReader>,
+    options: OptionValues,
+}
+
+impl DatasetSource {
+    /// Look up the driver's reader and prepare its open options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the driver is not registered or cannot read, or if an open
+    /// option is not supported by the driver.
+    fn open(
+        input: &str,
+        driver: &Driver,
+        geometry_column: &str,
+        geometry_type: Option<&str>,
+        open_options: &OptionValues,
+    ) -> Result<Self> {
+        // Get factory from global registry
+        let registry = geoetl_core_common::driver_registry();
+        let factory =
+            registry
+                .find_factory(driver.short_name)
+                .ok_or_else(|| DriverError::NotRegistered {
+                    driver: driver.short_name.to_string(),
+                })?;
+
+        // Create reader strategy
+        let reader = factory
+            .create_reader()
+            .ok_or_else(|| DriverError::OperationNotSupported {
+                driver: driver.short_name.to_string(),
+                operation: "reading".to_string(),
+            })?;
+
+        // Prepare format-specific options
+        let options = driver_options(
+            "open",
+            &factory.open_options(),
+            open_options,
+            geometry_column,
+            geometry_type,
+        )?;
+
+        Ok(Self {
+            path: input.to_string(),
+            driver: driver.short_name.to_string(),
+            reader,
+            options,
+        })
+    }
+
+    /// Wrap a reader error with the dataset's format and path.
+    fn read_error(&self, e: anyhow::Error) -> GeoEtlError {
+        GeoEtlError::Io(error::IoError::Read {
+            format: self.driver.clone(),
+            path: self.path.clone().into(),
+            source: e.into(),
+        })
+    }
+
+    /// List the layers of the dataset, in dataset order.
+    async fn layers(&self, ctx: &SessionContext) -> Result<Vec<String>> {
+        self.reader
+            .layers(&ctx.state(), &self.path, &self.options)
+            .await
+            .map_err(|e| self.read_error(e))
+    }
+
+    /// Resolve the requested layer names against the dataset's `layers`.
+    ///
+    /// Names match case-insensitively and resolve to the dataset's spelling. Without
+    /// requested names, every layer is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError::InvalidOption` if a requested layer does not exist or the
+    /// dataset has no layers.
+    fn select_layers(&self, layers: &[String], requested: &[String]) -> Result<Vec<String>> {
+        if layers.is_empty() {
+            return Err(ConfigError::InvalidOption {
+                option: "layer".to_string(),
+                message: format!("the dataset '{}' has no layers", self.path),
+            }
+            .into());
         }
-        .into()),
+        if requested.is_empty() {
+            return Ok(layers.to_vec());
+        }
+
+        requested
+            .iter()
+            .map(|name| {
+                layers
+                    .iter()
+                    .find(|layer| layer.eq_ignore_ascii_case(name))
+                    .cloned()
+                    .ok_or_else(|| {
+                        ConfigError::InvalidOption {
+                            option: "layer".to_string(),
+                            message: format!(
+                                "layer '{name}' not found; available layers: {}",
+                                layers.join(", ")
+                            ),
+                        }
+                        .into()
+                    })
+            })
+            .collect()
+    }
+
+    /// Register a layer of the dataset as `table_name`, or the first layer when `layer` is
+    /// `None`.
+    async fn register(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        layer: Option<&str>,
+    ) -> Result<()> {
+        // Use polymorphic dispatch - no switch statement needed!
+        let state = ctx.state();
+        let table = match layer {
+            Some(layer) => {
+                self.reader
+                    .create_layer_provider(&state, &self.path, layer, &self.options)
+                    .await
+            },
+            None => {
+                self.reader
+                    .create_table_provider(&state, &self.path, &self.options)
+                    .await
+            },
+        }
+        .map_err(|e| self.read_error(e))?;
+
+        ctx.register_table(table_name, table).map_err(|e| {
+            GeoEtlError::from(anyhow::anyhow!(
+                "Failed to register table '{table_name}': {e}"
+            ))
+        })?;
+
+        Ok(())
     }
 }
 
@@ -99,7 +286,8 @@ fn prepare_reader_options(
 ///
 /// This function handles the registration of different data formats (`CSV`, `GeoJSON`, etc.)
 /// into a `DataFusion` session context, making them available for SQL queries or conversion.
-/// Uses the factory pattern to dynamically dispatch to the appropriate format reader.
+/// Uses the factory pattern to dynamically dispatch to the appropriate format reader; the
+/// first layer of multi-layer datasets is registered.
 ///
 /// # Arguments
 ///
@@ -109,6 +297,7 @@ fn prepare_reader_options(
 /// * `table_name` - Name to register the table as
 /// * `geometry_column` - Name of the geometry column (for CSV)
 /// * `geometry_type` - Optional geometry type hint (for CSV)
+/// * `open_options` - Driver-specific open options
 ///
 /// # Returns
 ///
@@ -120,8 +309,15 @@ async fn register_catalog(
     table_name: &str,
     geometry_column: &str,
     geometry_type: Option<&str>,
+    open_options: &OptionValues,
 ) -> Result<()> {
-    // Get factory from global registry
+    DatasetSource::open(input, driver, geometry_column, geometry_type, open_options)?
+        .register(ctx, table_name, None)
+        .await
+}
+
+/// Look up the driver's format factory and writer.
+fn dataset_writer(driver: &Driver) -> Result<(Arc<dyn FormatFactory>, Arc<dyn DataWriter>)> {
     let registry = geoetl_core_common::driver_registry();
     let factory =
         registry
@@ -130,165 +326,162 @@ async fn register_catalog(
                 driver: driver.short_name.to_string(),
             })?;
 
-    // Create reader strategy
-    let reader = factory
-        .create_reader()
+    let writer = factory
+        .create_writer()
         .ok_or_else(|| DriverError::OperationNotSupported {
             driver: driver.short_name.to_string(),
-            operation: "reading".to_string(),
-        })?;
-
-    // Prepare format-specific options
-    let options = prepare_reader_options(driver.short_name, geometry_column, geometry_type)?;
-
-    // Use polymorphic dispatch - no switch statement needed!
-    let table = reader
-        .create_table_provider(&ctx.state(), input, options)
-        .await
-        .map_err(|e| {
-            GeoEtlError::Io(error::IoError::Read {
-                format: driver.short_name.to_string(),
-                path: input.into(),
-                source: e.into(),
-            })
+            operation: "writing".to_string(),
         })?;
 
-    ctx.register_table(table_name, table).map_err(|e| {
-        GeoEtlError::from(anyhow::anyhow!(
-            "Failed to register table '{table_name}': {e}"
-        ))
-    })?;
-
-    Ok(())
-}
-
-/// Parse geometry type string into `GeoArrowType`
-fn parse_geometry_type(geom_type_str: &str) -> Result<geoarrow_schema::GeoArrowType> {
-    use geoarrow_schema::{
-        Dimension, GeoArrowType, GeometryType, LineStringType, MultiLineStringType, MultiPointType,
-        MultiPolygonType, PointType, PolygonType,
-    };
-    use std::sync::Arc;
-
-    let geoarrow_type = match geom_type_str.to_lowercase().as_str() {
-        "geometry" => GeoArrowType::Geometry(GeometryType::new(Arc::default())),
-        "point" => GeoArrowType::Point(PointType::new(Dimension::XY, Arc::default())),
-        "linestring" => {
-            GeoArrowType::LineString(LineStringType::new(Dimension::XY, Arc::default()))
-        },
-        "polygon" => GeoArrowType::Polygon(PolygonType::new(Dimension::XY, Arc::default())),
-        "multipoint" => {
-            GeoArrowType::MultiPoint(MultiPointType::new(Dimension::XY, Arc::default()))
-        },
-        "multilinestring" => {
-            GeoArrowType::MultiLineString(MultiLineStringType::new(Dimension::XY, Arc::default()))
-        },
-        "multipolygon" => {
-            GeoArrowType::MultiPolygon(MultiPolygonType::new(Dimension::XY, Arc::default()))
-        },
-        _ => {
-            return Err(error::FormatError::UnsupportedGeometryType {
-                geometry_type: geom_type_str.to_string(),
-            }
-            .into());
-        },
-    };
-    Ok(geoarrow_type)
+    Ok((factory, writer))
 }
 
-/// Write data to CSV file
-fn write_csv(output: &str, batches: &[RecordBatch], geometry_column: &str) -> Result<()> {
-    use datafusion_csv::{CsvWriterOptions, write_csv};
-    info!("Writing CSV file: {output}");
-
-    // Convert geometry columns to WKT before writing
-    let converted_batches = convert_geometry_to_wkt(batches, geometry_column)?;
+/// Paths the layers of a conversion are written to, in layer order.
+///
+/// The layers share the output dataset when the driver stores several layers in one
+/// dataset or a single layer is written. Otherwise `output` is created as a directory
+/// holding one file per layer, named after the layer with the driver's first extension.
+fn layer_outputs(output: &str, driver: &Driver, layers: &[String]) -> Result<Vec<String>> {
+    let (factory, writer) = dataset_writer(driver)?;
+    if layers.len() == 1 || writer.supports_layers() {
+        return Ok(vec![output.to_string(); layers.len()]);
+    }
 
-    let mut output_file = File::create(output)
-        .map_err(|e| GeoEtlError::from(anyhow::anyhow!("Failed to create output file: {e}")))?;
-    let options = CsvWriterOptions::default();
-    write_csv(&mut output_file, &converted_batches, &options)
-        .map_err(|e| GeoEtlError::from(anyhow::anyhow!("Failed to write CSV file: {e}")))
+    std::fs::create_dir_all(output).map_err(|e| {
+        GeoEtlError::Io(error::IoError::Write {
+            format: driver.short_name.to_string(),
+            path: output.into(),
+            source: e.into(),
+        })
+    })?;
+    let extension = factory.extensions().first().copied();
+    Ok(layers
+        .iter()
+        .map(|layer| {
+            let file_name = extension.map_or_else(|| layer.clone(), |ext| format!("{layer}.{ext}"));
+            Path::new(output)
+                .join(file_name)
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect())
 }
 
-/// Convert geometry columns to WKT format for CSV writing
-fn convert_geometry_to_wkt(
-    batches: &[RecordBatch],
+/// Write the rows of `table` as layer `layer` of `output` through the driver's registered
+/// writer, replacing, appending to or refusing to replace an existing output as `mode` says.
+///
+/// The writer plan is built by the format factory and executed in the table's session;
+/// the number of rows written is read from the `count` column it reports.
+///
+/// # Returns
+///
+/// The number of rows written.
+///
+/// # Errors
+///
+/// Returns `IoError::AlreadyExists` if `mode` is `WriteMode::Fail` and the writer found the
+/// output already existing, a `ConfigError::InvalidOption` if the geometries do not fit the
+/// type checked by [`check_geometry_cast`], after discarding what was written, and
+/// `IoError::Write` if writing fails otherwise.
+async fn write_dataset(
+    table: DataFrame,
+    output: &str,
+    driver: &Driver,
+    layer: &str,
     geometry_column: &str,
-) -> Result<Vec<RecordBatch>> {
-    use arrow_schema::Schema;
-    use geoarrow_array::GeoArrowArray;
-    use geoarrow_array::array::from_arrow_array;
-    use geoarrow_array::cast::to_wkt;
-    use std::sync::Arc;
-
-    let mut converted_batches = Vec::with_capacity(batches.len());
-
-    for batch in batches {
-        let schema = batch.schema();
-
-        // Find the geometry column index
-        let geom_idx = schema
-            .fields()
-            .iter()
-            .position(|field| field.name() == geometry_column);
-
-        if let Some(idx) = geom_idx {
-            // Get the geometry column and its field
-            let geom_array = batch.column(idx);
-            let geom_field = schema.field(idx);
-
-            // Convert Arrow array to GeoArrowArray
-            let geoarrow_array =
-                from_arrow_array(geom_array.as_ref(), geom_field).map_err(|e| {
-                    GeoEtlError::from(anyhow::anyhow!("Failed to convert to GeoArrowArray: {e}"))
-                })?;
-
-            // Convert to WKT using geoarrow cast (using i32 offset)
-            let wkt_array: geoarrow_array::array::WktArray =
-                to_wkt(&geoarrow_array).map_err(|e| {
-                    GeoEtlError::from(anyhow::anyhow!("Failed to convert geometry to WKT: {e}"))
-                })?;
-
-            // Create new schema with WKT column
-            let mut new_fields = schema.fields().to_vec();
-            new_fields[idx] = Arc::new(arrow_schema::Field::new(
-                geometry_column,
-                arrow_schema::DataType::Utf8,
-                true,
-            ));
-            let new_schema = Arc::new(Schema::new(new_fields));
-
-            // Create new columns with WKT
-            let mut new_columns = batch.columns().to_vec();
-            new_columns[idx] = wkt_array.to_array_ref();
-
-            // Create new batch
-            let new_batch = RecordBatch::try_new(new_schema, new_columns).map_err(|e| {
-                GeoEtlError::from(anyhow::anyhow!(
-                    "Failed to create record batch with WKT: {e}"
-                ))
-            })?;
+    mode: WriteMode,
+    creation_options: &OptionValues,
+) -> Result<u64> {
+    let (factory, writer) = dataset_writer(driver)?;
+
+    let options = driver_options(
+        "creation",
+        &factory.creation_options(),
+        creation_options,
+        geometry_column,
+        None,
+    )?;
 
-            converted_batches.push(new_batch);
-        } else {
-            // No geometry column found, use batch as-is
-            converted_batches.push(batch.clone());
+    let task_ctx = Arc::new(table.task_ctx());
+    let plan = table
+        .create_physical_plan()
+        .await
+        .map_err(error::DataFusionError::from)?;
+
+    info!("Writing {} layer '{layer}': {output}", driver.short_name);
+    let existing_len = std::fs::metadata(output)
+        .ok()
+        .filter(std::fs::Metadata::is_file)
+        .map(|metadata| metadata.len());
+    let write_error = |source: Box<dyn std::error::Error + Send + Sync>| {
+        let cast_error =
+            std::iter::successors(Some(source.as_ref() as &dyn std::error::Error), |e| {
+                e.source()
+            })
+            .find_map(|e| e.downcast_ref::<GeometryCastError>());
+        if let Some(cast_error) = cast_error {
+            discard_partial_output(output, mode, existing_len);
+            return GeoEtlError::from(ConfigError::InvalidOption {
+                option: "nlt".to_string(),
+                message: cast_error.to_string(),
+            });
         }
-    }
+        if mode == WriteMode::Fail && is_already_exists(source.as_ref()) {
+            return GeoEtlError::from(error::IoError::AlreadyExists {
+                path: output.into(),
+            });
+        }
+        GeoEtlError::Io(error::IoError::Write {
+            format: driver.short_name.to_string(),
+            path: output.into(),
+            source,
+        })
+    };
+    let writer_plan = writer
+        .create_layer_writer_plan(plan, output, layer, mode, &options)
+        .await
+        .map_err(|e| write_error(e.into()))?;
+    let batches = collect(writer_plan, task_ctx)
+        .await
+        .map_err(|e| write_error(e.into()))?;
+
+    Ok(batches
+        .iter()
+        .filter_map(|batch| batch.column_by_name("count"))
+        .filter_map(|column| column.as_any().downcast_ref::<UInt64Array>())
+        .flat_map(|counts| counts.iter().flatten())
+        .sum())
+}
 
-    Ok(converted_batches)
+/// Undo what a write that failed on its geometry types left of the output file at `path`.
+///
+/// The rows appended to a file that existed, `existing_len` bytes long, are truncated away;
+/// any other output file is removed, as it was created or truncated by the write.
+fn discard_partial_output(path: &str, mode: WriteMode, existing_len: Option<u64>) {
+    let path = Path::new(path);
+    let result = match existing_len {
+        Some(len) if mode == WriteMode::Append => std::fs::OpenOptions::new()
+            .write(true)
+            .open(path)
+            .and_then(|file| file.set_len(len)),
+        _ if path.is_file() => std::fs::remove_file(path),
+        _ => Ok(()),
+    };
+    if let Err(e) = result {
+        warn!(
+            "Failed to discard the partially written '{}': {e}",
+            path.display()
+        );
+    }
 }
 
-/// Write data to `GeoJSON` file
-fn write_geojson(output: &str, batches: &[RecordBatch]) -> Result<()> {
-    use datafusion_geojson::{GeoJsonWriterOptions, write_geojson};
-    info!("Writing GeoJSON file: {output}");
-    let mut output_file = File::create(output)
-        .map_err(|e| GeoEtlError::from(anyhow::anyhow!("Failed to create output file: {e}")))?;
-    let options = GeoJsonWriterOptions::default();
-    write_geojson(&mut output_file, batches, &options)
-        .map_err(|e| GeoEtlError::from(anyhow::anyhow!("Failed to write GeoJSON file: {e}")))
+/// Whether `error` was caused by creating a file that already exists.
+fn is_already_exists(error: &(dyn std::error::Error + 'static)) -> bool {
+    std::iter::successors(Some(error), |error| error.source()).any(|error| {
+        error
+            .downcast_ref::<std::io::Error>()
+            .is_some_and(|error| error.kind() == std::io::ErrorKind::AlreadyExists)
+    })
 }
 
 /// Performs a geospatial data conversion from an input format to an output format.
@@ -296,6 +489,10 @@ fn write_geojson(output: &str, batches: &[RecordBatch]) -> Result<()> {
 /// This function orchestrates the reading of data from the `input` path using the
 /// `input_driver` and writing it to the `output` path using the `output_driver`.
 ///
+/// The selected layers, or every layer of the input, are converted in turn. Drivers that
+/// store several layers in one dataset write them all to `output`; otherwise several
+/// layers are written to a directory at `output`, one file per layer.
+///
 /// # Arguments
 ///
 /// * `input` - The path to the input geospatial data file.
@@ -304,6 +501,8 @@ fn write_geojson(output: &str, batches: &[RecordBatch]) -> Result<()> {
 /// * `output_driver` - The driver responsible for writing the output format.
 /// * `geometry_column` - Name of the geometry column (for CSV)
 /// * `geometry_type` - Optional geometry type hint (for CSV)
+/// * `options` - Layers to convert, driver-specific open and creation options, and the
+///   filter, column selection, renames and reprojection applied before writing
 ///
 /// # Returns
 ///
@@ -313,7 +512,16 @@ fn write_geojson(output: &str, batches: &[RecordBatch]) -> Result<()> {
 ///
 /// This function will return an error if:
 /// - The file cannot be read or parsed.
+/// - A selected layer does not exist.
 /// - The file format is not yet implemented.
+/// - An open or creation option is not supported by the driver.
+/// - The filter is invalid or a selected or renamed column does not exist.
+/// - A CRS is assigned to a format that records its own, or the source or target CRS is
+///   not supported.
+/// - A geometry does not fit the requested geometry type; the features that do not are
+///   listed, and what was written of the output is discarded.
+/// - The output already exists and the write mode is `WriteMode::Fail`, or its columns do
+///   not match the written ones when appending.
 /// - The output file cannot be written.
 ///
 /// # Note
@@ -326,50 +534,595 @@ pub async fn convert(
     output_driver: &Driver,
     geometry_column: &str,
     geometry_type: Option<&str>,
+    options: &ConvertOptions,
 ) -> Result<()> {
     info!("Starting conversion:");
     info!("Input: {} (Driver: {})", input, input_driver.short_name);
     info!("Output: {} (Driver: {})", output, output_driver.short_name);
 
-    // Initialize context and register dataset
-    let ctx = initialize_context(input, input_driver, geometry_column, geometry_type).await?;
+    // Open the dataset and resolve the layers to convert
+    let open_options = assign_crs(
+        input_driver,
+        &options.open_options,
+        options.assigned_crs.as_deref(),
+    )?;
+    let source = DatasetSource::open(
+        input,
+        input_driver,
+        geometry_column,
+        geometry_type,
+        &open_options,
+    )?;
+    let ctx = geoetl_operations::spatial_session_context(SessionConfig::new());
+    let layers = source.layers(&ctx).await?;
+    let layers = source.select_layers(&layers, &options.layers)?;
+
+    let outputs = layer_outputs(output, output_driver, &layers)?;
+
+    // An explicit GEOMETRY_COLUMN open option names the geometry column being written,
+    // under its new name if it was renamed
+    let geometry_column = options
+        .open_options
+        .get("GEOMETRY_COLUMN")
+        .unwrap_or(geometry_column);
+    let geometry_column = options
+        .renames
+        .iter()
+        .find(|(old, _)| old == geometry_column)
+        .map_or(geometry_column, |(_, new)| new.as_str());
+
+    for (layer, output) in layers.iter().zip(&outputs) {
+        info!("Converting layer '{layer}'");
+        let ctx = geoetl_operations::spatial_session_context(SessionConfig::new());
+        source.register(&ctx, "dataset", Some(layer)).await?;
+
+        // Read the layer and apply the requested transforms
+        let table = ctx
+            .table("dataset")
+            .await
+            .map_err(|e| GeoEtlError::from(anyhow::anyhow!("Failed to get table: {e}")))?;
+        let mut table = apply_transforms(table, options)?;
+        if let Some(geometry_type) = &options.geometry_type {
+            table = check_geometry_cast(&ctx, table, geometry_type).await?;
+        }
+        let table = coerce_geometry_types(table, options)?;
+        let geometry_column = output_geometry_column(table.schema().as_arrow(), geometry_column);
+
+        // Stream the table through the output driver's writer
+        let total_rows = write_dataset(
+            table,
+            output,
+            output_driver,
+            layer,
+            &geometry_column,
+            options.write_mode,
+            &options.creation_options,
+        )
+        .await?;
+        info!("Total rows: {total_rows}");
+    }
+
+    info!("Conversion completed successfully");
+    Ok(())
+}
 
-    // Collect batches from the registered table
+/// Apply the spatial filter, filter, column selection, renames, geometry repair,
+/// simplification, reprojection, grid snapping and explosion of a conversion to a table.
+///
+/// Each step is a `DataFrame` operation, so the filters and projection are pushed down into
+/// the scan where the format supports it. The filter sees the input column names, and the
+/// renames apply to the selected columns. Simplification uses input CRS units and grid
+/// snapping output CRS units. Geometry type coercion is left to [`coerce_geometry_types`],
+/// as these operations produce `geoarrow.geometry` columns of mixed types.
+///
+/// # Errors
+///
+/// Returns a `ConfigError::InvalidOption` if the filter cannot be parsed, a selected or
+/// renamed column does not exist, the table has no geometry to filter, a CRS is not
+/// supported or a tolerance or grid size is not a positive number.
+fn apply_transforms(mut df: DataFrame, options: &ConvertOptions) -> Result<DataFrame> {
+    let invalid = |option: &str, message: String| ConfigError::InvalidOption {
+        option: option.to_string(),
+        message,
+    };
+
+    if let Some(wkt) = &options.spatial_filter {
+        df = apply_spatial_filter(df, wkt, options.clip)?;
+    }
+
+    if let Some(filter) = &options.filter {
+        info!("Filter: {filter}");
+        let predicate = df
+            .parse_sql_expr(filter)
+            .map_err(|e| invalid("where", e.to_string()))?;
+        df = df
+            .filter(predicate)
+            .map_err(|e| invalid("where", e.to_string()))?;
+    }
+
+    if let Some(columns) = &options.columns {
+        info!("Columns: {}", columns.join(", "));
+        let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+        df = df
+            .select_columns(&columns)
+            .map_err(|e| invalid("select", e.to_string()))?;
+    }
+
+    for (old, new) in &options.renames {
+        if df.schema().field_with_unqualified_name(old).is_err() {
+            return Err(invalid("rename", format!("column '{old}' does not exist")).into());
+        }
+        info!("Rename: {old} -> {new}");
+        df = df
+            .with_column_renamed(old, new)
+            .map_err(|e| invalid("rename", e.to_string()))?;
+    }
+
+    if options.make_valid {
+        df = make_geometries_valid(df)?;
+    }
+
+    if let Some(tolerance) = options.simplify_tolerance {
+        df = simplify_geometries(df, options.simplify_method, tolerance)?;
+    }
+
+    if let Some(target_crs) = &options.target_crs {
+        df = reproject(df, options.source_crs.as_deref(), target_crs)?;
+    }
+
+    if let Some(size) = options.snap_to_grid {
+        df = snap_geometries_to_grid(df, size)?;
+    }
+
+    if options.explode {
+        df = explode_geometries(df)?;
+    }
+    Ok(df)
+}
+
+/// Apply the geometry type and dimension coercion of a conversion to a table.
+///
+/// # Errors
+///
+/// Returns a `ConfigError::InvalidOption` if a geometry type is not known.
+fn coerce_geometry_types(mut df: DataFrame, options: &ConvertOptions) -> Result<DataFrame> {
+    if let Some(geometry_type) = &options.geometry_type {
+        df = cast_geometries(df, geometry_type)?;
+    }
+
+    if let Some(dimension) = options.dimension {
+        df = force_dimension(df, dimension)?;
+    }
+    Ok(df)
+}
+
+/// Split the multi-geometries and collections of the first `GeoArrow` column of a table into
+/// one row per part, duplicating the other columns.
+///
+/// Rows with a null geometry are kept; the parts are written as WKB.
+///
+/// # Errors
+///
+/// Returns a `ConfigError::InvalidOption` if the table has no geometry column.
+fn explode_geometries(df: DataFrame) -> Result<DataFrame> {
+    let invalid = |message: String| ConfigError::InvalidOption {
+        option: "explode".to_string(),
+        message,
+    };
+    let Some(name) = geometry_columns(&df).into_iter().next() else {
+        return Err(invalid("the table has no geometry column".to_string()).into());
+    };
+
+    info!("Explode: {name}");
+    let dump = ScalarUDF::new_from_impl(DumpUdf::default());
+    let df = df
+        .with_column(&name, dump.call(vec![ident(&name)]))
+        .and_then(|df| {
+            df.unnest_columns_with_options(
+                &[name.as_str()],
+                UnnestOptions::new().with_preserve_nulls(true),
+            )
+        })
+        .and_then(|df| df.with_column(&name, get_field(ident(&name), "geom")))
+        .map_err(|e| invalid(e.to_string()))?;
+    Ok(df)
+}
+
+/// Check every geometry of a table against `geometry_type` as the table is written.
+///
+/// The table is replaced by a streaming table over its plan that checks the batches on their
+/// way to the writer (see [`GeometryCastCheck`]), so the input is read once. The write then
+/// fails with a [`GeometryCastError`] listing the features whose geometry cannot be cast.
+/// `PROMOTE_TO_MULTI` accepts every geometry.
+///
+/// # Errors
+///
+/// Returns an error if the plan of the table cannot be created.
+async fn check_geometry_cast(
+    ctx: &SessionContext,
+    df: DataFrame,
+    geometry_type: &str,
+) -> Result<DataFrame> {
+    if geometry_columns(&df).is_empty() || geometry_type.eq_ignore_ascii_case("PROMOTE_TO_MULTI") {
+        return Ok(df);
+    }
+
+    let plan = df
+        .create_physical_plan()
+        .await
+        .map_err(error::DataFusionError::from)?;
+    let check = GeometryCastCheck::new(Arc::clone(&plan), geometry_type);
+    let table = StreamingTable::try_new(plan.schema(), vec![Arc::new(check)])
+        .map_err(error::DataFusionError::from)?;
+    Ok(ctx
+        .read_table(Arc::new(table))
+        .map_err(error::DataFusionError::from)?)
+}
+
+/// Convert every `GeoArrow` column of a table to a geometry type with `ST_CastGeometry`, or
+/// to multi-geometries with `ST_Multi` for `PROMOTE_TO_MULTI`.
+///
+/// Geometries that do not fit the type fail the query; [`check_geometry_cast`] reports them
+/// all instead.
+///
+/// # Errors
+///
+/// Returns a `ConfigError::InvalidOption` if the geometry type is not known.
+fn cast_geometries(mut df: DataFrame, geometry_type: &str) -> Result<DataFrame> {
+    let promote = geometry_type.eq_ignore_ascii_case("PROMOTE_TO_MULTI");
+    let multi = ScalarUDF::new_from_impl(MultiUdf::default());
+    let cast = ScalarUDF::new_from_impl(CastGeometryUdf::default());
+    for name in geometry_columns(&df) {
+        info!("Geometry type: {name} -> {geometry_type}");
+        let geometry = if promote {
+            multi.call(vec![ident(&name)])
+        } else {
+            cast.call(vec![ident(&name), lit(geometry_type)])
+        };
+        df = df
+            .with_column(&name, geometry)
+            .map_err(|e| ConfigError::InvalidOption {
+                option: "nlt".to_string(),
+                message: e.to_string(),
+            })?;
+    }
+    Ok(df)
+}
+
+/// Force the coordinate dimension of every `GeoArrow` column of a table.
+///
+/// # Errors
+///
+/// Returns a `ConfigError::InvalidOption` if a column cannot be converted.
+fn force_dimension(mut df: DataFrame, dimension: Dimension) -> Result<DataFrame> {
+    let force = ScalarUDF::new_from_impl(match dimension {
+        Dimension::XY => ForceDimensionUdf::force_2d(),
+        Dimension::XYZ => ForceDimensionUdf::force_3d(),
+        Dimension::XYM => ForceDimensionUdf::force_3dm(),
+        Dimension::XYZM => ForceDimensionUdf::force_4d(),
+    });
+    for name in geometry_columns(&df) {
+        info!("Dimension: {name} -> {dimension:?}");
+        df = df
+            .with_column(&name, force.call(vec![ident(&name)]))
+            .map_err(|e| ConfigError::InvalidOption {
+                option: "dim".to_string(),
+                message: e.to_string(),
+            })?;
+    }
+    Ok(df)
+}
+
+/// Names of the `GeoArrow` columns of a table
+fn geometry_columns(df: &DataFrame) -> Vec<String> {
+    df.schema()
+        .fields()
+        .iter()
+        .filter(|field| is_geoarrow_field(field))
+        .map(|field| field.name().clone())
+        .collect()
+}
+
+/// Replace every `GeoArrow` column of a table with `udf(column, parameter)`, for the
+/// transforms taking a positive distance or area.
+///
+/// # Errors
+///
+/// Returns a `ConfigError::InvalidOption` named `option` if `parameter` is not a positive
+/// number or a column cannot be transformed.
+fn map_geometry_columns(
+    mut df: DataFrame,
+    option: &str,
+    udf: &ScalarUDF,
+    parameter: f64,
+) -> Result<DataFrame> {
+    let invalid = |message: String| ConfigError::InvalidOption {
+        option: option.to_string(),
+        message,
+    };
+    if !(parameter > 0.0 && parameter.is_finite()) {
+        return Err(invalid(format!("{parameter} is not a positive number")).into());
+    }
+
+    for name in geometry_columns(&df) {
+        info!("{}: {name} ({parameter})", udf.name());
+        df = df
+            .with_column(&name, udf.call(vec![ident(&name), lit(parameter)]))
+            .map_err(|e| invalid(e.to_string()))?;
+    }
+    Ok(df)
+}
+
+/// Simplify the lines and polygons of every `GeoArrow` column of a table.
+///
+/// # Errors
+///
+/// Returns a `ConfigError::InvalidOption` if the tolerance is not a positive number.
+fn simplify_geometries(df: DataFrame, method: Simplification, tolerance: f64) -> Result<DataFrame> {
+    let udf = match method {
+        Simplification::DouglasPeucker => ConstructorUdf::simplify(),
+        Simplification::Visvalingam => ConstructorUdf::simplify_vw(),
+        Simplification::PreserveTopology => ConstructorUdf::simplify_preserve_topology(),
+    };
+    map_geometry_columns(df, "simplify", &ScalarUDF::new_from_impl(udf), tolerance)
+}
+
+/// Round the coordinates of every `GeoArrow` column of a table to multiples of `size`.
+///
+/// # Errors
+///
+/// Returns a `ConfigError::InvalidOption` if the grid size is not a positive number.
+fn snap_geometries_to_grid(df: DataFrame, size: f64) -> Result<DataFrame> {
+    let udf = ScalarUDF::new_from_impl(ConstructorUdf::snap_to_grid());
+    map_geometry_columns(df, "snap-to-grid", &udf, size)
+}
+
+/// Repair the invalid geometries of every `GeoArrow` column of a table with `ST_MakeValid`.
+///
+/// Geometries that collapse entirely become null.
+///
+/// # Errors
+///
+/// Returns a `ConfigError::InvalidOption` if a geometry column cannot be repaired.
+fn make_geometries_valid(mut df: DataFrame) -> Result<DataFrame> {
+    let columns = geometry_columns(&df);
+    let make_valid = ScalarUDF::new_from_impl(MakeValidUdf::default());
+    for name in columns {
+        info!("Make valid: {name}");
+        df = df
+            .with_column(&name, make_valid.call(vec![ident(&name)]))
+            .map_err(|e| ConfigError::InvalidOption {
+                option: "make-valid".to_string(),
+                message: e.to_string(),
+            })?;
+    }
+    Ok(df)
+}
+
+/// Keep the rows whose first geometry column intersects `wkt`, clipping the geometries to it
+/// when `clip` is set.
+///
+/// The `st_intersects` predicate is pushed into the scan, where the readers skip features
+/// outside the filter's bounding box before decoding them into Arrow.
+///
+/// # Errors
+///
+/// Returns a `ConfigError::InvalidOption` if the table has no geometry column.
+fn apply_spatial_filter(df: DataFrame, wkt: &str, clip: bool) -> Result<DataFrame> {
+    let invalid = |message: String| ConfigError::InvalidOption {
+        option: "spatial filter".to_string(),
+        message,
+    };
+    let column = df
+        .schema()
+        .fields()
+        .iter()
+        .find(|field| is_geoarrow_field(field))
+        .map(|field| field.name().clone())
+        .ok_or_else(|| invalid("the dataset has no geometry column".to_string()))?;
+
+    info!("Spatial filter: {column} intersects {wkt}");
+    let intersects = ScalarUDF::new_from_impl(PredicateUdf::intersects());
+    let mut df = df
+        .filter(intersects.call(vec![ident(&column), lit(wkt)]))
+        .map_err(|e| invalid(e.to_string()))?;
+    if clip {
+        let intersection = ScalarUDF::new_from_impl(IntersectionUdf::default());
+        df = df
+            .with_column(&column, intersection.call(vec![ident(&column), lit(wkt)]))
+            .map_err(|e| invalid(e.to_string()))?;
+    }
+    Ok(df)
+}
+
+/// Resolve a spatial filter to a WKT geometry.
+///
+/// `spec` is a bounding box (`XMIN,YMIN,XMAX,YMAX`), a WKT geometry, a file holding WKT, or a
+/// dataset in any readable format, whose geometries are unioned (e.g., a study area
+/// boundary).
+///
+/// # Errors
+///
+/// Returns a `ConfigError::InvalidOption` if `spec` is none of these, or an error if the
+/// dataset cannot be read.
+pub async fn spatial_filter_geometry(spec: &str) -> Result<String> {
+    let invalid = |message: String| ConfigError::InvalidOption {
+        option: "spatial filter".to_string(),
+        message,
+    };
+
+    let wkt = if let Some(bbox) = parse_bbox(spec) {
+        bbox.map_err(invalid)?
+    } else if std::path::Path::new(spec).is_file() {
+        match crate::drivers::detect_driver(spec) {
+            Some(driver) if driver.capabilities.read == SupportStatus::Supported => {
+                union_geometries(spec, &driver).await?
+            },
+            _ => std::fs::read_to_string(spec)
+                .map_err(|e| invalid(format!("cannot read '{spec}': {e}")))?
+                .trim()
+                .to_string(),
+        }
+    } else {
+        spec.trim().to_string()
+    };
+
+    let field = Field::new("spatial filter", DataType::Utf8, true);
+    match read_geometries(&StringArray::from(vec![wkt.as_str()]), &field).as_deref() {
+        Ok([Some(_)]) => Ok(wkt),
+        _ => Err(invalid(format!(
+            "'{spec}' is not a bounding box (XMIN,YMIN,XMAX,YMAX), a WKT geometry or a \
+             readable file"
+        ))
+        .into()),
+    }
+}
+
+/// Parse `XMIN,YMIN,XMAX,YMAX` into a WKT polygon, or `None` if `spec` is not four numbers.
+fn parse_bbox(spec: &str) -> Option<std::result::Result<String, String>> {
+    let values = spec
+        .split(',')
+        .map(|value| value.trim().parse::<f64>().ok())
+        .collect::<Option<Vec<f64>>>()?;
+    let [xmin, ymin, xmax, ymax] = values[..] else {
+        return None;
+    };
+    if xmin > xmax || ymin > ymax {
+        return Some(Err(format!(
+            "bounding box '{spec}' has its minimum above its maximum"
+        )));
+    }
+    Some(Ok(format!(
+        "POLYGON(({xmin} {ymin}, {xmax} {ymin}, {xmax} {ymax}, {xmin} {ymax}, {xmin} {ymin}))"
+    )))
+}
+
+/// Union the geometries of a dataset into a single WKT geometry.
+async fn union_geometries(path: &str, driver: &Driver) -> Result<String> {
+    let ctx = initialize_context(path, driver, "geometry", None, &OptionValues::new()).await?;
     let table = ctx
         .table("dataset")
         .await
-        .map_err(|e| GeoEtlError::from(anyhow::anyhow!("Failed to get table: {e}")))?;
-    let batches = table
+        .map_err(error::DataFusionError::from)?;
+    let column = table
+        .schema()
+        .fields()
+        .iter()
+        .find(|field| is_geoarrow_field(field))
+        .map(|field| field.name().clone())
+        .ok_or_else(|| ConfigError::InvalidOption {
+            option: "spatial filter".to_string(),
+            message: format!("'{path}' has no geometry column"),
+        })?;
+
+    let query = format!("SELECT ST_AsText(ST_Union_Agg(\"{column}\")) FROM dataset");
+    let batches = ctx
+        .sql(&query)
+        .await
+        .map_err(error::DataFusionError::from)?
         .collect()
         .await
-        .map_err(|e| GeoEtlError::from(anyhow::anyhow!("Failed to collect data: {e}")))?;
-
-    info!("Read {} record batch(es)", batches.len());
-    let total_rows: usize = batches.iter().map(RecordBatch::num_rows).sum();
-    info!("Total rows: {total_rows}");
+        .map_err(error::DataFusionError::from)?;
+    batches
+        .first()
+        .and_then(|batch| batch.column(0).as_string_opt::<i32>())
+        .filter(|values| values.is_valid(0))
+        .map(|values| values.value(0).to_string())
+        .ok_or_else(|| {
+            ConfigError::InvalidOption {
+                option: "spatial filter".to_string(),
+                message: format!("'{path}' has no geometries"),
+            }
+            .into()
+        })
+}
 
-    // Write data based on output driver
-    match output_driver.short_name {
-        "CSV" => write_csv(output, &batches, geometry_column).with_write_context("CSV", output)?,
-        "GeoJSON" => write_geojson(output, &batches).with_write_context("GeoJSON", output)?,
-        _ => {
-            return Err(DriverError::NotRegistered {
-                driver: output_driver.short_name.to_string(),
+/// Add the `CRS` open option for a CRS assigned with `--a-srs`.
+///
+/// # Errors
+///
+/// Returns a `ConfigError::InvalidOption` if the input driver cannot assign a CRS.
+fn assign_crs(
+    driver: &Driver,
+    open_options: &OptionValues,
+    assigned_crs: Option<&str>,
+) -> Result<OptionValues> {
+    let mut open_options = open_options.clone();
+    if let Some(crs) = assigned_crs {
+        let supported = crate::drivers::get_open_options(driver.short_name);
+        if !supported.iter().any(|option| option.name == "CRS") {
+            return Err(ConfigError::InvalidOption {
+                option: "a-srs".to_string(),
+                message: format!(
+                    "the {} driver reads the CRS from the dataset and cannot assign one",
+                    driver.short_name
+                ),
             }
             .into());
-        },
+        }
+        open_options.insert("CRS", crs);
     }
+    Ok(open_options)
+}
 
-    info!("Conversion completed successfully");
-    Ok(())
+/// Reproject every `GeoArrow` column of a table to `target_crs` with `ST_Transform`.
+///
+/// Geometries are assumed to be in `source_crs` when it is given, and otherwise in the CRS
+/// recorded in their column's metadata (WGS 84 when there is none). Each column keeps its
+/// geometry type and records the target CRS.
+///
+/// # Errors
+///
+/// Returns a `ConfigError::InvalidOption` if a CRS is not supported.
+fn reproject(mut df: DataFrame, source_crs: Option<&str>, target_crs: &str) -> Result<DataFrame> {
+    let invalid = |option: &str, message: String| ConfigError::InvalidOption {
+        option: option.to_string(),
+        message,
+    };
+    if let Some(source_crs) = source_crs {
+        resolve_crs(source_crs).map_err(|e| invalid("s-srs", e.to_string()))?;
+    }
+    resolve_crs(target_crs).map_err(|e| invalid("t-srs", e.to_string()))?;
+
+    let columns = geometry_columns(&df);
+    let transform = ScalarUDF::new_from_impl(TransformUdf::default());
+    for name in columns {
+        info!("Reproject: {name} -> {target_crs}");
+        let mut args = vec![ident(&name)];
+        args.extend(source_crs.map(lit));
+        args.push(lit(target_crs));
+        df = df
+            .with_column(&name, transform.call(args))
+            .map_err(|e| invalid("t-srs", e.to_string()))?;
+    }
+    Ok(df)
+}
+
+/// Choose the column written as the geometry.
+///
+/// `preferred` is used if the schema has it; otherwise the first `GeoArrow` column is used, so
+/// that a renamed or computed geometry is still written as the geometry.
+fn output_geometry_column(schema: &Schema, preferred: &str) -> String {
+    if schema.field_with_name(preferred).is_ok() {
+        return preferred.to_string();
+    }
+    schema
+        .fields()
+        .iter()
+        .find(|field| is_geoarrow_field(field))
+        .map_or_else(|| preferred.to_string(), |field| field.name().clone())
 }
 
 /// Get information about a geospatial dataset.
 ///
-/// This function reads a geospatial file and returns structured information about it, including:
-/// - Dataset path and driver
+/// This function reads a geospatial file and returns structured information about one of
+/// its layers, including:
+/// - Dataset path, driver and layers
 /// - Geometry column information (name, extension, CRS)
 /// - Field schema (name, data type, nullable status)
+/// - With a spatial filter, the number of features intersecting it
+/// - With `stats`, the feature count, extent, geometry types and field statistics (see
+///   [`DatasetInfo`] and [`FieldStats`]), over the features intersecting the spatial filter
 ///
 /// # Arguments
 ///
@@ -377,6 +1130,9 @@ pub async fn convert(
 /// * `input_driver` - The driver responsible for reading the input format.
 /// * `geometry_column` - Name of the geometry column (for CSV)
 /// * `geometry_type` - Optional geometry type hint (for CSV)
+/// * `options` - Driver-specific open options, the layer to describe (the first one by
+///   default), an optional WKT geometry the counted features must intersect and whether
+///   to read every feature to compute statistics
 ///
 /// # Returns
 ///
@@ -387,6 +1143,8 @@ pub async fn convert(
 /// This function will return an error if:
 /// - The file cannot be read or parsed.
 /// - The file format is not yet implemented.
+/// - The layer does not exist.
+/// - The spatial filter is given for a dataset without a geometry column.
 ///
 /// # Note
 ///
@@ -396,20 +1154,487 @@ pub async fn info(
     input_driver: &Driver,
     geometry_column: &str,
     geometry_type: Option<&str>,
+    options: &InfoOptions,
 ) -> Result<DatasetInfo> {
     info!("Reading dataset information:");
     info!("Input: {} (Driver: {})", input, input_driver.short_name);
 
-    // Initialize context and register dataset
-    let ctx = initialize_context(input, input_driver, geometry_column, geometry_type).await?;
+    // Open the dataset and register the described layer
+    let source = DatasetSource::open(
+        input,
+        input_driver,
+        geometry_column,
+        geometry_type,
+        &options.open_options,
+    )?;
+    let ctx = geoetl_operations::spatial_session_context(SessionConfig::new());
+    let layers = source.layers(&ctx).await?;
+    let layer = source
+        .select_layers(&layers, options.layer.as_slice())?
+        .swap_remove(0);
+    source.register(&ctx, "dataset", Some(&layer)).await?;
 
     // Build dataset info using context
-    let dataset_info =
+    let mut dataset_info =
         build_dataset_info_from_context(&ctx, "dataset", input, input_driver).await?;
+    dataset_info.layer = layer;
+    dataset_info.layers = layers;
+
+    if options.spatial_filter.is_none() && !options.stats {
+        return Ok(dataset_info);
+    }
+
+    let mut table = ctx
+        .table("dataset")
+        .await
+        .map_err(error::DataFusionError::from)?;
+    if let Some(wkt) = &options.spatial_filter {
+        table = apply_spatial_filter(table, wkt, false)?;
+    }
+    if options.stats {
+        compute_statistics(table, &mut dataset_info).await?;
+    } else {
+        let count = table.count().await.map_err(error::DataFusionError::from)?;
+        dataset_info.feature_count = Some(count as u64);
+    }
 
     Ok(dataset_info)
 }
 
+/// Number of most frequent values reported for each string field.
+const TOP_VALUES: usize = 5;
+
+/// Fill in the statistics of `info` from the features of `table`.
+///
+/// A single aggregation, and so a single pass over the features, computes the feature count,
+/// the bounds and geometry types of the geometry columns and the null count, minimum, maximum,
+/// distinct estimate and top values of each field.
+async fn compute_statistics(table: DataFrame, info: &mut DatasetInfo) -> Result<()> {
+    let bounds = [
+        (MeasureUdf::x_min(), true),
+        (MeasureUdf::y_min(), true),
+        (MeasureUdf::x_max(), false),
+        (MeasureUdf::y_max(), false),
+    ]
+    .map(|(measure, smallest)| (ScalarUDF::new_from_impl(measure), smallest));
+    let geometry_columns = geometry_columns(&table);
+    let top_values = AggregateUDF::new_from_impl(ValueCountsUdf::new(Some(TOP_VALUES)));
+    let all_values = AggregateUDF::new_from_impl(ValueCountsUdf::new(None));
+    let geometry_type = ScalarUDF::new_from_impl(GeometryTypeUdf::default());
+    let value_counts_type = top_values
+        .return_type(&[DataType::Utf8])
+        .map_err(error::DataFusionError::from)?;
+
+    // Aggregates with the type their value is read as
+    let mut aggregates = vec![(count(lit(1)), DataType::UInt64)];
+    for column in &geometry_columns {
+        for (udf, smallest) in &bounds {
+            let value = udf.call(vec![ident(column)]);
+            let bound = if *smallest { min(value) } else { max(value) };
+            aggregates.push((bound, DataType::Float64));
+        }
+        let types = geometry_type.call(vec![ident(column)]);
+        aggregates.push((all_values.call(vec![types]), value_counts_type.clone()));
+    }
+    for field in &info.fields {
+        let value = ident(&field.name);
+        aggregates.push((count(value.clone()), DataType::UInt64));
+        if has_ordered_values(&table, &field.name) {
+            aggregates.push((min(value.clone()), DataType::Utf8));
+            aggregates.push((max(value.clone()), DataType::Utf8));
+            aggregates.push((
+                approx_distinct(cast(value.clone(), DataType::Utf8)),
+                DataType::UInt64,
+            ));
+        }
+        if is_string_field(&table, &field.name) {
+            let value = cast(value, DataType::Utf8);
+            aggregates.push((top_values.call(vec![value]), value_counts_type.clone()));
+        }
+    }
+    let (aggregates, casts): (Vec<Expr>, Vec<Expr>) = aggregates
+        .into_iter()
+        .enumerate()
+        .map(|(i, (aggregate, data_type))| {
+            let name = format!("stat_{i}");
+            (aggregate.alias(&name), cast(col(name), data_type))
+        })
+        .unzip();
+    let batches = table
+        .clone()
+        .aggregate(vec![], aggregates)
+        .and_then(|df| df.select(casts))
+        .map_err(error::DataFusionError::from)?
+        .collect()
+        .await
+        .map_err(error::DataFusionError::from)?;
+    let Some(batch) = batches.into_iter().find(|batch| batch.num_rows() == 1) else {
+        return Err(error::DataFusionError::Collection(
+            "statistics query returned no rows".to_string(),
+        )
+        .into());
+    };
+
+    let mut values = batch.columns().iter();
+    let mut next = || values.next().filter(|array| array.is_valid(0));
+    let feature_count = next().map_or(0, |array| array.as_primitive::<UInt64Type>().value(0));
+    info.feature_count = Some(feature_count);
+    let mut type_counts: BTreeMap<String, u64> = BTreeMap::new();
+    for _ in &geometry_columns {
+        let mut bound = || next().map(|array| array.as_primitive::<Float64Type>().value(0));
+        let bounds = (bound(), bound(), bound(), bound());
+        for (name, count) in next().map_or_else(Vec::new, |array| ValueCountsUdf::read(array, 0)) {
+            *type_counts.entry(name).or_default() += count;
+        }
+        let (Some(min_x), Some(min_y), Some(max_x), Some(max_y)) = bounds else {
+            continue;
+        };
+        info.extent = Some(match info.extent {
+            Some(extent) => Extent {
+                min_x: extent.min_x.min(min_x),
+                min_y: extent.min_y.min(min_y),
+                max_x: extent.max_x.max(max_x),
+                max_y: extent.max_y.max(max_y),
+            },
+            None => Extent {
+                min_x,
+                min_y,
+                max_x,
+                max_y,
+            },
+        });
+    }
+    for field in &mut info.fields {
+        let non_null = next().map_or(0, |array| array.as_primitive::<UInt64Type>().value(0));
+        let mut stats = FieldStats {
+            null_count: feature_count - non_null,
+            ..FieldStats::default()
+        };
+        if has_ordered_values(&table, &field.name) {
+            let mut text = || next().map(|array| array.as_string::<i32>().value(0).to_string());
+            stats.min = text();
+            stats.max = text();
+            stats.distinct_count = next().map(|array| array.as_primitive::<UInt64Type>().value(0));
+        }
+        if is_string_field(&table, &field.name) {
+            stats.top_values = next().map_or_else(Vec::new, |array| ValueCountsUdf::read(array, 0));
+        }
+        field.stats = Some(stats);
+    }
+
+    let mut geometry_types: Vec<(String, u64)> = type_counts.into_iter().collect();
+    geometry_types.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    info.geometry_types = geometry_types;
+
+    Ok(())
+}
+
+/// Whether a column of `table` holds values with an order, which have a minimum and maximum.
+fn has_ordered_values(table: &DataFrame, column: &str) -> bool {
+    table
+        .schema()
+        .field_with_unqualified_name(column)
+        .is_ok_and(|field| {
+            let data_type = field.data_type();
+            data_type.is_numeric()
+                || data_type.is_temporal()
+                || matches!(
+                    data_type,
+                    DataType::Boolean | DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+                )
+        })
+}
+
+/// Whether a column of `table` holds strings.
+fn is_string_field(table: &DataFrame, column: &str) -> bool {
+    table
+        .schema()
+        .field_with_unqualified_name(column)
+        .is_ok_and(|field| {
+            matches!(
+                field.data_type(),
+                DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+            )
+        })
+}
+
+/// Check the geometries of a dataset for validity.
+///
+/// Every geometry of every `GeoArrow` column is checked against the OGC simple feature rules
+/// (see `ST_IsValid`), and must also have closed rings, exterior rings counter-clockwise and
+/// interior rings clockwise. Each invalid geometry is reported with the first rule it breaks
+/// and where. Null and empty geometries are valid.
+///
+/// # Arguments
+///
+/// * `input` - The path to the input geospatial data file.
+/// * `input_driver` - The driver responsible for reading the input format.
+/// * `geometry_column` - Name of the geometry column (for CSV)
+/// * `geometry_type` - Optional geometry type hint (for CSV)
+/// * `layer` - Layer to check; the first layer when `None`
+/// * `open_options` - Driver-specific open options
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The file cannot be read or parsed.
+/// - The file format is not yet implemented.
+/// - The layer does not exist.
+/// - The dataset has no geometry column.
+pub async fn validate(
+    input: &str,
+    input_driver: &Driver,
+    geometry_column: &str,
+    geometry_type: Option<&str>,
+    layer: Option<&str>,
+    open_options: &OptionValues,
+) -> Result<ValidationReport> {
+    info!("Validating geometries:");
+    info!("Input: {} (Driver: {})", input, input_driver.short_name);
+
+    // Open the dataset and register the checked layer
+    let source = DatasetSource::open(
+        input,
+        input_driver,
+        geometry_column,
+        geometry_type,
+        open_options,
+    )?;
+    let ctx = geoetl_operations::spatial_session_context(SessionConfig::new());
+    let layers = source.layers(&ctx).await?;
+    let layer = source
+        .select_layers(&layers, layer.map(str::to_string).as_slice())?
+        .swap_remove(0);
+    source.register(&ctx, "dataset", Some(&layer)).await?;
+    let table = ctx
+        .table("dataset")
+        .await
+        .map_err(error::DataFusionError::from)?;
+    let columns = geometry_columns(&table);
+    if columns.is_empty() {
+        return Err(ConfigError::InvalidOption {
+            option: "validate".to_string(),
+            message: "the dataset has no geometry column".to_string(),
+        }
+        .into());
+    }
+
+    let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+    let mut stream = table
+        .select_columns(&columns)
+        .map_err(error::DataFusionError::from)?
+        .execute_stream()
+        .await
+        .map_err(error::DataFusionError::from)?;
+
+    let mut feature_count = 0;
+    let mut issues = Vec::new();
+    while let Some(batch) = stream.next().await {
+        let batch = batch.map_err(error::DataFusionError::from)?;
+        let schema = batch.schema();
+        for (field, array) in schema.fields().iter().zip(batch.columns()) {
+            let column_issues =
+                validity_issues(array.as_ref(), field).map_err(error::DataFusionError::from)?;
+            for (row, issue) in column_issues.into_iter().enumerate() {
+                if let Some(issue) = issue {
+                    issues.push(ValidationIssue {
+                        feature: feature_count + row as u64,
+                        column: field.name().clone(),
+                        location: issue.location_wkt(),
+                        reason: issue.reason,
+                    });
+                }
+            }
+        }
+        feature_count += batch.num_rows() as u64;
+    }
+    issues.sort_by_key(|issue| issue.feature);
+    info!(
+        "{} invalid geometries in {feature_count} features",
+        issues.len()
+    );
+
+    Ok(ValidationReport {
+        dataset: input.to_string(),
+        driver: input_driver.short_name.to_string(),
+        layer,
+        feature_count,
+        issues,
+    })
+}
+
+/// Register every table in a new session context and plan `query` over them.
+///
+/// # Errors
+///
+/// Returns a `ConfigError::ConflictingOptions` if two tables share a name, or an error if a
+/// dataset cannot be registered or the query cannot be planned.
+async fn plan_sql(tables: &[SqlTable], query: &str) -> Result<(SessionContext, DataFrame)> {
+    let ctx = geoetl_operations::spatial_session_context(SessionConfig::new());
+    for (idx, table) in tables.iter().enumerate() {
+        if tables[..idx]
+            .iter()
+            .any(|other| other.name.eq_ignore_ascii_case(&table.name))
+        {
+            return Err(ConfigError::ConflictingOptions {
+                options: format!("table '{}' is registered more than once", table.name),
+            }
+            .into());
+        }
+
+        info!(
+            "Registering {} as '{}' (Driver: {})",
+            table.path, table.name, table.driver.short_name
+        );
+        register_catalog(
+            &ctx,
+            &table.path,
+            &table.driver,
+            &table.name,
+            &table.geometry_column,
+            table.geometry_type.as_deref(),
+            &table.open_options,
+        )
+        .await?;
+    }
+
+    info!("Planning query: {query}");
+    let df = ctx.sql(query).await.map_err(error::DataFusionError::from)?;
+    Ok((ctx, df))
+}
+
+/// Runs a SQL query over one or more datasets and returns its result.
+///
+/// Each dataset is registered under its table name with its own driver, so a query can join
+/// datasets of different formats. The spatial functions from `geoetl-operations` (`ST_Area`,
+/// `ST_Intersects`, `ST_Transform`, ...) are available in the query.
+///
+/// # Arguments
+///
+/// * `tables` - The datasets to register
+/// * `query` - The SQL query to run
+///
+/// # Returns
+///
+/// The result with every value formatted as text; geometries are formatted as WKT.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - Two tables share a name.
+/// - A file cannot be read or parsed.
+/// - The query is invalid or fails to execute.
+///
+/// # Note
+///
+/// Driver capability validation should be performed by the caller before invoking this function.
+pub async fn sql(tables: &[SqlTable], query: &str) -> Result<QueryResult> {
+    let (_ctx, df) = plan_sql(tables, query).await?;
+    let schema = df.schema().as_arrow().clone();
+    let batches = df.collect().await.map_err(error::DataFusionError::from)?;
+
+    format_query_result(&schema, &batches)
+}
+
+/// Runs a SQL query over one or more datasets and writes its result with an output driver.
+///
+/// The query result is streamed through the output driver's writer like a conversion. Its
+/// `geometry` column, or else its first `GeoArrow` column, is written as the geometry.
+///
+/// # Arguments
+///
+/// * `tables` - The datasets to register
+/// * `query` - The SQL query to run
+/// * `output` - The path where the result will be written
+/// * `output_driver` - The driver responsible for writing the output format
+/// * `write_mode` - What to do when the output already exists
+/// * `creation_options` - Driver-specific creation options
+///
+/// # Returns
+///
+/// The number of rows written.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - Two tables share a name.
+/// - A file cannot be read or parsed.
+/// - The query is invalid or fails to execute.
+/// - A creation option is not supported by the driver.
+/// - The output already exists and `write_mode` is `WriteMode::Fail`, or its columns do
+///   not match the result when appending.
+/// - The output file cannot be written.
+///
+/// # Note
+///
+/// Driver capability validation should be performed by the caller before invoking this function.
+pub async fn sql_to_file(
+    tables: &[SqlTable],
+    query: &str,
+    output: &str,
+    output_driver: &Driver,
+    write_mode: WriteMode,
+    creation_options: &OptionValues,
+) -> Result<u64> {
+    let (_ctx, df) = plan_sql(tables, query).await?;
+    let geometry_column = output_geometry_column(df.schema().as_arrow(), "geometry");
+
+    let total_rows = write_dataset(
+        df,
+        output,
+        output_driver,
+        &default_layer_name(output),
+        &geometry_column,
+        write_mode,
+        creation_options,
+    )
+    .await?;
+    info!("Total rows: {total_rows}");
+    Ok(total_rows)
+}
+
+/// Format query batches as text, encoding geometry columns as WKT.
+fn format_query_result(schema: &Schema, batches: &[RecordBatch]) -> Result<QueryResult> {
+    let options = FormatOptions::default().with_null("NULL");
+    let mut result = QueryResult {
+        columns: schema
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect(),
+        rows: Vec::new(),
+    };
+
+    for batch in batches {
+        let batch =
+            datafusion_csv::geometry_columns_to_wkt(batch).map_err(error::DataFusionError::from)?;
+        let formatters = batch
+            .columns()
+            .iter()
+            .map(|column| ArrayFormatter::try_new(column.as_ref(), &options))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| error::DataFusionError::Collection(e.to_string()))?;
+
+        for row in 0..batch.num_rows() {
+            result.rows.push(
+                formatters
+                    .iter()
+                    .map(|formatter| formatter.value(row).to_string())
+                    .collect(),
+            );
+        }
+    }
+    Ok(result)
+}
+
+/// Returns `true` if the field carries a `GeoArrow` extension type.
+fn is_geoarrow_field(field: &datafusion::arrow::datatypes::Field) -> bool {
+    field
+        .metadata()
+        .get("ARROW:extension:name")
+        .is_some_and(|name| name.starts_with("geoarrow"))
+}
+
 /// Build dataset information structure using `DataFusion` context.
 async fn build_dataset_info_from_context(
     ctx: &SessionContext,
@@ -435,9 +1660,9 @@ async fn build_dataset_info_from_context(
             if extension_name.starts_with("geoarrow") {
                 geometry_column_info.push(GeometryColumnInfo {
                     name: field.name().to_string(),
-                    data_type: format!("{:?}", field.data_type()),
+                    data_type: field_geometry_type(field),
                     extension: Some(extension_name.clone()),
-                    crs: metadata.get("ARROW:extension:metadata").cloned(),
+                    crs: field_crs(field),
                 });
             }
         }
@@ -461,6 +1686,7 @@ async fn build_dataset_info_from_context(
             name: field.name().to_string(),
             data_type: field.data_type().format(),
             nullable: field.is_nullable(),
+            stats: None,
         });
     }
 
@@ -468,8 +1694,13 @@ async fn build_dataset_info_from_context(
         dataset: input.to_string(),
         driver: driver.short_name.to_string(),
         driver_long_name: driver.long_name.to_string(),
+        layer: String::new(),
+        layers: Vec::new(),
         geometry_columns: geometry_column_info,
         fields: field_infos,
+        feature_count: None,
+        extent: None,
+        geometry_types: Vec::new(),
     })
 }
 
@@ -561,6 +1792,7 @@ mod tests {
             &output_driver,
             "wkt",
             None,
+            &ConvertOptions::default(),
         )
         .await;
 
@@ -611,6 +1843,7 @@ mod tests {
             &output_driver,
             "geometry",
             None,
+            &ConvertOptions::default(),
         )
         .await;
 
@@ -653,6 +1886,7 @@ mod tests {
             &output_driver,
             "geometry",
             None,
+            &ConvertOptions::default(),
         )
         .await;
         assert!(result.is_err());
@@ -692,6 +1926,7 @@ mod tests {
             &output_driver,
             "geometry",
             None,
+            &ConvertOptions::default(),
         )
         .await;
         assert!(result.is_err());
@@ -742,13 +1977,14 @@ mod tests {
             &output_driver,
             "geometry",
             None,
+            &ConvertOptions::default(),
         )
         .await;
 
         // This might succeed or fail depending on CSV parser tolerance
         // Either outcome is acceptable for malformed data
-        if result.is_err() {
-            assert!(result.unwrap_err().to_string().contains("Failed to"));
+        if let Err(err) = result {
+            assert!(err.to_string().contains("Failed to"));
         }
         Ok(())
     }
@@ -784,6 +2020,7 @@ mod tests {
             &output_driver,
             "geometry",
             None,
+            &ConvertOptions::default(),
         )
         .await;
 
@@ -832,6 +2069,7 @@ mod tests {
             &output_driver,
             "wkt",
             None,
+            &ConvertOptions::default(),
         )
         .await;
 
@@ -840,4 +2078,151 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_convert_invalid_driver_option() -> Result<()> {
+        crate::init::initialize();
+
+        let temp_dir = TempDir::new().unwrap();
+        let input_path = temp_dir.path().join("input.csv");
+        let output_path = temp_dir.path().join("output.csv");
+        create_test_csv(&input_path).unwrap();
+
+        let csv_driver = crate::drivers::find_driver("CSV").unwrap();
+
+        for options in [
+            ConvertOptions::new().with_open_options(OptionValues::new().with("UNKNOWN", "1")),
+            ConvertOptions::new()
+                .with_creation_options(OptionValues::new().with("HEADER", "maybe")),
+        ] {
+            let result = convert(
+                input_path.to_str().unwrap(),
+                output_path.to_str().unwrap(),
+                &csv_driver,
+                &csv_driver,
+                "wkt",
+                None,
+                &options,
+            )
+            .await;
+
+            assert!(matches!(
+                result,
+                Err(GeoEtlError::Config(ConfigError::InvalidOption { .. }))
+            ));
+        }
+        assert!(!output_path.exists(), "No output should be written");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_statistics_over_several_batches_and_partitions() -> Result<()> {
+        use datafusion::datasource::MemTable;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("name", DataType::Utf8, true),
+            Field::new("wkt", DataType::Utf8, true),
+        ]));
+        let batch = |names: Vec<Option<&str>>, wkts: Vec<&str>| {
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(StringArray::from(names)),
+                    Arc::new(StringArray::from(wkts)),
+                ],
+            )
+            .unwrap()
+        };
+        let partitions = vec![
+            vec![
+                batch(vec![Some("a"), Some("b")], vec!["POINT(0 0)", "POINT(1 5)"]),
+                batch(vec![Some("a")], vec!["LINESTRING(2 2, 3 3)"]),
+            ],
+            vec![
+                batch(vec![Some("c"), None], vec!["POINT(-1 1)", "POINT(4 4)"]),
+                batch(
+                    vec![Some("b"), Some("a")],
+                    vec!["POINT(1 1)", "LINESTRING(0 0, 9 1)"],
+                ),
+            ],
+        ];
+
+        let ctx = geoetl_operations::spatial_session_context(
+            SessionConfig::new().with_target_partitions(4),
+        );
+        let table =
+            MemTable::try_new(schema.clone(), partitions).map_err(error::DataFusionError::from)?;
+        ctx.register_table("raw", Arc::new(table))
+            .map_err(error::DataFusionError::from)?;
+        let dataset = ctx
+            .sql("SELECT name, ST_GeomFromText(wkt) AS geometry FROM raw")
+            .await
+            .map_err(error::DataFusionError::from)?;
+        ctx.register_table("dataset", dataset.into_view())
+            .map_err(error::DataFusionError::from)?;
+
+        let driver = crate::drivers::find_driver("CSV").unwrap();
+        let mut info = build_dataset_info_from_context(&ctx, "dataset", "raw", &driver).await?;
+        let table = ctx
+            .table("dataset")
+            .await
+            .map_err(error::DataFusionError::from)?;
+        compute_statistics(table, &mut info).await?;
+
+        assert_eq!(info.feature_count, Some(7));
+        assert_eq!(
+            info.extent,
+            Some(Extent {
+                min_x: -1.0,
+                min_y: 0.0,
+                max_x: 9.0,
+                max_y: 5.0,
+            })
+        );
+        assert_eq!(
+            info.geometry_types,
+            vec![("Point".to_string(), 5), ("LineString".to_string(), 2)]
+        );
+        let stats = info.fields[0].stats.as_ref().unwrap();
+        assert_eq!(stats.null_count, 1);
+        assert_eq!(stats.min.as_deref(), Some("a"));
+        assert_eq!(stats.max.as_deref(), Some("c"));
+        assert_eq!(
+            stats.top_values,
+            vec![
+                ("a".to_string(), 3),
+                ("b".to_string(), 2),
+                ("c".to_string(), 1)
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_driver_options_fills_geometry_defaults() {
+        let supported = crate::drivers::get_open_options("CSV");
+
+        let options = driver_options(
+            "open",
+            &supported,
+            &OptionValues::new(),
+            "wkt",
+            Some("Point"),
+        )
+        .unwrap();
+        assert_eq!(options.get("GEOMETRY_COLUMN"), Some("wkt"));
+        assert_eq!(options.get("GEOMETRY_TYPE"), Some("Point"));
+
+        // Explicit options win over the operation arguments
+        let given = OptionValues::new().with("geometry_column", "geom");
+        let options = driver_options("open", &supported, &given, "wkt", None).unwrap();
+        assert_eq!(options.get("GEOMETRY_COLUMN"), Some("geom"));
+        assert!(!options.contains("GEOMETRY_TYPE"));
+
+        // Drivers that do not declare the option do not receive it
+        let options = driver_options("creation", &[], &OptionValues::new(), "wkt", None).unwrap();
+        assert!(options.is_empty());
+    }
 }
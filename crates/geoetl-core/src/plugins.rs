@@ -0,0 +1,902 @@
+//! Loading of format plugins from shared libraries.
+//!
+//! Plugins are shared libraries that export the C ABI described in
+//! [`geoetl_core_common::plugin`]; Rust plugins implement
+//! [`geoetl_core_common::plugin::PluginFormat`] and export it with
+//! [`geoetl_core_common::declare_plugin!`]. At startup, [`crate::init::initialize`] loads every
+//! plugin found in the directories or files listed in the `GEOETL_PLUGIN_PATH` environment
+//! variable (separated like `PATH`) and registers its formats with the global driver registry,
+//! where they behave like built-in drivers.
+//!
+//! Data crosses the library boundary only through the Arrow C data and stream interfaces, so a
+//! plugin does not need to be built with the same compiler or dependency versions as `geoetl`.
+
+use std::env;
+use std::ffi::{CString, OsStr, c_char};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail};
+use arrow::array::{RecordBatch, RecordBatchReader};
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::error::ArrowError;
+use arrow::ffi::FFI_ArrowSchema;
+use arrow::ffi_stream::{ArrowArrayStreamReader, FFI_ArrowArrayStream};
+use async_trait::async_trait;
+use datafusion::catalog::streaming::StreamingTable;
+use datafusion::datasource::TableProvider;
+use datafusion::datasource::sink::{DataSink, DataSinkExec};
+use datafusion::error::DataFusionError;
+use datafusion::execution::context::SessionState;
+use datafusion::execution::{SendableRecordBatchStream, TaskContext};
+use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::metrics::MetricsSet;
+use datafusion::physical_plan::stream::RecordBatchReceiverStreamBuilder;
+use datafusion::physical_plan::streaming::PartitionStream;
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, ExecutionPlanProperties,
+};
+use datafusion_shared::create_output;
+use futures::StreamExt;
+use geoetl_core_common::plugin::{
+    FreeErrorFn, PLUGIN_ABI_VERSION, PLUGIN_ENTRY_SYMBOL, PluginDescriptor, PluginEntryFn,
+    PluginFormatDescriptor, PluginOption, PluginOptionValue, ReadFn, ReadSchemaFn, WriteFn, c_str,
+    option_type_from_code,
+};
+use geoetl_core_common::{
+    DataReader, DataWriter, Driver, FormatFactory, FormatOption, OptionValues, SupportStatus,
+    WriteMode, driver_registry,
+};
+use libloading::Library;
+use log::{info, warn};
+
+use crate::error::{DriverError, GeoEtlError, Result};
+
+/// Environment variable listing plugin libraries or directories containing them.
+pub const PLUGIN_PATH_ENV: &str = "GEOETL_PLUGIN_PATH";
+
+/// Loads the plugins listed in `GEOETL_PLUGIN_PATH`.
+///
+/// Plugins that fail to load are logged and skipped so that one broken library does not
+/// prevent the built-in drivers from being used.
+///
+/// # Returns
+///
+/// The short names of the drivers registered by the loaded plugins.
+pub fn load_plugins_from_env() -> Vec<String> {
+    let Some(paths) = env::var_os(PLUGIN_PATH_ENV) else {
+        return Vec::new();
+    };
+
+    let mut drivers = Vec::new();
+    for path in env::split_paths(&paths).filter(|path| !path.as_os_str().is_empty()) {
+        for library in plugin_libraries(&path) {
+            match load_plugin(&library) {
+                Ok(names) => drivers.extend(names),
+                Err(e) => warn!("{e}"),
+            }
+        }
+    }
+    drivers
+}
+
+/// Lists the plugin libraries at `path`.
+///
+/// A file is returned as is; a directory yields the files in it with the platform's shared
+/// library extension (`so`, `dylib` or `dll`), sorted by name. Missing paths yield nothing.
+#[must_use]
+pub fn plugin_libraries(path: &Path) -> Vec<PathBuf> {
+    if path.is_file() {
+        return vec![path.to_path_buf()];
+    }
+
+    let Ok(entries) = path.read_dir() else {
+        if path.exists() {
+            warn!("Cannot read plugin directory '{}'", path.display());
+        }
+        return Vec::new();
+    };
+
+    let mut libraries: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file() && path.extension() == Some(OsStr::new(env::consts::DLL_EXTENSION))
+        })
+        .collect();
+    libraries.sort();
+    libraries
+}
+
+/// Loads a plugin library and registers its formats.
+///
+/// The library stays loaded for the rest of the process, since the registered drivers call
+/// into it.
+///
+/// # Returns
+///
+/// The short names of the drivers the plugin registered.
+///
+/// # Errors
+///
+/// Returns [`DriverError::PluginLoad`] if the library cannot be opened, does not export a
+/// plugin entry point, targets another plugin ABI version, or provides a driver whose name is
+/// already registered.
+pub fn load_plugin(path: &Path) -> Result<Vec<String>> {
+    let source = path.display().to_string();
+
+    // SAFETY: loading a library runs its initialisers; plugin paths are chosen by the user
+    // through `GEOETL_PLUGIN_PATH`, in the same way as executables on `PATH`.
+    let library = unsafe { Library::new(path) }.map_err(|e| plugin_error(&source, e))?;
+
+    // SAFETY: the entry point has the signature documented by `PLUGIN_ENTRY_SYMBOL`.
+    let descriptor = unsafe {
+        let entry = library
+            .get::<PluginEntryFn>(PLUGIN_ENTRY_SYMBOL)
+            .map_err(|e| plugin_error(&source, format!("not a GeoETL plugin: {e}")))?;
+        entry()
+    };
+
+    // Registered drivers call into the library, so it must never be unloaded
+    std::mem::forget(library);
+
+    // SAFETY: the plugin guarantees the descriptor lives as long as the loaded library,
+    // which is never unloaded.
+    let descriptor = unsafe { descriptor.as_ref() }
+        .ok_or_else(|| plugin_error(&source, "plugin entry point returned null"))?;
+    // SAFETY: see above.
+    unsafe { register_plugin(descriptor, &source) }
+}
+
+/// Registers the formats described by a plugin descriptor.
+///
+/// This is what [`load_plugin`] does once the library is loaded; it can also be used for
+/// plugins linked into the host.
+///
+/// # Returns
+///
+/// The short names of the registered drivers.
+///
+/// # Errors
+///
+/// Returns [`DriverError::PluginLoad`] if the descriptor targets another plugin ABI version, is
+/// malformed, or provides a driver whose name is already registered. Nothing is registered
+/// in that case.
+///
+/// # Safety
+///
+/// `descriptor` and every pointer reachable from it must follow the contract documented in
+/// [`geoetl_core_common::plugin`] and remain valid for the rest of the process.
+pub unsafe fn register_plugin(
+    descriptor: &'static PluginDescriptor,
+    source: &str,
+) -> Result<Vec<String>> {
+    if descriptor.abi_version != PLUGIN_ABI_VERSION {
+        return Err(plugin_error(
+            source,
+            format!(
+                "plugin ABI version {} is not supported (expected {PLUGIN_ABI_VERSION})",
+                descriptor.abi_version
+            ),
+        ));
+    }
+
+    // SAFETY: guaranteed by the caller.
+    let formats = unsafe { slice(descriptor.formats, descriptor.format_count) };
+    let factories = formats
+        .iter()
+        // SAFETY: guaranteed by the caller.
+        .map(|format| unsafe { PluginFormatFactory::new(format) })
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(|e| plugin_error(source, format!("{e:#}")))?;
+
+    let registry = driver_registry();
+    if let Some(factory) = factories
+        .iter()
+        .find(|factory| registry.find_factory(factory.short_name).is_some())
+    {
+        return Err(plugin_error(
+            source,
+            format!("driver '{}' is already registered", factory.short_name),
+        ));
+    }
+
+    let mut drivers = Vec::with_capacity(factories.len());
+    for factory in factories {
+        let name = factory.short_name.to_string();
+        registry
+            .try_register(Arc::new(factory))
+            .map_err(|e| plugin_error(source, e))?;
+        info!("Registered driver '{name}' from plugin '{source}'");
+        drivers.push(name);
+    }
+    Ok(drivers)
+}
+
+fn plugin_error(path: &str, message: impl fmt::Display) -> GeoEtlError {
+    DriverError::PluginLoad {
+        path: path.to_string(),
+        message: message.to_string(),
+    }
+    .into()
+}
+
+/// Borrows a C array as a slice.
+///
+/// # Safety
+///
+/// `data` must point to `len` valid entries that live for `'a`, or `len` must be zero.
+unsafe fn slice<'a, T>(data: *const T, len: usize) -> &'a [T] {
+    if len == 0 || data.is_null() {
+        &[]
+    } else {
+        // SAFETY: guaranteed by the caller.
+        unsafe { std::slice::from_raw_parts(data, len) }
+    }
+}
+
+/// Converts plugin option descriptors into [`FormatOption`]s.
+///
+/// # Safety
+///
+/// `options` must point to `len` valid entries whose strings live for the rest of the process.
+unsafe fn format_options(
+    options: *const PluginOption,
+    len: usize,
+) -> anyhow::Result<Vec<FormatOption>> {
+    // SAFETY: guaranteed by the caller.
+    unsafe { slice(options, len) }
+        .iter()
+        .map(|option| {
+            // SAFETY: guaranteed by the caller.
+            let (name, description) = unsafe { (c_str(option.name)?, c_str(option.description)?) };
+            let mut format_option = FormatOption::new(
+                name,
+                option_type_from_code(option.option_type)?,
+                description,
+            );
+            if !option.default.is_null() {
+                // SAFETY: guaranteed by the caller.
+                format_option = format_option.with_default(unsafe { c_str(option.default) }?);
+            }
+            Ok(format_option)
+        })
+        .collect()
+}
+
+/// Functions of a plugin format, shared by its reader and writer.
+#[derive(Clone, Copy)]
+struct PluginFunctions {
+    short_name: &'static str,
+    read_schema: Option<ReadSchemaFn>,
+    read: Option<ReadFn>,
+    write: Option<WriteFn>,
+    free_error: FreeErrorFn,
+}
+
+impl PluginFunctions {
+    /// Calls a plugin function with `path` and `options` converted to C strings.
+    fn call(
+        &self,
+        path: &str,
+        options: &OptionValues,
+        function: impl FnOnce(*const c_char, *const PluginOptionValue, usize) -> *mut c_char,
+    ) -> anyhow::Result<()> {
+        let path = CString::new(path)?;
+        let strings = options
+            .iter()
+            .map(|(key, value)| Ok((CString::new(key)?, CString::new(value)?)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let values: Vec<PluginOptionValue> = strings
+            .iter()
+            .map(|(key, value)| PluginOptionValue {
+                key: key.as_ptr(),
+                value: value.as_ptr(),
+            })
+            .collect();
+
+        let error = function(path.as_ptr(), values.as_ptr(), values.len());
+        if error.is_null() {
+            return Ok(());
+        }
+
+        // SAFETY: plugin functions return null or a nul-terminated string they allocated.
+        let message = unsafe { std::ffi::CStr::from_ptr(error) }
+            .to_string_lossy()
+            .into_owned();
+        // SAFETY: the string was returned by this plugin and is released exactly once.
+        unsafe { (self.free_error)(error) };
+        bail!("{}: {message}", self.short_name)
+    }
+
+    fn read_schema(&self, path: &str, options: &OptionValues) -> anyhow::Result<SchemaRef> {
+        let read_schema = self
+            .read_schema
+            .ok_or_else(|| anyhow!("{} does not support reading", self.short_name))?;
+        let mut schema = FFI_ArrowSchema::empty();
+        self.call(path, options, |path, values, count| {
+            // SAFETY: the arguments are valid for the duration of the call.
+            unsafe { read_schema(path, values, count, &raw mut schema) }
+        })?;
+        Ok(Arc::new(Schema::try_from(&schema)?))
+    }
+
+    fn read(&self, path: &str, options: &OptionValues) -> anyhow::Result<ArrowArrayStreamReader> {
+        let read = self
+            .read
+            .ok_or_else(|| anyhow!("{} does not support reading", self.short_name))?;
+        let mut stream = FFI_ArrowArrayStream::empty();
+        self.call(path, options, |path, values, count| {
+            // SAFETY: the arguments are valid for the duration of the call.
+            unsafe { read(path, values, count, &raw mut stream) }
+        })?;
+        Ok(ArrowArrayStreamReader::try_new(stream)?)
+    }
+
+    fn write(
+        &self,
+        path: &str,
+        options: &OptionValues,
+        input: Box<dyn RecordBatchReader + Send>,
+    ) -> anyhow::Result<u64> {
+        let write = self
+            .write
+            .ok_or_else(|| anyhow!("{} does not support writing", self.short_name))?;
+        let mut stream = FFI_ArrowArrayStream::new(input);
+        let mut rows = 0;
+        self.call(path, options, |path, values, count| {
+            // SAFETY: the arguments are valid for the duration of the call; the plugin takes
+            // ownership of the stream.
+            unsafe { write(path, values, count, &raw mut stream, &raw mut rows) }
+        })?;
+        Ok(rows)
+    }
+}
+
+impl fmt::Debug for PluginFunctions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PluginFunctions")
+            .field("short_name", &self.short_name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Format factory backed by a plugin format.
+#[derive(Debug)]
+struct PluginFormatFactory {
+    short_name: &'static str,
+    long_name: &'static str,
+    open_options: Vec<FormatOption>,
+    creation_options: Vec<FormatOption>,
+    functions: PluginFunctions,
+}
+
+impl PluginFormatFactory {
+    /// Reads a plugin format descriptor.
+    ///
+    /// # Safety
+    ///
+    /// The descriptor's pointers must be valid for the rest of the process.
+    unsafe fn new(format: &'static PluginFormatDescriptor) -> anyhow::Result<Self> {
+        // SAFETY: guaranteed by the caller.
+        let (short_name, long_name) =
+            unsafe { (c_str(format.short_name)?, c_str(format.long_name)?) };
+        if short_name.is_empty() {
+            bail!("plugin format has an empty name");
+        }
+        if format.read.is_some() != format.read_schema.is_some() {
+            bail!("{short_name}: read and read_schema must be provided together");
+        }
+
+        Ok(Self {
+            short_name,
+            long_name,
+            // SAFETY: guaranteed by the caller.
+            open_options: unsafe { format_options(format.open_options, format.open_option_count) }?,
+            // SAFETY: guaranteed by the caller.
+            creation_options: unsafe {
+                format_options(format.creation_options, format.creation_option_count)
+            }?,
+            functions: PluginFunctions {
+                short_name,
+                read_schema: format.read_schema,
+                read: format.read,
+                write: format.write,
+                free_error: format.free_error,
+            },
+        })
+    }
+}
+
+impl FormatFactory for PluginFormatFactory {
+    fn driver(&self) -> Driver {
+        let status = |available: bool| {
+            if available {
+                SupportStatus::Supported
+            } else {
+                SupportStatus::NotSupported
+            }
+        };
+        let can_read = self.functions.read.is_some();
+        Driver::new(
+            self.short_name,
+            self.long_name,
+            status(can_read),
+            status(can_read),
+            status(self.functions.write.is_some()),
+        )
+    }
+
+    fn create_reader(&self) -> Option<Arc<dyn DataReader>> {
+        self.functions
+            .read
+            .map(|_| Arc::new(PluginReader(self.functions)) as Arc<dyn DataReader>)
+    }
+
+    fn create_writer(&self) -> Option<Arc<dyn DataWriter>> {
+        self.functions
+            .write
+            .map(|_| Arc::new(PluginWriter(self.functions)) as Arc<dyn DataWriter>)
+    }
+
+    fn open_options(&self) -> Vec<FormatOption> {
+        self.open_options.clone()
+    }
+
+    fn creation_options(&self) -> Vec<FormatOption> {
+        self.creation_options.clone()
+    }
+}
+
+/// Reader exposing a plugin dataset as a single-partition streaming table.
+#[derive(Debug)]
+struct PluginReader(PluginFunctions);
+
+#[async_trait]
+impl DataReader for PluginReader {
+    async fn create_table_provider(
+        &self,
+        _state: &SessionState,
+        path: &str,
+        options: &OptionValues,
+    ) -> anyhow::Result<Arc<dyn TableProvider>> {
+        let schema = self.0.read_schema(path, options)?;
+        let partition = PluginPartition {
+            functions: self.0,
+            path: path.to_string(),
+            options: options.clone(),
+            schema: Arc::clone(&schema),
+        };
+        Ok(Arc::new(StreamingTable::try_new(
+            schema,
+            vec![Arc::new(partition)],
+        )?))
+    }
+}
+
+/// The record batches of a plugin dataset, read on a blocking thread.
+#[derive(Debug)]
+struct PluginPartition {
+    functions: PluginFunctions,
+    path: String,
+    options: OptionValues,
+    schema: SchemaRef,
+}
+
+impl PartitionStream for PluginPartition {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let mut builder = RecordBatchReceiverStreamBuilder::new(Arc::clone(&self.schema), 2);
+        let tx = builder.tx();
+        let functions = self.functions;
+        let path = self.path.clone();
+        let options = self.options.clone();
+
+        builder.spawn_blocking(move || {
+            let reader = functions
+                .read(&path, &options)
+                .map_err(|e| DataFusionError::External(e.into()))?;
+            for batch in reader {
+                if tx
+                    .blocking_send(batch.map_err(DataFusionError::from))
+                    .is_err()
+                {
+                    // The consumer has stopped reading
+                    break;
+                }
+            }
+            Ok(())
+        });
+        builder.build()
+    }
+}
+
+/// Writer funnelling a plan's output into a plugin through a [`PluginSink`].
+#[derive(Debug)]
+struct PluginWriter(PluginFunctions);
+
+#[async_trait]
+impl DataWriter for PluginWriter {
+    async fn create_writer_plan(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        path: &str,
+        mode: WriteMode,
+        options: &OptionValues,
+    ) -> anyhow::Result<Arc<dyn ExecutionPlan>> {
+        // The plugin ABI writes whole datasets, so only overwriting is passed through
+        if mode == WriteMode::Append {
+            bail!("Plugin formats can only overwrite their output");
+        }
+
+        // All input partitions are funnelled into the single output dataset
+        let input: Arc<dyn ExecutionPlan> = if input.output_partitioning().partition_count() > 1 {
+            Arc::new(CoalescePartitionsExec::new(input))
+        } else {
+            input
+        };
+        let sink = PluginSink {
+            functions: self.0,
+            path: path.to_string(),
+            options: options.clone(),
+            schema: input.schema(),
+            create_new: mode == WriteMode::Fail,
+        };
+        Ok(Arc::new(DataSinkExec::new(input, Arc::new(sink), None)))
+    }
+}
+
+/// Data sink handing record batches to a plugin's `write` function.
+#[derive(Debug)]
+struct PluginSink {
+    functions: PluginFunctions,
+    path: String,
+    options: OptionValues,
+    schema: SchemaRef,
+    /// Create the output before the plugin writes it, failing if it already exists
+    create_new: bool,
+}
+
+#[async_trait]
+impl DataSink for PluginSink {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        None
+    }
+
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    async fn write_all(
+        &self,
+        mut data: SendableRecordBatchStream,
+        _context: &Arc<TaskContext>,
+    ) -> datafusion::error::Result<u64> {
+        if self.create_new {
+            // Checked and created in one operation, then handed to the plugin to overwrite
+            create_output(Path::new(&self.path), true)?;
+        }
+
+        // The plugin pulls batches synchronously, so it runs on a blocking thread fed
+        // through a channel
+        let (tx, rx) = tokio::sync::mpsc::channel(2);
+        let reader = ChannelReader {
+            schema: Arc::clone(&self.schema),
+            rx,
+        };
+        let functions = self.functions;
+        let path = self.path.clone();
+        let options = self.options.clone();
+        let writer =
+            tokio::task::spawn_blocking(move || functions.write(&path, &options, Box::new(reader)));
+
+        while let Some(batch) = data.next().await {
+            let batch = batch.map_err(|e| ArrowError::ExternalError(Box::new(e)));
+            if tx.send(batch).await.is_err() {
+                // The plugin has stopped reading; its result explains why
+                break;
+            }
+        }
+        drop(tx);
+
+        writer
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?
+            .map_err(|e| DataFusionError::External(e.into()))
+    }
+}
+
+impl DisplayAs for PluginSink {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PluginSink({})", self.functions.short_name)
+    }
+}
+
+/// Record batch reader fed by the async side of a [`PluginSink`].
+struct ChannelReader {
+    schema: SchemaRef,
+    rx: tokio::sync::mpsc::Receiver<std::result::Result<RecordBatch, ArrowError>>,
+}
+
+impl Iterator for ChannelReader {
+    type Item = std::result::Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rx.blocking_recv()
+    }
+}
+
+impl RecordBatchReader for ChannelReader {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int64Array, StringArray};
+    use arrow::datatypes::{DataType, Field};
+    use datafusion::prelude::SessionContext;
+    use geoetl_core_common::plugin::{PluginExport, PluginFormat};
+    use geoetl_core_common::{OptionType, options::OptionValues};
+    use std::fs;
+    use std::sync::{Mutex, OnceLock};
+    use tempfile::TempDir;
+
+    /// In-memory format: reads two rows and records what it is asked to write.
+    #[derive(Default)]
+    struct MemoryFormat;
+
+    static WRITTEN: Mutex<Vec<(String, usize)>> = Mutex::new(Vec::new());
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+        ]))
+    }
+
+    impl PluginFormat for MemoryFormat {
+        const SHORT_NAME: &'static str = "PluginMemory";
+        const LONG_NAME: &'static str = "In-memory plugin format";
+        const CAN_READ: bool = true;
+        const CAN_WRITE: bool = true;
+
+        fn open_options() -> Vec<FormatOption> {
+            vec![FormatOption::new("PREFIX", OptionType::String, "Name prefix").with_default("")]
+        }
+
+        fn read(
+            &self,
+            path: &str,
+            options: &OptionValues,
+        ) -> anyhow::Result<Box<dyn RecordBatchReader + Send>> {
+            if path.ends_with("missing") {
+                bail!("no such dataset '{path}'");
+            }
+            let prefix = options.get("PREFIX").unwrap_or_default();
+            let batch = RecordBatch::try_new(
+                schema(),
+                vec![
+                    Arc::new(Int64Array::from(vec![1, 2])),
+                    Arc::new(StringArray::from(vec![
+                        format!("{prefix}a"),
+                        format!("{prefix}b"),
+                    ])),
+                ],
+            )?;
+            Ok(Box::new(arrow::array::RecordBatchIterator::new(
+                vec![Ok(batch)],
+                schema(),
+            )))
+        }
+
+        fn write(
+            &self,
+            path: &str,
+            _options: &OptionValues,
+            input: &mut dyn RecordBatchReader,
+        ) -> anyhow::Result<u64> {
+            let mut rows = 0;
+            for batch in input {
+                rows += batch?.num_rows();
+            }
+            WRITTEN.lock().unwrap().push((path.to_string(), rows));
+            Ok(rows as u64)
+        }
+    }
+
+    fn memory_factory() -> Arc<dyn FormatFactory> {
+        static EXPORT: OnceLock<PluginExport> = OnceLock::new();
+        static REGISTERED: OnceLock<()> = OnceLock::new();
+        let export = EXPORT.get_or_init(|| PluginExport::new().with_format::<MemoryFormat>());
+        REGISTERED.get_or_init(|| {
+            let descriptor = unsafe { &*export.descriptor() };
+            let names = unsafe { register_plugin(descriptor, "memory") }.unwrap();
+            assert_eq!(names, vec!["PluginMemory".to_string()]);
+        });
+        driver_registry().find_factory("PluginMemory").unwrap()
+    }
+
+    #[test]
+    fn test_plugin_factory_describes_driver() {
+        let factory = memory_factory();
+        let driver = driver_registry().find_driver("pluginmemory").unwrap();
+        assert_eq!(driver.long_name, "In-memory plugin format");
+        assert_eq!(driver.capabilities.read, SupportStatus::Supported);
+        assert_eq!(driver.capabilities.write, SupportStatus::Supported);
+
+        let options = factory.open_options();
+        assert_eq!(options.len(), 1);
+        assert_eq!(options[0].name, "PREFIX");
+        assert_eq!(options[0].option_type, OptionType::String);
+        assert!(factory.creation_options().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_plugin_round_trip_through_arrow_streams() {
+        let factory = memory_factory();
+        let ctx = SessionContext::new();
+        let options = OptionValues::new().with("PREFIX", "x_");
+
+        let provider = factory
+            .create_reader()
+            .unwrap()
+            .create_table_provider(&ctx.state(), "dataset", &options)
+            .await
+            .unwrap();
+        assert_eq!(provider.schema(), schema());
+        ctx.register_table("plugin", provider).unwrap();
+
+        let df = ctx
+            .sql("SELECT name FROM plugin ORDER BY id")
+            .await
+            .unwrap();
+        let batches = df.clone().collect().await.unwrap();
+        let names = batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(names.value(0), "x_a");
+        assert_eq!(names.value(1), "x_b");
+
+        let plan = df.create_physical_plan().await.unwrap();
+        let plan = factory
+            .create_writer()
+            .unwrap()
+            .create_writer_plan(plan, "output", WriteMode::Overwrite, &OptionValues::new())
+            .await
+            .unwrap();
+        let result = datafusion::physical_plan::collect(plan, ctx.task_ctx())
+            .await
+            .unwrap();
+        let count = result[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<arrow::array::UInt64Array>()
+            .unwrap();
+        assert_eq!(count.value(0), 2);
+        assert!(WRITTEN.lock().unwrap().contains(&("output".to_string(), 2)));
+    }
+
+    #[tokio::test]
+    async fn test_plugin_writer_fails_on_existing_output() {
+        let factory = memory_factory();
+        let ctx = SessionContext::new();
+        let temp_dir = TempDir::new().unwrap();
+        let output = temp_dir.path().join("output.dat");
+        let path = output.to_str().unwrap();
+        let write = || async {
+            let plan = ctx
+                .sql("SELECT 1 AS id")
+                .await?
+                .create_physical_plan()
+                .await?;
+            let plan = factory
+                .create_writer()
+                .unwrap()
+                .create_writer_plan(plan, path, WriteMode::Fail, &OptionValues::new())
+                .await
+                .unwrap();
+            datafusion::physical_plan::collect(plan, ctx.task_ctx()).await
+        };
+
+        write().await.unwrap();
+        assert!(WRITTEN.lock().unwrap().contains(&(path.to_string(), 1)));
+
+        // The output now exists, so the plugin is not asked to write it again
+        let err = write().await.unwrap_err();
+        assert!(
+            matches!(&err, DataFusionError::IoError(e) if e.kind() == std::io::ErrorKind::AlreadyExists),
+            "{err:?}"
+        );
+        let writes = WRITTEN.lock().unwrap();
+        assert_eq!(writes.iter().filter(|(p, _)| p == path).count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_plugin_errors_are_reported() {
+        let factory = memory_factory();
+        let ctx = SessionContext::new();
+        let err = factory
+            .create_reader()
+            .unwrap()
+            .create_table_provider(&ctx.state(), "missing", &OptionValues::new())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no such dataset 'missing'"));
+    }
+
+    #[test]
+    fn test_register_plugin_rejects_duplicates_and_other_abi_versions() {
+        memory_factory();
+
+        let export = Box::leak(Box::new(PluginExport::new().with_format::<MemoryFormat>()));
+        let descriptor = unsafe { &*export.descriptor() };
+        let err = unsafe { register_plugin(descriptor, "duplicate") }.unwrap_err();
+        assert!(err.to_string().contains("already registered"));
+
+        let other = Box::leak(Box::new(PluginDescriptor {
+            abi_version: PLUGIN_ABI_VERSION + 1,
+            formats: std::ptr::null(),
+            format_count: 0,
+        }));
+        let err = unsafe { register_plugin(other, "future") }.unwrap_err();
+        assert!(err.to_string().contains("ABI version"));
+    }
+
+    #[test]
+    fn test_plugin_libraries_filters_by_extension() {
+        let dir = TempDir::new().unwrap();
+        let extension = env::consts::DLL_EXTENSION;
+        for name in [
+            format!("b_plugin.{extension}"),
+            format!("a_plugin.{extension}"),
+            "README.txt".to_string(),
+        ] {
+            fs::write(dir.path().join(name), b"").unwrap();
+        }
+
+        let libraries = plugin_libraries(dir.path());
+        let names: Vec<_> = libraries
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                format!("a_plugin.{extension}"),
+                format!("b_plugin.{extension}")
+            ]
+        );
+
+        let file = dir.path().join("README.txt");
+        assert_eq!(plugin_libraries(&file), vec![file]);
+        assert!(plugin_libraries(&dir.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn test_load_plugin_rejects_invalid_library() {
+        let dir = TempDir::new().unwrap();
+        let path = dir
+            .path()
+            .join(format!("broken.{}", env::consts::DLL_EXTENSION));
+        fs::write(&path, b"not a shared library").unwrap();
+
+        let err = load_plugin(&path).unwrap_err();
+        assert!(matches!(
+            err,
+            GeoEtlError::Driver(DriverError::PluginLoad { .. })
+        ));
+        assert!(err.to_string().contains("broken"));
+    }
+}
@@ -1,10 +1,26 @@
 //! Data types for geospatial ETL operations.
 //!
 //! This module defines the data structures used to represent dataset information,
-//! geometry columns, and field schemas.
+//! geometry columns, and field schemas, as well as the options accepted by operations.
+//!
+//! Dataset information serialises with `serde` under a versioned schema (see
+//! [`SCHEMA_VERSION`]) for machine-readable output.
+
+pub use geoetl_core_common::{OptionValues, WriteMode};
+pub use geoetl_operations::{Dimension, Simplification};
+
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+
+use crate::drivers::Driver;
+
+/// Version of the serialised form of [`DatasetInfo`] and the driver descriptions.
+///
+/// Adding fields keeps the version; renaming, removing or retyping one raises it.
+pub const SCHEMA_VERSION: u32 = 1;
 
 /// Information about a dataset.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DatasetInfo {
     /// Path to the dataset
     pub dataset: String,
@@ -12,18 +28,72 @@ pub struct DatasetInfo {
     pub driver: String,
     /// Driver long name
     pub driver_long_name: String,
+    /// Layer described by the geometry columns, fields and statistics
+    pub layer: String,
+    /// Names of every layer in the dataset, in dataset order
+    pub layers: Vec<String>,
     /// Geometry columns information
     pub geometry_columns: Vec<GeometryColumnInfo>,
     /// Schema fields
     pub fields: Vec<FieldInfo>,
+    /// Number of features, counted when statistics are computed or a spatial filter is given
+    /// (matching the filter, if any)
+    pub feature_count: Option<u64>,
+    /// Total bounds of the geometry columns, with statistics; `None` without geometries
+    pub extent: Option<Extent>,
+    /// Number of geometries of each type (e.g., "Point", "Polygon Z"), most frequent first,
+    /// with statistics. Serialised as `{"type", "count"}` objects.
+    #[serde(serialize_with = "serialize_type_counts")]
+    pub geometry_types: Vec<(String, u64)>,
 }
 
-/// Information about a geometry column.
+/// A bounding box.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Extent {
+    /// Smallest X coordinate
+    pub min_x: f64,
+    /// Smallest Y coordinate
+    pub min_y: f64,
+    /// Largest X coordinate
+    pub max_x: f64,
+    /// Largest Y coordinate
+    pub max_y: f64,
+}
+
+/// Result of validating the geometries of a dataset.
 #[derive(Debug, Clone)]
+pub struct ValidationReport {
+    /// Path to the dataset
+    pub dataset: String,
+    /// Driver name
+    pub driver: String,
+    /// Name of the checked layer
+    pub layer: String,
+    /// Number of features checked
+    pub feature_count: u64,
+    /// Invalid geometries, in feature order
+    pub issues: Vec<ValidationIssue>,
+}
+
+/// An invalid geometry found by validation.
+#[derive(Debug, Clone)]
+pub struct ValidationIssue {
+    /// Zero-based index of the feature in the dataset
+    pub feature: u64,
+    /// Geometry column holding the invalid geometry
+    pub column: String,
+    /// Why the geometry is invalid (e.g., "exterior ring has a self-intersection")
+    pub reason: String,
+    /// Where the geometry is invalid, as a WKT point
+    pub location: Option<String>,
+}
+
+/// Information about a geometry column.
+#[derive(Debug, Clone, Serialize)]
 pub struct GeometryColumnInfo {
     /// Column name
     pub name: String,
-    /// Data type description
+    /// Geometry type (e.g., "Point", "MultiPolygon Z"), or "Geometry" for mixed types
     pub data_type: String,
     /// Extension name (e.g., "geoarrow.geometry")
     pub extension: Option<String>,
@@ -32,7 +102,7 @@ pub struct GeometryColumnInfo {
 }
 
 /// Information about a field/column.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FieldInfo {
     /// Field name
     pub name: String,
@@ -40,4 +110,373 @@ pub struct FieldInfo {
     pub data_type: String,
     /// Whether the field is nullable
     pub nullable: bool,
+    /// Value statistics, when computed
+    pub stats: Option<FieldStats>,
+}
+
+/// Statistics of the values of a field.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FieldStats {
+    /// Number of null values
+    pub null_count: u64,
+    /// Smallest value, for ordered types
+    pub min: Option<String>,
+    /// Largest value, for ordered types
+    pub max: Option<String>,
+    /// Estimated number of distinct values, for ordered types
+    pub distinct_count: Option<u64>,
+    /// Most frequent values with their counts, for string fields. Counts are estimated for
+    /// fields with many distinct values. Serialised as `{"value", "count"}` objects.
+    #[serde(serialize_with = "serialize_value_counts")]
+    pub top_values: Vec<(String, u64)>,
+}
+
+/// A name and its count, serialised as `{key: name, "count": count}`.
+struct Count<'a> {
+    key: &'static str,
+    name: &'a str,
+    count: u64,
+}
+
+impl Serialize for Count<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry(self.key, self.name)?;
+        map.serialize_entry("count", &self.count)?;
+        map.end()
+    }
+}
+
+fn serialize_counts<S: Serializer>(
+    key: &'static str,
+    counts: &[(String, u64)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(counts.iter().map(|(name, count)| Count {
+        key,
+        name,
+        count: *count,
+    }))
+}
+
+fn serialize_type_counts<S: Serializer>(
+    counts: &[(String, u64)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serialize_counts("type", counts, serializer)
+}
+
+fn serialize_value_counts<S: Serializer>(
+    counts: &[(String, u64)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serialize_counts("value", counts, serializer)
+}
+
+/// Options for a conversion.
+#[derive(Debug, Clone, Default)]
+pub struct ConvertOptions {
+    /// Layers to convert, in output order; every layer of the input when empty
+    pub layers: Vec<String>,
+    /// Open options passed to the input driver (e.g., `--oo KEY=VALUE`)
+    pub open_options: OptionValues,
+    /// Creation options passed to the output driver (e.g., `--lco KEY=VALUE`)
+    pub creation_options: OptionValues,
+    /// SQL filter expression rows must satisfy to be written (e.g., `population > 1000`)
+    pub filter: Option<String>,
+    /// Columns to write, in output order; all columns when `None`
+    pub columns: Option<Vec<String>>,
+    /// Column renames applied after selection, as `(old, new)` pairs
+    pub renames: Vec<(String, String)>,
+    /// WKT geometry, in the input CRS, that written features must intersect
+    pub spatial_filter: Option<String>,
+    /// Clip geometries to the spatial filter
+    pub clip: bool,
+    /// Repair invalid geometries before writing them
+    pub make_valid: bool,
+    /// Simplification tolerance, in input CRS units: a distance for Douglas-Peucker and an
+    /// area for the Visvalingam methods
+    pub simplify_tolerance: Option<f64>,
+    /// Algorithm used when simplifying
+    pub simplify_method: Simplification,
+    /// Grid size output coordinates are snapped to, in output CRS units
+    pub snap_to_grid: Option<f64>,
+    /// Split multi-geometries and collections into one feature per part
+    pub explode: bool,
+    /// Geometry type written (e.g., `MultiPolygon`, `Point Z`), or `PROMOTE_TO_MULTI` to
+    /// wrap single geometries in multi-geometries
+    pub geometry_type: Option<String>,
+    /// Coordinate dimension written
+    pub dimension: Option<Dimension>,
+    /// CRS assigned to the input geometries when the dataset does not record one
+    /// (e.g., `EPSG:32618` for a CSV file)
+    pub assigned_crs: Option<String>,
+    /// CRS of the input geometries, overriding the CRS recorded in the dataset
+    pub source_crs: Option<String>,
+    /// CRS the geometries are reprojected to (e.g., `EPSG:3857`)
+    pub target_crs: Option<String>,
+    /// What to do when the output already exists
+    pub write_mode: WriteMode,
+}
+
+impl ConvertOptions {
+    /// Create conversion options with no driver options set
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a layer of the input to convert
+    #[must_use]
+    pub fn with_layer(mut self, layer: impl Into<String>) -> Self {
+        self.layers.push(layer.into());
+        self
+    }
+
+    /// Set the open options passed to the input driver
+    #[must_use]
+    pub fn with_open_options(mut self, open_options: OptionValues) -> Self {
+        self.open_options = open_options;
+        self
+    }
+
+    /// Set the creation options passed to the output driver
+    #[must_use]
+    pub fn with_creation_options(mut self, creation_options: OptionValues) -> Self {
+        self.creation_options = creation_options;
+        self
+    }
+
+    /// Set the SQL filter expression rows must satisfy to be written
+    #[must_use]
+    pub fn with_filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// Set the columns to write, in output order
+    #[must_use]
+    pub fn with_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Add a column rename applied after selection
+    #[must_use]
+    pub fn with_rename(mut self, old: impl Into<String>, new: impl Into<String>) -> Self {
+        self.renames.push((old.into(), new.into()));
+        self
+    }
+
+    /// Keep only the features intersecting a WKT geometry given in the input CRS
+    ///
+    /// See [`crate::operations::spatial_filter_geometry`] to build it from a bounding box or
+    /// a file.
+    #[must_use]
+    pub fn with_spatial_filter(mut self, wkt: impl Into<String>) -> Self {
+        self.spatial_filter = Some(wkt.into());
+        self
+    }
+
+    /// Clip geometries to the spatial filter
+    #[must_use]
+    pub fn with_clip(mut self, clip: bool) -> Self {
+        self.clip = clip;
+        self
+    }
+
+    /// Repair invalid geometries with `ST_MakeValid` before writing them
+    #[must_use]
+    pub fn with_make_valid(mut self, make_valid: bool) -> Self {
+        self.make_valid = make_valid;
+        self
+    }
+
+    /// Simplify lines and polygons with `tolerance` before reprojection
+    #[must_use]
+    pub fn with_simplify(mut self, tolerance: f64) -> Self {
+        self.simplify_tolerance = Some(tolerance);
+        self
+    }
+
+    /// Set the algorithm used when simplifying
+    #[must_use]
+    pub fn with_simplify_method(mut self, method: Simplification) -> Self {
+        self.simplify_method = method;
+        self
+    }
+
+    /// Round output coordinates to multiples of `size`, after reprojection
+    #[must_use]
+    pub fn with_snap_to_grid(mut self, size: f64) -> Self {
+        self.snap_to_grid = Some(size);
+        self
+    }
+
+    /// Split multi-geometries and collections into one feature per part, duplicating the
+    /// other columns
+    #[must_use]
+    pub fn with_explode(mut self, explode: bool) -> Self {
+        self.explode = explode;
+        self
+    }
+
+    /// Convert the geometries to a type, or to multi-geometries with `PROMOTE_TO_MULTI`
+    #[must_use]
+    pub fn with_geometry_type(mut self, geometry_type: impl Into<String>) -> Self {
+        self.geometry_type = Some(geometry_type.into());
+        self
+    }
+
+    /// Force the coordinate dimension, adding zero Z or M values where missing
+    #[must_use]
+    pub fn with_dimension(mut self, dimension: Dimension) -> Self {
+        self.dimension = Some(dimension);
+        self
+    }
+
+    /// Assign a CRS to the input geometries, recorded in the geometry column's metadata
+    #[must_use]
+    pub fn with_assigned_crs(mut self, crs: impl Into<String>) -> Self {
+        self.assigned_crs = Some(crs.into());
+        self
+    }
+
+    /// Set the CRS of the input geometries, overriding the CRS recorded in the dataset
+    #[must_use]
+    pub fn with_source_crs(mut self, crs: impl Into<String>) -> Self {
+        self.source_crs = Some(crs.into());
+        self
+    }
+
+    /// Set the CRS the geometries are reprojected to
+    #[must_use]
+    pub fn with_target_crs(mut self, crs: impl Into<String>) -> Self {
+        self.target_crs = Some(crs.into());
+        self
+    }
+
+    /// Set what to do when the output already exists
+    #[must_use]
+    pub fn with_write_mode(mut self, write_mode: WriteMode) -> Self {
+        self.write_mode = write_mode;
+        self
+    }
+}
+
+/// Options for reading dataset information.
+#[derive(Debug, Clone, Default)]
+pub struct InfoOptions {
+    /// Open options passed to the driver (e.g., `--oo KEY=VALUE`)
+    pub open_options: OptionValues,
+    /// Layer to describe; the first layer of the dataset when `None`
+    pub layer: Option<String>,
+    /// WKT geometry, in the dataset CRS, that counted features must intersect
+    pub spatial_filter: Option<String>,
+    /// Read every feature to compute the dataset and field statistics
+    pub stats: bool,
+}
+
+impl InfoOptions {
+    /// Create info options describing the first layer without statistics
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the open options passed to the driver
+    #[must_use]
+    pub fn with_open_options(mut self, open_options: OptionValues) -> Self {
+        self.open_options = open_options;
+        self
+    }
+
+    /// Set the layer to describe
+    #[must_use]
+    pub fn with_layer(mut self, layer: impl Into<String>) -> Self {
+        self.layer = Some(layer.into());
+        self
+    }
+
+    /// Count the features intersecting a WKT geometry given in the dataset CRS
+    #[must_use]
+    pub fn with_spatial_filter(mut self, wkt: impl Into<String>) -> Self {
+        self.spatial_filter = Some(wkt.into());
+        self
+    }
+
+    /// Compute the feature count, extent, geometry types and field statistics
+    #[must_use]
+    pub fn with_stats(mut self, stats: bool) -> Self {
+        self.stats = stats;
+        self
+    }
+}
+
+/// A dataset registered as a named table for a SQL query.
+#[derive(Debug, Clone)]
+pub struct SqlTable {
+    /// Table name used in the query
+    pub name: String,
+    /// Path to the dataset
+    pub path: String,
+    /// Driver used to read the dataset
+    pub driver: Driver,
+    /// Name of the geometry column (for CSV)
+    pub geometry_column: String,
+    /// Optional geometry type hint (for CSV)
+    pub geometry_type: Option<String>,
+    /// Open options passed to the driver
+    pub open_options: OptionValues,
+}
+
+impl SqlTable {
+    /// Create a table reading `path` with `driver`, with a `geometry` geometry column
+    #[must_use]
+    pub fn new(name: impl Into<String>, path: impl Into<String>, driver: Driver) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+            driver,
+            geometry_column: "geometry".to_string(),
+            geometry_type: None,
+            open_options: OptionValues::new(),
+        }
+    }
+
+    /// Set the name of the geometry column
+    #[must_use]
+    pub fn with_geometry_column(mut self, geometry_column: impl Into<String>) -> Self {
+        self.geometry_column = geometry_column.into();
+        self
+    }
+
+    /// Set the geometry type hint
+    #[must_use]
+    pub fn with_geometry_type(mut self, geometry_type: Option<String>) -> Self {
+        self.geometry_type = geometry_type;
+        self
+    }
+
+    /// Set the open options passed to the driver
+    #[must_use]
+    pub fn with_open_options(mut self, open_options: OptionValues) -> Self {
+        self.open_options = open_options;
+        self
+    }
+}
+
+/// Result of a SQL query, with every value formatted as text.
+///
+/// Geometry columns are formatted as WKT and null values as `NULL`.
+#[derive(Debug, Clone, Default)]
+pub struct QueryResult {
+    /// Column names
+    pub columns: Vec<String>,
+    /// Row values, in column order
+    pub rows: Vec<Vec<String>>,
 }
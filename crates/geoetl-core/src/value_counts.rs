@@ -0,0 +1,251 @@
+//! The `value_counts` aggregate used by `info --stats`.
+//!
+//! Counting the values of a column inside the statistics aggregation, instead of with a
+//! `GROUP BY` per column, lets every statistic be computed in a single pass over the dataset.
+//! With a limit, the values are counted by a Space-Saving sketch of a fixed number of
+//! counters, so a column of unique values is not held in memory.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::sync::Arc;
+
+use datafusion::arrow::array::{Array, ArrayRef, AsArray, StringArray, StructArray, UInt64Array};
+use datafusion::arrow::datatypes::{DataType, Field, FieldRef, Fields, UInt64Type};
+use datafusion::common::utils::SingleRowListArrayBuilder;
+use datafusion::common::{Result, ScalarValue};
+use datafusion::logical_expr::function::{AccumulatorArgs, StateFieldsArgs};
+use datafusion::logical_expr::utils::format_state_name;
+use datafusion::logical_expr::{Accumulator, AggregateUDFImpl, Signature, Volatility};
+
+/// Counters kept by the sketch for each value reported
+const COUNTERS_PER_VALUE: usize = 20;
+
+/// `value_counts(value)`: the non-null string values with their counts, most frequent first
+/// and ties in value order, keeping the first `limit`
+///
+/// The result is a list of `{value, count}` structs. Without a limit every value is counted
+/// exactly. With one, only `limit * COUNTERS_PER_VALUE` values are tracked: counts are exact
+/// when a column has no more distinct values than that, and otherwise estimates that can
+/// overcount a value by the count of the values it displaced.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub(crate) struct ValueCountsUdf {
+    limit: Option<usize>,
+    signature: Signature,
+}
+
+impl ValueCountsUdf {
+    pub(crate) fn new(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            signature: Signature::exact(vec![DataType::Utf8], Volatility::Immutable),
+        }
+    }
+
+    /// Fields of the structs in the result list
+    fn entry_fields() -> Fields {
+        Fields::from(vec![
+            Field::new("value", DataType::Utf8, false),
+            Field::new("count", DataType::UInt64, false),
+        ])
+    }
+
+    /// Read the `(value, count)` pairs of a result list at `row` of `array`
+    pub(crate) fn read(array: &dyn Array, row: usize) -> Vec<(String, u64)> {
+        let entries = array.as_list::<i32>().value(row);
+        let entries = entries.as_struct();
+        let values = entries.column(0).as_string::<i32>();
+        let counts = entries.column(1).as_primitive::<UInt64Type>();
+        (0..entries.len())
+            .map(|i| (values.value(i).to_string(), counts.value(i)))
+            .collect()
+    }
+}
+
+impl AggregateUDFImpl for ValueCountsUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "value_counts"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::new_list(
+            DataType::Struct(Self::entry_fields()),
+            true,
+        ))
+    }
+
+    fn accumulator(&self, _acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(ValueCountsAccumulator {
+            limit: self.limit,
+            capacity: self
+                .limit
+                .map(|limit| limit.max(1).saturating_mul(COUNTERS_PER_VALUE)),
+            counts: HashMap::new(),
+        }))
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<FieldRef>> {
+        Ok(vec![
+            Arc::new(Field::new(
+                format_state_name(args.name, "values"),
+                DataType::new_list(DataType::Utf8, true),
+                true,
+            )),
+            Arc::new(Field::new(
+                format_state_name(args.name, "counts"),
+                DataType::new_list(DataType::UInt64, true),
+                true,
+            )),
+        ])
+    }
+}
+
+#[derive(Debug)]
+struct ValueCountsAccumulator {
+    limit: Option<usize>,
+    /// Maximum number of values counted, when sketching
+    capacity: Option<usize>,
+    counts: HashMap<String, u64>,
+}
+
+impl ValueCountsAccumulator {
+    /// Count `count` occurrences of `value`
+    ///
+    /// Once `capacity` values are tracked, a new value replaces the least frequent one and
+    /// takes over its count, as in the Space-Saving algorithm.
+    fn add(&mut self, value: &str, count: u64) {
+        if let Some(total) = self.counts.get_mut(value) {
+            *total += count;
+            return;
+        }
+
+        let mut count = count;
+        if self
+            .capacity
+            .is_some_and(|capacity| self.counts.len() >= capacity)
+        {
+            let least = self
+                .counts
+                .iter()
+                .min_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(b.cmp(a)))
+                .map(|(value, count)| (value.clone(), *count));
+            if let Some((least, least_count)) = least {
+                self.counts.remove(&least);
+                count += least_count;
+            }
+        }
+        self.counts.insert(value.to_string(), count);
+    }
+}
+
+impl Accumulator for ValueCountsAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        for value in values[0].as_string::<i32>().iter().flatten() {
+            self.add(value, 1);
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let (values, counts) = (states[0].as_list::<i32>(), states[1].as_list::<i32>());
+        for row in (0..values.len()).filter(|&row| values.is_valid(row)) {
+            let (row_values, row_counts) = (values.value(row), counts.value(row));
+            let row_counts = row_counts.as_primitive::<UInt64Type>();
+            for (value, count) in row_values.as_string::<i32>().iter().zip(row_counts) {
+                if let (Some(value), Some(count)) = (value, count) {
+                    self.add(value, count);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        let (values, counts): (Vec<&str>, Vec<u64>) = self
+            .counts
+            .iter()
+            .map(|(value, count)| (value.as_str(), *count))
+            .unzip();
+        Ok(vec![
+            SingleRowListArrayBuilder::new(Arc::new(StringArray::from(values))).build_list_scalar(),
+            SingleRowListArrayBuilder::new(Arc::new(UInt64Array::from(counts))).build_list_scalar(),
+        ])
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let mut counts: Vec<(&String, &u64)> = self.counts.iter().collect();
+        counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        counts.truncate(self.limit.unwrap_or(usize::MAX));
+
+        let (values, counts): (Vec<&str>, Vec<u64>) = counts
+            .into_iter()
+            .map(|(value, count)| (value.as_str(), *count))
+            .unzip();
+        let entries = StructArray::try_new(
+            ValueCountsUdf::entry_fields(),
+            vec![
+                Arc::new(StringArray::from(values)),
+                Arc::new(UInt64Array::from(counts)),
+            ],
+            None,
+        )?;
+        Ok(SingleRowListArrayBuilder::new(Arc::new(entries)).build_list_scalar())
+    }
+
+    fn size(&self) -> usize {
+        let text: usize = self.counts.keys().map(String::capacity).sum();
+        size_of::<Self>() + self.counts.capacity() * size_of::<(String, u64)>() + text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accumulator(limit: Option<usize>) -> ValueCountsAccumulator {
+        ValueCountsAccumulator {
+            limit,
+            capacity: limit.map(|limit| limit * COUNTERS_PER_VALUE),
+            counts: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn sketch_keeps_frequent_values_in_bounded_memory() {
+        let mut counts = accumulator(Some(2));
+        for i in 0..10_000 {
+            counts.add(&format!("id-{i}"), 1);
+            if i % 4 == 0 {
+                counts.add("common", 1);
+            }
+        }
+        assert!(counts.counts.len() <= 2 * COUNTERS_PER_VALUE);
+
+        let Ok(ScalarValue::List(list)) = counts.evaluate() else {
+            panic!("expected a list");
+        };
+        let top = ValueCountsUdf::read(list.as_ref(), 0);
+        assert_eq!(top[0].0, "common");
+        assert!(top[0].1 >= 2_500);
+    }
+
+    #[test]
+    fn counts_are_exact_below_capacity() {
+        let mut sketched = accumulator(Some(2));
+        let mut exact = accumulator(None);
+        for value in ["a", "b", "a", "c", "a", "b"] {
+            sketched.add(value, 1);
+            exact.add(value, 1);
+        }
+        assert_eq!(sketched.counts, exact.counts);
+        assert_eq!(sketched.counts["a"], 3);
+    }
+}
@@ -4,7 +4,10 @@
 //! through the driver system to the final output.
 
 use geoetl_core::drivers::{Driver, SupportStatus, find_driver};
-use geoetl_core::operations::convert;
+use geoetl_core::error::{ConfigError, GeoEtlError};
+use geoetl_core::operations::{convert, info, spatial_filter_geometry, validate};
+use geoetl_core::types::{ConvertOptions, Dimension, Extent, InfoOptions, Simplification};
+use geoetl_core_common::OptionValues;
 use std::fs::File;
 use std::io::Write;
 use tempfile::TempDir;
@@ -100,6 +103,7 @@ async fn test_e2e_csv_to_csv_conversion() {
         &csv_driver,
         "wkt",
         None,
+        &ConvertOptions::default(),
     )
     .await;
 
@@ -142,6 +146,7 @@ async fn test_e2e_geojson_to_geojson_conversion() {
         &geojson_driver,
         "geometry",
         None,
+        &ConvertOptions::default(),
     )
     .await;
 
@@ -188,6 +193,7 @@ async fn test_e2e_large_csv_conversion() {
         &csv_driver,
         "wkt",
         None,
+        &ConvertOptions::default(),
     )
     .await;
 
@@ -240,6 +246,7 @@ async fn test_e2e_driver_validation() {
         &output_driver,
         "wkt",
         None,
+        &ConvertOptions::default(),
     )
     .await;
 
@@ -278,6 +285,7 @@ async fn test_e2e_csv_with_special_characters() {
         &csv_driver,
         "wkt",
         None,
+        &ConvertOptions::default(),
     )
     .await;
 
@@ -318,6 +326,7 @@ async fn test_e2e_multiple_conversions_same_session() {
         &csv_driver,
         "wkt",
         None,
+        &ConvertOptions::default(),
     )
     .await;
 
@@ -329,6 +338,7 @@ async fn test_e2e_multiple_conversions_same_session() {
         &geojson_driver,
         "geometry",
         None,
+        &ConvertOptions::default(),
     )
     .await;
 
@@ -337,3 +347,866 @@ async fn test_e2e_multiple_conversions_same_session() {
     assert!(output1.exists());
     assert!(output2.exists());
 }
+
+#[tokio::test]
+async fn test_e2e_convert_with_filter_select_and_rename() {
+    geoetl_core::init::initialize();
+
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("input_data.csv");
+    let output_path = temp_dir.path().join("filtered.geojson");
+    create_spatial_csv(&input_path).unwrap();
+
+    let csv_driver = find_driver("CSV").expect("CSV driver should exist");
+    let geojson_driver = find_driver("GeoJSON").expect("GeoJSON driver should exist");
+
+    let options = ConvertOptions::new()
+        .with_filter("category = 'retail' OR value > 250")
+        .with_columns(["name", "value", "wkt"])
+        .with_rename("name", "label")
+        .with_rename("wkt", "geom");
+    convert(
+        input_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        &csv_driver,
+        &geojson_driver,
+        "wkt",
+        None,
+        &options,
+    )
+    .await
+    .expect("filtered conversion should succeed");
+
+    let output = std::fs::read_to_string(&output_path).unwrap();
+    // Location A and D are retail, E has a value above 250
+    for kept in ["Location A", "Location D", "Location E"] {
+        assert!(output.contains(kept), "{kept} should be kept");
+    }
+    for dropped in ["Location B", "Location C"] {
+        assert!(
+            !output.contains(dropped),
+            "{dropped} should be filtered out"
+        );
+    }
+    // Renamed columns are written, unselected columns are not
+    assert!(output.contains("\"label\""));
+    assert!(!output.contains("\"name\""));
+    assert!(!output.contains("\"category\""));
+    // The renamed geometry column is still written as the feature geometry
+    assert!(output.contains("\"Point\""));
+}
+
+#[tokio::test]
+async fn test_e2e_convert_with_spatial_filter() {
+    geoetl_core::init::initialize();
+
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("input_data.csv");
+    let output_path = temp_dir.path().join("east.csv");
+    create_spatial_csv(&input_path).unwrap();
+
+    let csv_driver = find_driver("CSV").expect("CSV driver should exist");
+    convert(
+        input_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        &csv_driver,
+        &csv_driver,
+        "wkt",
+        None,
+        &ConvertOptions::new()
+            .with_filter(
+                "ST_X(wkt) > -90 AND ST_Within(wkt, ST_Envelope('LINESTRING(-180 35, 0 90)'))",
+            )
+            .with_columns(["name"]),
+    )
+    .await
+    .expect("spatial filter should be accepted");
+
+    let output = std::fs::read_to_string(&output_path).unwrap();
+    // Only New York (A) and Chicago (C) are east of -90 and north of 35
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        ["name", "Location A", "Location C"]
+    );
+}
+
+#[tokio::test]
+async fn test_e2e_convert_with_bbox_and_clip() {
+    geoetl_core::init::initialize();
+
+    let temp_dir = TempDir::new().unwrap();
+    let csv_path = temp_dir.path().join("input_data.csv");
+    let geojson_path = temp_dir.path().join("cities.geojson");
+    let output_path = temp_dir.path().join("west.csv");
+    create_spatial_csv(&csv_path).unwrap();
+    create_sample_geojson(&geojson_path).unwrap();
+    let csv = find_driver("CSV").unwrap();
+    let geojson = find_driver("GeoJSON").unwrap();
+
+    // Los Angeles (B), Chicago (C) and Phoenix (E) lie in the box; Houston is south of it
+    let bbox = spatial_filter_geometry("-120,30,-80,45").await.unwrap();
+    convert(
+        csv_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        &csv,
+        &csv,
+        "wkt",
+        None,
+        &ConvertOptions::new()
+            .with_spatial_filter(bbox.clone())
+            .with_columns(["name"]),
+    )
+    .await
+    .expect("bbox filter should be accepted");
+    let output = std::fs::read_to_string(&output_path).unwrap();
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        ["name", "Location B", "Location C", "Location E"]
+    );
+
+    let dataset = info(
+        geojson_path.to_str().unwrap(),
+        &geojson,
+        "geometry",
+        None,
+        &InfoOptions::new().with_spatial_filter(bbox.as_str()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(dataset.feature_count, Some(1));
+
+    // Clipping cuts lines at the filter geometry and drops those outside it
+    let lines_path = temp_dir.path().join("lines.csv");
+    std::fs::write(
+        &lines_path,
+        "id,wkt\n1,\"LINESTRING(0 0, 10 0)\"\n2,\"LINESTRING(20 20, 30 30)\"\n",
+    )
+    .unwrap();
+    let clip = spatial_filter_geometry("POLYGON((0 -1, 5 -1, 5 1, 0 1, 0 -1))")
+        .await
+        .unwrap();
+    convert(
+        lines_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        &csv,
+        &csv,
+        "wkt",
+        Some("LineString"),
+        &ConvertOptions::new()
+            .with_spatial_filter(clip)
+            .with_clip(true),
+    )
+    .await
+    .expect("clipping should succeed");
+    let output = std::fs::read_to_string(&output_path).unwrap();
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        ["id,wkt", "1,\"LINESTRING(0 0,5 0)\""]
+    );
+
+    // Filter geometries can also come from a dataset
+    let polygon_path = temp_dir.path().join("area.wkt");
+    std::fs::write(
+        &polygon_path,
+        "POLYGON((-90 40, -70 40, -70 45, -90 45, -90 40))",
+    )
+    .unwrap();
+    let area = spatial_filter_geometry(polygon_path.to_str().unwrap())
+        .await
+        .unwrap();
+    let dataset = info(
+        geojson_path.to_str().unwrap(),
+        &geojson,
+        "geometry",
+        None,
+        &InfoOptions::new().with_spatial_filter(area.as_str()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(dataset.feature_count, Some(2));
+
+    for spec in ["10,0,0,10", "1,2,3", "POLYGON((0 0"] {
+        let err = spatial_filter_geometry(spec).await.unwrap_err();
+        assert!(
+            matches!(
+                err,
+                GeoEtlError::Config(ConfigError::InvalidOption { ref option, .. })
+                    if option == "spatial filter"
+            ),
+            "{spec}: {err}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_e2e_validate_and_make_valid() {
+    geoetl_core::init::initialize();
+
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("parcels.csv");
+    let output_path = temp_dir.path().join("repaired.csv");
+    std::fs::write(
+        &input_path,
+        "id,wkt\n\
+         1,\"POLYGON((0 0, 1 0, 1 1, 0 1, 0 0))\"\n\
+         2,\"POLYGON((0 0, 2 2, 2 0, 0 2, 0 0))\"\n\
+         3,\"POLYGON((0 0, 0 1, 1 1, 1 0, 0 0))\"\n\
+         4,\"POLYGON((0 0, 1 0, 1 1, 0 1))\"\n",
+    )
+    .unwrap();
+    let csv = find_driver("CSV").unwrap();
+
+    let report = validate(
+        input_path.to_str().unwrap(),
+        &csv,
+        "wkt",
+        Some("Polygon"),
+        None,
+        &OptionValues::new(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(report.feature_count, 4);
+    let issues: Vec<_> = report
+        .issues
+        .iter()
+        .map(|issue| {
+            (
+                issue.feature,
+                issue.reason.as_str(),
+                issue.location.as_deref(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        issues,
+        [
+            (
+                1,
+                "exterior ring has a self-intersection",
+                Some("POINT(1 1)")
+            ),
+            (2, "exterior ring is clockwise", Some("POINT(0 0)")),
+            (3, "ring is not closed", Some("POINT(0 0)")),
+        ]
+    );
+
+    convert(
+        input_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        &csv,
+        &csv,
+        "wkt",
+        Some("Polygon"),
+        &ConvertOptions::new().with_make_valid(true),
+    )
+    .await
+    .expect("repair should succeed");
+    let report = validate(
+        output_path.to_str().unwrap(),
+        &csv,
+        "wkt",
+        None,
+        None,
+        &OptionValues::new(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(report.feature_count, 4);
+    assert!(report.issues.is_empty(), "{:?}", report.issues);
+}
+
+#[tokio::test]
+async fn test_e2e_convert_reprojects_geometries() {
+    geoetl_core::init::initialize();
+
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("input_data.csv");
+    let output_path = temp_dir.path().join("utm.geojson");
+    create_spatial_csv(&input_path).unwrap();
+
+    convert(
+        input_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        &find_driver("CSV").unwrap(),
+        &find_driver("GeoJSON").unwrap(),
+        "wkt",
+        Some("Point"),
+        &ConvertOptions::new()
+            .with_filter("name = 'Location A'")
+            .with_target_crs("EPSG:32618"),
+    )
+    .await
+    .expect("reprojection should succeed");
+
+    let output = std::fs::read_to_string(&output_path).unwrap();
+    // New York in UTM zone 18N
+    assert!(output.contains("\"Point\""), "{output}");
+    assert!(
+        output.contains("[583959.") && output.contains(",4507350."),
+        "{output}"
+    );
+
+    // Reprojecting back, with the source CRS given explicitly, restores the coordinates
+    let roundtrip_path = temp_dir.path().join("wgs84.csv");
+    convert(
+        output_path.to_str().unwrap(),
+        roundtrip_path.to_str().unwrap(),
+        &find_driver("GeoJSON").unwrap(),
+        &find_driver("CSV").unwrap(),
+        "geometry",
+        None,
+        &ConvertOptions::new()
+            .with_source_crs("32618")
+            .with_target_crs("EPSG:4326"),
+    )
+    .await
+    .expect("reprojection should succeed");
+    let output = std::fs::read_to_string(&roundtrip_path).unwrap();
+    assert!(output.contains("POINT(-74.006"), "{output}");
+
+    let result = convert(
+        input_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        &find_driver("CSV").unwrap(),
+        &find_driver("GeoJSON").unwrap(),
+        "wkt",
+        Some("Point"),
+        &ConvertOptions::new().with_target_crs("EPSG:9999"),
+    )
+    .await;
+    assert!(
+        matches!(result, Err(GeoEtlError::Config(ConfigError::InvalidOption { ref option, .. })) if option == "t-srs"),
+        "{result:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_e2e_convert_assigns_crs() {
+    geoetl_core::init::initialize();
+
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("utm.csv");
+    let output_path = temp_dir.path().join("wgs84.geojson");
+    std::fs::write(
+        &input_path,
+        "name,wkt\nNew York,\"POINT(583959.37 4507350.99)\"\n",
+    )
+    .unwrap();
+    let csv = find_driver("CSV").unwrap();
+    let geojson = find_driver("GeoJSON").unwrap();
+
+    // The assigned CRS is recorded on the geometry column...
+    let dataset = info(
+        input_path.to_str().unwrap(),
+        &csv,
+        "wkt",
+        Some("Point"),
+        &InfoOptions::new().with_open_options(OptionValues::new().with("CRS", "32618")),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        dataset.geometry_columns[0].crs.as_deref(),
+        Some("EPSG:32618")
+    );
+
+    // ...and used as the source CRS when reprojecting
+    convert(
+        input_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        &csv,
+        &geojson,
+        "wkt",
+        Some("Point"),
+        &ConvertOptions::new()
+            .with_assigned_crs("EPSG:32618")
+            .with_target_crs("EPSG:4326"),
+    )
+    .await
+    .expect("conversion should succeed");
+    let output = std::fs::read_to_string(&output_path).unwrap();
+    assert!(output.contains("[-74.006"), "{output}");
+
+    // GeoJSON is always WGS 84
+    let dataset = info(
+        output_path.to_str().unwrap(),
+        &geojson,
+        "geometry",
+        None,
+        &InfoOptions::new(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        dataset.geometry_columns[0].crs.as_deref(),
+        Some("EPSG:4326")
+    );
+
+    let result = convert(
+        output_path.to_str().unwrap(),
+        temp_dir.path().join("out.csv").to_str().unwrap(),
+        &geojson,
+        &csv,
+        "geometry",
+        None,
+        &ConvertOptions::new().with_assigned_crs("EPSG:3857"),
+    )
+    .await;
+    assert!(
+        matches!(result, Err(GeoEtlError::Config(ConfigError::InvalidOption { ref option, .. })) if option == "a-srs"),
+        "{result:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_e2e_convert_select_reorders_columns() {
+    geoetl_core::init::initialize();
+
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("input_data.csv");
+    let output_path = temp_dir.path().join("reordered.csv");
+    create_spatial_csv(&input_path).unwrap();
+
+    let csv_driver = find_driver("CSV").expect("CSV driver should exist");
+    convert(
+        input_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        &csv_driver,
+        &csv_driver,
+        "wkt",
+        None,
+        &ConvertOptions::new().with_columns(["value", "id"]),
+    )
+    .await
+    .expect("conversion should succeed");
+
+    let output = std::fs::read_to_string(&output_path).unwrap();
+    let mut lines = output.lines();
+    assert_eq!(lines.next(), Some("value,id"));
+    assert_eq!(lines.next(), Some("100,1"));
+}
+
+#[tokio::test]
+async fn test_e2e_convert_invalid_transforms() {
+    use geoetl_core::error::{ConfigError, GeoEtlError};
+
+    geoetl_core::init::initialize();
+
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("input_data.csv");
+    let output_path = temp_dir.path().join("output.csv");
+    create_spatial_csv(&input_path).unwrap();
+    let csv_driver = find_driver("CSV").expect("CSV driver should exist");
+
+    for options in [
+        ConvertOptions::new().with_filter("value >"),
+        ConvertOptions::new().with_columns(["missing"]),
+        ConvertOptions::new().with_rename("missing", "other"),
+    ] {
+        let result = convert(
+            input_path.to_str().unwrap(),
+            output_path.to_str().unwrap(),
+            &csv_driver,
+            &csv_driver,
+            "wkt",
+            None,
+            &options,
+        )
+        .await;
+        assert!(
+            matches!(
+                result,
+                Err(GeoEtlError::Config(ConfigError::InvalidOption { .. }))
+            ),
+            "{options:?} should be rejected, got {result:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_e2e_convert_simplifies_and_reduces_precision() {
+    geoetl_core::init::initialize();
+
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("coast.geojson");
+    std::fs::write(
+        &input_path,
+        r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "coast"},
+             "geometry": {"type": "LineString", "coordinates": [
+                [0.123456789, 0.0], [1.0, 0.000012345], [2.0, -0.000023456], [3.987654321, 0.0],
+                [3.987654321, 2.5]]}}
+        ]}"#,
+    )
+    .unwrap();
+    let geojson = find_driver("GeoJSON").unwrap();
+    let output = |path: &std::path::Path| {
+        let mut output = std::fs::read_to_string(path).unwrap();
+        output.retain(|c| !c.is_whitespace());
+        output
+    };
+
+    let output_path = temp_dir.path().join("simplified.geojson");
+    convert(
+        input_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        &geojson,
+        &geojson,
+        "geometry",
+        None,
+        &ConvertOptions::new()
+            .with_simplify(0.001)
+            .with_simplify_method(Simplification::DouglasPeucker)
+            .with_creation_options(OptionValues::new().with("COORDINATE_PRECISION", "3")),
+    )
+    .await
+    .expect("simplification should succeed");
+    let simplified = output(&output_path);
+    assert!(
+        simplified.contains(r#""coordinates":[[0.123,0.0],[3.988,0.0],[3.988,2.5]]"#),
+        "{simplified}"
+    );
+
+    let output_path = temp_dir.path().join("snapped.geojson");
+    convert(
+        input_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        &geojson,
+        &geojson,
+        "geometry",
+        None,
+        &ConvertOptions::new().with_snap_to_grid(0.5),
+    )
+    .await
+    .expect("snapping should succeed");
+    let snapped = output(&output_path);
+    assert!(
+        snapped.contains(r#""coordinates":[[0.0,0.0],[1.0,0.0],[2.0,0.0],[4.0,0.0],[4.0,2.5]]"#),
+        "{snapped}"
+    );
+
+    let result = convert(
+        input_path.to_str().unwrap(),
+        temp_dir.path().join("invalid.geojson").to_str().unwrap(),
+        &geojson,
+        &geojson,
+        "geometry",
+        None,
+        &ConvertOptions::new().with_simplify(-1.0),
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(GeoEtlError::Config(ConfigError::InvalidOption { ref option, .. })) if option == "simplify"
+    ));
+}
+
+#[tokio::test]
+async fn test_e2e_convert_explodes_and_coerces_geometry_types() {
+    geoetl_core::init::initialize();
+
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("sites.csv");
+    std::fs::write(
+        &input_path,
+        "id,wkt\n\
+         1,\"MULTIPOINT Z((0 0 1), (1 1 2))\"\n\
+         2,\"POINT Z(5 5 3)\"\n\
+         3,\"LINESTRING(0 0, 1 1)\"\n",
+    )
+    .unwrap();
+    let csv = find_driver("CSV").unwrap();
+    let convert_csv = |output: &str, options: ConvertOptions| {
+        let input_path = input_path.clone();
+        let output_path = temp_dir.path().join(output);
+        let csv = csv.clone();
+        async move {
+            convert(
+                input_path.to_str().unwrap(),
+                output_path.to_str().unwrap(),
+                &csv,
+                &csv,
+                "wkt",
+                None,
+                &options.with_filter("id < 3"),
+            )
+            .await
+            .map(|()| std::fs::read_to_string(&output_path).unwrap())
+        }
+    };
+
+    let exploded = convert_csv("exploded.csv", ConvertOptions::new().with_explode(true))
+        .await
+        .expect("explode should succeed");
+    assert_eq!(
+        exploded.lines().collect::<Vec<_>>(),
+        [
+            "id,wkt",
+            "1,POINT Z(0 0 1)",
+            "1,POINT Z(1 1 2)",
+            "2,POINT Z(5 5 3)"
+        ]
+    );
+
+    let promoted = convert_csv(
+        "promoted.csv",
+        ConvertOptions::new()
+            .with_geometry_type("PROMOTE_TO_MULTI")
+            .with_dimension(Dimension::XY),
+    )
+    .await
+    .expect("promotion should succeed");
+    assert!(
+        promoted.contains("2,MULTIPOINT((5 5))")
+            && promoted.contains(r#"1,"MULTIPOINT((0 0),(1 1))""#),
+        "{promoted}"
+    );
+
+    let cast = convert_csv(
+        "cast.csv",
+        ConvertOptions::new()
+            .with_explode(true)
+            .with_geometry_type("Point ZM"),
+    )
+    .await
+    .expect("cast should succeed");
+    assert!(cast.contains("2,POINT ZM(5 5 3 0)"), "{cast}");
+
+    // Features that cannot be cast are reported, and what was written is discarded
+    let mixed_path = temp_dir.path().join("mixed.csv");
+    let result = convert(
+        input_path.to_str().unwrap(),
+        mixed_path.to_str().unwrap(),
+        &csv,
+        &csv,
+        "wkt",
+        None,
+        &ConvertOptions::new().with_geometry_type("MultiPoint"),
+    )
+    .await;
+    let error = result.unwrap_err().to_string();
+    assert!(
+        error.contains("cannot cast geometries to MultiPoint: 1 LineString in 3 features"),
+        "{error}"
+    );
+    assert!(error.contains("feature 2 (wkt): LineString"), "{error}");
+    assert!(!mixed_path.exists());
+
+    // An existing file appended to is left as it was
+    std::fs::write(&mixed_path, "id,wkt\n9,POINT(9 9)").unwrap();
+    let result = convert(
+        input_path.to_str().unwrap(),
+        mixed_path.to_str().unwrap(),
+        &csv,
+        &csv,
+        "wkt",
+        None,
+        &ConvertOptions::new()
+            .with_geometry_type("MultiPoint")
+            .with_write_mode(geoetl_core::types::WriteMode::Append),
+    )
+    .await;
+    assert!(result.is_err());
+    assert_eq!(
+        std::fs::read_to_string(&mixed_path).unwrap(),
+        "id,wkt\n9,POINT(9 9)"
+    );
+
+    let result = convert_csv(
+        "unknown.csv",
+        ConvertOptions::new().with_geometry_type("Circle"),
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(GeoEtlError::Config(ConfigError::InvalidOption { ref option, .. })) if option == "nlt"
+    ));
+}
+
+#[tokio::test]
+async fn test_e2e_info_statistics() {
+    geoetl_core::init::initialize();
+
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("sites.csv");
+    std::fs::write(
+        &input_path,
+        "id,name,wkt,value\n\
+         1,a,\"POINT(0 0)\",10\n\
+         2,b,\"POINT(2 3)\",\n\
+         3,a,\"LINESTRING(-1 1, 4 5)\",7\n\
+         4,c,\"MULTIPOINT Z((0 0 1))\",3\n",
+    )
+    .unwrap();
+    let csv = find_driver("CSV").unwrap();
+
+    let dataset = info(
+        input_path.to_str().unwrap(),
+        &csv,
+        "wkt",
+        None,
+        &InfoOptions::new().with_stats(true),
+    )
+    .await
+    .unwrap();
+    assert_eq!(dataset.feature_count, Some(4));
+    assert_eq!(
+        dataset.extent,
+        Some(Extent {
+            min_x: -1.0,
+            min_y: 0.0,
+            max_x: 4.0,
+            max_y: 5.0,
+        })
+    );
+    assert_eq!(
+        dataset.geometry_types,
+        [
+            ("Point".to_string(), 2),
+            ("LineString".to_string(), 1),
+            ("MultiPoint Z".to_string(), 1)
+        ]
+    );
+
+    let stats = |name: &str| {
+        dataset
+            .fields
+            .iter()
+            .find(|field| field.name == name)
+            .and_then(|field| field.stats.clone())
+            .unwrap()
+    };
+    let value = stats("value");
+    assert_eq!(value.null_count, 1);
+    assert_eq!(value.min.as_deref(), Some("3"));
+    assert_eq!(value.max.as_deref(), Some("10"));
+    assert_eq!(value.distinct_count, Some(3));
+    assert!(value.top_values.is_empty());
+    let name = stats("name");
+    assert_eq!(name.null_count, 0);
+    assert_eq!(
+        name.top_values,
+        [
+            ("a".to_string(), 2),
+            ("b".to_string(), 1),
+            ("c".to_string(), 1)
+        ]
+    );
+
+    // Statistics cover the features matching the spatial filter
+    let bbox = spatial_filter_geometry("1,1,3,4").await.unwrap();
+    let dataset = info(
+        input_path.to_str().unwrap(),
+        &csv,
+        "wkt",
+        None,
+        &InfoOptions::new()
+            .with_spatial_filter(bbox.as_str())
+            .with_stats(true),
+    )
+    .await
+    .unwrap();
+    assert_eq!(dataset.feature_count, Some(2));
+    assert_eq!(
+        dataset.extent.map(|extent| (extent.min_x, extent.max_x)),
+        Some((-1.0, 4.0))
+    );
+}
+
+#[tokio::test]
+async fn test_e2e_convert_write_modes() {
+    use geoetl_core::error::IoError;
+    use geoetl_core::types::WriteMode;
+
+    geoetl_core::init::initialize();
+
+    let temp_dir = TempDir::new().unwrap();
+    let csv_path = temp_dir.path().join("input_data.csv");
+    let geojson_path = temp_dir.path().join("cities.geojson");
+    create_spatial_csv(&csv_path).unwrap();
+    create_sample_geojson(&geojson_path).unwrap();
+    let csv_driver = find_driver("CSV").expect("CSV driver should exist");
+    let geojson_driver = find_driver("GeoJSON").expect("GeoJSON driver should exist");
+
+    let run = |input: std::path::PathBuf, output: std::path::PathBuf, driver, mode| async move {
+        convert(
+            input.to_str().unwrap(),
+            output.to_str().unwrap(),
+            driver,
+            driver,
+            if input.extension().unwrap() == "csv" {
+                "wkt"
+            } else {
+                "geometry"
+            },
+            None,
+            &ConvertOptions::new().with_write_mode(mode),
+        )
+        .await
+    };
+
+    // Appending to CSV adds the rows once more without a second header
+    let csv_output = temp_dir.path().join("all.csv");
+    for mode in [WriteMode::Append, WriteMode::Append] {
+        run(csv_path.clone(), csv_output.clone(), &csv_driver, mode)
+            .await
+            .unwrap();
+    }
+    let output = std::fs::read_to_string(&csv_output).unwrap();
+    assert_eq!(output.lines().count(), 11);
+    assert_eq!(output.matches("id,name,wkt").count(), 1);
+
+    // Overwriting replaces the appended rows
+    run(
+        csv_path.clone(),
+        csv_output.clone(),
+        &csv_driver,
+        WriteMode::Overwrite,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&csv_output)
+            .unwrap()
+            .lines()
+            .count(),
+        6
+    );
+
+    // Failing leaves an existing output untouched
+    let result = run(
+        csv_path.clone(),
+        csv_output.clone(),
+        &csv_driver,
+        WriteMode::Fail,
+    )
+    .await;
+    assert!(
+        matches!(result, Err(GeoEtlError::Io(IoError::AlreadyExists { .. }))),
+        "existing output should be rejected, got {result:?}"
+    );
+    assert_eq!(
+        std::fs::read_to_string(&csv_output)
+            .unwrap()
+            .lines()
+            .count(),
+        6
+    );
+
+    // Appending to GeoJSON merges the features into one collection
+    let geojson_output = temp_dir.path().join("all.geojson");
+    for mode in [WriteMode::Fail, WriteMode::Append] {
+        run(
+            geojson_path.clone(),
+            geojson_output.clone(),
+            &geojson_driver,
+            mode,
+        )
+        .await
+        .unwrap();
+    }
+    let output = std::fs::read_to_string(&geojson_output).unwrap();
+    assert_eq!(output.matches("FeatureCollection").count(), 1);
+    assert_eq!(output.matches("San Francisco").count(), 2);
+}
@@ -0,0 +1,367 @@
+//! Integration tests for the driver registry
+//!
+//! These tests register custom format factories with the global registry and
+//! verify that driver lookups reflect what is actually registered.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use datafusion::datasource::TableProvider;
+use datafusion::execution::context::SessionState;
+use datafusion::physical_plan::ExecutionPlan;
+use datafusion_csv::factory::CsvFormatFactory;
+use datafusion_geojson::factory::GeoJsonFormatFactory;
+use geoetl_core::drivers::{
+    Driver, SupportStatus, detect_driver, detect_driver_from_extension, find_driver,
+    get_available_drivers,
+};
+use geoetl_core::operations::{convert, info, validate};
+use geoetl_core::types::{ConvertOptions, InfoOptions};
+use geoetl_core_common::{
+    DataReader, DataWriter, FormatFactory, OptionValues, WriteMode, default_layer_name,
+    driver_registry,
+};
+
+/// Read-only format that reuses the CSV reader but advertises write support it lacks
+struct ReadOnlyFormatFactory;
+
+impl FormatFactory for ReadOnlyFormatFactory {
+    fn driver(&self) -> Driver {
+        Driver::new(
+            "ReadOnlyTest",
+            "Read-only test format",
+            SupportStatus::Supported,
+            SupportStatus::Supported,
+            SupportStatus::Supported,
+        )
+    }
+
+    fn create_reader(&self) -> Option<Arc<dyn DataReader>> {
+        CsvFormatFactory.create_reader()
+    }
+
+    fn create_writer(&self) -> Option<Arc<dyn DataWriter>> {
+        None
+    }
+}
+
+/// Format recognised by its `.probed` extension or a `PROBED` header
+struct ProbedFormatFactory;
+
+impl FormatFactory for ProbedFormatFactory {
+    fn driver(&self) -> Driver {
+        Driver::new(
+            "ProbedTest",
+            "Probed test format",
+            SupportStatus::Supported,
+            SupportStatus::Supported,
+            SupportStatus::NotSupported,
+        )
+    }
+
+    fn create_reader(&self) -> Option<Arc<dyn DataReader>> {
+        CsvFormatFactory.create_reader()
+    }
+
+    fn create_writer(&self) -> Option<Arc<dyn DataWriter>> {
+        None
+    }
+
+    fn extensions(&self) -> Vec<&'static str> {
+        vec!["probed"]
+    }
+
+    fn probe(&self, header: &[u8]) -> bool {
+        header.starts_with(b"PROBED")
+    }
+}
+
+/// Multi-layer format storing each layer as a `GeoJSON` file in a directory
+struct LayeredFormatFactory;
+
+impl LayeredFormatFactory {
+    fn layer_path(path: &str, layer: &str) -> String {
+        Path::new(path)
+            .join(format!("{layer}.geojson"))
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+impl FormatFactory for LayeredFormatFactory {
+    fn driver(&self) -> Driver {
+        Driver::new(
+            "LayeredTest",
+            "Multi-layer test format",
+            SupportStatus::Supported,
+            SupportStatus::Supported,
+            SupportStatus::Supported,
+        )
+    }
+
+    fn create_reader(&self) -> Option<Arc<dyn DataReader>> {
+        Some(Arc::new(LayeredFormatFactory))
+    }
+
+    fn create_writer(&self) -> Option<Arc<dyn DataWriter>> {
+        Some(Arc::new(LayeredFormatFactory))
+    }
+}
+
+#[async_trait]
+impl DataReader for LayeredFormatFactory {
+    async fn create_table_provider(
+        &self,
+        state: &SessionState,
+        path: &str,
+        options: &OptionValues,
+    ) -> Result<Arc<dyn TableProvider>> {
+        let layers = self.layers(state, path, options).await?;
+        let first = layers.first().ok_or_else(|| anyhow::anyhow!("no layers"))?;
+        self.create_layer_provider(state, path, first, options)
+            .await
+    }
+
+    async fn layers(
+        &self,
+        _state: &SessionState,
+        path: &str,
+        _options: &OptionValues,
+    ) -> Result<Vec<String>> {
+        let mut layers = std::fs::read_dir(path)?
+            .map(|entry| Ok(default_layer_name(&entry?.path().to_string_lossy())))
+            .collect::<Result<Vec<_>>>()?;
+        layers.sort();
+        Ok(layers)
+    }
+
+    async fn create_layer_provider(
+        &self,
+        state: &SessionState,
+        path: &str,
+        layer: &str,
+        options: &OptionValues,
+    ) -> Result<Arc<dyn TableProvider>> {
+        GeoJsonFormatFactory
+            .create_reader()
+            .unwrap()
+            .create_table_provider(state, &Self::layer_path(path, layer), options)
+            .await
+    }
+}
+
+#[async_trait]
+impl DataWriter for LayeredFormatFactory {
+    async fn create_writer_plan(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        path: &str,
+        mode: WriteMode,
+        options: &OptionValues,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.create_layer_writer_plan(input, path, &default_layer_name(path), mode, options)
+            .await
+    }
+
+    fn supports_layers(&self) -> bool {
+        true
+    }
+
+    async fn create_layer_writer_plan(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        path: &str,
+        layer: &str,
+        mode: WriteMode,
+        options: &OptionValues,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        std::fs::create_dir_all(path)?;
+        GeoJsonFormatFactory
+            .create_writer()
+            .unwrap()
+            .create_writer_plan(input, &Self::layer_path(path, layer), mode, options)
+            .await
+    }
+}
+
+fn write_layer(dir: &Path, layer: &str, name: &str) {
+    std::fs::create_dir_all(dir).unwrap();
+    let geojson = format!(
+        r#"{{"type": "FeatureCollection", "features": [{{"type": "Feature",
+            "geometry": {{"type": "Point", "coordinates": [1.0, 2.0]}},
+            "properties": {{"name": "{name}"}}}}]}}"#
+    );
+    std::fs::write(dir.join(format!("{layer}.geojson")), geojson).unwrap();
+}
+
+#[tokio::test]
+async fn test_multi_layer_dataset() {
+    driver_registry().register(Arc::new(LayeredFormatFactory));
+    let layered = find_driver("LayeredTest").unwrap();
+    let geojson = find_driver("GeoJSON").unwrap();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let input = temp_dir.path().join("input");
+    write_layer(&input, "roads", "Main Street");
+    write_layer(&input, "cities", "Springfield");
+    let input = input.to_str().unwrap();
+
+    // The first layer is described by default; layers are selected case-insensitively
+    let dataset = info(input, &layered, "geometry", None, &InfoOptions::new())
+        .await
+        .unwrap();
+    assert_eq!(dataset.layers, ["cities", "roads"]);
+    assert_eq!(dataset.layer, "cities");
+    let dataset = info(
+        input,
+        &layered,
+        "geometry",
+        None,
+        &InfoOptions::new().with_layer("ROADS").with_stats(true),
+    )
+    .await
+    .unwrap();
+    assert_eq!(dataset.layer, "roads");
+    assert_eq!(dataset.feature_count, Some(1));
+
+    let err = info(
+        input,
+        &layered,
+        "geometry",
+        None,
+        &InfoOptions::new().with_layer("rivers"),
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("layer 'rivers' not found; available layers: cities, roads"),
+        "{err}"
+    );
+
+    // Layers are validated one at a time
+    let report = validate(
+        input,
+        &layered,
+        "geometry",
+        None,
+        Some("roads"),
+        &OptionValues::new(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(report.layer, "roads");
+    assert_eq!(report.feature_count, 1);
+    let err = validate(
+        input,
+        &layered,
+        "geometry",
+        None,
+        Some("rivers"),
+        &OptionValues::new(),
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("layer 'rivers' not found"),
+        "{err}"
+    );
+
+    // Every layer is written to one multi-layer dataset
+    let output = temp_dir.path().join("output");
+    let output = output.to_str().unwrap();
+    convert(
+        input,
+        output,
+        &layered,
+        &layered,
+        "geometry",
+        None,
+        &ConvertOptions::new(),
+    )
+    .await
+    .unwrap();
+    let dataset = info(output, &layered, "geometry", None, &InfoOptions::new())
+        .await
+        .unwrap();
+    assert_eq!(dataset.layers, ["cities", "roads"]);
+
+    // Single-layer formats get a directory with one file per layer
+    let directory = temp_dir.path().join("geojson");
+    convert(
+        input,
+        directory.to_str().unwrap(),
+        &layered,
+        &geojson,
+        "geometry",
+        None,
+        &ConvertOptions::new(),
+    )
+    .await
+    .unwrap();
+    let written = std::fs::read_to_string(directory.join("roads.geojson")).unwrap();
+    assert!(written.contains("Main Street"));
+    assert!(directory.join("cities.geojson").is_file());
+
+    // A single selected layer is written to the output file itself
+    let file = temp_dir.path().join("cities.geojson");
+    convert(
+        input,
+        file.to_str().unwrap(),
+        &layered,
+        &geojson,
+        "geometry",
+        None,
+        &ConvertOptions::new().with_layer("cities"),
+    )
+    .await
+    .unwrap();
+    let written = std::fs::read_to_string(&file).unwrap();
+    assert!(written.contains("Springfield"));
+    assert!(!written.contains("Main Street"));
+}
+
+#[test]
+fn test_registered_factory_is_detected() {
+    driver_registry().register(Arc::new(ProbedFormatFactory));
+
+    let driver = detect_driver_from_extension("data.PROBED").expect("extension should match");
+    assert_eq!(driver.short_name, "ProbedTest");
+
+    // Content wins over a misleading extension
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("data.geojson");
+    std::fs::write(&path, "PROBED,1,2\n").unwrap();
+    let driver = detect_driver(path.to_str().unwrap()).expect("content should match");
+    assert_eq!(driver.short_name, "ProbedTest");
+}
+
+#[test]
+fn test_registered_factory_is_a_known_driver() {
+    driver_registry().register(Arc::new(ReadOnlyFormatFactory));
+
+    let driver = find_driver("readonlytest").expect("registered driver should be found");
+    assert_eq!(driver.short_name, "ReadOnlyTest");
+    assert!(driver.capabilities.read.is_supported());
+    assert!(driver.capabilities.info.is_supported());
+    // Capabilities come from the factory's reader/writer, not its declared metadata
+    assert_eq!(driver.capabilities.write, SupportStatus::NotSupported);
+
+    let available = get_available_drivers();
+    assert!(available.iter().any(|d| d.short_name == "ReadOnlyTest"));
+    assert!(available.iter().any(|d| d.short_name == "GeoJSON"));
+}
+
+#[test]
+fn test_builtin_drivers_come_from_registry() {
+    let csv = find_driver("CSV").expect("CSV should be registered");
+    assert!(csv.capabilities.read.is_supported());
+    assert!(csv.capabilities.write.is_supported());
+
+    // Planned formats remain informational catalog entries
+    let parquet = find_driver("Parquet").expect("Parquet should be in the catalog");
+    assert_eq!(parquet.capabilities.read, SupportStatus::Planned);
+    assert!(driver_registry().find_factory("Parquet").is_none());
+}
@@ -0,0 +1,274 @@
+//! End-to-end integration tests for the sql operation
+//!
+//! These tests register datasets of different formats as named tables and
+//! verify query results and their output through the driver system.
+
+use geoetl_core::drivers::find_driver;
+use geoetl_core::error::{ConfigError, GeoEtlError, IoError};
+use geoetl_core::operations::{sql, sql_to_file};
+use geoetl_core::types::{OptionValues, SqlTable, WriteMode};
+use std::fs;
+use tempfile::TempDir;
+
+/// Helper to create the tables used by the tests: `places` (CSV) and `cities` (`GeoJSON`)
+fn create_tables(dir: &std::path::Path) -> Vec<SqlTable> {
+    let csv_path = dir.join("places.csv");
+    fs::write(
+        &csv_path,
+        "id,city,wkt\n1,Paris,\"POINT(2.35 48.85)\"\n2,Rome,\"POINT(12.5 41.9)\"\n3,Oslo,\"POINT(10.75 59.9)\"\n",
+    )
+    .unwrap();
+
+    let geojson_path = dir.join("cities.geojson");
+    fs::write(
+        &geojson_path,
+        r#"{"type":"FeatureCollection","features":[
+{"type":"Feature","geometry":{"type":"Point","coordinates":[2.35,48.85]},"properties":{"city":"Paris","population":2100000}},
+{"type":"Feature","geometry":{"type":"Point","coordinates":[12.5,41.9]},"properties":{"city":"Rome","population":2800000}}
+]}"#,
+    )
+    .unwrap();
+
+    vec![
+        SqlTable::new(
+            "places",
+            csv_path.to_str().unwrap(),
+            find_driver("CSV").unwrap(),
+        )
+        .with_geometry_column("wkt")
+        .with_geometry_type(Some("Point".to_string())),
+        SqlTable::new(
+            "cities",
+            geojson_path.to_str().unwrap(),
+            find_driver("GeoJSON").unwrap(),
+        ),
+    ]
+}
+
+#[tokio::test]
+async fn test_sql_joins_tables() -> Result<(), GeoEtlError> {
+    let temp_dir = TempDir::new().unwrap();
+    let tables = create_tables(temp_dir.path());
+
+    let result = sql(
+        &tables,
+        "SELECT p.id, c.city, c.population FROM places p JOIN cities c ON p.city = c.city \
+         ORDER BY p.id",
+    )
+    .await?;
+
+    assert_eq!(result.columns, vec!["id", "city", "population"]);
+    assert_eq!(
+        result.rows,
+        vec![vec!["1", "Paris", "2100000"], vec!["2", "Rome", "2800000"],]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sql_formats_geometry_as_wkt() -> Result<(), GeoEtlError> {
+    let temp_dir = TempDir::new().unwrap();
+    let tables = create_tables(temp_dir.path());
+
+    let result = sql(&tables, "SELECT wkt FROM places WHERE city = 'Oslo'").await?;
+    assert_eq!(result.rows.len(), 1);
+    assert!(result.rows[0][0].starts_with("POINT"), "{:?}", result.rows);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sql_to_file() -> Result<(), GeoEtlError> {
+    let temp_dir = TempDir::new().unwrap();
+    let tables = create_tables(temp_dir.path());
+    let output_path = temp_dir.path().join("large.csv");
+
+    let rows = sql_to_file(
+        &tables,
+        "SELECT city, population, geometry FROM cities WHERE population > 2500000",
+        output_path.to_str().unwrap(),
+        &find_driver("CSV").unwrap(),
+        WriteMode::Overwrite,
+        &OptionValues::new(),
+    )
+    .await?;
+
+    assert_eq!(rows, 1);
+    let output = fs::read_to_string(&output_path).unwrap();
+    assert!(output.contains("Rome"));
+    assert!(output.contains("POINT"));
+    assert!(!output.contains("Paris"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sql_to_file_write_modes() -> Result<(), GeoEtlError> {
+    let temp_dir = TempDir::new().unwrap();
+    let tables = create_tables(temp_dir.path());
+    let output_path = temp_dir.path().join("cities.csv");
+    let (driver, options) = (find_driver("CSV").unwrap(), OptionValues::new());
+    let write = |write_mode| {
+        sql_to_file(
+            &tables,
+            "SELECT city FROM places WHERE id < 3",
+            output_path.to_str().unwrap(),
+            &driver,
+            write_mode,
+            &options,
+        )
+    };
+
+    assert_eq!(write(WriteMode::Fail).await?, 2);
+    assert_eq!(write(WriteMode::Append).await?, 2);
+    assert_eq!(
+        fs::read_to_string(&output_path).unwrap(),
+        "city\nParis\nRome\nParis\nRome\n"
+    );
+
+    let result = write(WriteMode::Fail).await;
+    assert!(
+        matches!(result, Err(GeoEtlError::Io(IoError::AlreadyExists { .. }))),
+        "existing output should be rejected, got {result:?}"
+    );
+    assert_eq!(write(WriteMode::Overwrite).await?, 2);
+    assert_eq!(
+        fs::read_to_string(&output_path).unwrap(),
+        "city\nParis\nRome\n"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sql_rejects_duplicate_table_names() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut tables = create_tables(temp_dir.path());
+    tables[1].name = "PLACES".to_string();
+
+    let result = sql(&tables, "SELECT 1").await;
+    assert!(matches!(
+        result,
+        Err(GeoEtlError::Config(ConfigError::ConflictingOptions { .. }))
+    ));
+}
+
+#[tokio::test]
+async fn test_sql_invalid_query() {
+    let temp_dir = TempDir::new().unwrap();
+    let tables = create_tables(temp_dir.path());
+
+    let result = sql(&tables, "SELECT * FROM missing_table").await;
+    assert!(matches!(result, Err(GeoEtlError::DataFusion(_))));
+}
+
+#[tokio::test]
+async fn test_sql_spatial_functions() -> Result<(), GeoEtlError> {
+    let temp_dir = TempDir::new().unwrap();
+    let tables = create_tables(temp_dir.path());
+
+    let result = sql(
+        &tables,
+        "SELECT p.city, ST_X(p.wkt) AS x, ST_AsText(ST_Centroid(c.geometry)) AS centroid \
+         FROM places p JOIN cities c ON ST_Intersects(p.wkt, ST_Buffer(c.geometry, 0.1)) \
+         WHERE ST_Within(p.wkt, ST_GeomFromText('POLYGON((0 40, 20 40, 20 50, 0 50, 0 40))')) \
+         ORDER BY p.id",
+    )
+    .await?;
+
+    assert_eq!(result.columns, vec!["city", "x", "centroid"]);
+    assert_eq!(
+        result.rows,
+        vec![
+            vec!["Paris", "2.35", "POINT(2.35 48.85)"],
+            vec!["Rome", "12.5", "POINT(12.5 41.9)"],
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sql_spatial_join_uses_index() -> Result<(), GeoEtlError> {
+    let temp_dir = TempDir::new().unwrap();
+    let tables = create_tables(temp_dir.path());
+    let query = "SELECT p.id, c.city FROM places p \
+                 JOIN cities c ON ST_DWithin(p.wkt, c.geometry, 0.5) ORDER BY p.id";
+
+    let result = sql(&tables, query).await?;
+    assert_eq!(result.rows, vec![vec!["1", "Paris"], vec!["2", "Rome"]]);
+
+    let plan = sql(&tables, &format!("EXPLAIN {query}")).await?;
+    let plan: Vec<String> = plan.rows.into_iter().flatten().collect();
+    assert!(
+        plan.iter().any(|text| text.contains("SpatialJoinExec")),
+        "{plan:?}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sql_to_file_writes_computed_geometries() -> Result<(), GeoEtlError> {
+    let temp_dir = TempDir::new().unwrap();
+    let tables = create_tables(temp_dir.path());
+    let output_path = temp_dir.path().join("projected.geojson");
+
+    let rows = sql_to_file(
+        &tables,
+        "SELECT city, ST_Transform(geometry, 'EPSG:3857') AS geometry FROM cities",
+        output_path.to_str().unwrap(),
+        &find_driver("GeoJSON").unwrap(),
+        WriteMode::Overwrite,
+        &OptionValues::new(),
+    )
+    .await?;
+
+    assert_eq!(rows, 2);
+    let output = fs::read_to_string(&output_path).unwrap();
+    assert!(output.contains("\"Point\""), "{output}");
+    // Paris lies about 261 km east of the prime meridian in Web Mercator
+    assert!(output.contains("[261600."), "{output}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sql_dissolve_by_attribute() -> Result<(), GeoEtlError> {
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("parcels.geojson");
+    fs::write(
+        &input_path,
+        r#"{"type":"FeatureCollection","features":[
+{"type":"Feature","geometry":{"type":"Polygon","coordinates":[[[0,0],[2,0],[2,2],[0,2],[0,0]]]},"properties":{"zone":"residential"}},
+{"type":"Feature","geometry":{"type":"Polygon","coordinates":[[[2,0],[4,0],[4,2],[2,2],[2,0]]]},"properties":{"zone":"residential"}},
+{"type":"Feature","geometry":{"type":"Polygon","coordinates":[[[10,10],[11,10],[11,11],[10,11],[10,10]]]},"properties":{"zone":"industrial"}}
+]}"#,
+    )
+    .unwrap();
+    let tables = vec![SqlTable::new(
+        "parcels",
+        input_path.to_str().unwrap(),
+        find_driver("GeoJSON").unwrap(),
+    )];
+
+    let output_path = temp_dir.path().join("zones.geojson");
+    let rows = sql_to_file(
+        &tables,
+        "SELECT zone, ST_Union_Agg(geometry) AS geometry FROM parcels GROUP BY zone",
+        output_path.to_str().unwrap(),
+        &find_driver("GeoJSON").unwrap(),
+        WriteMode::Overwrite,
+        &OptionValues::new(),
+    )
+    .await?;
+    assert_eq!(rows, 2);
+    let output = fs::read_to_string(&output_path).unwrap();
+    assert!(output.contains("\"Polygon\""), "{output}");
+    assert!(!output.contains("\"geometry\":null"), "{output}");
+
+    let result = sql(
+        &tables,
+        "SELECT zone, ST_Area(ST_Union_Agg(geometry)) AS area, \
+         ST_AsText(ST_Extent(geometry)) AS extent FROM parcels GROUP BY zone ORDER BY zone",
+    )
+    .await?;
+    assert_eq!(result.rows[0][..2], ["industrial", "1.0"]);
+    assert_eq!(result.rows[1][..2], ["residential", "8.0"]);
+    assert!(result.rows[1][2].starts_with("POLYGON"));
+    Ok(())
+}
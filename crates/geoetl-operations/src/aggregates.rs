@@ -0,0 +1,326 @@
+//! Spatial aggregates: `ST_Union_Agg`, `ST_Extent`, `ST_Collect` and `ST_ConvexHull_Agg`.
+//!
+//! Each group keeps its geometries in memory and reduces them when a partial state is
+//! emitted or the final value is evaluated. Partial states are exchanged between partitions
+//! as WKB, so the aggregates work with `GROUP BY` and with repartitioned plans.
+
+use std::any::Any;
+use std::mem::size_of;
+use std::sync::Arc;
+
+use arrow::array::ArrayRef;
+use arrow::datatypes::{DataType, Field, FieldRef};
+use datafusion::common::{Result, ScalarValue, internal_err};
+use datafusion::logical_expr::function::{AccumulatorArgs, StateFieldsArgs};
+use datafusion::logical_expr::utils::format_state_name;
+use datafusion::logical_expr::{Accumulator, AggregateUDFImpl, Signature, Volatility};
+use geo::{
+    Area, Contains, ConvexHull, Coord, CoordsIter, Geometry, GeometryCollection, LineString,
+    MultiLineString, MultiPoint, MultiPolygon, Point, Polygon, unary_union,
+};
+use geoarrow_schema::Metadata;
+
+use crate::constructors::envelope;
+use crate::geometry::{
+    field_metadata, geometry_array, geometry_return_field, read_geometries, wkb_bytes,
+};
+
+/// Number of buffered geometries after which reducible aggregates fold them together
+const COMPACT_THRESHOLD: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Aggregation {
+    Union,
+    Extent,
+    Collect,
+    ConvexHull,
+}
+
+/// An aggregate combining the geometries of each group into one geometry
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct SpatialAggregateUdf {
+    aggregation: Aggregation,
+    signature: Signature,
+}
+
+impl SpatialAggregateUdf {
+    fn new(aggregation: Aggregation) -> Self {
+        Self {
+            aggregation,
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+
+    /// `ST_Union_Agg(geom)`: dissolve polygons into their union; points and lines are
+    /// collected alongside, without noding
+    #[must_use]
+    pub fn union() -> Self {
+        Self::new(Aggregation::Union)
+    }
+
+    /// `ST_Extent(geom)`: the bounding box of all geometries as a polygon
+    #[must_use]
+    pub fn extent() -> Self {
+        Self::new(Aggregation::Extent)
+    }
+
+    /// `ST_Collect(geom)`: gather geometries into a multi-geometry or geometry collection
+    #[must_use]
+    pub fn collect() -> Self {
+        Self::new(Aggregation::Collect)
+    }
+
+    /// `ST_ConvexHull_Agg(geom)`: the convex hull of all geometries, as a point or line string
+    /// when it is degenerate
+    #[must_use]
+    pub fn convex_hull() -> Self {
+        Self::new(Aggregation::ConvexHull)
+    }
+}
+
+impl AggregateUDFImpl for SpatialAggregateUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        match self.aggregation {
+            Aggregation::Union => "st_union_agg",
+            Aggregation::Extent => "st_extent",
+            Aggregation::Collect => "st_collect",
+            Aggregation::ConvexHull => "st_convexhull_agg",
+        }
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        internal_err!("return_field should be used instead")
+    }
+
+    fn return_field(&self, arg_fields: &[FieldRef]) -> Result<FieldRef> {
+        Ok(geometry_return_field(
+            self.name(),
+            field_metadata(&arg_fields[0]),
+        ))
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(SpatialAccumulator {
+            aggregation: self.aggregation,
+            input_field: acc_args.exprs[0].return_field(acc_args.schema)?,
+            metadata: field_metadata(&acc_args.return_field),
+            parts: Vec::new(),
+        }))
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<FieldRef>> {
+        Ok(vec![Arc::new(Field::new(
+            format_state_name(args.name, "geometry"),
+            DataType::Binary,
+            true,
+        ))])
+    }
+}
+
+#[derive(Debug)]
+struct SpatialAccumulator {
+    aggregation: Aggregation,
+    input_field: FieldRef,
+    metadata: Arc<Metadata>,
+    parts: Vec<Geometry>,
+}
+
+impl SpatialAccumulator {
+    fn push(&mut self, geometry: Geometry) {
+        self.parts.push(geometry);
+        if self.parts.len() >= COMPACT_THRESHOLD && self.aggregation != Aggregation::Collect {
+            self.compact();
+        }
+    }
+
+    /// Fold the buffered geometries into a single partial result
+    fn compact(&mut self) {
+        if let Some(reduced) = reduce(self.aggregation, &self.parts) {
+            self.parts = vec![reduced];
+        }
+    }
+}
+
+impl Accumulator for SpatialAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        for geometry in read_geometries(values[0].as_ref(), &self.input_field)?
+            .into_iter()
+            .flatten()
+        {
+            self.push(geometry);
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let field = Field::new("state", DataType::Binary, true);
+        for partial in read_geometries(states[0].as_ref(), &field)?
+            .into_iter()
+            .flatten()
+        {
+            if self.aggregation == Aggregation::Collect {
+                // Partial collections are unpacked so that collecting is associative
+                self.parts.extend(members(partial));
+            } else {
+                self.push(partial);
+            }
+        }
+        Ok(())
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        let state = reduce(self.aggregation, &self.parts)
+            .map(|geometry| wkb_bytes(&geometry))
+            .transpose()?;
+        Ok(vec![ScalarValue::Binary(state)])
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let result = reduce(self.aggregation, &self.parts);
+        let array = geometry_array(&[result], Arc::clone(&self.metadata))?;
+        ScalarValue::try_from_array(&array, 0)
+    }
+
+    fn size(&self) -> usize {
+        let coords: usize = self.parts.iter().map(CoordsIter::coords_count).sum();
+        size_of::<Self>()
+            + self.parts.capacity() * size_of::<Geometry>()
+            + coords * size_of::<Coord>()
+    }
+}
+
+fn reduce(aggregation: Aggregation, parts: &[Geometry]) -> Option<Geometry> {
+    if parts.is_empty() {
+        return None;
+    }
+
+    let collection = || GeometryCollection::from(parts.to_vec());
+    match aggregation {
+        Aggregation::Union => Some(union(parts)),
+        Aggregation::Extent => envelope(&Geometry::GeometryCollection(collection())),
+        Aggregation::Collect => Some(collect(parts.to_vec())),
+        Aggregation::ConvexHull => Some(convex_hull(&collection())),
+    }
+}
+
+/// The convex hull of a collection: a polygon, or a point when all its coordinates coincide
+/// and a line string between the outermost ones when they are collinear
+fn convex_hull(collection: &GeometryCollection) -> Geometry {
+    let hull = collection.convex_hull();
+    if hull.unsigned_area() > 0.0 {
+        return Geometry::Polygon(hull);
+    }
+
+    let mut coords = collection.coords_iter();
+    let Some(first) = coords.next() else {
+        return Geometry::Polygon(hull);
+    };
+    let key = |c: &Coord| (c.x, c.y);
+    let (min, max) = coords.fold((first, first), |(min, max), c| {
+        (
+            if key(&c) < key(&min) { c } else { min },
+            if key(&c) > key(&max) { c } else { max },
+        )
+    });
+    if min == max {
+        Geometry::Point(Point(min))
+    } else {
+        Geometry::LineString(LineString::new(vec![min, max]))
+    }
+}
+
+/// The members of a multi-geometry or collection, or the geometry itself
+fn members(geometry: Geometry) -> Vec<Geometry> {
+    match geometry {
+        Geometry::MultiPoint(points) => points.into_iter().map(Geometry::Point).collect(),
+        Geometry::MultiLineString(lines) => lines.into_iter().map(Geometry::LineString).collect(),
+        Geometry::MultiPolygon(polygons) => polygons.into_iter().map(Geometry::Polygon).collect(),
+        Geometry::GeometryCollection(collection) => collection.0,
+        other => vec![other],
+    }
+}
+
+/// Gather geometries into the narrowest multi-geometry that holds them all
+pub(crate) fn collect(parts: Vec<Geometry>) -> Geometry {
+    if parts.iter().all(|g| matches!(g, Geometry::Point(_))) {
+        return Geometry::MultiPoint(MultiPoint::new(
+            parts
+                .into_iter()
+                .filter_map(|g| Point::try_from(g).ok())
+                .collect(),
+        ));
+    }
+    if parts.iter().all(|g| matches!(g, Geometry::LineString(_))) {
+        return Geometry::MultiLineString(MultiLineString::new(
+            parts
+                .into_iter()
+                .filter_map(|g| LineString::try_from(g).ok())
+                .collect(),
+        ));
+    }
+    if parts.iter().all(|g| matches!(g, Geometry::Polygon(_))) {
+        return Geometry::MultiPolygon(MultiPolygon::new(
+            parts
+                .into_iter()
+                .filter_map(|g| Polygon::try_from(g).ok())
+                .collect(),
+        ));
+    }
+    Geometry::GeometryCollection(GeometryCollection::from(parts))
+}
+
+/// Dissolve polygonal parts; points not covered by the result and lines are kept as they are
+fn union(parts: &[Geometry]) -> Geometry {
+    let mut polygons = Vec::new();
+    let mut lines = Vec::new();
+    let mut points: Vec<Point> = Vec::new();
+    for part in parts {
+        split_by_dimension(part.clone(), &mut polygons, &mut lines, &mut points);
+    }
+
+    let dissolved = unary_union(&polygons);
+    points.retain(|point| !dissolved.contains(point));
+    points.sort_by(|a, b| a.x().total_cmp(&b.x()).then(a.y().total_cmp(&b.y())));
+    points.dedup();
+
+    let mut result: Vec<Geometry> = dissolved.into_iter().map(Geometry::Polygon).collect();
+    result.extend(lines.into_iter().map(Geometry::LineString));
+    result.extend(points.into_iter().map(Geometry::Point));
+    if result.len() == 1 {
+        result.remove(0)
+    } else {
+        collect(result)
+    }
+}
+
+pub(crate) fn split_by_dimension(
+    geometry: Geometry,
+    polygons: &mut Vec<Polygon>,
+    lines: &mut Vec<LineString>,
+    points: &mut Vec<Point>,
+) {
+    match geometry {
+        Geometry::Point(point) => points.push(point),
+        Geometry::Line(line) => lines.push(line.into()),
+        Geometry::LineString(line) => lines.push(line),
+        Geometry::Polygon(polygon) => polygons.push(polygon),
+        Geometry::Rect(rect) => polygons.push(rect.to_polygon()),
+        Geometry::Triangle(triangle) => polygons.push(triangle.to_polygon()),
+        Geometry::MultiPoint(_)
+        | Geometry::MultiLineString(_)
+        | Geometry::MultiPolygon(_)
+        | Geometry::GeometryCollection(_) => {
+            for member in members(geometry) {
+                split_by_dimension(member, polygons, lines, points);
+            }
+        },
+    }
+}
@@ -0,0 +1,815 @@
+//! Geometry type coercion: `ST_Dump`, `ST_Multi`, `ST_Force2D`, `ST_Force3D`, `ST_Force3DM`,
+//! `ST_Force4D` and `ST_CastGeometry`, plus `ST_GeometryType` naming the type of a geometry.
+//!
+//! These functions change how geometries are typed rather than their shape. They read
+//! geometries through `geo-traits` into owned WKT structures instead of `geo`, which only
+//! holds XY coordinates, so Z and M values survive. Where the input column has a concrete
+//! `GeoArrow` type, `ST_Multi` and the `ST_Force*` functions keep it, adjusting only the
+//! geometry kind or dimension.
+
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use arrow::array::{Array, ListArray, StringArray, StructArray};
+use arrow::buffer::{NullBuffer, OffsetBuffer};
+use arrow::datatypes::{DataType, Field, FieldRef};
+use datafusion::common::{Result, exec_err, internal_err};
+use datafusion::logical_expr::{
+    ColumnarValue, ReturnFieldArgs, ScalarFunctionArgs, ScalarUDFImpl, Signature, Volatility,
+};
+use datafusion_shared::parse_geometry_type;
+use geo_traits::to_geo::{ToGeoLine, ToGeoRect, ToGeoTriangle};
+use geo_traits::{
+    CoordTrait, Dimensions, GeometryCollectionTrait, GeometryTrait, GeometryType, LineStringTrait,
+    MultiLineStringTrait, MultiPointTrait, MultiPolygonTrait, PointTrait, PolygonTrait,
+};
+use geoarrow_array::builder::WkbBuilder;
+use geoarrow_array::{GeoArrowArray, GeoArrowArrayAccessor, downcast_geoarrow_array};
+use geoarrow_schema::error::GeoArrowResult;
+use geoarrow_schema::{
+    Dimension, GeoArrowType, GeometryCollectionType, LineStringType, MultiLineStringType,
+    MultiPointType, MultiPolygonType, PointType, PolygonType, WkbType,
+};
+use wkt::Wkt;
+use wkt::types::{self, Dimension as WktDimension};
+
+use crate::geometry::{
+    field_metadata, geoarrow_array, geoarrow_error, geometry_return_field, typed_geometry_array,
+};
+
+/// Decode argument `idx` into one optional WKT geometry per row, keeping Z and M values
+pub(crate) fn wkt_geometry_arg(args: &ScalarFunctionArgs, idx: usize) -> Result<Vec<Option<Wkt>>> {
+    let array = args.args[idx].to_array(args.number_rows)?;
+    read_wkt_geometries(array.as_ref(), &args.arg_fields[idx], None)
+}
+
+/// Decode an Arrow array holding geometries into owned WKT geometries, optionally forcing
+/// their coordinate dimension
+fn read_wkt_geometries(
+    array: &dyn Array,
+    field: &Field,
+    dimension: Option<WktDimension>,
+) -> Result<Vec<Option<Wkt>>> {
+    if array.data_type() == &DataType::Null {
+        return Ok(vec![None; array.len()]);
+    }
+    let geometries = geoarrow_array(array, field)?;
+    let geometries = geometries.as_ref();
+    downcast_geoarrow_array!(geometries, collect_wkt, dimension).map_err(geoarrow_error)
+}
+
+fn collect_wkt<'a>(
+    array: &'a impl GeoArrowArrayAccessor<'a>,
+    dimension: Option<WktDimension>,
+) -> GeoArrowResult<Vec<Option<Wkt>>> {
+    array
+        .iter()
+        .map(|item| {
+            item.transpose()
+                .map(|geometry| geometry.map(|g| to_wkt(&g, dimension)))
+        })
+        .collect()
+}
+
+fn wkt_dimension(dimensions: Dimensions) -> WktDimension {
+    match dimensions {
+        Dimensions::Xyz | Dimensions::Unknown(3) => WktDimension::XYZ,
+        Dimensions::Xym => WktDimension::XYM,
+        Dimensions::Xyzm | Dimensions::Unknown(4) => WktDimension::XYZM,
+        _ => WktDimension::XY,
+    }
+}
+
+fn from_geoarrow_dimension(dimension: Dimension) -> WktDimension {
+    match dimension {
+        Dimension::XY => WktDimension::XY,
+        Dimension::XYZ => WktDimension::XYZ,
+        Dimension::XYM => WktDimension::XYM,
+        Dimension::XYZM => WktDimension::XYZM,
+    }
+}
+
+/// Copy a geometry into WKT structures, in `dimension` when given
+///
+/// Missing Z and M values are set to zero; values the dimension lacks are dropped.
+pub(crate) fn to_wkt(
+    geometry: &impl GeometryTrait<T = f64>,
+    dimension: Option<WktDimension>,
+) -> Wkt {
+    let dim = dimension.unwrap_or_else(|| wkt_dimension(geometry.dim()));
+    match geometry.as_type() {
+        GeometryType::Point(p) => Wkt::Point(point(p, dim)),
+        GeometryType::LineString(l) => Wkt::LineString(line_string(l, dim)),
+        GeometryType::Polygon(p) => Wkt::Polygon(polygon(p, dim)),
+        GeometryType::MultiPoint(mp) => Wkt::MultiPoint(types::MultiPoint::new(
+            mp.points().map(|p| point(&p, dim)).collect(),
+            dim,
+        )),
+        GeometryType::MultiLineString(ml) => Wkt::MultiLineString(types::MultiLineString::new(
+            ml.line_strings().map(|l| line_string(&l, dim)).collect(),
+            dim,
+        )),
+        GeometryType::MultiPolygon(mp) => Wkt::MultiPolygon(types::MultiPolygon::new(
+            mp.polygons().map(|p| polygon(&p, dim)).collect(),
+            dim,
+        )),
+        GeometryType::GeometryCollection(gc) => {
+            Wkt::GeometryCollection(types::GeometryCollection::new(
+                gc.geometries().map(|g| to_wkt(&g, dimension)).collect(),
+                dim,
+            ))
+        },
+        GeometryType::Rect(r) => Wkt::Polygon(polygon(&r.to_rect().to_polygon(), dim)),
+        GeometryType::Triangle(t) => Wkt::Polygon(polygon(&t.to_triangle().to_polygon(), dim)),
+        GeometryType::Line(l) => {
+            Wkt::LineString(line_string(&geo::LineString::from(l.to_line()), dim))
+        },
+    }
+}
+
+fn coord(coord: &impl CoordTrait<T = f64>, dim: WktDimension) -> types::Coord {
+    let (z, m) = match coord.dim() {
+        Dimensions::Xyz | Dimensions::Unknown(3) => (coord.nth(2), None),
+        Dimensions::Xym => (None, coord.nth(2)),
+        Dimensions::Xyzm | Dimensions::Unknown(4) => (coord.nth(2), coord.nth(3)),
+        _ => (None, None),
+    };
+    let has_z = matches!(dim, WktDimension::XYZ | WktDimension::XYZM);
+    let has_m = matches!(dim, WktDimension::XYM | WktDimension::XYZM);
+    types::Coord {
+        x: coord.x(),
+        y: coord.y(),
+        z: has_z.then(|| z.unwrap_or(0.0)),
+        m: has_m.then(|| m.unwrap_or(0.0)),
+    }
+}
+
+fn point(point: &impl PointTrait<T = f64>, dim: WktDimension) -> types::Point {
+    types::Point::new(point.coord().map(|c| coord(&c, dim)), dim)
+}
+
+fn line_string(line: &impl LineStringTrait<T = f64>, dim: WktDimension) -> types::LineString {
+    types::LineString::new(line.coords().map(|c| coord(&c, dim)).collect(), dim)
+}
+
+fn polygon(polygon: &impl PolygonTrait<T = f64>, dim: WktDimension) -> types::Polygon {
+    types::Polygon::new(
+        polygon
+            .exterior()
+            .into_iter()
+            .chain(polygon.interiors())
+            .map(|ring| line_string(&ring, dim))
+            .collect(),
+        dim,
+    )
+}
+
+/// Replace every coordinate of a geometry with `f(coord)`, keeping its structure and dimension
+pub(crate) fn try_map_coords<E>(
+    geometry: Wkt,
+    f: &mut impl FnMut(types::Coord) -> Result<types::Coord, E>,
+) -> Result<Wkt, E> {
+    fn line<E>(
+        line: types::LineString,
+        f: &mut impl FnMut(types::Coord) -> Result<types::Coord, E>,
+    ) -> Result<types::LineString, E> {
+        let (coords, dim) = line.into_inner();
+        let coords = coords.into_iter().map(&mut *f).collect::<Result<_, E>>()?;
+        Ok(types::LineString::new(coords, dim))
+    }
+
+    fn polygon<E>(
+        polygon: types::Polygon,
+        f: &mut impl FnMut(types::Coord) -> Result<types::Coord, E>,
+    ) -> Result<types::Polygon, E> {
+        let (rings, dim) = polygon.into_inner();
+        let rings = rings
+            .into_iter()
+            .map(|ring| line(ring, f))
+            .collect::<Result<_, E>>()?;
+        Ok(types::Polygon::new(rings, dim))
+    }
+
+    Ok(match geometry {
+        Wkt::Point(p) => {
+            let (coord, dim) = p.into_inner();
+            Wkt::Point(types::Point::new(coord.map(&mut *f).transpose()?, dim))
+        },
+        Wkt::LineString(l) => Wkt::LineString(line(l, f)?),
+        Wkt::Polygon(p) => Wkt::Polygon(polygon(p, f)?),
+        Wkt::MultiPoint(mp) => {
+            let (points, dim) = mp.into_inner();
+            let points = points
+                .into_iter()
+                .map(|p| {
+                    let (coord, dim) = p.into_inner();
+                    Ok(types::Point::new(coord.map(&mut *f).transpose()?, dim))
+                })
+                .collect::<Result<_, E>>()?;
+            Wkt::MultiPoint(types::MultiPoint::new(points, dim))
+        },
+        Wkt::MultiLineString(ml) => {
+            let (lines, dim) = ml.into_inner();
+            let lines = lines
+                .into_iter()
+                .map(|l| line(l, f))
+                .collect::<Result<_, E>>()?;
+            Wkt::MultiLineString(types::MultiLineString::new(lines, dim))
+        },
+        Wkt::MultiPolygon(mp) => {
+            let (polygons, dim) = mp.into_inner();
+            let polygons = polygons
+                .into_iter()
+                .map(|p| polygon(p, f))
+                .collect::<Result<_, E>>()?;
+            Wkt::MultiPolygon(types::MultiPolygon::new(polygons, dim))
+        },
+        Wkt::GeometryCollection(gc) => {
+            let (geometries, dim) = gc.into_inner();
+            let geometries = geometries
+                .into_iter()
+                .map(|g| try_map_coords(g, f))
+                .collect::<Result<_, E>>()?;
+            Wkt::GeometryCollection(types::GeometryCollection::new(geometries, dim))
+        },
+    })
+}
+
+/// A geometry derived in XY from `original`, in the dimension of `original`
+///
+/// Vertices kept from `original` get back their Z and M values; new vertices get zero.
+pub(crate) fn restore_dimension(geometry: &impl GeometryTrait<T = f64>, original: &Wkt) -> Wkt {
+    let dim = wkt_dimension(original.dim());
+    if dim == WktDimension::XY {
+        return to_wkt(geometry, None);
+    }
+
+    let mut values = HashMap::new();
+    let _ = try_map_coords(original.clone(), &mut |c| {
+        values
+            .entry((c.x.to_bits(), c.y.to_bits()))
+            .or_insert((c.z, c.m));
+        Ok::<_, Infallible>(c)
+    });
+    let restored = try_map_coords(to_wkt(geometry, Some(dim)), &mut |c| {
+        let (z, m) = values
+            .get(&(c.x.to_bits(), c.y.to_bits()))
+            .copied()
+            .unwrap_or((c.z, c.m));
+        Ok::<_, Infallible>(types::Coord { z, m, ..c })
+    });
+    match restored {
+        Ok(geometry) => geometry,
+        Err(never) => match never {},
+    }
+}
+
+/// The single parts of a geometry, unpacking multi-geometries and collections recursively
+fn push_parts(geometry: Wkt, parts: &mut Vec<Option<Wkt>>) {
+    match geometry {
+        Wkt::MultiPoint(mp) => {
+            parts.extend(mp.into_inner().0.into_iter().map(|p| Some(Wkt::Point(p))));
+        },
+        Wkt::MultiLineString(ml) => {
+            parts.extend(
+                ml.into_inner()
+                    .0
+                    .into_iter()
+                    .map(|l| Some(Wkt::LineString(l))),
+            );
+        },
+        Wkt::MultiPolygon(mp) => {
+            parts.extend(mp.into_inner().0.into_iter().map(|p| Some(Wkt::Polygon(p))));
+        },
+        Wkt::GeometryCollection(gc) => {
+            for member in gc.into_inner().0 {
+                push_parts(member, parts);
+            }
+        },
+        single => parts.push(Some(single)),
+    }
+}
+
+/// Wrap a point, line string or polygon in the matching multi-geometry
+fn promote_to_multi(geometry: Wkt) -> Wkt {
+    let dim = wkt_dimension(geometry.dim());
+    match geometry {
+        Wkt::Point(p) => {
+            let points = if p.coord().is_some() { vec![p] } else { vec![] };
+            Wkt::MultiPoint(types::MultiPoint::new(points, dim))
+        },
+        Wkt::LineString(l) => Wkt::MultiLineString(types::MultiLineString::new(vec![l], dim)),
+        Wkt::Polygon(p) => Wkt::MultiPolygon(types::MultiPolygon::new(vec![p], dim)),
+        other => other,
+    }
+}
+
+/// Unwrap a multi-geometry with exactly one part
+fn single_part(geometry: Wkt) -> Wkt {
+    match geometry {
+        Wkt::MultiPoint(mp) if mp.points().len() == 1 => Wkt::Point(mp.into_inner().0.remove(0)),
+        Wkt::MultiLineString(ml) if ml.line_strings().len() == 1 => {
+            Wkt::LineString(ml.into_inner().0.remove(0))
+        },
+        Wkt::MultiPolygon(mp) if mp.polygons().len() == 1 => {
+            Wkt::Polygon(mp.into_inner().0.remove(0))
+        },
+        other => other,
+    }
+}
+
+/// Convert a geometry to the kind and dimension of `data_type`, or `None` if it does not fit
+fn cast(geometry: Wkt, data_type: &GeoArrowType) -> Option<Wkt> {
+    let geometry = match data_type.dimension() {
+        Some(dimension) => to_wkt(&geometry, Some(from_geoarrow_dimension(dimension))),
+        None => geometry,
+    };
+    match data_type {
+        GeoArrowType::Point(_) => {
+            Some(single_part(geometry)).filter(|g| matches!(g, Wkt::Point(_)))
+        },
+        GeoArrowType::LineString(_) => {
+            Some(single_part(geometry)).filter(|g| matches!(g, Wkt::LineString(_)))
+        },
+        GeoArrowType::Polygon(_) => {
+            Some(single_part(geometry)).filter(|g| matches!(g, Wkt::Polygon(_)))
+        },
+        GeoArrowType::MultiPoint(_) => {
+            Some(promote_to_multi(geometry)).filter(|g| matches!(g, Wkt::MultiPoint(_)))
+        },
+        GeoArrowType::MultiLineString(_) => {
+            Some(promote_to_multi(geometry)).filter(|g| matches!(g, Wkt::MultiLineString(_)))
+        },
+        GeoArrowType::MultiPolygon(_) => {
+            Some(promote_to_multi(geometry)).filter(|g| matches!(g, Wkt::MultiPolygon(_)))
+        },
+        GeoArrowType::GeometryCollection(_) => Some(match geometry {
+            Wkt::GeometryCollection(gc) => Wkt::GeometryCollection(gc),
+            other => {
+                let dim = wkt_dimension(other.dim());
+                Wkt::GeometryCollection(types::GeometryCollection::new(vec![other], dim))
+            },
+        }),
+        _ => Some(geometry),
+    }
+}
+
+/// A WKT type name with its dimension suffix (e.g. `LineString Z`)
+fn type_name(name: &str, dim: WktDimension) -> String {
+    match dim {
+        WktDimension::XY => name.to_string(),
+        WktDimension::XYZ => format!("{name} Z"),
+        WktDimension::XYM => format!("{name} M"),
+        WktDimension::XYZM => format!("{name} ZM"),
+    }
+}
+
+fn geometry_type_name(geometry: &Wkt) -> String {
+    let name = match geometry {
+        Wkt::Point(_) => "Point",
+        Wkt::LineString(_) => "LineString",
+        Wkt::Polygon(_) => "Polygon",
+        Wkt::MultiPoint(_) => "MultiPoint",
+        Wkt::MultiLineString(_) => "MultiLineString",
+        Wkt::MultiPolygon(_) => "MultiPolygon",
+        Wkt::GeometryCollection(_) => "GeometryCollection",
+    };
+    type_name(name, wkt_dimension(geometry.dim()))
+}
+
+fn geoarrow_type_name(data_type: &GeoArrowType) -> String {
+    let name = match data_type {
+        GeoArrowType::Point(_) => "Point",
+        GeoArrowType::LineString(_) => "LineString",
+        GeoArrowType::Polygon(_) => "Polygon",
+        GeoArrowType::MultiPoint(_) => "MultiPoint",
+        GeoArrowType::MultiLineString(_) => "MultiLineString",
+        GeoArrowType::MultiPolygon(_) => "MultiPolygon",
+        GeoArrowType::GeometryCollection(_) => "GeometryCollection",
+        _ => "Geometry",
+    };
+    let dim = data_type
+        .dimension()
+        .map_or(WktDimension::XY, from_geoarrow_dimension);
+    type_name(name, dim)
+}
+
+/// The geometry type name of a `GeoArrow` field, as accepted by `ST_CastGeometry` (e.g.
+/// `Point`, `MultiPolygon Z`); `Geometry` for mixed, WKB and WKT columns
+#[must_use]
+pub fn field_geometry_type(field: &Field) -> String {
+    geoarrow_type_name(&argument_type(field))
+}
+
+/// The type name of each geometry of an array that `ST_CastGeometry` cannot convert to
+/// `geometry_type`; `None` for the geometries that fit and for nulls
+///
+/// # Errors
+///
+/// Returns an error if the geometry type is not known, the array is not a geometry encoding
+/// or a value cannot be parsed
+pub fn cast_failures(
+    array: &dyn Array,
+    field: &Field,
+    geometry_type: &str,
+) -> Result<Vec<Option<String>>> {
+    let data_type = parse_geometry_type(geometry_type, field_metadata(field))?;
+    Ok(read_wkt_geometries(array, field, None)?
+        .into_iter()
+        .map(|geometry| {
+            let geometry = geometry?;
+            let name = geometry_type_name(&geometry);
+            cast(geometry, &data_type).is_none().then_some(name)
+        })
+        .collect())
+}
+
+/// The `GeoArrow` type of a geometry argument; plain WKT/WKB and untagged values count as
+/// `geoarrow.geometry`
+fn argument_type(field: &Field) -> GeoArrowType {
+    GeoArrowType::try_from(field).unwrap_or_else(|_| {
+        GeoArrowType::Geometry(geoarrow_schema::GeometryType::new(field_metadata(field)))
+    })
+}
+
+/// The same kind of geometry type in another dimension; types without a dimension become
+/// `geoarrow.geometry`
+fn with_dimension(data_type: &GeoArrowType, dim: Dimension) -> GeoArrowType {
+    let metadata = Arc::clone(data_type.metadata());
+    match data_type {
+        GeoArrowType::Point(_) => GeoArrowType::Point(PointType::new(dim, metadata)),
+        GeoArrowType::LineString(_) => GeoArrowType::LineString(LineStringType::new(dim, metadata)),
+        GeoArrowType::Polygon(_) => GeoArrowType::Polygon(PolygonType::new(dim, metadata)),
+        GeoArrowType::MultiPoint(_) => GeoArrowType::MultiPoint(MultiPointType::new(dim, metadata)),
+        GeoArrowType::MultiLineString(_) => {
+            GeoArrowType::MultiLineString(MultiLineStringType::new(dim, metadata))
+        },
+        GeoArrowType::MultiPolygon(_) => {
+            GeoArrowType::MultiPolygon(MultiPolygonType::new(dim, metadata))
+        },
+        GeoArrowType::GeometryCollection(_) => {
+            GeoArrowType::GeometryCollection(GeometryCollectionType::new(dim, metadata))
+        },
+        _ => GeoArrowType::Geometry(geoarrow_schema::GeometryType::new(metadata)),
+    }
+}
+
+/// `ST_Dump(geom)`: the single parts of a geometry, as a list of `{geom}` structs
+///
+/// Multi-geometries and collections are unpacked recursively; null and empty geometries
+/// produce a null list. `UNNEST` turns the parts into rows. Parts are `geoarrow.wkb` values,
+/// which unlike the `geoarrow.geometry` union can be hashed when a constant is folded.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct DumpUdf {
+    signature: Signature,
+}
+
+impl Default for DumpUdf {
+    fn default() -> Self {
+        Self {
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for DumpUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "st_dump"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        internal_err!("return_field_from_args should be used instead")
+    }
+
+    fn return_field_from_args(&self, args: ReturnFieldArgs) -> Result<FieldRef> {
+        let part = GeoArrowType::Wkb(WkbType::new(field_metadata(&args.arg_fields[0])))
+            .to_field("geom", true);
+        Ok(Arc::new(Field::new_list(
+            self.name(),
+            Field::new_struct("item", vec![part], true),
+            true,
+        )))
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let DataType::List(item) = args.return_field.data_type() else {
+            return internal_err!("st_dump must return a list");
+        };
+        let DataType::Struct(fields) = item.data_type() else {
+            return internal_err!("st_dump must return a list of structs");
+        };
+
+        let array = args.args[0].to_array(args.number_rows)?;
+        let mut parts = Vec::new();
+        let mut lengths = Vec::with_capacity(array.len());
+        for geometry in read_wkt_geometries(array.as_ref(), &args.arg_fields[0], None)? {
+            let start = parts.len();
+            if let Some(geometry) = geometry {
+                push_parts(geometry, &mut parts);
+            }
+            lengths.push(parts.len() - start);
+        }
+
+        let values = WkbBuilder::<i32>::from_nullable_geometries(
+            &parts,
+            WkbType::new(field_metadata(&fields[0])),
+        )
+        .map_err(geoarrow_error)?
+        .finish()
+        .into_array_ref();
+        let structs = StructArray::try_new(fields.clone(), vec![values], None)?;
+        let validity = NullBuffer::from_iter(lengths.iter().map(|&len| len > 0));
+        let list = ListArray::try_new(
+            Arc::clone(item),
+            OffsetBuffer::from_lengths(lengths),
+            Arc::new(structs),
+            Some(validity),
+        )?;
+        Ok(ColumnarValue::Array(Arc::new(list)))
+    }
+}
+
+/// `ST_Multi(geom)`: points, line strings and polygons as single-part multi-geometries
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct MultiUdf {
+    signature: Signature,
+}
+
+impl Default for MultiUdf {
+    fn default() -> Self {
+        Self {
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for MultiUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "st_multi"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        internal_err!("return_field_from_args should be used instead")
+    }
+
+    fn return_field_from_args(&self, args: ReturnFieldArgs) -> Result<FieldRef> {
+        let data_type = argument_type(&args.arg_fields[0]);
+        let metadata = Arc::clone(data_type.metadata());
+        let multi = match data_type {
+            GeoArrowType::Point(t) => {
+                GeoArrowType::MultiPoint(MultiPointType::new(t.dimension(), metadata))
+            },
+            GeoArrowType::LineString(t) => {
+                GeoArrowType::MultiLineString(MultiLineStringType::new(t.dimension(), metadata))
+            },
+            GeoArrowType::Polygon(t) => {
+                GeoArrowType::MultiPolygon(MultiPolygonType::new(t.dimension(), metadata))
+            },
+            multi @ (GeoArrowType::MultiPoint(_)
+            | GeoArrowType::MultiLineString(_)
+            | GeoArrowType::MultiPolygon(_)) => multi,
+            _ => return Ok(geometry_return_field(self.name(), metadata)),
+        };
+        Ok(Arc::new(multi.to_field(self.name(), true)))
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let array = args.args[0].to_array(args.number_rows)?;
+        let results: Vec<Option<Wkt>> =
+            read_wkt_geometries(array.as_ref(), &args.arg_fields[0], None)?
+                .into_iter()
+                .map(|geometry| geometry.map(promote_to_multi))
+                .collect();
+        let data_type =
+            GeoArrowType::try_from(args.return_field.as_ref()).map_err(geoarrow_error)?;
+        Ok(ColumnarValue::Array(typed_geometry_array(
+            &results, data_type,
+        )?))
+    }
+}
+
+/// `ST_Force2D(geom)`, `ST_Force3D(geom)`, `ST_Force3DM(geom)` and `ST_Force4D(geom)`: set
+/// the coordinate dimension, adding zero Z or M values and dropping those not kept
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct ForceDimensionUdf {
+    dimension: Dimension,
+    signature: Signature,
+}
+
+impl ForceDimensionUdf {
+    fn new(dimension: Dimension) -> Self {
+        Self {
+            dimension,
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+
+    /// `ST_Force2D(geom)`: XY coordinates
+    #[must_use]
+    pub fn force_2d() -> Self {
+        Self::new(Dimension::XY)
+    }
+
+    /// `ST_Force3D(geom)`: XYZ coordinates
+    #[must_use]
+    pub fn force_3d() -> Self {
+        Self::new(Dimension::XYZ)
+    }
+
+    /// `ST_Force3DM(geom)`: XYM coordinates
+    #[must_use]
+    pub fn force_3dm() -> Self {
+        Self::new(Dimension::XYM)
+    }
+
+    /// `ST_Force4D(geom)`: XYZM coordinates
+    #[must_use]
+    pub fn force_4d() -> Self {
+        Self::new(Dimension::XYZM)
+    }
+}
+
+impl ScalarUDFImpl for ForceDimensionUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        match self.dimension {
+            Dimension::XY => "st_force2d",
+            Dimension::XYZ => "st_force3d",
+            Dimension::XYM => "st_force3dm",
+            Dimension::XYZM => "st_force4d",
+        }
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        internal_err!("return_field_from_args should be used instead")
+    }
+
+    fn return_field_from_args(&self, args: ReturnFieldArgs) -> Result<FieldRef> {
+        let data_type = with_dimension(&argument_type(&args.arg_fields[0]), self.dimension);
+        Ok(Arc::new(data_type.to_field(self.name(), true)))
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let array = args.args[0].to_array(args.number_rows)?;
+        let results = read_wkt_geometries(
+            array.as_ref(),
+            &args.arg_fields[0],
+            Some(from_geoarrow_dimension(self.dimension)),
+        )?;
+        let data_type =
+            GeoArrowType::try_from(args.return_field.as_ref()).map_err(geoarrow_error)?;
+        Ok(ColumnarValue::Array(typed_geometry_array(
+            &results, data_type,
+        )?))
+    }
+}
+
+/// `ST_CastGeometry(geom, type)`: convert geometries to a `GeoArrow` type named like
+/// `MultiPolygon` or `Point Z`
+///
+/// Single geometries are promoted to a multi type, single-part multi-geometries are unwrapped
+/// for a single type and the coordinate dimension is forced to the type's. Geometries of
+/// another kind cannot be cast and fail the query with a count of them by type.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct CastGeometryUdf {
+    signature: Signature,
+}
+
+impl Default for CastGeometryUdf {
+    fn default() -> Self {
+        Self {
+            signature: Signature::any(2, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for CastGeometryUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "st_castgeometry"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        internal_err!("return_field_from_args should be used instead")
+    }
+
+    fn return_field_from_args(&self, args: ReturnFieldArgs) -> Result<FieldRef> {
+        let Some(Some(name)) = args.scalar_arguments[1].map(|value| value.try_as_str()) else {
+            return exec_err!("ST_CastGeometry requires a constant geometry type name");
+        };
+        let Some(name) = name else {
+            return exec_err!("ST_CastGeometry does not accept a NULL geometry type");
+        };
+        let data_type = parse_geometry_type(name, field_metadata(&args.arg_fields[0]))?;
+        Ok(Arc::new(data_type.to_field(self.name(), true)))
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let data_type =
+            GeoArrowType::try_from(args.return_field.as_ref()).map_err(geoarrow_error)?;
+        let array = args.args[0].to_array(args.number_rows)?;
+
+        let mut rejected: BTreeMap<String, usize> = BTreeMap::new();
+        let results: Vec<Option<Wkt>> =
+            read_wkt_geometries(array.as_ref(), &args.arg_fields[0], None)?
+                .into_iter()
+                .map(|geometry| {
+                    let geometry = geometry?;
+                    let name = geometry_type_name(&geometry);
+                    let cast = cast(geometry, &data_type);
+                    if cast.is_none() {
+                        *rejected.entry(name).or_default() += 1;
+                    }
+                    cast
+                })
+                .collect();
+        if !rejected.is_empty() {
+            let counts: Vec<String> = rejected
+                .iter()
+                .map(|(name, count)| format!("{count} {name}"))
+                .collect();
+            return exec_err!(
+                "cannot cast geometries to {}: {}",
+                geoarrow_type_name(&data_type),
+                counts.join(", ")
+            );
+        }
+        Ok(ColumnarValue::Array(typed_geometry_array(
+            &results, data_type,
+        )?))
+    }
+}
+
+/// `ST_GeometryType(geom)`: the geometry type name with its dimension suffix, as accepted by
+/// `ST_CastGeometry` (e.g. `Point`, `MultiPolygon Z`)
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct GeometryTypeUdf {
+    signature: Signature,
+}
+
+impl Default for GeometryTypeUdf {
+    fn default() -> Self {
+        Self {
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for GeometryTypeUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "st_geometrytype"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Utf8)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let array = args.args[0].to_array(args.number_rows)?;
+        let names: StringArray = read_wkt_geometries(array.as_ref(), &args.arg_fields[0], None)?
+            .iter()
+            .map(|geometry| geometry.as_ref().map(geometry_type_name))
+            .collect();
+        Ok(ColumnarValue::Array(Arc::new(names)))
+    }
+}
@@ -0,0 +1,407 @@
+//! Functions deriving a new geometry: `ST_Centroid`, `ST_Envelope`, `ST_Buffer`,
+//! `ST_Simplify`, `ST_SimplifyVW`, `ST_SimplifyPreserveTopology`, `ST_SnapToGrid` and
+//! `ST_Intersection`.
+//!
+//! Results are `geoarrow.geometry` columns carrying the CRS of the input. Simplification and
+//! grid snapping keep the coordinate dimension of their input.
+
+use std::any::Any;
+use std::convert::Infallible;
+
+use arrow::datatypes::{DataType, FieldRef};
+use datafusion::common::{Result, internal_err};
+use datafusion::logical_expr::{
+    ColumnarValue, ReturnFieldArgs, ScalarFunctionArgs, ScalarUDFImpl, Signature, Volatility,
+};
+use geo::{
+    BooleanOps, BoundingRect, Buffer, Centroid, Geometry, GeometryCollection, Intersects,
+    MultiLineString, MultiPolygon, Point, Simplify, SimplifyVw, SimplifyVwPreserve,
+};
+use geo_traits::to_geo::ToGeoGeometry;
+use wkt::Wkt;
+use wkt::types;
+
+use crate::aggregates::{collect, split_by_dimension};
+use crate::coercion::{restore_dimension, try_map_coords, wkt_geometry_arg};
+use crate::geometry::{
+    field_metadata, float_arg, geometry_arg, geometry_array, geometry_return_field,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Construction {
+    Centroid,
+    Envelope,
+    Buffer,
+    Simplify(Simplification),
+    SnapToGrid,
+}
+
+/// Line simplification algorithm
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Simplification {
+    /// Douglas-Peucker: drop vertices closer than the tolerance to the simplified line
+    DouglasPeucker,
+    /// Visvalingam-Whyatt: drop vertices forming triangles smaller than the tolerance (an
+    /// area)
+    Visvalingam,
+    /// Visvalingam-Whyatt, keeping vertices whose removal would make rings or lines cross
+    #[default]
+    PreserveTopology,
+}
+
+/// A function building one geometry from each input geometry
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct ConstructorUdf {
+    construction: Construction,
+    signature: Signature,
+}
+
+impl ConstructorUdf {
+    fn new(construction: Construction, arity: usize) -> Self {
+        Self {
+            construction,
+            signature: Signature::any(arity, Volatility::Immutable),
+        }
+    }
+
+    /// `ST_Centroid(geom)`: the centre of mass of a geometry
+    #[must_use]
+    pub fn centroid() -> Self {
+        Self::new(Construction::Centroid, 1)
+    }
+
+    /// `ST_Envelope(geom)`: the bounding box of a geometry as a polygon, or a point for
+    /// single-coordinate geometries
+    #[must_use]
+    pub fn envelope() -> Self {
+        Self::new(Construction::Envelope, 1)
+    }
+
+    /// `ST_Buffer(geom, distance)`: the area within `distance` of a geometry
+    #[must_use]
+    pub fn buffer() -> Self {
+        Self::new(Construction::Buffer, 2)
+    }
+
+    /// `ST_Simplify(geom, tolerance)`: Douglas-Peucker simplification of lines and polygons
+    #[must_use]
+    pub fn simplify() -> Self {
+        Self::new(Construction::Simplify(Simplification::DouglasPeucker), 2)
+    }
+
+    /// `ST_SimplifyVW(geom, area)`: Visvalingam-Whyatt simplification of lines and polygons
+    #[must_use]
+    pub fn simplify_vw() -> Self {
+        Self::new(Construction::Simplify(Simplification::Visvalingam), 2)
+    }
+
+    /// `ST_SimplifyPreserveTopology(geom, area)`: Visvalingam-Whyatt simplification that does
+    /// not introduce self-intersections
+    #[must_use]
+    pub fn simplify_preserve_topology() -> Self {
+        Self::new(Construction::Simplify(Simplification::PreserveTopology), 2)
+    }
+
+    /// `ST_SnapToGrid(geom, size)`: round X and Y to multiples of `size`, dropping the
+    /// repeated vertices this creates
+    #[must_use]
+    pub fn snap_to_grid() -> Self {
+        Self::new(Construction::SnapToGrid, 2)
+    }
+}
+
+impl ScalarUDFImpl for ConstructorUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        match self.construction {
+            Construction::Centroid => "st_centroid",
+            Construction::Envelope => "st_envelope",
+            Construction::Buffer => "st_buffer",
+            Construction::Simplify(Simplification::DouglasPeucker) => "st_simplify",
+            Construction::Simplify(Simplification::Visvalingam) => "st_simplifyvw",
+            Construction::Simplify(Simplification::PreserveTopology) => {
+                "st_simplifypreservetopology"
+            },
+            Construction::SnapToGrid => "st_snaptogrid",
+        }
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        internal_err!("return_field_from_args should be used instead")
+    }
+
+    fn return_field_from_args(&self, args: ReturnFieldArgs) -> Result<FieldRef> {
+        Ok(geometry_return_field(
+            self.name(),
+            field_metadata(&args.arg_fields[0]),
+        ))
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let metadata = field_metadata(&args.return_field);
+        let array = match self.construction {
+            Construction::Simplify(method) => {
+                let results = map_with_parameter(&args, |geometry, tolerance| {
+                    simplify(geometry, method, tolerance)
+                })?;
+                geometry_array(&results, metadata)?
+            },
+            Construction::SnapToGrid => {
+                geometry_array(&map_with_parameter(&args, snap_to_grid)?, metadata)?
+            },
+            Construction::Centroid | Construction::Envelope | Construction::Buffer => {
+                geometry_array(&self.construct(&args)?, metadata)?
+            },
+        };
+        Ok(ColumnarValue::Array(array))
+    }
+}
+
+impl ConstructorUdf {
+    /// Build the XY geometries of the centroid, envelope and buffer functions
+    fn construct(&self, args: &ScalarFunctionArgs) -> Result<Vec<Option<Geometry>>> {
+        let geometries = geometry_arg(args, 0)?;
+        let parameters = match self.construction {
+            Construction::Buffer => float_arg(args, 1)?,
+            _ => vec![None; geometries.len()],
+        };
+
+        Ok(geometries
+            .iter()
+            .zip(parameters)
+            .map(|(geometry, parameter)| {
+                let geometry = geometry.as_ref()?;
+                match self.construction {
+                    Construction::Centroid => geometry.centroid().map(Geometry::Point),
+                    Construction::Envelope => envelope(geometry),
+                    Construction::Buffer => {
+                        Some(Geometry::MultiPolygon(geometry.buffer(parameter?)))
+                    },
+                    Construction::Simplify(_) | Construction::SnapToGrid => None,
+                }
+            })
+            .collect())
+    }
+}
+
+/// Apply `f` to the geometry and number arguments of each row, keeping Z and M values
+fn map_with_parameter(
+    args: &ScalarFunctionArgs,
+    f: impl Fn(Wkt, f64) -> Wkt,
+) -> Result<Vec<Option<Wkt>>> {
+    Ok(wkt_geometry_arg(args, 0)?
+        .into_iter()
+        .zip(float_arg(args, 1)?)
+        .map(|(geometry, parameter)| Some(f(geometry?, parameter?)))
+        .collect())
+}
+
+/// `ST_Intersection(a, b)`: the part of `a` inside the polygonal parts of `b`
+///
+/// Polygons are intersected and lines clipped to the polygons of `b`; points are kept where
+/// they intersect `b`. Rows that do not overlap give an empty geometry collection.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct IntersectionUdf {
+    signature: Signature,
+}
+
+impl Default for IntersectionUdf {
+    fn default() -> Self {
+        Self {
+            signature: Signature::any(2, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for IntersectionUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "st_intersection"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        internal_err!("return_field_from_args should be used instead")
+    }
+
+    fn return_field_from_args(&self, args: ReturnFieldArgs) -> Result<FieldRef> {
+        Ok(geometry_return_field(
+            self.name(),
+            field_metadata(&args.arg_fields[0]),
+        ))
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let left = geometry_arg(&args, 0)?;
+        let right = geometry_arg(&args, 1)?;
+        let results: Vec<Option<Geometry>> = left
+            .iter()
+            .zip(&right)
+            .map(|(a, b)| Some(intersection(a.as_ref()?, b.as_ref()?)))
+            .collect();
+
+        let metadata = field_metadata(&args.return_field);
+        Ok(ColumnarValue::Array(geometry_array(&results, metadata)?))
+    }
+}
+
+fn intersection(a: &Geometry, b: &Geometry) -> Geometry {
+    let mut mask = Vec::new();
+    split_by_dimension(b.clone(), &mut mask, &mut Vec::new(), &mut Vec::new());
+    let mask = MultiPolygon::new(mask);
+
+    let mut polygons = Vec::new();
+    let mut lines = Vec::new();
+    let mut points = Vec::new();
+    split_by_dimension(a.clone(), &mut polygons, &mut lines, &mut points);
+
+    let mut parts: Vec<Geometry> = Vec::new();
+    if !polygons.is_empty() {
+        let clipped = MultiPolygon::new(polygons).intersection(&mask);
+        parts.extend(clipped.into_iter().map(Geometry::Polygon));
+    }
+    if !lines.is_empty() {
+        let clipped = mask.clip(&MultiLineString::new(lines), false);
+        parts.extend(clipped.into_iter().map(Geometry::LineString));
+    }
+    parts.extend(
+        points
+            .into_iter()
+            .filter(|point| b.intersects(point))
+            .map(Geometry::Point),
+    );
+
+    match parts.len() {
+        0 => Geometry::GeometryCollection(GeometryCollection::default()),
+        1 => parts.remove(0),
+        _ => collect(parts),
+    }
+}
+
+pub(crate) fn envelope(geometry: &Geometry) -> Option<Geometry> {
+    let rect = geometry.bounding_rect()?;
+    if rect.min() == rect.max() {
+        Some(Geometry::Point(Point::from(rect.min())))
+    } else {
+        Some(Geometry::Polygon(rect.to_polygon()))
+    }
+}
+
+/// Simplify the lines and polygons of a geometry; points are kept as they are
+///
+/// The vertices kept keep their Z and M values.
+#[must_use]
+pub fn simplify(geometry: Wkt, method: Simplification, tolerance: f64) -> Wkt {
+    match geometry.try_to_geometry() {
+        Some(xy) => restore_dimension(&simplify_xy(&xy, method, tolerance), &geometry),
+        None => geometry,
+    }
+}
+
+fn simplify_xy(geometry: &Geometry, method: Simplification, tolerance: f64) -> Geometry {
+    fn apply<G>(geometry: &G, method: Simplification, tolerance: f64) -> G
+    where
+        G: Simplify<f64> + SimplifyVw<f64> + SimplifyVwPreserve<f64>,
+    {
+        match method {
+            Simplification::DouglasPeucker => geometry.simplify(tolerance),
+            Simplification::Visvalingam => geometry.simplify_vw(tolerance),
+            Simplification::PreserveTopology => geometry.simplify_vw_preserve(tolerance),
+        }
+    }
+
+    match geometry {
+        Geometry::LineString(line) => Geometry::LineString(apply(line, method, tolerance)),
+        Geometry::MultiLineString(lines) => {
+            Geometry::MultiLineString(apply(lines, method, tolerance))
+        },
+        Geometry::Polygon(polygon) => Geometry::Polygon(apply(polygon, method, tolerance)),
+        Geometry::MultiPolygon(polygons) => {
+            Geometry::MultiPolygon(apply(polygons, method, tolerance))
+        },
+        Geometry::GeometryCollection(collection) => Geometry::GeometryCollection(
+            collection
+                .iter()
+                .map(|geometry| simplify_xy(geometry, method, tolerance))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Round the X and Y coordinates of a geometry to multiples of `size`
+///
+/// Consecutive vertices that fall on the same grid point are merged, keeping the Z and M
+/// values of the first. Sizes that are not positive leave the geometry unchanged.
+#[must_use]
+pub fn snap_to_grid(geometry: Wkt, size: f64) -> Wkt {
+    fn dedup(line: types::LineString) -> types::LineString {
+        let (mut coords, dim) = line.into_inner();
+        coords.dedup_by(|a, b| a.x == b.x && a.y == b.y);
+        types::LineString::new(coords, dim)
+    }
+
+    fn dedup_rings(polygon: types::Polygon) -> types::Polygon {
+        let (rings, dim) = polygon.into_inner();
+        types::Polygon::new(rings.into_iter().map(dedup).collect(), dim)
+    }
+
+    if !(size > 0.0 && size.is_finite()) {
+        return geometry;
+    }
+    // Adding zero turns the -0 of values rounded up to zero into 0
+    let snap = |value: f64| (value / size).round() * size + 0.0;
+    let snapped = try_map_coords(geometry, &mut |c| {
+        Ok::<_, Infallible>(types::Coord {
+            x: snap(c.x),
+            y: snap(c.y),
+            ..c
+        })
+    });
+    let snapped = match snapped {
+        Ok(geometry) => geometry,
+        Err(never) => match never {},
+    };
+    match snapped {
+        Wkt::LineString(line) => Wkt::LineString(dedup(line)),
+        Wkt::MultiLineString(lines) => {
+            let (lines, dim) = lines.into_inner();
+            Wkt::MultiLineString(types::MultiLineString::new(
+                lines.into_iter().map(dedup).collect(),
+                dim,
+            ))
+        },
+        Wkt::Polygon(polygon) => Wkt::Polygon(dedup_rings(polygon)),
+        Wkt::MultiPolygon(polygons) => {
+            let (polygons, dim) = polygons.into_inner();
+            Wkt::MultiPolygon(types::MultiPolygon::new(
+                polygons.into_iter().map(dedup_rings).collect(),
+                dim,
+            ))
+        },
+        Wkt::GeometryCollection(collection) => {
+            let (geometries, dim) = collection.into_inner();
+            Wkt::GeometryCollection(types::GeometryCollection::new(
+                geometries
+                    .into_iter()
+                    .map(|geometry| snap_to_grid(geometry, size))
+                    .collect(),
+                dim,
+            ))
+        },
+        other => other,
+    }
+}
@@ -0,0 +1,118 @@
+//! WKT conversion: `ST_AsText` and `ST_GeomFromText`.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::new_null_array;
+use arrow::datatypes::{DataType, FieldRef};
+use datafusion::common::{Result, internal_err};
+use datafusion::logical_expr::{
+    ColumnarValue, ReturnFieldArgs, ScalarFunctionArgs, ScalarUDFImpl, Signature, TypeSignature,
+    Volatility,
+};
+use geoarrow_array::GeoArrowArray;
+use geoarrow_array::cast::to_wkt;
+use geoarrow_schema::Metadata;
+
+use crate::crs::crs_metadata;
+use crate::geometry::{
+    constant_crs, field_metadata, geoarrow_array, geoarrow_error, geometry_arg, geometry_array,
+    geometry_return_field,
+};
+
+/// `ST_AsText(geom)`: the WKT representation of a geometry
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct AsTextUdf {
+    signature: Signature,
+}
+
+impl Default for AsTextUdf {
+    fn default() -> Self {
+        Self {
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for AsTextUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "st_astext"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Utf8)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let array = args.args[0].to_array(args.number_rows)?;
+        if array.data_type() == &DataType::Null {
+            return Ok(ColumnarValue::Array(new_null_array(
+                &DataType::Utf8,
+                array.len(),
+            )));
+        }
+
+        let geometries = geoarrow_array(array.as_ref(), &args.arg_fields[0])?;
+        let wkt = to_wkt::<i32>(geometries.as_ref()).map_err(geoarrow_error)?;
+        Ok(ColumnarValue::Array(wkt.to_array_ref()))
+    }
+}
+
+/// `ST_GeomFromText(wkt [, srid])`: parse WKT into a geometry, optionally tagging it with a CRS
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct GeomFromTextUdf {
+    signature: Signature,
+}
+
+impl Default for GeomFromTextUdf {
+    fn default() -> Self {
+        Self {
+            signature: Signature::one_of(
+                vec![TypeSignature::Any(1), TypeSignature::Any(2)],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for GeomFromTextUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "st_geomfromtext"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        internal_err!("return_field_from_args should be used instead")
+    }
+
+    fn return_field_from_args(&self, args: ReturnFieldArgs) -> Result<FieldRef> {
+        let metadata = if args.arg_fields.len() > 1 {
+            let crs = constant_crs(args.scalar_arguments[1], "ST_GeomFromText")?;
+            Arc::new(Metadata::new(crs_metadata(&crs), None))
+        } else {
+            Arc::default()
+        };
+        Ok(geometry_return_field(self.name(), metadata))
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let geometries = geometry_arg(&args, 0)?;
+        let metadata = field_metadata(&args.return_field);
+        Ok(ColumnarValue::Array(geometry_array(&geometries, metadata)?))
+    }
+}
@@ -0,0 +1,225 @@
+//! Coordinate reference system lookup and reprojection.
+//!
+//! CRS definitions are resolved to [`proj4rs`] projections. Accepted forms are PROJ strings
+//! (`+proj=...`), `EPSG:<code>` (or a bare code) for the codes in the built-in table, and the
+//! `WGS84`/`OGC:CRS84` aliases. Geographic coordinates are always longitude/latitude in
+//! degrees. Definitions are normalised by [`normalize_crs`], which the format readers and
+//! writers share.
+
+use datafusion::common::{DataFusionError, Result};
+use geo_traits::GeometryTrait;
+use proj4rs::Proj;
+use wkt::Wkt;
+use wkt::types::Coord;
+
+use crate::coercion::{to_wkt, try_map_coords};
+
+pub use datafusion_shared::{DEFAULT_CRS, crs_definition, crs_metadata, field_crs, normalize_crs};
+
+/// PROJ definitions of the EPSG codes known without a CRS database
+const EPSG_DEFINITIONS: &[(u16, &str)] = &[
+    (4326, "+proj=longlat +datum=WGS84 +no_defs"),
+    (4269, "+proj=longlat +datum=NAD83 +no_defs"),
+    (
+        4258,
+        "+proj=longlat +ellps=GRS80 +towgs84=0,0,0,0,0,0,0 +no_defs",
+    ),
+    (
+        3857,
+        "+proj=merc +a=6378137 +b=6378137 +lat_ts=0 +lon_0=0 +x_0=0 +y_0=0 +k=1 +units=m +no_defs",
+    ),
+    (
+        3395,
+        "+proj=merc +lon_0=0 +k=1 +x_0=0 +y_0=0 +datum=WGS84 +units=m +no_defs",
+    ),
+    (
+        3035,
+        "+proj=laea +lat_0=52 +lon_0=10 +x_0=4321000 +y_0=3210000 +ellps=GRS80 \
+         +towgs84=0,0,0,0,0,0,0 +units=m +no_defs",
+    ),
+    (
+        2154,
+        "+proj=lcc +lat_0=46.5 +lon_0=3 +lat_1=49 +lat_2=44 +x_0=700000 +y_0=6600000 \
+         +ellps=GRS80 +towgs84=0,0,0,0,0,0,0 +units=m +no_defs",
+    ),
+    (
+        27700,
+        "+proj=tmerc +lat_0=49 +lon_0=-2 +k=0.9996012717 +x_0=400000 +y_0=-100000 +ellps=airy \
+         +towgs84=446.448,-125.157,542.06,0.15,0.247,0.842,-20.489 +units=m +no_defs",
+    ),
+    (
+        5070,
+        "+proj=aea +lat_0=23 +lon_0=-96 +lat_1=29.5 +lat_2=45.5 +x_0=0 +y_0=0 +datum=NAD83 \
+         +units=m +no_defs",
+    ),
+];
+
+/// PROJ definition of an EPSG code, including the WGS 84 (326xx/327xx), NAD83 (269xx) and
+/// ETRS89 (258xx) UTM zones
+#[must_use]
+pub fn epsg_definition(code: u16) -> Option<String> {
+    if let Some((_, definition)) = EPSG_DEFINITIONS.iter().find(|(c, _)| *c == code) {
+        return Some((*definition).to_string());
+    }
+
+    let zone = code % 100;
+    match code / 100 {
+        326 if (1..=60).contains(&zone) => Some(format!(
+            "+proj=utm +zone={zone} +datum=WGS84 +units=m +no_defs"
+        )),
+        327 if (1..=60).contains(&zone) => Some(format!(
+            "+proj=utm +zone={zone} +south +datum=WGS84 +units=m +no_defs"
+        )),
+        269 if (1..=23).contains(&zone) => Some(format!(
+            "+proj=utm +zone={zone} +datum=NAD83 +units=m +no_defs"
+        )),
+        258 if (28..=38).contains(&zone) => Some(format!(
+            "+proj=utm +zone={zone} +ellps=GRS80 +towgs84=0,0,0,0,0,0,0 +units=m +no_defs"
+        )),
+        _ => None,
+    }
+}
+
+/// Resolve a CRS definition to a projection
+///
+/// # Errors
+///
+/// Returns an error if the definition is not a PROJ string or a supported EPSG code
+pub fn resolve_crs(definition: &str) -> Result<Proj> {
+    let normalized = normalize_crs(definition);
+    let proj_string = if normalized.starts_with('+') {
+        normalized
+    } else if let Some(code) = normalized
+        .strip_prefix("EPSG:")
+        .and_then(|code| code.parse::<u16>().ok())
+    {
+        epsg_definition(code).ok_or_else(|| {
+            DataFusionError::Plan(format!(
+                "Unsupported CRS 'EPSG:{code}'. Use a PROJ string (+proj=...) instead."
+            ))
+        })?
+    } else {
+        return Err(DataFusionError::Plan(format!(
+            "Unrecognised CRS '{definition}'. Use EPSG:<code> or a PROJ string (+proj=...)."
+        )));
+    };
+
+    Proj::from_proj_string(&proj_string)
+        .map_err(|e| DataFusionError::Plan(format!("Invalid CRS '{definition}': {e}")))
+}
+
+/// Reproject a geometry from one CRS to another
+///
+/// The geometry keeps its coordinate dimension: Z values are transformed as heights and M
+/// values are copied unchanged.
+///
+/// # Errors
+///
+/// Returns an error if a coordinate cannot be transformed
+pub fn reproject(geometry: &impl GeometryTrait<T = f64>, from: &Proj, to: &Proj) -> Result<Wkt> {
+    try_map_coords(to_wkt(geometry, None), &mut |coord| {
+        transform_coord(coord, from, to)
+    })
+}
+
+fn transform_coord(coord: Coord, from: &Proj, to: &Proj) -> Result<Coord> {
+    let z = coord.z.unwrap_or(0.0);
+    let mut point = if from.is_latlong() {
+        (coord.x.to_radians(), coord.y.to_radians(), z)
+    } else {
+        (coord.x, coord.y, z)
+    };
+    proj4rs::transform::transform(from, to, &mut point).map_err(|e| {
+        DataFusionError::Execution(format!(
+            "Failed to reproject coordinate ({}, {}): {e}",
+            coord.x, coord.y
+        ))
+    })?;
+
+    let (x, y) = if to.is_latlong() {
+        (point.0.to_degrees(), point.1.to_degrees())
+    } else {
+        (point.0, point.1)
+    };
+    Ok(Coord {
+        x,
+        y,
+        z: coord.z.map(|_| point.2),
+        m: coord.m,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use geo::Point;
+    use wkt::types::Dimension;
+
+    fn point_coord(geometry: Wkt) -> Coord {
+        let Wkt::Point(point) = geometry else {
+            panic!("expected a point");
+        };
+        *point.coord().expect("a non-empty point")
+    }
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() < tolerance,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn reprojects_to_web_mercator() {
+        let from = resolve_crs("EPSG:4326").unwrap();
+        let to = resolve_crs("EPSG:3857").unwrap();
+        let point = point_coord(reproject(&Point::new(10.0, 0.0), &from, &to).unwrap());
+        assert_close(point.x, 1_113_194.907, 0.01);
+        assert_close(point.y, 0.0, 0.01);
+        assert_eq!(point.z, None);
+    }
+
+    #[test]
+    fn keeps_z_and_m_values() {
+        let from = resolve_crs("EPSG:4326").unwrap();
+        let to = resolve_crs("EPSG:3857").unwrap();
+        let point = point_coord(
+            reproject(&Wkt::from_str("POINT ZM(10 0 123 7)").unwrap(), &from, &to).unwrap(),
+        );
+        assert_close(point.x, 1_113_194.907, 0.01);
+        assert_close(point.z.expect("a Z value"), 123.0, 1e-9);
+        assert_eq!(point.m, Some(7.0));
+
+        let Wkt::LineString(line) = reproject(
+            &Wkt::from_str("LINESTRING Z(10 0 1, 11 1 2)").unwrap(),
+            &from,
+            &to,
+        )
+        .unwrap() else {
+            panic!("expected a line string");
+        };
+        assert_eq!(line.dimension(), Dimension::XYZ);
+        let heights: Vec<_> = line.coords().iter().map(|c| c.z).collect();
+        assert_eq!(heights, vec![Some(1.0), Some(2.0)]);
+    }
+
+    #[test]
+    fn round_trips_through_utm() {
+        let wgs84 = resolve_crs("EPSG:4326").unwrap();
+        let utm = resolve_crs("EPSG:32633").unwrap();
+        let projected = reproject(&Point::new(15.0, 45.0), &wgs84, &utm).unwrap();
+        assert_close(point_coord(projected.clone()).x, 500_000.0, 0.01);
+
+        let point = point_coord(reproject(&projected, &utm, &wgs84).unwrap());
+        assert_close(point.x, 15.0, 1e-9);
+        assert_close(point.y, 45.0, 1e-9);
+    }
+
+    #[test]
+    fn rejects_unknown_codes() {
+        assert!(resolve_crs("EPSG:9999").is_err());
+        assert!(resolve_crs("not a crs").is_err());
+    }
+}
@@ -0,0 +1,173 @@
+//! Conversion between `GeoArrow` columns and `geo` geometries.
+
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, Float64Array};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, FieldRef};
+use datafusion::common::{DataFusionError, Result, ScalarValue, exec_err};
+use datafusion::logical_expr::ScalarFunctionArgs;
+use geo::Geometry;
+use geo_traits::GeometryTrait;
+use geo_traits::to_geo::ToGeoGeometry;
+use geoarrow_array::array::from_arrow_array;
+use geoarrow_array::builder::{GeometryBuilder, WkbBuilder};
+use geoarrow_array::cast::from_wkb;
+use geoarrow_array::{GeoArrowArray, GeoArrowArrayAccessor, downcast_geoarrow_array};
+use geoarrow_schema::error::{GeoArrowError, GeoArrowResult};
+use geoarrow_schema::{GeoArrowType, GeometryType, Metadata, WkbType};
+
+use crate::crs::normalize_crs;
+
+/// Whether a field holds geometries, either as a `GeoArrow` extension type or as WKT/WKB
+#[must_use]
+pub fn is_geometry_field(field: &Field) -> bool {
+    field
+        .extension_type_name()
+        .is_some_and(|name| name.starts_with("geoarrow."))
+}
+
+/// `GeoArrow` metadata (CRS and edges) attached to a field, or the default when there is none
+pub(crate) fn field_metadata(field: &Field) -> Arc<Metadata> {
+    Arc::new(Metadata::try_from(field).unwrap_or_default())
+}
+
+/// The return field of a function producing `geoarrow.geometry` values
+pub(crate) fn geometry_return_field(name: &str, metadata: Arc<Metadata>) -> FieldRef {
+    Arc::new(GeometryType::new(metadata).to_field(name, true))
+}
+
+/// Decode argument `idx` into one optional geometry per row
+///
+/// `GeoArrow` columns are read natively, plain strings as WKT and plain binary values as
+/// WKB. Nulls and empty geometries become `None`.
+pub(crate) fn geometry_arg(args: &ScalarFunctionArgs, idx: usize) -> Result<Vec<Option<Geometry>>> {
+    let array = args.args[idx].to_array(args.number_rows)?;
+    read_geometries(array.as_ref(), &args.arg_fields[idx])
+}
+
+/// Decode an Arrow array holding geometries into `geo` geometries
+///
+/// # Errors
+///
+/// Returns an error if the array is not a geometry encoding or a value cannot be parsed
+pub fn read_geometries(array: &dyn Array, field: &Field) -> Result<Vec<Option<Geometry>>> {
+    if array.data_type() == &DataType::Null {
+        return Ok(vec![None; array.len()]);
+    }
+    let geometries = geoarrow_array(array, field)?;
+    let geometries = geometries.as_ref();
+    downcast_geoarrow_array!(geometries, collect_geometries).map_err(geoarrow_error)
+}
+
+/// Interpret an Arrow array as a `GeoArrow` array
+pub(crate) fn geoarrow_array(array: &dyn Array, field: &Field) -> Result<Arc<dyn GeoArrowArray>> {
+    // Constant-folded geometry literals lose their extension metadata but keep the
+    // union layout of `geoarrow.geometry`.
+    let field = if field.extension_type_name().is_none()
+        && matches!(field.data_type(), DataType::Union(_, _))
+    {
+        GeometryType::new(Arc::default()).to_field(field.name(), true)
+    } else {
+        field.clone()
+    };
+
+    from_arrow_array(array, &field).map_err(|e| {
+        DataFusionError::Execution(format!(
+            "Argument '{}' of type {} is not a geometry: {e}",
+            field.name(),
+            field.data_type()
+        ))
+    })
+}
+
+fn collect_geometries<'a>(
+    array: &'a impl GeoArrowArrayAccessor<'a>,
+) -> GeoArrowResult<Vec<Option<Geometry>>> {
+    array
+        .iter()
+        .map(|item| {
+            item.transpose()
+                .map(|geometry| geometry.and_then(|g| g.try_to_geometry()))
+        })
+        .collect()
+}
+
+/// Encode geometries as a `geoarrow.geometry` array
+///
+/// # Errors
+///
+/// Returns an error if a geometry cannot be encoded
+pub fn geometry_array(
+    geometries: &[Option<impl GeometryTrait<T = f64>>],
+    metadata: Arc<Metadata>,
+) -> Result<ArrayRef> {
+    let builder =
+        GeometryBuilder::from_nullable_geometries(geometries, GeometryType::new(metadata))
+            .map_err(geoarrow_error)?;
+    Ok(builder.finish().into_array_ref())
+}
+
+/// Encode geometries as an array of the given `GeoArrow` type
+///
+/// # Errors
+///
+/// Returns an error if a geometry does not fit the type, for example a polygon in a
+/// `geoarrow.point` array
+pub fn typed_geometry_array(
+    geometries: &[Option<impl GeometryTrait<T = f64>>],
+    data_type: GeoArrowType,
+) -> Result<ArrayRef> {
+    if let GeoArrowType::Geometry(data_type) = data_type {
+        let builder = GeometryBuilder::from_nullable_geometries(geometries, data_type)
+            .map_err(geoarrow_error)?;
+        return Ok(builder.finish().into_array_ref());
+    }
+    let wkb = WkbBuilder::<i32>::from_nullable_geometries(geometries, WkbType::new(Arc::default()))
+        .map_err(geoarrow_error)?
+        .finish();
+    Ok(from_wkb(&wkb, data_type)
+        .map_err(geoarrow_error)?
+        .to_array_ref())
+}
+
+/// Encode a single geometry as WKB
+pub(crate) fn wkb_bytes(geometry: &Geometry) -> Result<Vec<u8>> {
+    let mut builder = WkbBuilder::<i32>::new(WkbType::new(Arc::default()));
+    builder
+        .push_geometry(Some(geometry))
+        .map_err(geoarrow_error)?;
+    Ok(builder.finish().inner().value(0).to_vec())
+}
+
+/// Decode a numeric argument as one optional `f64` per row
+pub(crate) fn float_arg(args: &ScalarFunctionArgs, idx: usize) -> Result<Vec<Option<f64>>> {
+    let array = args.args[idx].to_array(args.number_rows)?;
+    let values = cast(array.as_ref(), &DataType::Float64)?;
+    let values = values
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .ok_or_else(|| DataFusionError::Internal("cast to Float64 failed".to_string()))?;
+    Ok(values.iter().collect())
+}
+
+/// The CRS named by a constant argument: a definition string or an EPSG code
+pub(crate) fn constant_crs(value: Option<&ScalarValue>, function: &str) -> Result<String> {
+    let Some(value) = value else {
+        return exec_err!("{function} requires a constant CRS argument");
+    };
+    if value.is_null() {
+        return exec_err!("{function} does not accept a NULL CRS");
+    }
+    if let Some(Some(text)) = value.try_as_str() {
+        return Ok(normalize_crs(text));
+    }
+    match value.cast_to(&DataType::Int64)? {
+        ScalarValue::Int64(Some(code)) => Ok(format!("EPSG:{code}")),
+        _ => exec_err!("{function} expects a CRS string or EPSG code, got {value}"),
+    }
+}
+
+pub(crate) fn geoarrow_error(error: GeoArrowError) -> DataFusionError {
+    DataFusionError::External(Box::new(error))
+}
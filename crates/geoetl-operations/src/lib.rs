@@ -1,15 +1,723 @@
+//! Spatial SQL functions for `GeoETL`.
+//!
+//! Scalar UDFs operating on `GeoArrow` geometry columns. Geometry arguments may be any
+//! `GeoArrow` array, plain strings (read as WKT) or plain binary values (read as WKB); functions
+//! returning geometries produce `geoarrow.geometry` columns. Use [`register_spatial_functions`]
+//! to make them available on a `DataFusion` [`SessionContext`], or [`spatial_session_context`]
+//! to also plan spatial joins with [`SpatialJoinExec`].
+//!
+//! | Function | Result |
+//! |----------|--------|
+//! | `ST_Area(geom)`, `ST_Length(geom)` | planar area / length |
+//! | `ST_X(geom)`, `ST_Y(geom)` | point coordinates |
+//! | `ST_XMin(geom)`, `ST_YMin(geom)`, `ST_XMax(geom)`, `ST_YMax(geom)` | bounding box coordinates |
+//! | `ST_Distance(a, b)` | minimum planar distance |
+//! | `ST_Intersects(a, b)`, `ST_Contains(a, b)`, `ST_Within(a, b)` | spatial predicates |
+//! | `ST_DWithin(a, b, distance)` | distance predicate |
+//! | `ST_IsValid(geom)` | OGC validity |
+//! | `ST_IsValidReason(geom)` | why a geometry is invalid, and where |
+//! | `ST_MakeValid(geom)` | repaired geometry |
+//! | `ST_Centroid(geom)`, `ST_Envelope(geom)` | derived geometries |
+//! | `ST_Buffer(geom, distance)`, `ST_Simplify(geom, tolerance)` | derived geometries |
+//! | `ST_SimplifyVW(geom, area)`, `ST_SimplifyPreserveTopology(geom, area)` | simplified geometries |
+//! | `ST_SnapToGrid(geom, size)` | coordinates rounded to a grid |
+//! | `ST_Intersection(a, b)` | `a` clipped to the polygons of `b` |
+//! | `ST_Dump(geom)` | single parts, as a list of `{geom}` structs to `UNNEST` |
+//! | `ST_Multi(geom)`, `ST_CastGeometry(geom, type)` | geometries of another type |
+//! | `ST_Force2D(geom)`, `ST_Force3D(geom)`, `ST_Force3DM(geom)`, `ST_Force4D(geom)` | geometries of another dimension |
+//! | `ST_GeometryType(geom)` | type name, such as `Point` or `MultiPolygon Z` |
+//! | `ST_AsText(geom)`, `ST_GeomFromText(wkt [, srid])` | WKT conversion |
+//! | `ST_Transform(geom, [from,] to)` | reprojection |
+//!
+//! The aggregates `ST_Union_Agg`, `ST_Extent`, `ST_Collect` and `ST_ConvexHull_Agg` combine
+//! the geometries of each group into one geometry.
+
+use std::sync::Arc;
+
+use datafusion::execution::SessionStateBuilder;
+use datafusion::logical_expr::{AggregateUDF, ScalarUDF};
+use datafusion::prelude::{SessionConfig, SessionContext};
+
+mod aggregates;
+mod coercion;
+mod constructors;
+mod conversions;
+pub mod crs;
+pub mod geometry;
+mod measures;
+mod predicates;
+mod spatial_join;
+mod transform;
+pub mod validity;
+
+pub use aggregates::SpatialAggregateUdf;
+pub use coercion::{
+    CastGeometryUdf, DumpUdf, ForceDimensionUdf, GeometryTypeUdf, MultiUdf, cast_failures,
+    field_geometry_type,
+};
+pub use constructors::{ConstructorUdf, IntersectionUdf, Simplification};
+pub use conversions::{AsTextUdf, GeomFromTextUdf};
+pub use geoarrow_schema::Dimension;
+pub use measures::{DistanceUdf, MeasureUdf};
+pub use predicates::{DWithinUdf, IsValidUdf, PredicateUdf};
+pub use spatial_join::{SpatialJoinExec, SpatialJoinRule};
+pub use transform::TransformUdf;
+pub use validity::{IsValidReasonUdf, MakeValidUdf};
+
+/// All spatial scalar functions
+#[must_use]
+pub fn spatial_functions() -> Vec<ScalarUDF> {
+    vec![
+        ScalarUDF::new_from_impl(MeasureUdf::area()),
+        ScalarUDF::new_from_impl(MeasureUdf::length()),
+        ScalarUDF::new_from_impl(MeasureUdf::x()),
+        ScalarUDF::new_from_impl(MeasureUdf::y()),
+        ScalarUDF::new_from_impl(MeasureUdf::x_min()),
+        ScalarUDF::new_from_impl(MeasureUdf::y_min()),
+        ScalarUDF::new_from_impl(MeasureUdf::x_max()),
+        ScalarUDF::new_from_impl(MeasureUdf::y_max()),
+        ScalarUDF::new_from_impl(DistanceUdf::default()),
+        ScalarUDF::new_from_impl(PredicateUdf::intersects()),
+        ScalarUDF::new_from_impl(PredicateUdf::contains()),
+        ScalarUDF::new_from_impl(PredicateUdf::within()),
+        ScalarUDF::new_from_impl(DWithinUdf::default()),
+        ScalarUDF::new_from_impl(IsValidUdf::default()),
+        ScalarUDF::new_from_impl(IsValidReasonUdf::default()),
+        ScalarUDF::new_from_impl(MakeValidUdf::default()),
+        ScalarUDF::new_from_impl(ConstructorUdf::centroid()),
+        ScalarUDF::new_from_impl(ConstructorUdf::envelope()),
+        ScalarUDF::new_from_impl(ConstructorUdf::buffer()),
+        ScalarUDF::new_from_impl(ConstructorUdf::simplify()),
+        ScalarUDF::new_from_impl(ConstructorUdf::simplify_vw()),
+        ScalarUDF::new_from_impl(ConstructorUdf::simplify_preserve_topology()),
+        ScalarUDF::new_from_impl(ConstructorUdf::snap_to_grid()),
+        ScalarUDF::new_from_impl(IntersectionUdf::default()),
+        ScalarUDF::new_from_impl(DumpUdf::default()),
+        ScalarUDF::new_from_impl(MultiUdf::default()),
+        ScalarUDF::new_from_impl(ForceDimensionUdf::force_2d()),
+        ScalarUDF::new_from_impl(ForceDimensionUdf::force_3d()),
+        ScalarUDF::new_from_impl(ForceDimensionUdf::force_3dm()),
+        ScalarUDF::new_from_impl(ForceDimensionUdf::force_4d()),
+        ScalarUDF::new_from_impl(CastGeometryUdf::default()),
+        ScalarUDF::new_from_impl(GeometryTypeUdf::default()),
+        ScalarUDF::new_from_impl(AsTextUdf::default()),
+        ScalarUDF::new_from_impl(GeomFromTextUdf::default()),
+        ScalarUDF::new_from_impl(TransformUdf::default()),
+    ]
+}
+
+/// All spatial aggregate functions
+#[must_use]
+pub fn spatial_aggregate_functions() -> Vec<AggregateUDF> {
+    vec![
+        AggregateUDF::new_from_impl(SpatialAggregateUdf::union()),
+        AggregateUDF::new_from_impl(SpatialAggregateUdf::extent()),
+        AggregateUDF::new_from_impl(SpatialAggregateUdf::collect()),
+        AggregateUDF::new_from_impl(SpatialAggregateUdf::convex_hull()),
+    ]
+}
+
+/// Register all spatial scalar and aggregate functions on a session
+pub fn register_spatial_functions(ctx: &SessionContext) {
+    for function in spatial_functions() {
+        ctx.register_udf(function);
+    }
+    for function in spatial_aggregate_functions() {
+        ctx.register_udaf(function);
+    }
+}
+
+/// A session with the spatial functions registered and joins on spatial predicates executed
+/// by [`SpatialJoinExec`]
 #[must_use]
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
+pub fn spatial_session_context(config: SessionConfig) -> SessionContext {
+    let state = SessionStateBuilder::new()
+        .with_config(config)
+        .with_default_features()
+        .with_physical_optimizer_rule(Arc::new(SpatialJoinRule))
+        .build();
+    let ctx = SessionContext::new_with_state(state);
+    register_spatial_functions(&ctx);
+    ctx
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use arrow::array::{Array, AsArray, RecordBatch};
+    use arrow::datatypes::Float64Type;
+    use datafusion::common::Result;
+
+    async fn query(sql: &str) -> Result<RecordBatch> {
+        let ctx = SessionContext::new();
+        register_spatial_functions(&ctx);
+        let batches = ctx.sql(sql).await?.collect().await?;
+        Ok(batches.into_iter().next().expect("one batch"))
+    }
+
+    async fn scalar_f64(sql: &str) -> f64 {
+        let batch = query(sql).await.unwrap();
+        batch.column(0).as_primitive::<Float64Type>().value(0)
+    }
+
+    async fn scalar_bool(sql: &str) -> bool {
+        let batch = query(sql).await.unwrap();
+        batch.column(0).as_boolean().value(0)
+    }
+
+    async fn scalar_text(sql: &str) -> String {
+        let batch = query(sql).await.unwrap();
+        batch.column(0).as_string::<i32>().value(0).to_string()
+    }
+
+    #[tokio::test]
+    async fn measures() {
+        let square = "'POLYGON((0 0, 4 0, 4 4, 0 4, 0 0))'";
+        assert!((scalar_f64(&format!("SELECT ST_Area({square})")).await - 16.0).abs() < 1e-9);
+        assert!((scalar_f64("SELECT ST_Length('LINESTRING(0 0, 3 4)')").await - 5.0).abs() < 1e-9);
+        assert!((scalar_f64("SELECT ST_X('POINT(1.5 2.5)')").await - 1.5).abs() < 1e-9);
+        assert!((scalar_f64("SELECT ST_Y('POINT(1.5 2.5)')").await - 2.5).abs() < 1e-9);
+        let line = "'LINESTRING(3 -1, -2 5)'";
+        assert!((scalar_f64(&format!("SELECT ST_XMin({line})")).await + 2.0).abs() < 1e-9);
+        assert!((scalar_f64(&format!("SELECT ST_YMin({line})")).await + 1.0).abs() < 1e-9);
+        assert!((scalar_f64(&format!("SELECT ST_XMax({line})")).await - 3.0).abs() < 1e-9);
+        assert!((scalar_f64(&format!("SELECT ST_YMax({line})")).await - 5.0).abs() < 1e-9);
+        assert!(
+            (scalar_f64("SELECT ST_Distance('POINT(0 0)', 'POINT(3 4)')").await - 5.0).abs() < 1e-9
+        );
+    }
+
+    #[tokio::test]
+    async fn predicates() {
+        let square = "'POLYGON((0 0, 4 0, 4 4, 0 4, 0 0))'";
+        assert!(scalar_bool(&format!("SELECT ST_Contains({square}, 'POINT(1 1)')")).await);
+        assert!(scalar_bool(&format!("SELECT ST_Within('POINT(1 1)', {square})")).await);
+        assert!(!scalar_bool(&format!("SELECT ST_Intersects({square}, 'POINT(9 9)')")).await);
+        assert!(!scalar_bool("SELECT ST_IsValid('POLYGON((0 0, 4 4, 4 0, 0 4, 0 0))')").await);
+    }
+
+    #[tokio::test]
+    async fn constructors_return_geoarrow_geometries() {
+        let batch = query("SELECT ST_Centroid('POLYGON((0 0, 4 0, 4 4, 0 4, 0 0))') AS c")
+            .await
+            .unwrap();
+        let field = batch.schema().field(0).clone();
+        assert_eq!(field.extension_type_name(), Some("geoarrow.geometry"));
+        assert_eq!(
+            geometry::read_geometries(batch.column(0).as_ref(), &field).unwrap(),
+            vec![Some(geo::Point::new(2.0, 2.0).into())]
+        );
+
+        assert_eq!(
+            scalar_text("SELECT ST_AsText(ST_Envelope('LINESTRING(0 0, 2 3)'))").await,
+            "POLYGON((2 0,2 3,0 3,0 0,2 0))"
+        );
+        assert_eq!(
+            scalar_text("SELECT ST_AsText(ST_Simplify('LINESTRING(0 0, 1 0.01, 2 0)', 0.1))").await,
+            "LINESTRING(0 0,2 0)"
+        );
+        assert_eq!(
+            scalar_text(
+                "SELECT ST_AsText(ST_SimplifyVW('LINESTRING(0 0, 1 0.01, 2 0, 3 5)', 0.1))"
+            )
+            .await,
+            "LINESTRING(0 0,2 0,3 5)"
+        );
+        assert_eq!(
+            scalar_text(
+                "SELECT ST_AsText(ST_SimplifyPreserveTopology(\
+                 'POLYGON((0 0, 2 0.01, 4 0, 4 4, 0 4, 0 0))', 0.1))"
+            )
+            .await,
+            "POLYGON((0 0,4 0,4 4,0 4,0 0))"
+        );
+        assert_eq!(
+            scalar_text(
+                "SELECT ST_AsText(ST_SnapToGrid('LINESTRING(0.12 0.49, 0.2 0.51, 1.7 1)', 0.5))"
+            )
+            .await,
+            "LINESTRING(0 0.5,1.5 1)"
+        );
+
+        // Z values are kept by the vertices that remain
+        assert_eq!(
+            scalar_text("SELECT ST_AsText(ST_SnapToGrid('POINT Z(10.2 0.1 123.4)', 1))").await,
+            "POINT Z(10 0 123.4)"
+        );
+        assert_eq!(
+            scalar_text(
+                "SELECT ST_AsText(ST_Simplify('LINESTRING Z(0 0 1, 1 0.01 2, 2 0 3)', 0.1))"
+            )
+            .await,
+            "LINESTRING Z(0 0 1,2 0 3)"
+        );
+        assert!(
+            scalar_f64("SELECT ST_Area(ST_Buffer(ST_GeomFromText('POINT(0 0)'), 1.0))").await > 3.0
+        );
+    }
+
+    #[tokio::test]
+    async fn intersection_clips_to_polygons() {
+        let square = "'POLYGON((0 0, 4 0, 4 4, 0 4, 0 0))'";
+        let clipped_area = scalar_f64(&format!(
+            "SELECT ST_Area(ST_Intersection('POLYGON((2 2, 6 2, 6 6, 2 6, 2 2))', {square}))"
+        ))
+        .await;
+        assert!((clipped_area - 4.0).abs() < 1e-9);
+        let clipped_length = scalar_f64(&format!(
+            "SELECT ST_Length(ST_Intersection('LINESTRING(-2 1, 6 1)', {square}))"
+        ))
+        .await;
+        assert!((clipped_length - 4.0).abs() < 1e-9);
+        assert_eq!(
+            scalar_text(&format!(
+                "SELECT ST_AsText(ST_Intersection('MULTIPOINT(1 1, 9 9)', {square}))"
+            ))
+            .await,
+            "POINT(1 1)"
+        );
+        assert_eq!(
+            scalar_text(&format!(
+                "SELECT ST_AsText(ST_Intersection('POINT(9 9)', {square}))"
+            ))
+            .await,
+            "GEOMETRYCOLLECTION EMPTY"
+        );
+    }
+
+    #[tokio::test]
+    async fn validity_reasons_and_repair() {
+        let bowtie = "'POLYGON((0 0, 2 2, 2 0, 0 2, 0 0))'";
+        assert_eq!(
+            scalar_text(&format!("SELECT ST_IsValidReason({bowtie})")).await,
+            "exterior ring has a self-intersection[1 1]"
+        );
+        assert_eq!(
+            scalar_text("SELECT ST_IsValidReason('POLYGON((0 0, 1 0, 1 1, 0 0))')").await,
+            "Valid Geometry"
+        );
+        assert_eq!(
+            scalar_text("SELECT ST_IsValidReason('POLYGON((0 0, 0 1, 1 1, 0 0))')").await,
+            "exterior ring is clockwise[0 0]"
+        );
+        assert_eq!(
+            scalar_text("SELECT ST_IsValidReason('POLYGON((0 0, 1 0, 1 1, 0 1))')").await,
+            "ring is not closed[0 0]"
+        );
+
+        // The bowtie becomes two triangles of area 1 each
+        let repaired = format!("ST_MakeValid({bowtie})");
+        assert!((scalar_f64(&format!("SELECT ST_Area({repaired})")).await - 2.0).abs() < 1e-9);
+        assert_eq!(
+            scalar_text(&format!("SELECT ST_IsValidReason({repaired})")).await,
+            "Valid Geometry"
+        );
+        assert_eq!(
+            scalar_text("SELECT ST_AsText(ST_MakeValid('POLYGON((0 0, 0 1, 1 1, 0 0))'))").await,
+            "POLYGON((0 0,1 1,0 1,0 0))"
+        );
+        assert_eq!(
+            scalar_text("SELECT ST_AsText(ST_MakeValid('POLYGON((0 0, 1 1, 1 1, 0 0))'))").await,
+            "LINESTRING(0 0,1 1)"
+        );
+
+        // Valid geometries are untouched and repairs keep the Z of the vertices they keep
+        assert_eq!(
+            scalar_text("SELECT ST_AsText(ST_MakeValid('POINT Z(10 0 123)'))").await,
+            "POINT Z(10 0 123)"
+        );
+        assert_eq!(
+            scalar_text(
+                "SELECT ST_AsText(ST_MakeValid('POLYGON Z((0 0 1, 0 1 2, 1 1 3, 0 0 1))'))"
+            )
+            .await,
+            "POLYGON Z((0 0 1,1 1 3,0 1 2,0 0 1))"
+        );
+    }
+
+    #[tokio::test]
+    async fn coercion_keeps_z_and_types_columns() {
+        let batch = query(
+            "SELECT ST_AsText(part['geom']) AS part FROM \
+             (SELECT unnest(ST_Dump('GEOMETRYCOLLECTION Z(MULTIPOINT Z((1 2 3), (4 5 6)), \
+             LINESTRING Z(0 0 1, 1 1 2))')) AS part)",
+        )
+        .await
+        .unwrap();
+        let parts: Vec<_> = batch
+            .column(0)
+            .as_string::<i32>()
+            .iter()
+            .flatten()
+            .collect();
+        assert_eq!(
+            parts,
+            [
+                "POINT Z(1 2 3)",
+                "POINT Z(4 5 6)",
+                "LINESTRING Z(0 0 1,1 1 2)"
+            ]
+        );
+
+        let batch =
+            query("SELECT ST_CastGeometry('POLYGON((0 0, 1 0, 1 1, 0 0))', 'MultiPolygon Z') AS g")
+                .await
+                .unwrap();
+        assert_eq!(
+            batch.schema().field(0).extension_type_name(),
+            Some("geoarrow.multipolygon")
+        );
+        assert_eq!(
+            scalar_text("SELECT ST_AsText(ST_CastGeometry('MULTIPOINT(1 2)', 'Point Z'))").await,
+            "POINT Z(1 2 0)"
+        );
+        assert_eq!(
+            scalar_text("SELECT ST_AsText(ST_Multi('LINESTRING(0 0, 1 1)'))").await,
+            "MULTILINESTRING((0 0,1 1))"
+        );
+        assert_eq!(
+            scalar_text("SELECT ST_AsText(ST_Force2D('POINT ZM(1 2 3 4)'))").await,
+            "POINT(1 2)"
+        );
+        assert_eq!(
+            scalar_text("SELECT ST_AsText(ST_Force3DM('POINT Z(1 2 3)'))").await,
+            "POINT M(1 2 0)"
+        );
+        assert_eq!(
+            scalar_text("SELECT ST_GeometryType('MULTIPOLYGON Z(((0 0 1, 1 0 1, 1 1 1, 0 0 1)))')")
+                .await,
+            "MultiPolygon Z"
+        );
+
+        let error = query("SELECT ST_CastGeometry('LINESTRING(0 0, 1 1)', 'Polygon') AS g")
+            .await
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("cannot cast geometries to Polygon: 1 LineString"),
+            "{error}"
+        );
+        assert!(
+            query("SELECT ST_CastGeometry('POINT(0 0)', 'Circle') AS g")
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn transform_sets_target_crs() {
+        let batch = query("SELECT ST_Transform(ST_GeomFromText('POINT(10 0)', 4326), 3857) AS g")
+            .await
+            .unwrap();
+        let metadata = batch.schema().field(0).metadata().clone();
+        assert!(
+            metadata
+                .get("ARROW:extension:metadata")
+                .is_some_and(|m| m.contains("EPSG:3857"))
+        );
+
+        let x =
+            scalar_f64("SELECT ST_X(ST_Transform('POINT(10 0)', 'EPSG:4326', 'EPSG:3857'))").await;
+        assert!((x - 1_113_194.907).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn transform_keeps_z() {
+        let text = scalar_text(
+            "SELECT ST_AsText(ST_Transform('POINT Z(10 0 5)', 'EPSG:4326', 'EPSG:3857'))",
+        )
+        .await;
+        assert!(text.starts_with("POINT Z(1113194.9"), "{text}");
+        assert!(text.ends_with(" 5)"), "{text}");
+
+        // Typed columns keep their dimension
+        assert_eq!(
+            scalar_text(
+                "SELECT ST_GeometryType(ST_Transform(ST_Force3D('LINESTRING(10 0, 11 1)'), \
+                 'EPSG:4326', 'EPSG:3857'))"
+            )
+            .await,
+            "LineString Z"
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_arguments_fail() {
+        assert!(query("SELECT ST_Area('NOT WKT')").await.is_err());
+        assert!(
+            query("SELECT ST_Transform('POINT(0 0)', 'EPSG:9999')")
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn nulls_propagate() {
+        let batch = query("SELECT ST_Area(NULL), ST_AsText(NULL)")
+            .await
+            .unwrap();
+        assert!(batch.column(0).is_null(0));
+        assert!(batch.column(1).is_null(0));
+    }
+
+    /// Run `sql` over `parcels(zone, wkt)` split across several partitions, so that partial
+    /// aggregates have to be merged
+    async fn query_parcels(sql: &str) -> Result<Vec<(String, String)>> {
+        use arrow::array::StringArray;
+        use arrow::datatypes::{DataType, Field, Schema};
+        use datafusion::datasource::MemTable;
+        use datafusion::prelude::SessionConfig;
+        use std::sync::Arc;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("zone", DataType::Utf8, false),
+            Field::new("wkt", DataType::Utf8, true),
+        ]));
+        let rows = [
+            ("a", Some("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))")),
+            ("a", Some("POLYGON((1 0, 3 0, 3 2, 1 2, 1 0))")),
+            ("b", Some("POINT(10 10)")),
+            ("a", None),
+            ("b", Some("POINT(12 14)")),
+            ("b", Some("POINT(10 14)")),
+        ];
+        let partitions = rows
+            .chunks(2)
+            .map(|chunk| {
+                let zones: StringArray = chunk.iter().map(|(zone, _)| Some(*zone)).collect();
+                let wkts: StringArray = chunk.iter().map(|(_, wkt)| *wkt).collect();
+                RecordBatch::try_new(Arc::clone(&schema), vec![Arc::new(zones), Arc::new(wkts)])
+                    .map(|batch| vec![batch])
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let ctx = SessionContext::new_with_config(SessionConfig::new().with_target_partitions(4));
+        register_spatial_functions(&ctx);
+        ctx.register_table("parcels", Arc::new(MemTable::try_new(schema, partitions)?))?;
+
+        let batches = ctx.sql(sql).await?.collect().await?;
+        let mut rows = Vec::new();
+        for batch in batches {
+            let zones = arrow::compute::cast(batch.column(0), &DataType::Utf8)?;
+            let values = arrow::compute::cast(batch.column(1), &DataType::Utf8)?;
+            let (zones, values) = (zones.as_string::<i32>(), values.as_string::<i32>());
+            for row in 0..batch.num_rows() {
+                rows.push((zones.value(row).to_string(), values.value(row).to_string()));
+            }
+        }
+        rows.sort();
+        Ok(rows)
+    }
+
+    #[tokio::test]
+    async fn aggregates_group_and_merge() {
+        let rows =
+            query_parcels("SELECT zone, ST_AsText(ST_Extent(wkt)) FROM parcels GROUP BY zone")
+                .await
+                .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    "a".to_string(),
+                    "POLYGON((3 0,3 2,0 2,0 0,3 0))".to_string()
+                ),
+                (
+                    "b".to_string(),
+                    "POLYGON((12 10,12 14,10 14,10 10,12 10))".to_string()
+                ),
+            ]
+        );
+
+        let rows = query_parcels(
+            "SELECT zone, CAST(ST_Area(ST_Union_Agg(wkt)) AS VARCHAR) FROM parcels GROUP BY zone",
+        )
+        .await
+        .unwrap();
+        assert_eq!(rows[0], ("a".to_string(), "6.0".to_string()));
+
+        let rows = query_parcels(
+            "SELECT zone, ST_AsText(ST_Collect(wkt)) FROM parcels WHERE zone = 'b' GROUP BY zone",
+        )
+        .await
+        .unwrap();
+        assert!(rows[0].1.starts_with("MULTIPOINT"), "{rows:?}");
+        assert_eq!(rows[0].1.matches(',').count(), 2, "{rows:?}");
+
+        let rows = query_parcels(
+            "SELECT zone, CAST(ST_Area(ST_ConvexHull_Agg(wkt)) AS VARCHAR) FROM parcels \
+             GROUP BY zone",
+        )
+        .await
+        .unwrap();
+        assert_eq!(rows[1], ("b".to_string(), "4.0".to_string()));
+    }
+
+    #[tokio::test]
+    async fn convex_hull_of_degenerate_inputs() {
+        let hull = |values: &'static str| async move {
+            let batch = query(&format!(
+                "SELECT ST_AsText(ST_ConvexHull_Agg(g)) FROM (VALUES {values}) t(g)"
+            ))
+            .await
+            .unwrap();
+            batch.column(0).as_string::<i32>().value(0).to_string()
+        };
+
+        assert_eq!(hull("('POINT(1 1)'), ('POINT(1 1)')").await, "POINT(1 1)");
+        assert_eq!(
+            hull("('POINT(1 1)'), ('POINT(0 0)'), ('LINESTRING(2 2, 3 3)')").await,
+            "LINESTRING(0 0,3 3)"
+        );
+        assert!(
+            hull("('POINT(0 0)'), ('POINT(2 0)'), ('POINT(1 1)')")
+                .await
+                .starts_with("POLYGON")
+        );
+    }
+
+    #[tokio::test]
+    async fn aggregates_without_group_by() {
+        let batch = query(
+            "SELECT ST_AsText(ST_Union_Agg(g)) FROM (VALUES \
+             ('POLYGON((0 0, 1 0, 1 1, 0 1, 0 0))'), ('POLYGON((1 0, 2 0, 2 1, 1 1, 1 0))')) t(g)",
+        )
+        .await
+        .unwrap();
+        let text = batch.column(0).as_string::<i32>().value(0);
+        assert!(text.starts_with("POLYGON"), "{text}");
+
+        let batch = query("SELECT ST_Extent(g) FROM (VALUES (CAST(NULL AS VARCHAR))) t(g)")
+            .await
+            .unwrap();
+        assert!(
+            batch
+                .column(0)
+                .logical_nulls()
+                .is_some_and(|n| n.is_null(0))
+        );
+    }
+
+    /// Run `sql` over `zones(zone, wkt)` and `sites(id, wkt)`, each split across partitions,
+    /// returning the sorted output rows and the physical plan
+    async fn query_sites(sql: &str, spatial_joins: bool) -> Result<(Vec<String>, String)> {
+        use arrow::array::StringArray;
+        use arrow::datatypes::{DataType, Field, Schema};
+        use arrow::util::pretty::pretty_format_batches;
+        use datafusion::datasource::MemTable;
+        use std::sync::Arc;
+
+        fn table(key: &str, rows: &[(&str, Option<&str>)]) -> Result<MemTable> {
+            let schema = Arc::new(Schema::new(vec![
+                Field::new(key, DataType::Utf8, false),
+                Field::new("wkt", DataType::Utf8, true),
+            ]));
+            let partitions = rows
+                .chunks(2)
+                .map(|chunk| {
+                    let keys: StringArray = chunk.iter().map(|(key, _)| Some(*key)).collect();
+                    let wkts: StringArray = chunk.iter().map(|(_, wkt)| *wkt).collect();
+                    RecordBatch::try_new(Arc::clone(&schema), vec![Arc::new(keys), Arc::new(wkts)])
+                        .map(|batch| vec![batch])
+                })
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            MemTable::try_new(schema, partitions)
+        }
+
+        let config = SessionConfig::new().with_target_partitions(4);
+        let ctx = if spatial_joins {
+            spatial_session_context(config)
+        } else {
+            let ctx = SessionContext::new_with_config(config);
+            register_spatial_functions(&ctx);
+            ctx
+        };
+        let zones = table(
+            "zone",
+            &[
+                ("west", Some("POLYGON((0 0, 5 0, 5 5, 0 5, 0 0))")),
+                ("east", Some("POLYGON((5 0, 10 0, 10 5, 5 5, 5 0))")),
+                ("north", Some("POLYGON((0 5, 10 5, 10 10, 0 10, 0 5))")),
+                ("empty", None),
+            ],
+        )?;
+        let sites = table(
+            "id",
+            &[
+                ("a", Some("POINT(1 1)")),
+                ("b", Some("POINT(6 1)")),
+                ("c", Some("POINT(5 2)")),
+                ("d", Some("POINT(20 20)")),
+                ("e", Some("POINT(3 11)")),
+                ("f", None),
+            ],
+        )?;
+        ctx.register_table("zones", Arc::new(zones))?;
+        ctx.register_table("sites", Arc::new(sites))?;
+
+        let df = ctx.sql(sql).await?;
+        let plan = df.clone().create_physical_plan().await?;
+        let plan = datafusion::physical_plan::displayable(plan.as_ref())
+            .indent(true)
+            .to_string();
+        let output = pretty_format_batches(&df.collect().await?)?.to_string();
+        let mut rows: Vec<String> = output
+            .lines()
+            .filter(|line| line.starts_with("| "))
+            .skip(1)
+            .map(str::to_string)
+            .collect();
+        rows.sort();
+        Ok((rows, plan))
+    }
+
+    async fn assert_spatial_join(sql: &str, expected_rows: usize) {
+        let (rows, plan) = query_sites(sql, true).await.unwrap();
+        assert!(plan.contains("SpatialJoinExec"), "{plan}");
+        assert!(!plan.contains("NestedLoopJoinExec"), "{plan}");
+
+        let (nested_loop_rows, _) = query_sites(sql, false).await.unwrap();
+        assert_eq!(rows, nested_loop_rows);
+        assert_eq!(rows.len(), expected_rows, "{rows:?}");
+    }
+
+    #[tokio::test]
+    async fn spatial_joins_use_the_index() {
+        assert_spatial_join(
+            "SELECT z.zone, s.id FROM zones z JOIN sites s ON ST_Intersects(z.wkt, s.wkt)",
+            4,
+        )
+        .await;
+        assert_spatial_join(
+            "SELECT z.zone, s.id FROM sites s JOIN zones z ON ST_Within(s.wkt, z.wkt)",
+            2,
+        )
+        .await;
+        assert_spatial_join(
+            "SELECT z.zone, s.id FROM zones z, sites s \
+             WHERE ST_Contains(z.wkt, s.wkt) AND s.id <> 'a'",
+            1,
+        )
+        .await;
+        assert_spatial_join(
+            "SELECT z.zone, s.id FROM zones z JOIN sites s ON ST_DWithin(z.wkt, s.wkt, 1.5)",
+            6,
+        )
+        .await;
+    }
 
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
+    #[tokio::test]
+    async fn spatial_outer_joins_keep_unmatched_rows() {
+        assert_spatial_join(
+            "SELECT s.id, z.zone FROM sites s LEFT JOIN zones z ON ST_Intersects(s.wkt, z.wkt)",
+            7,
+        )
+        .await;
+        assert_spatial_join(
+            "SELECT s.id, z.zone FROM zones z RIGHT JOIN sites s ON ST_Intersects(z.wkt, s.wkt)",
+            7,
+        )
+        .await;
     }
 }
@@ -0,0 +1,203 @@
+//! Numeric measurements: `ST_Area`, `ST_Length`, `ST_X`, `ST_Y`, the bounding box coordinates
+//! `ST_XMin`, `ST_YMin`, `ST_XMax` and `ST_YMax`, and `ST_Distance`.
+//!
+//! Measurements are planar and expressed in the units of the geometry's CRS.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::Float64Array;
+use arrow::datatypes::DataType;
+use datafusion::common::Result;
+use datafusion::logical_expr::{
+    ColumnarValue, ScalarFunctionArgs, ScalarUDFImpl, Signature, Volatility,
+};
+use geo::{Area, BoundingRect, Distance, Euclidean, Geometry, Length};
+
+use crate::geometry::geometry_arg;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Measure {
+    Area,
+    Length,
+    X,
+    Y,
+    XMin,
+    YMin,
+    XMax,
+    YMax,
+}
+
+/// A function returning one number per geometry
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct MeasureUdf {
+    measure: Measure,
+    signature: Signature,
+}
+
+impl MeasureUdf {
+    fn new(measure: Measure) -> Self {
+        Self {
+            measure,
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+
+    /// `ST_Area(geom)`: the planar area of polygonal geometries, 0 for points and lines
+    #[must_use]
+    pub fn area() -> Self {
+        Self::new(Measure::Area)
+    }
+
+    /// `ST_Length(geom)`: the length of linear geometries, 0 for points and polygons
+    #[must_use]
+    pub fn length() -> Self {
+        Self::new(Measure::Length)
+    }
+
+    /// `ST_X(geom)`: the X coordinate of a point, NULL for other geometries
+    #[must_use]
+    pub fn x() -> Self {
+        Self::new(Measure::X)
+    }
+
+    /// `ST_Y(geom)`: the Y coordinate of a point, NULL for other geometries
+    #[must_use]
+    pub fn y() -> Self {
+        Self::new(Measure::Y)
+    }
+
+    /// `ST_XMin(geom)`: the smallest X coordinate of a geometry, NULL when empty
+    #[must_use]
+    pub fn x_min() -> Self {
+        Self::new(Measure::XMin)
+    }
+
+    /// `ST_YMin(geom)`: the smallest Y coordinate of a geometry, NULL when empty
+    #[must_use]
+    pub fn y_min() -> Self {
+        Self::new(Measure::YMin)
+    }
+
+    /// `ST_XMax(geom)`: the largest X coordinate of a geometry, NULL when empty
+    #[must_use]
+    pub fn x_max() -> Self {
+        Self::new(Measure::XMax)
+    }
+
+    /// `ST_YMax(geom)`: the largest Y coordinate of a geometry, NULL when empty
+    #[must_use]
+    pub fn y_max() -> Self {
+        Self::new(Measure::YMax)
+    }
+}
+
+impl ScalarUDFImpl for MeasureUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        match self.measure {
+            Measure::Area => "st_area",
+            Measure::Length => "st_length",
+            Measure::X => "st_x",
+            Measure::Y => "st_y",
+            Measure::XMin => "st_xmin",
+            Measure::YMin => "st_ymin",
+            Measure::XMax => "st_xmax",
+            Measure::YMax => "st_ymax",
+        }
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Float64)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let values: Float64Array = geometry_arg(&args, 0)?
+            .iter()
+            .map(|geometry| geometry.as_ref().and_then(|g| measure(self.measure, g)))
+            .collect();
+        Ok(ColumnarValue::Array(Arc::new(values)))
+    }
+}
+
+fn measure(measure: Measure, geometry: &Geometry) -> Option<f64> {
+    match measure {
+        Measure::Area => Some(geometry.unsigned_area()),
+        Measure::Length => Some(linear_length(geometry)),
+        Measure::X => match geometry {
+            Geometry::Point(point) => Some(point.x()),
+            _ => None,
+        },
+        Measure::Y => match geometry {
+            Geometry::Point(point) => Some(point.y()),
+            _ => None,
+        },
+        Measure::XMin => geometry.bounding_rect().map(|rect| rect.min().x),
+        Measure::YMin => geometry.bounding_rect().map(|rect| rect.min().y),
+        Measure::XMax => geometry.bounding_rect().map(|rect| rect.max().x),
+        Measure::YMax => geometry.bounding_rect().map(|rect| rect.max().y),
+    }
+}
+
+fn linear_length(geometry: &Geometry) -> f64 {
+    match geometry {
+        Geometry::Line(line) => Euclidean.length(line),
+        Geometry::LineString(line) => Euclidean.length(line),
+        Geometry::MultiLineString(lines) => Euclidean.length(lines),
+        Geometry::GeometryCollection(collection) => collection.iter().map(linear_length).sum(),
+        _ => 0.0,
+    }
+}
+
+/// `ST_Distance(a, b)`: the minimum planar distance between two geometries
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct DistanceUdf {
+    signature: Signature,
+}
+
+impl Default for DistanceUdf {
+    fn default() -> Self {
+        Self {
+            signature: Signature::any(2, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for DistanceUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "st_distance"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Float64)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let left = geometry_arg(&args, 0)?;
+        let right = geometry_arg(&args, 1)?;
+        let values: Float64Array = left
+            .iter()
+            .zip(&right)
+            .map(|(a, b)| match (a, b) {
+                (Some(a), Some(b)) => Some(Euclidean.distance(a, b)),
+                _ => None,
+            })
+            .collect();
+        Ok(ColumnarValue::Array(Arc::new(values)))
+    }
+}
@@ -0,0 +1,189 @@
+//! Spatial predicates: `ST_Intersects`, `ST_Contains`, `ST_Within`, `ST_DWithin` and
+//! `ST_IsValid`.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::BooleanArray;
+use arrow::datatypes::DataType;
+use datafusion::common::Result;
+use datafusion::logical_expr::{
+    ColumnarValue, ScalarFunctionArgs, ScalarUDFImpl, Signature, Volatility,
+};
+use geo::{Contains, Distance, Euclidean, Geometry, Intersects, Validation, Within};
+
+use crate::geometry::{float_arg, geometry_arg};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Relation {
+    Intersects,
+    Contains,
+    Within,
+}
+
+/// A binary predicate comparing two geometries row by row
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct PredicateUdf {
+    relation: Relation,
+    signature: Signature,
+}
+
+impl PredicateUdf {
+    fn new(relation: Relation) -> Self {
+        Self {
+            relation,
+            signature: Signature::any(2, Volatility::Immutable),
+        }
+    }
+
+    /// `ST_Intersects(a, b)`: whether the geometries share any point
+    #[must_use]
+    pub fn intersects() -> Self {
+        Self::new(Relation::Intersects)
+    }
+
+    /// `ST_Contains(a, b)`: whether `b` lies inside `a`
+    #[must_use]
+    pub fn contains() -> Self {
+        Self::new(Relation::Contains)
+    }
+
+    /// `ST_Within(a, b)`: whether `a` lies inside `b`
+    #[must_use]
+    pub fn within() -> Self {
+        Self::new(Relation::Within)
+    }
+}
+
+impl ScalarUDFImpl for PredicateUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        match self.relation {
+            Relation::Intersects => "st_intersects",
+            Relation::Contains => "st_contains",
+            Relation::Within => "st_within",
+        }
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let left = geometry_arg(&args, 0)?;
+        let right = geometry_arg(&args, 1)?;
+        let values: BooleanArray = left
+            .iter()
+            .zip(&right)
+            .map(|(a, b)| match (a, b) {
+                (Some(a), Some(b)) => Some(relate(self.relation, a, b)),
+                _ => None,
+            })
+            .collect();
+        Ok(ColumnarValue::Array(Arc::new(values)))
+    }
+}
+
+fn relate(relation: Relation, a: &Geometry, b: &Geometry) -> bool {
+    match relation {
+        Relation::Intersects => a.intersects(b),
+        Relation::Contains => a.contains(b),
+        Relation::Within => a.is_within(b),
+    }
+}
+
+/// `ST_DWithin(a, b, distance)`: whether the geometries are within a planar distance of each
+/// other
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct DWithinUdf {
+    signature: Signature,
+}
+
+impl Default for DWithinUdf {
+    fn default() -> Self {
+        Self {
+            signature: Signature::any(3, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for DWithinUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "st_dwithin"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let left = geometry_arg(&args, 0)?;
+        let right = geometry_arg(&args, 1)?;
+        let distances = float_arg(&args, 2)?;
+        let values: BooleanArray = left
+            .iter()
+            .zip(&right)
+            .zip(distances)
+            .map(|((a, b), distance)| match (a, b, distance) {
+                (Some(a), Some(b), Some(distance)) => Some(Euclidean.distance(a, b) <= distance),
+                _ => None,
+            })
+            .collect();
+        Ok(ColumnarValue::Array(Arc::new(values)))
+    }
+}
+
+/// `ST_IsValid(geom)`: whether a geometry is valid according to the OGC simple feature rules
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct IsValidUdf {
+    signature: Signature,
+}
+
+impl Default for IsValidUdf {
+    fn default() -> Self {
+        Self {
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for IsValidUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "st_isvalid"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let values: BooleanArray = geometry_arg(&args, 0)?
+            .iter()
+            .map(|geometry| geometry.as_ref().map(Validation::is_valid))
+            .collect();
+        Ok(ColumnarValue::Array(Arc::new(values)))
+    }
+}
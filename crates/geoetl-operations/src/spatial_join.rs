@@ -0,0 +1,600 @@
+//! Spatial joins: an R-tree probe replacing nested-loop joins on spatial predicates.
+//!
+//! [`SpatialJoinRule`] rewrites a [`NestedLoopJoinExec`] whose filter relates a geometry column
+//! of each input with `ST_Intersects`, `ST_Contains`, `ST_Within` or `ST_DWithin` (with a
+//! constant distance) into a [`SpatialJoinExec`]. Every partition of the build side is indexed
+//! into its own R-tree of bounding boxes, concurrently, and each probe partition looks up its
+//! candidates in all of them. Candidates are refined with the original join filter, so the
+//! result is the same as the nested-loop join's.
+
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, AsArray, RecordBatch, RecordBatchOptions, UInt32Array, new_null_array,
+};
+use arrow::compute::{concat_batches, filter, take};
+use arrow::datatypes::{DataType, FieldRef, SchemaRef, UInt32Type};
+use datafusion::common::cast::as_boolean_array;
+use datafusion::common::config::ConfigOptions;
+use datafusion::common::runtime::SpawnedTask;
+use datafusion::common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion::common::{DataFusionError, JoinSide, JoinType, Result, ScalarValue};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::expressions::{Column, Literal};
+use datafusion::physical_expr::{
+    EquivalenceProperties, Partitioning, PhysicalExpr, ScalarFunctionExpr, split_conjunction,
+};
+use datafusion::physical_optimizer::PhysicalOptimizerRule;
+use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::execution_plan::{Boundedness, EmissionType};
+use datafusion::physical_plan::joins::NestedLoopJoinExec;
+use datafusion::physical_plan::joins::utils::{JoinFilter, build_join_schema};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, ExecutionPlanProperties, PlanProperties,
+    SendableRecordBatchStream,
+};
+use futures::{StreamExt, TryStreamExt, stream};
+use geo::BoundingRect;
+use rstar::primitives::{GeomWithData, Rectangle};
+use rstar::{AABB, RTree};
+use tokio::sync::OnceCell;
+
+use crate::geometry::read_geometries;
+
+/// The spatial relationship a join is keyed on
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SpatialPredicate {
+    Intersects,
+    Contains,
+    Within,
+    DWithin(f64),
+}
+
+impl SpatialPredicate {
+    /// The predicate computed by a filter conjunct, if it can drive an index lookup
+    fn from_function(function: &ScalarFunctionExpr) -> Option<Self> {
+        match function.name() {
+            "st_intersects" => Some(Self::Intersects),
+            "st_contains" => Some(Self::Contains),
+            "st_within" => Some(Self::Within),
+            "st_dwithin" => {
+                let literal = function.args().get(2)?.as_any().downcast_ref::<Literal>()?;
+                match literal.value().cast_to(&DataType::Float64).ok()? {
+                    ScalarValue::Float64(Some(distance)) if distance >= 0.0 => {
+                        Some(Self::DWithin(distance))
+                    },
+                    _ => None,
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// How far bounding boxes are expanded before looking up candidates
+    fn search_distance(self) -> f64 {
+        match self {
+            Self::DWithin(distance) => distance,
+            Self::Intersects | Self::Contains | Self::Within => 0.0,
+        }
+    }
+}
+
+impl fmt::Display for SpatialPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Intersects => write!(f, "ST_Intersects"),
+            Self::Contains => write!(f, "ST_Contains"),
+            Self::Within => write!(f, "ST_Within"),
+            Self::DWithin(distance) => write!(f, "ST_DWithin({distance})"),
+        }
+    }
+}
+
+type IndexEntry = GeomWithData<Rectangle<[f64; 2]>, u32>;
+
+/// One build partition and the R-tree over its geometries' bounding boxes
+#[derive(Debug)]
+struct BuildPartition {
+    batch: RecordBatch,
+    tree: RTree<IndexEntry>,
+}
+
+impl BuildPartition {
+    fn try_new(batch: RecordBatch, geometry: usize) -> Result<Self> {
+        let field = batch.schema().field(geometry).clone();
+        let entries = read_geometries(batch.column(geometry).as_ref(), &field)?
+            .iter()
+            .zip(0u32..)
+            .filter_map(|(geometry, row)| {
+                let rect = geometry.as_ref()?.bounding_rect()?;
+                let corners = Rectangle::from_corners(
+                    [rect.min().x, rect.min().y],
+                    [rect.max().x, rect.max().y],
+                );
+                Some(GeomWithData::new(corners, row))
+            })
+            .collect();
+        Ok(Self {
+            batch,
+            tree: RTree::bulk_load(entries),
+        })
+    }
+}
+
+/// A join on a spatial predicate, answered by probing R-tree indexes of the build side
+///
+/// The build side is the left input for inner and right joins and the right input for left
+/// joins, so that unmatched rows of the outer input are produced by the probe partition that
+/// reads them. The output has one partition per probe partition.
+#[derive(Debug)]
+pub struct SpatialJoinExec {
+    left: Arc<dyn ExecutionPlan>,
+    right: Arc<dyn ExecutionPlan>,
+    filter: JoinFilter,
+    join_type: JoinType,
+    predicate: SpatialPredicate,
+    /// Geometry column of the left and the right input
+    on: (usize, usize),
+    projection: Option<Vec<usize>>,
+    join_schema: SchemaRef,
+    build_index: Arc<OnceCell<Arc<Vec<BuildPartition>>>>,
+    cache: PlanProperties,
+}
+
+impl SpatialJoinExec {
+    /// Replace a nested-loop join, if its filter contains a spatial predicate between the inputs
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the join's projection does not fit its schema
+    pub fn try_from_nested_loop_join(join: &NestedLoopJoinExec) -> Result<Option<Self>> {
+        if !matches!(
+            join.join_type(),
+            JoinType::Inner | JoinType::Left | JoinType::Right
+        ) {
+            return Ok(None);
+        }
+        let Some(filter) = join.filter() else {
+            return Ok(None);
+        };
+        let Some((predicate, on)) = spatial_condition(filter) else {
+            return Ok(None);
+        };
+
+        Self::try_new(
+            unpartitioned_input(join.left()),
+            unpartitioned_input(join.right()),
+            filter.clone(),
+            *join.join_type(),
+            predicate,
+            on,
+            join.projection().cloned(),
+        )
+        .map(Some)
+    }
+
+    fn try_new(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        filter: JoinFilter,
+        join_type: JoinType,
+        predicate: SpatialPredicate,
+        on: (usize, usize),
+        projection: Option<Vec<usize>>,
+    ) -> Result<Self> {
+        let (join_schema, _) = build_join_schema(&left.schema(), &right.schema(), &join_type);
+        let join_schema = Arc::new(join_schema);
+        let schema = match &projection {
+            Some(projection) => Arc::new(join_schema.project(projection)?),
+            None => Arc::clone(&join_schema),
+        };
+
+        let probe_partitions = if join_type == JoinType::Left {
+            left.output_partitioning().partition_count()
+        } else {
+            right.output_partitioning().partition_count()
+        };
+        let cache = PlanProperties::new(
+            EquivalenceProperties::new(schema),
+            Partitioning::UnknownPartitioning(probe_partitions),
+            EmissionType::Incremental,
+            Boundedness::Bounded,
+        );
+
+        Ok(Self {
+            left,
+            right,
+            filter,
+            join_type,
+            predicate,
+            on,
+            projection,
+            join_schema,
+            build_index: Arc::new(OnceCell::new()),
+            cache,
+        })
+    }
+
+    /// The input that is indexed
+    fn build_side(&self) -> JoinSide {
+        if self.join_type == JoinType::Left {
+            JoinSide::Right
+        } else {
+            JoinSide::Left
+        }
+    }
+
+    fn prober(&self) -> Prober {
+        let (probe, probe_geometry) = match self.build_side() {
+            JoinSide::Right => (&self.left, self.on.0),
+            _ => (&self.right, self.on.1),
+        };
+        let build = match self.build_side() {
+            JoinSide::Right => &self.right,
+            _ => &self.left,
+        };
+        Prober {
+            filter: self.filter.clone(),
+            build_side: self.build_side(),
+            build_schema: build.schema(),
+            probe_geometry,
+            probe_field: Arc::new(probe.schema().field(probe_geometry).clone()),
+            search_distance: self.predicate.search_distance(),
+            emit_unmatched: self.join_type != JoinType::Inner,
+            join_schema: Arc::clone(&self.join_schema),
+            projection: self.projection.clone(),
+            schema: self.schema(),
+        }
+    }
+}
+
+impl DisplayAs for SpatialJoinExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let build = match self.build_side() {
+            JoinSide::Right => "right",
+            _ => "left",
+        };
+        write!(
+            f,
+            "SpatialJoinExec: join_type={}, predicate={}, build={build}, filter={}",
+            self.join_type,
+            self.predicate,
+            self.filter.expression()
+        )?;
+        if let Some(projection) = &self.projection {
+            write!(f, ", projection={projection:?}")?;
+        }
+        Ok(())
+    }
+}
+
+impl ExecutionPlan for SpatialJoinExec {
+    fn name(&self) -> &'static str {
+        "SpatialJoinExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.left, &self.right]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let [left, right] = <[_; 2]>::try_from(children).map_err(|_| {
+            DataFusionError::Internal("SpatialJoinExec expects two children".to_string())
+        })?;
+        Ok(Arc::new(Self::try_new(
+            left,
+            right,
+            self.filter.clone(),
+            self.join_type,
+            self.predicate,
+            self.on,
+            self.projection.clone(),
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let (build, build_geometry, probe) = match self.build_side() {
+            JoinSide::Right => (Arc::clone(&self.right), self.on.1, &self.left),
+            _ => (Arc::clone(&self.left), self.on.0, &self.right),
+        };
+        let probe_stream = probe.execute(partition, Arc::clone(&context))?;
+        let build_index = Arc::clone(&self.build_index);
+        let prober = Arc::new(self.prober());
+
+        let batches = stream::once(async move {
+            let index = build_index
+                .get_or_try_init(|| index_build_side(build, build_geometry, context))
+                .await?;
+            let index = Arc::clone(index);
+            Ok::<_, DataFusionError>(probe_stream.map(move |batch| prober.probe(&index, &batch?)))
+        })
+        .try_flatten();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            batches,
+        )))
+    }
+}
+
+/// Read every build partition concurrently and index each into its own R-tree
+async fn index_build_side(
+    plan: Arc<dyn ExecutionPlan>,
+    geometry: usize,
+    context: Arc<TaskContext>,
+) -> Result<Arc<Vec<BuildPartition>>> {
+    let tasks = (0..plan.output_partitioning().partition_count())
+        .map(|partition| {
+            let batches = plan.execute(partition, Arc::clone(&context));
+            let schema = plan.schema();
+            SpawnedTask::spawn(async move {
+                let batches: Vec<RecordBatch> = batches?.try_collect().await?;
+                BuildPartition::try_new(concat_batches(&schema, &batches)?, geometry)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut partitions = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        let partition = task
+            .join_unwind()
+            .await
+            .map_err(|e| DataFusionError::ExecutionJoin(Box::new(e)))??;
+        if partition.batch.num_rows() > 0 {
+            partitions.push(partition);
+        }
+    }
+    Ok(Arc::new(partitions))
+}
+
+/// Everything a probe partition needs to join its batches against the index
+#[derive(Debug)]
+struct Prober {
+    filter: JoinFilter,
+    build_side: JoinSide,
+    build_schema: SchemaRef,
+    probe_geometry: usize,
+    probe_field: FieldRef,
+    search_distance: f64,
+    /// Whether probe rows without a match are kept, padded with nulls
+    emit_unmatched: bool,
+    join_schema: SchemaRef,
+    projection: Option<Vec<usize>>,
+    schema: SchemaRef,
+}
+
+impl Prober {
+    fn probe(&self, index: &[BuildPartition], probe: &RecordBatch) -> Result<RecordBatch> {
+        let distance = self.search_distance;
+        let envelopes: Vec<Option<AABB<[f64; 2]>>> = read_geometries(
+            probe.column(self.probe_geometry).as_ref(),
+            &self.probe_field,
+        )?
+        .iter()
+        .map(|geometry| {
+            let rect = geometry.as_ref()?.bounding_rect()?;
+            Some(AABB::from_corners(
+                [rect.min().x - distance, rect.min().y - distance],
+                [rect.max().x + distance, rect.max().y + distance],
+            ))
+        })
+        .collect();
+
+        let mut matched = vec![false; probe.num_rows()];
+        let mut batches = Vec::new();
+        for partition in index {
+            let mut build_rows = Vec::new();
+            let mut probe_rows = Vec::new();
+            for (envelope, row) in envelopes.iter().zip(0u32..) {
+                let Some(envelope) = envelope else {
+                    continue;
+                };
+                for candidate in partition.tree.locate_in_envelope_intersecting(envelope) {
+                    build_rows.push(candidate.data);
+                    probe_rows.push(row);
+                }
+            }
+            if build_rows.is_empty() {
+                continue;
+            }
+
+            let (build_rows, probe_rows) = self.refine(
+                &partition.batch,
+                probe,
+                build_rows.into(),
+                probe_rows.into(),
+            )?;
+            for row in probe_rows.values() {
+                matched[*row as usize] = true;
+            }
+            batches.push(self.output_batch(
+                Some((&partition.batch, &build_rows)),
+                probe,
+                &probe_rows,
+            )?);
+        }
+
+        if self.emit_unmatched {
+            let unmatched: UInt32Array = matched
+                .iter()
+                .zip(0u32..)
+                .filter(|(matched, _)| !**matched)
+                .map(|(_, row)| row)
+                .collect::<Vec<_>>()
+                .into();
+            if !unmatched.is_empty() {
+                batches.push(self.output_batch(None, probe, &unmatched)?);
+            }
+        }
+
+        Ok(concat_batches(&self.schema, &batches)?)
+    }
+
+    /// Keep the candidate pairs that satisfy the full join filter
+    fn refine(
+        &self,
+        build: &RecordBatch,
+        probe: &RecordBatch,
+        build_rows: UInt32Array,
+        probe_rows: UInt32Array,
+    ) -> Result<(UInt32Array, UInt32Array)> {
+        let columns = self
+            .filter
+            .column_indices()
+            .iter()
+            .map(|column| {
+                if column.side == self.build_side {
+                    take(build.column(column.index), &build_rows, None)
+                } else {
+                    take(probe.column(column.index), &probe_rows, None)
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let candidates = RecordBatch::try_new_with_options(
+            Arc::clone(self.filter.schema()),
+            columns,
+            &RecordBatchOptions::new().with_row_count(Some(build_rows.len())),
+        )?;
+
+        let mask = self
+            .filter
+            .expression()
+            .evaluate(&candidates)?
+            .into_array(candidates.num_rows())?;
+        let mask = as_boolean_array(&mask)?;
+        Ok((
+            filter(&build_rows, mask)?
+                .as_primitive::<UInt32Type>()
+                .clone(),
+            filter(&probe_rows, mask)?
+                .as_primitive::<UInt32Type>()
+                .clone(),
+        ))
+    }
+
+    /// Assemble joined rows, with a null build side when `build` is `None`
+    fn output_batch(
+        &self,
+        build: Option<(&RecordBatch, &UInt32Array)>,
+        probe: &RecordBatch,
+        probe_rows: &UInt32Array,
+    ) -> Result<RecordBatch> {
+        let build_columns: Vec<ArrayRef> = match build {
+            Some((batch, rows)) => batch
+                .columns()
+                .iter()
+                .map(|column| take(column, rows, None))
+                .collect::<Result<_, _>>()?,
+            None => self
+                .build_schema
+                .fields()
+                .iter()
+                .map(|field| new_null_array(field.data_type(), probe_rows.len()))
+                .collect(),
+        };
+        let probe_columns: Vec<ArrayRef> = probe
+            .columns()
+            .iter()
+            .map(|column| take(column, probe_rows, None))
+            .collect::<Result<_, _>>()?;
+
+        let columns = if self.build_side == JoinSide::Left {
+            [build_columns, probe_columns].concat()
+        } else {
+            [probe_columns, build_columns].concat()
+        };
+        let batch = RecordBatch::try_new_with_options(
+            Arc::clone(&self.join_schema),
+            columns,
+            &RecordBatchOptions::new().with_row_count(Some(probe_rows.len())),
+        )?;
+        match &self.projection {
+            Some(projection) => Ok(batch.project(projection)?),
+            None => Ok(batch),
+        }
+    }
+}
+
+/// The first spatial predicate in the join filter relating a column of each input, with the
+/// left and right geometry column indices
+fn spatial_condition(filter: &JoinFilter) -> Option<(SpatialPredicate, (usize, usize))> {
+    split_conjunction(filter.expression())
+        .into_iter()
+        .find_map(|conjunct| {
+            let function = conjunct.as_any().downcast_ref::<ScalarFunctionExpr>()?;
+            let predicate = SpatialPredicate::from_function(function)?;
+            let a = input_column(filter, function.args().first()?)?;
+            let b = input_column(filter, function.args().get(1)?)?;
+            match (a.1, b.1) {
+                (JoinSide::Left, JoinSide::Right) => Some((predicate, (a.0, b.0))),
+                (JoinSide::Right, JoinSide::Left) => Some((predicate, (b.0, a.0))),
+                _ => None,
+            }
+        })
+}
+
+/// The input column a filter argument refers to
+fn input_column(filter: &JoinFilter, expr: &Arc<dyn PhysicalExpr>) -> Option<(usize, JoinSide)> {
+    let column = expr.as_any().downcast_ref::<Column>()?;
+    let index = filter.column_indices().get(column.index())?;
+    Some((index.index, index.side))
+}
+
+/// Read partitions directly instead of through a coalescing node, so that they can be
+/// indexed or probed in parallel
+fn unpartitioned_input(plan: &Arc<dyn ExecutionPlan>) -> Arc<dyn ExecutionPlan> {
+    match plan.as_any().downcast_ref::<CoalescePartitionsExec>() {
+        Some(coalesce) if coalesce.fetch().is_none() => Arc::clone(coalesce.input()),
+        _ => Arc::clone(plan),
+    }
+}
+
+/// Physical optimizer rule replacing nested-loop joins on spatial predicates with
+/// [`SpatialJoinExec`]
+#[derive(Debug, Default)]
+pub struct SpatialJoinRule;
+
+impl PhysicalOptimizerRule for SpatialJoinRule {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        _config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        plan.transform_up(|node| {
+            let Some(join) = node.as_any().downcast_ref::<NestedLoopJoinExec>() else {
+                return Ok(Transformed::no(node));
+            };
+            Ok(match SpatialJoinExec::try_from_nested_loop_join(join)? {
+                Some(spatial_join) => Transformed::yes(Arc::new(spatial_join)),
+                None => Transformed::no(node),
+            })
+        })
+        .data()
+    }
+
+    fn name(&self) -> &str {
+        "spatial_join"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,123 @@
+//! Reprojection: `ST_Transform`.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Field, FieldRef};
+use datafusion::common::{Result, ScalarValue, internal_err};
+use datafusion::logical_expr::{
+    ColumnarValue, ReturnFieldArgs, ScalarFunctionArgs, ScalarUDFImpl, Signature, TypeSignature,
+    Volatility,
+};
+use geoarrow_schema::{GeoArrowType, GeometryType, Metadata};
+
+use crate::coercion::wkt_geometry_arg;
+use crate::crs::{DEFAULT_CRS, crs_metadata, field_crs, reproject, resolve_crs};
+use crate::geometry::{constant_crs, geoarrow_error, typed_geometry_array};
+
+/// `ST_Transform(geom, [from,] to)`: reproject geometries to another CRS
+///
+/// The source CRS is taken from the geometry column's metadata unless given explicitly, and
+/// defaults to WGS 84 when the column carries none. CRS arguments must be constants: an
+/// `EPSG:<code>` string, a bare EPSG code or a PROJ string.
+///
+/// `GeoArrow` columns keep their geometry type, dimension and encoding, with the target CRS in
+/// their metadata; WKT/WKB strings and boxes, which do not stay boxes, become
+/// `geoarrow.geometry`. Z values are transformed as heights and M values kept.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct TransformUdf {
+    signature: Signature,
+}
+
+impl Default for TransformUdf {
+    fn default() -> Self {
+        Self {
+            signature: Signature::one_of(
+                vec![TypeSignature::Any(2), TypeSignature::Any(3)],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl TransformUdf {
+    /// The type of the reprojected column
+    fn output_type(field: &Field, metadata: Arc<Metadata>) -> GeoArrowType {
+        match GeoArrowType::from_extension_field(field) {
+            Ok(GeoArrowType::Rect(_)) | Err(_) => {
+                GeoArrowType::Geometry(GeometryType::new(metadata))
+            },
+            Ok(data_type) => data_type.with_metadata(metadata),
+        }
+    }
+
+    fn source_crs(field: &Field, explicit: Option<Option<&ScalarValue>>) -> Result<String> {
+        match explicit {
+            Some(value) => constant_crs(value, "ST_Transform"),
+            None => Ok(field_crs(field).unwrap_or_else(|| DEFAULT_CRS.to_string())),
+        }
+    }
+}
+
+impl ScalarUDFImpl for TransformUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "st_transform"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        internal_err!("return_field_from_args should be used instead")
+    }
+
+    fn return_field_from_args(&self, args: ReturnFieldArgs) -> Result<FieldRef> {
+        let target = args.scalar_arguments.len() - 1;
+        let to = constant_crs(args.scalar_arguments[target], "ST_Transform")?;
+        resolve_crs(&to)?;
+        if target == 2 {
+            resolve_crs(&constant_crs(args.scalar_arguments[1], "ST_Transform")?)?;
+        }
+
+        let metadata = Arc::new(Metadata::new(crs_metadata(&to), None));
+        let output_type = Self::output_type(&args.arg_fields[0], metadata);
+        Ok(Arc::new(output_type.to_field(self.name(), true)))
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let constants: Vec<Option<&ScalarValue>> = args
+            .args
+            .iter()
+            .map(|arg| match arg {
+                ColumnarValue::Scalar(value) => Some(value),
+                ColumnarValue::Array(_) => None,
+            })
+            .collect();
+        let target = constants.len() - 1;
+        let explicit_source = (target == 2).then(|| constants[1]);
+        let from = resolve_crs(&Self::source_crs(&args.arg_fields[0], explicit_source)?)?;
+        let to = resolve_crs(&constant_crs(constants[target], "ST_Transform")?)?;
+
+        let geometries = wkt_geometry_arg(&args, 0)?
+            .iter()
+            .map(|geometry| {
+                geometry
+                    .as_ref()
+                    .map(|g| reproject(g, &from, &to))
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let output_type =
+            GeoArrowType::from_extension_field(&args.return_field).map_err(geoarrow_error)?;
+        Ok(ColumnarValue::Array(typed_geometry_array(
+            &geometries,
+            output_type,
+        )?))
+    }
+}
@@ -0,0 +1,462 @@
+//! Geometry validity: `ST_IsValidReason` and `ST_MakeValid`.
+//!
+//! Geometries are checked against the OGC simple feature rules implemented by
+//! [`geo::Validation`] (too few points, self-intersections, crossing rings, holes outside
+//! their shell, overlapping polygons, non-finite coordinates), and in addition must have
+//! closed rings, which `geo` closes silently, and oriented rings: exterior rings
+//! counter-clockwise and interior rings clockwise, as OGC SFA and RFC 7946 specify.
+
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use arrow::array::{Array, StringArray};
+use arrow::datatypes::{DataType, Field, FieldRef};
+use datafusion::common::{Result, internal_err};
+use datafusion::logical_expr::{
+    ColumnarValue, ReturnFieldArgs, ScalarFunctionArgs, ScalarUDFImpl, Signature, Volatility,
+};
+use geo::algorithm::orient::Direction;
+use geo::algorithm::validation::{
+    InvalidGeometry, InvalidGeometryCollection, InvalidLineString, InvalidMultiLineString,
+    InvalidMultiPolygon, InvalidPolygon, RingRole,
+};
+use geo::line_intersection::line_intersection;
+use geo::{
+    BooleanOps, Contains, Coord, Geometry, LineIntersection, LineString, MultiPolygon, Orient,
+    Point, Polygon, Validation, Winding, unary_union,
+};
+use geo_traits::to_geo::ToGeoGeometry;
+use geo_traits::{
+    CoordTrait, GeometryCollectionTrait, GeometryTrait, GeometryType, LineStringTrait,
+    MultiPolygonTrait, PolygonTrait,
+};
+use geoarrow_array::{GeoArrowArrayAccessor, downcast_geoarrow_array};
+use geoarrow_schema::error::GeoArrowResult;
+
+use wkt::Wkt;
+
+use crate::aggregates::{collect, split_by_dimension};
+use crate::coercion::{restore_dimension, wkt_geometry_arg};
+use crate::geometry::{
+    field_metadata, geoarrow_array, geoarrow_error, geometry_array, geometry_return_field,
+};
+
+/// Why a geometry is invalid, and where
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidityIssue {
+    /// Description of the first rule the geometry breaks
+    pub reason: String,
+    /// A coordinate where the rule is broken, when there is one
+    pub location: Option<Coord>,
+}
+
+impl ValidityIssue {
+    fn new(reason: impl fmt::Display, location: Option<Coord>) -> Self {
+        Self {
+            reason: reason.to_string(),
+            location,
+        }
+    }
+
+    /// The location as a WKT point
+    #[must_use]
+    pub fn location_wkt(&self) -> Option<String> {
+        self.location.map(|c| format!("POINT({} {})", c.x, c.y))
+    }
+}
+
+impl fmt::Display for ValidityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.location {
+            Some(c) => write!(f, "{}[{} {}]", self.reason, c.x, c.y),
+            None => write!(f, "{}", self.reason),
+        }
+    }
+}
+
+/// Check every geometry of an Arrow array, returning the issue of each invalid one
+///
+/// Null and empty geometries are valid.
+///
+/// # Errors
+///
+/// Returns an error if the array is not a geometry encoding or a value cannot be parsed
+pub fn validity_issues(array: &dyn Array, field: &Field) -> Result<Vec<Option<ValidityIssue>>> {
+    if array.data_type() == &DataType::Null {
+        return Ok(vec![None; array.len()]);
+    }
+    let geometries = geoarrow_array(array, field)?;
+    let geometries = geometries.as_ref();
+    downcast_geoarrow_array!(geometries, collect_issues).map_err(geoarrow_error)
+}
+
+fn collect_issues<'a>(
+    array: &'a impl GeoArrowArrayAccessor<'a>,
+) -> GeoArrowResult<Vec<Option<ValidityIssue>>> {
+    array
+        .iter()
+        .map(|item| item.transpose().map(|geometry| geometry_issue(&geometry?)))
+        .collect()
+}
+
+fn geometry_issue(geometry: &impl GeometryTrait<T = f64>) -> Option<ValidityIssue> {
+    // Rings are closed when converted to `geo`, so this is checked first
+    unclosed_ring(geometry)
+        .map(|start| ValidityIssue::new("ring is not closed", Some(start)))
+        .or_else(|| validity_issue(&geometry.try_to_geometry()?))
+}
+
+/// The first coordinate of the first ring whose last coordinate differs from it
+fn unclosed_ring(geometry: &impl GeometryTrait<T = f64>) -> Option<Coord> {
+    fn polygon(polygon: &impl PolygonTrait<T = f64>) -> Option<Coord> {
+        polygon
+            .exterior()
+            .into_iter()
+            .chain(polygon.interiors())
+            .find_map(|ring| {
+                let first = ring.coords().next()?;
+                let last = ring.coords().last()?;
+                (first.x_y() != last.x_y()).then(|| Coord::from(first.x_y()))
+            })
+    }
+
+    match geometry.as_type() {
+        GeometryType::Polygon(p) => polygon(p),
+        GeometryType::MultiPolygon(mp) => mp.polygons().find_map(|p| polygon(&p)),
+        GeometryType::GeometryCollection(gc) => gc.geometries().find_map(|g| unclosed_ring(&g)),
+        _ => None,
+    }
+}
+
+/// Check a geometry against the OGC rules and the ring orientation
+#[must_use]
+pub fn validity_issue(geometry: &Geometry) -> Option<ValidityIssue> {
+    if let Err(error) = geometry.check_validation() {
+        return Some(ValidityIssue::new(&error, locate(geometry, &error)));
+    }
+    misoriented_ring(geometry)
+}
+
+/// The first coordinate of a ring with the wrong orientation
+fn misoriented_ring(geometry: &Geometry) -> Option<ValidityIssue> {
+    fn polygon(polygon: &Polygon) -> Option<ValidityIssue> {
+        if polygon.exterior().is_cw() {
+            return Some(ValidityIssue::new(
+                "exterior ring is clockwise",
+                polygon.exterior().0.first().copied(),
+            ));
+        }
+        polygon
+            .interiors()
+            .iter()
+            .enumerate()
+            .find(|(_, ring)| ring.is_ccw())
+            .map(|(idx, ring)| {
+                ValidityIssue::new(
+                    format!("interior ring at index {idx} is counter-clockwise"),
+                    ring.0.first().copied(),
+                )
+            })
+    }
+
+    match geometry {
+        Geometry::Polygon(p) => polygon(p),
+        Geometry::MultiPolygon(mp) => mp.iter().find_map(polygon),
+        Geometry::GeometryCollection(gc) => gc.iter().find_map(misoriented_ring),
+        _ => None,
+    }
+}
+
+/// Where a geometry breaks the rule reported by `error`
+fn locate(geometry: &Geometry, error: &InvalidGeometry) -> Option<Coord> {
+    let located = match (geometry, error) {
+        (Geometry::Polygon(p), InvalidGeometry::InvalidPolygon(e)) => locate_in_polygon(p, e),
+        (Geometry::MultiPolygon(mp), InvalidGeometry::InvalidMultiPolygon(e)) => match e {
+            InvalidMultiPolygon::InvalidPolygon(idx, e) => locate_in_polygon(&mp.0[idx.0], e),
+            InvalidMultiPolygon::ElementsOverlaps(a, b)
+            | InvalidMultiPolygon::ElementsTouchOnALine(a, b) => {
+                crossing(mp.0[a.0].exterior(), mp.0[b.0].exterior())
+            },
+        },
+        (
+            Geometry::MultiLineString(ml),
+            InvalidGeometry::InvalidMultiLineString(InvalidMultiLineString::InvalidLineString(
+                idx,
+                InvalidLineString::TooFewPoints,
+            )),
+        ) => ml.0[idx.0].0.first().copied(),
+        (
+            Geometry::GeometryCollection(gc),
+            InvalidGeometry::InvalidGeometryCollection(InvalidGeometryCollection::InvalidGeometry(
+                idx,
+                e,
+            )),
+        ) => locate(&gc.0[idx.0], e),
+        _ => None,
+    };
+    located.or_else(|| first_finite_coord(geometry))
+}
+
+fn locate_in_polygon(polygon: &Polygon, error: &InvalidPolygon) -> Option<Coord> {
+    let ring = |role: &RingRole| match role {
+        RingRole::Exterior => polygon.exterior(),
+        RingRole::Interior(idx) => &polygon.interiors()[*idx],
+    };
+    match error {
+        InvalidPolygon::TooFewPointsInRing(role) => ring(role).0.first().copied(),
+        InvalidPolygon::SelfIntersection(role) => self_crossing(ring(role)),
+        InvalidPolygon::NonFiniteCoord(..) => None,
+        InvalidPolygon::InteriorRingNotContainedInExteriorRing(role) => {
+            let shell = Polygon::new(polygon.exterior().clone(), vec![]);
+            ring(role)
+                .0
+                .iter()
+                .find(|c| !shell.contains(*c))
+                .or_else(|| ring(role).0.first())
+                .copied()
+        },
+        InvalidPolygon::IntersectingRingsOnALine(a, b)
+        | InvalidPolygon::IntersectingRingsOnAnArea(a, b) => crossing(ring(a), ring(b)),
+    }
+}
+
+/// A point where two non-adjacent segments of a ring meet
+fn self_crossing(ring: &LineString) -> Option<Coord> {
+    let lines: Vec<_> = ring.lines().collect();
+    let last = lines.len().checked_sub(1)?;
+    lines.iter().enumerate().find_map(|(i, a)| {
+        lines[i + 2..]
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| !(i == 0 && i + 2 + j == last))
+            .find_map(|(_, b)| intersection_point(line_intersection(*a, *b)?))
+    })
+}
+
+/// A point where two rings meet
+fn crossing(a: &LineString, b: &LineString) -> Option<Coord> {
+    a.lines().find_map(|p| {
+        b.lines()
+            .find_map(|q| intersection_point(line_intersection(p, q)?))
+    })
+}
+
+fn intersection_point(intersection: LineIntersection<f64>) -> Option<Coord> {
+    match intersection {
+        LineIntersection::SinglePoint { intersection, .. } => Some(intersection),
+        LineIntersection::Collinear { intersection } => Some(intersection.start),
+    }
+}
+
+fn first_finite_coord(geometry: &Geometry) -> Option<Coord> {
+    use geo::CoordsIter;
+    geometry
+        .coords_iter()
+        .find(|c| c.x.is_finite() && c.y.is_finite())
+}
+
+/// Repair a geometry, returning `None` when nothing valid remains
+///
+/// Valid geometries only have their rings oriented. Otherwise non-finite coordinates and
+/// repeated points are dropped, rings and lines that collapse become lines and points,
+/// self-intersecting rings are split into simple polygons with their holes subtracted, and
+/// overlapping polygons are dissolved.
+#[must_use]
+pub fn make_valid(geometry: Geometry) -> Option<Geometry> {
+    if geometry.is_valid() {
+        return Some(orient(geometry));
+    }
+
+    let mut polygons = Vec::new();
+    let mut lines = Vec::new();
+    let mut points = Vec::new();
+    split_by_dimension(geometry, &mut polygons, &mut lines, &mut points);
+    points.retain(|p| p.x().is_finite() && p.y().is_finite());
+
+    let mut valid_lines = Vec::new();
+    for line in lines {
+        match clean(&line).as_slice() {
+            [] => {},
+            [point] => points.push(Point::from(*point)),
+            coords => valid_lines.push(LineString::from(coords.to_vec())),
+        }
+    }
+
+    let mut areas = Vec::new();
+    for polygon in polygons {
+        let (exterior, interiors) = polygon.into_inner();
+        match clean(&exterior).as_slice() {
+            [] => continue,
+            [point] => points.push(Point::from(*point)),
+            [a, b] => valid_lines.push(LineString::from(vec![*a, *b])),
+            coords => {
+                let shell = simple_polygons(coords.to_vec());
+                if shell.0.is_empty() {
+                    // A ring enclosing no area collapses to its outline
+                    valid_lines.push(LineString::from(coords.to_vec()));
+                    continue;
+                }
+                let holes: Vec<Polygon> = interiors
+                    .iter()
+                    .map(clean)
+                    .filter(|coords| coords.len() >= 3)
+                    .flat_map(simple_polygons)
+                    .collect();
+                areas.extend(shell.difference(&MultiPolygon::new(holes)));
+            },
+        }
+    }
+
+    let mut parts: Vec<Geometry> = unary_union(&areas)
+        .orient(Direction::Default)
+        .into_iter()
+        .map(Geometry::Polygon)
+        .collect();
+    parts.extend(valid_lines.into_iter().map(Geometry::LineString));
+    parts.extend(points.into_iter().map(Geometry::Point));
+    match parts.len() {
+        0 => None,
+        1 => parts.pop(),
+        _ => Some(collect(parts)),
+    }
+}
+
+/// Orient the rings of the polygons in a geometry
+fn orient(geometry: Geometry) -> Geometry {
+    match geometry {
+        Geometry::Polygon(p) => Geometry::Polygon(p.orient(Direction::Default)),
+        Geometry::MultiPolygon(mp) => Geometry::MultiPolygon(mp.orient(Direction::Default)),
+        Geometry::GeometryCollection(gc) => {
+            Geometry::GeometryCollection(gc.into_iter().map(orient).collect())
+        },
+        other => other,
+    }
+}
+
+/// The finite coordinates of a line or ring, without consecutive repeats or closing point
+fn clean(line: &LineString) -> Vec<Coord> {
+    let mut coords: Vec<Coord> = line
+        .coords()
+        .filter(|c| c.x.is_finite() && c.y.is_finite())
+        .copied()
+        .collect();
+    coords.dedup();
+    if coords.len() > 1 && coords.first() == coords.last() {
+        coords.pop();
+    }
+    coords
+}
+
+/// The simple polygons enclosed by a possibly self-intersecting ring
+fn simple_polygons(ring: Vec<Coord>) -> MultiPolygon {
+    MultiPolygon::new(vec![Polygon::new(LineString::from(ring), vec![])])
+        .union(&MultiPolygon::new(vec![]))
+}
+
+/// `ST_IsValidReason(geom)`: why a geometry is invalid, with the location in brackets, or
+/// `Valid Geometry`
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct IsValidReasonUdf {
+    signature: Signature,
+}
+
+impl Default for IsValidReasonUdf {
+    fn default() -> Self {
+        Self {
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for IsValidReasonUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "st_isvalidreason"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Utf8)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let array = args.args[0].to_array(args.number_rows)?;
+        let issues = validity_issues(array.as_ref(), &args.arg_fields[0])?;
+        let reasons: StringArray = issues
+            .into_iter()
+            .enumerate()
+            .map(|(row, issue)| match issue {
+                Some(issue) => Some(issue.to_string()),
+                None => array.is_valid(row).then(|| "Valid Geometry".to_string()),
+            })
+            .collect();
+        Ok(ColumnarValue::Array(Arc::new(reasons)))
+    }
+}
+
+/// `ST_MakeValid(geom)`: repair invalid geometries; those that collapse entirely become null
+///
+/// Valid geometries are returned unchanged. Repaired geometries keep their dimension, with
+/// the Z and M values of the vertices the repair keeps and zero for the vertices it adds.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct MakeValidUdf {
+    signature: Signature,
+}
+
+impl Default for MakeValidUdf {
+    fn default() -> Self {
+        Self {
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for MakeValidUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "st_makevalid"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        internal_err!("return_field_from_args should be used instead")
+    }
+
+    fn return_field_from_args(&self, args: ReturnFieldArgs) -> Result<FieldRef> {
+        Ok(geometry_return_field(
+            self.name(),
+            field_metadata(&args.arg_fields[0]),
+        ))
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let geometries: Vec<Option<Wkt>> = wkt_geometry_arg(&args, 0)?
+            .into_iter()
+            .map(|geometry| {
+                let geometry = geometry?;
+                if geometry_issue(&geometry).is_none() {
+                    return Some(geometry);
+                }
+                let repaired = make_valid(geometry.try_to_geometry()?)?;
+                Some(restore_dimension(&repaired, &geometry))
+            })
+            .collect();
+        Ok(ColumnarValue::Array(geometry_array(
+            &geometries,
+            field_metadata(&args.return_field),
+        )?))
+    }
+}